@@ -0,0 +1,23 @@
+#import bevy_core_pipeline::fullscreen_vertex_shader
+
+struct VignetteSettings {
+    intensity: f32,
+    radius: f32,
+    smoothness: f32,
+};
+
+@group(0) @binding(0)
+var screen_texture: texture_2d<f32>;
+@group(0) @binding(1)
+var<uniform> settings: VignetteSettings;
+
+@fragment
+fn fragment(in: FullscreenVertexOutput) -> @location(0) vec4<f32> {
+    let color = textureLoad(screen_texture, vec2<i32>(in.position.xy), 0);
+
+    let distance_to_center = distance(in.uv, vec2<f32>(0.5));
+    let fade = smoothstep(settings.radius, settings.radius + settings.smoothness, distance_to_center);
+    let vignette = 1.0 - settings.intensity * fade;
+
+    return vec4<f32>(color.rgb * vignette, color.a);
+}
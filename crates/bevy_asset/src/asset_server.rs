@@ -1,17 +1,27 @@
+use crate::load_stats::LoadStats;
+use crate::saver::{saved_hash, ErasedAssetSaver};
 use crate::{
     path::{AssetPath, AssetPathId, SourcePathId},
     Asset, AssetIo, AssetIoError, AssetLifecycle, AssetLifecycleChannel, AssetLifecycleEvent,
-    AssetLoader, Assets, Handle, HandleId, HandleUntyped, LabelId, LoadContext, LoadState,
-    RefChange, RefChangeChannel, SourceInfo, SourceMeta,
+    AssetLoader, AssetLoaderMeta, AssetLoaderMetaDeserializer, AssetSaver, Assets, Handle,
+    HandleId, HandleUntyped, LabelId, LoadContext, LoadProgress, LoadState, LoaderStats, RefChange,
+    RefChangeChannel, SourceInfo, SourceMeta,
 };
 use anyhow::Result;
-use bevy_ecs::system::{Res, ResMut, Resource};
+use bevy_ecs::{
+    event::EventWriter,
+    system::{Res, ResMut, Resource},
+};
 use bevy_log::warn;
-use bevy_tasks::IoTaskPool;
-use bevy_utils::{Entry, HashMap, Uuid};
-use crossbeam_channel::TryRecvError;
+use bevy_reflect::{TypeRegistryArc, TypeUuid};
+use bevy_tasks::{IoTaskPool, Task};
+use bevy_utils::{Entry, HashMap, HashSet, Instant, Uuid};
+use crossbeam_channel::{Receiver, Sender, TryRecvError};
 use parking_lot::{Mutex, RwLock};
-use std::{path::Path, sync::Arc};
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+};
 use thiserror::Error;
 
 /// Errors that occur while loading assets with an `AssetServer`.
@@ -30,19 +40,86 @@ pub enum AssetServerError {
         extensions: Vec<String>,
     },
 
+    /// No asset loader with the type name selected by the `.meta` file of the asset was found.
+    #[error("no `AssetLoader` found with the type name {type_name}")]
+    MissingAssetLoaderForTypeName {
+        /// The type name of the loader selected by the `.meta` file.
+        type_name: String,
+    },
+
+    /// The `.meta` file of the asset could not be read.
+    #[error("failed to read the meta file {path:?}: {error}")]
+    InvalidMetaFile {
+        /// The path of the `.meta` file.
+        path: PathBuf,
+        /// The error encountered while deserializing the `.meta` file.
+        error: ron::error::SpannedError,
+    },
+
+    /// The loader of the source of the asset didn't load an asset with the label of its path.
+    #[error("the asset {path:?} was not loaded by the loader of its source")]
+    MissingLabeledAsset {
+        /// The path of the missing asset.
+        path: AssetPath<'static>,
+    },
+
     /// The handle type does not match the type of the loaded asset.
     #[error("the given type does not match the type of the loaded asset")]
     IncorrectHandleType,
 
+    /// The asset loaded with [`LoadContext::load_direct`] is not of the requested type.
+    #[error("the asset {path:?} is not of the type {expected}")]
+    IncorrectAssetType {
+        /// The path of the loaded asset.
+        path: AssetPath<'static>,
+        /// The type name of the requested type.
+        expected: &'static str,
+    },
+
+    /// Assets load each other with [`LoadContext::load_direct`] in a cycle.
+    #[error("assets load each other directly in a cycle: {cycle:?}")]
+    LoadDirectCycle {
+        /// The paths of the assets in the cycle, starting and ending with the same path.
+        cycle: Vec<PathBuf>,
+    },
+
     /// Encountered an error while processing an asset.
     #[error("encountered an error while loading an asset: {0}")]
     AssetLoaderError(anyhow::Error),
 
+    /// No asset saver for the type of the asset was found for the specified extensions.
+    #[error("no `AssetSaver` found{}", format_missing_asset_ext(.extensions))]
+    MissingAssetSaver {
+        /// The list of extensions detected on the path the asset was saved to.
+        ///
+        /// The list may be empty if the asset path is invalid or doesn't have an extension.
+        extensions: Vec<String>,
+    },
+
+    /// Encountered an error while serializing an asset.
+    #[error("encountered an error while saving an asset: {0}")]
+    AssetSaverError(anyhow::Error),
+
     /// Encountered an error while reading an asset from disk.
     #[error("encountered an error while reading an asset: {0}")]
     AssetIoError(#[from] AssetIoError),
 }
 
+/// An event sent when an asset failed to load.
+///
+/// The load state of the asset is then [`LoadState::Failed`], as is the
+/// [recursive dependency load state](AssetServer::get_recursive_dependency_load_state) of the
+/// assets depending on it.
+#[derive(Debug, Clone)]
+pub struct AssetLoadFailedEvent {
+    /// The id of the asset that failed to load.
+    pub id: HandleId,
+    /// The path of the asset that failed to load.
+    pub path: AssetPath<'static>,
+    /// The error that caused the load to fail.
+    pub error: Arc<AssetServerError>,
+}
+
 fn format_missing_asset_ext(exts: &[String]) -> String {
     if !exts.is_empty() {
         format!(
@@ -72,7 +149,15 @@ pub struct AssetServerInternal {
     pub(crate) asset_lifecycles: Arc<RwLock<HashMap<Uuid, Box<dyn AssetLifecycle>>>>,
     loaders: RwLock<Vec<Arc<dyn AssetLoader>>>,
     extension_to_loader_index: RwLock<HashMap<String, usize>>,
+    type_name_to_loader_index: RwLock<HashMap<&'static str, usize>>,
+    type_registry: RwLock<TypeRegistryArc>,
+    savers: RwLock<HashMap<Uuid, Vec<Arc<dyn ErasedAssetSaver>>>>,
+    saved_hashes: RwLock<HashMap<PathBuf, u64>>,
+    direct_dependants: RwLock<HashMap<PathBuf, HashSet<AssetPath<'static>>>>,
     handle_to_path: Arc<RwLock<HashMap<HandleId, AssetPath<'static>>>>,
+    load_stats: LoadStats,
+    load_failed_sender: Sender<AssetLoadFailedEvent>,
+    load_failed_receiver: Receiver<AssetLoadFailedEvent>,
 }
 
 /// Loads assets from the filesystem in the background.
@@ -124,14 +209,23 @@ impl AssetServer {
 
     /// Creates a new asset server with a boxed asset I/O.
     pub fn with_boxed_io(asset_io: Box<dyn AssetIo>) -> Self {
+        let (load_failed_sender, load_failed_receiver) = crossbeam_channel::unbounded();
         AssetServer {
             server: Arc::new(AssetServerInternal {
                 loaders: Default::default(),
                 extension_to_loader_index: Default::default(),
+                type_name_to_loader_index: Default::default(),
+                type_registry: Default::default(),
+                savers: Default::default(),
+                saved_hashes: Default::default(),
+                direct_dependants: Default::default(),
                 asset_sources: Default::default(),
                 asset_ref_counter: Default::default(),
                 handle_to_path: Default::default(),
+                load_stats: Default::default(),
                 asset_lifecycles: Default::default(),
+                load_failed_sender,
+                load_failed_receiver,
                 asset_io,
             }),
         }
@@ -142,6 +236,14 @@ impl AssetServer {
         &*self.server.asset_io
     }
 
+    /// Sets the type registry used to deserialize the settings of `.meta` files.
+    ///
+    /// The [`AssetPlugin`](crate::AssetPlugin) sets it to the
+    /// [`AppTypeRegistry`](bevy_app::AppTypeRegistry).
+    pub fn set_type_registry(&self, type_registry: TypeRegistryArc) {
+        *self.server.type_registry.write() = type_registry;
+    }
+
     pub(crate) fn register_asset_type<T: Asset>(&self) -> Assets<T> {
         if self
             .server
@@ -175,9 +277,29 @@ impl AssetServer {
                 .write()
                 .insert(extension.to_string(), loader_index);
         }
+        self.server
+            .type_name_to_loader_index
+            .write()
+            .insert(std::any::type_name::<T>(), loader_index);
         loaders.push(Arc::new(loader));
     }
 
+    /// Adds the provided asset saver to the server.
+    ///
+    /// If `saver` supports extensions that are also supported by savers of the same asset type
+    /// that came before it, it takes precedence over them for those extensions.
+    pub fn add_saver<T>(&self, saver: T)
+    where
+        T: AssetSaver,
+    {
+        self.server
+            .savers
+            .write()
+            .entry(T::Asset::TYPE_UUID)
+            .or_default()
+            .push(Arc::new(saver));
+    }
+
     /// Gets a strong handle for an asset with the provided id.
     pub fn get_handle<T: Asset, I: Into<HandleId>>(&self, id: I) -> Handle<T> {
         let sender = self.server.asset_ref_counter.channel.sender.clone();
@@ -203,6 +325,45 @@ impl AssetServer {
             })
     }
 
+    fn get_asset_loader_by_type_name(
+        &self,
+        type_name: &str,
+    ) -> Result<Arc<dyn AssetLoader>, AssetServerError> {
+        let index = {
+            // scope map to drop lock as soon as possible
+            let map = self.server.type_name_to_loader_index.read();
+            map.get(type_name).copied()
+        };
+        index
+            .map(|index| self.server.loaders.read()[index].clone())
+            .ok_or_else(|| AssetServerError::MissingAssetLoaderForTypeName {
+                type_name: type_name.to_string(),
+            })
+    }
+
+    /// Reads the `.meta` settings file of the asset at `path`, if it exists.
+    ///
+    /// The file is watched for changes along with the asset, so editing it reloads the asset.
+    async fn load_meta(&self, path: &Path) -> Result<Option<AssetLoaderMeta>, AssetServerError> {
+        let meta_path = AssetLoaderMeta::meta_path(path);
+        let bytes = match self.asset_io().load_path(&meta_path).await {
+            Ok(bytes) => bytes,
+            Err(AssetIoError::NotFound(_)) => return Ok(None),
+            Err(err) => return Err(AssetServerError::AssetIoError(err)),
+        };
+        self.asset_io().watch_path_for_changes(&meta_path)?;
+
+        let type_registry = self.server.type_registry.read();
+        let type_registry = type_registry.read();
+        ron::Options::default()
+            .from_bytes_seed(&bytes, AssetLoaderMetaDeserializer::new(&type_registry))
+            .map(Some)
+            .map_err(|error| AssetServerError::InvalidMetaFile {
+                path: meta_path,
+                error,
+            })
+    }
+
     fn get_path_asset_loader<P: AsRef<Path>>(
         &self,
         path: P,
@@ -233,6 +394,93 @@ impl AssetServer {
         })
     }
 
+    fn get_path_asset_saver<T: Asset>(
+        &self,
+        path: &Path,
+    ) -> Result<Arc<dyn ErasedAssetSaver>, AssetServerError> {
+        let s = path
+            .file_name()
+            .and_then(|file_name| file_name.to_str())
+            .map(|s| s.to_lowercase())
+            .ok_or(AssetServerError::MissingAssetSaver {
+                extensions: Vec::new(),
+            })?;
+
+        let savers = self.server.savers.read();
+        let savers = savers.get(&T::TYPE_UUID).map(Vec::as_slice).unwrap_or(&[]);
+        let mut exts = Vec::new();
+        let mut ext = s.as_str();
+        while let Some(idx) = ext.find('.') {
+            ext = &ext[idx + 1..];
+            exts.push(ext);
+            // the last saver added for the extension takes precedence
+            if let Some(saver) = savers
+                .iter()
+                .rev()
+                .find(|saver| saver.extensions().contains(&ext))
+            {
+                return Ok(saver.clone());
+            }
+        }
+        Err(AssetServerError::MissingAssetSaver {
+            extensions: exts.into_iter().map(String::from).collect(),
+        })
+    }
+
+    /// Saves `asset` at `path` with the [`AssetSaver`] registered for its type and the extension
+    /// of the path.
+    ///
+    /// The asset is serialized immediately, then written through the [`AssetIo`] of the server in
+    /// the background. The returned task completes when the file is written, and can be detached
+    /// if the result isn't needed. The file change caused by the save doesn't hot reload the
+    /// asset, unless the file is modified again.
+    pub fn save<'a, T: Asset, P: Into<AssetPath<'a>>>(
+        &self,
+        path: P,
+        asset: &T,
+    ) -> Task<Result<(), AssetServerError>> {
+        let asset_path: AssetPath = path.into();
+        let path = asset_path.path().to_owned();
+        let bytes = self.get_path_asset_saver::<T>(&path).and_then(|saver| {
+            saver
+                .save(asset, &path)
+                .map_err(AssetServerError::AssetSaverError)
+        });
+
+        let server = self.server.clone();
+        IoTaskPool::get().spawn(async move {
+            let bytes = bytes?;
+            // recorded before writing so that the file watcher can't see the change first
+            server
+                .saved_hashes
+                .write()
+                .insert(path.clone(), saved_hash(&bytes));
+            server.asset_io.write_path(&path, &bytes).await?;
+            Ok(())
+        })
+    }
+
+    /// Returns `true` if the asset at `path` was saved by the server and the file still has the
+    /// content of the save, read with `read`, in which case its change must not reload it.
+    #[cfg_attr(not(feature = "filesystem_watcher"), allow(dead_code))]
+    pub(crate) fn is_saved_content(
+        &self,
+        path: &Path,
+        read: impl FnOnce() -> Option<Vec<u8>>,
+    ) -> bool {
+        let hash = match self.server.saved_hashes.read().get(path) {
+            Some(hash) => *hash,
+            None => return false,
+        };
+        if read().map(|bytes| saved_hash(&bytes)) == Some(hash) {
+            true
+        } else {
+            // the file was modified since the save
+            self.server.saved_hashes.write().remove(path);
+            false
+        }
+    }
+
     /// Gets the source path of an asset from the provided handle.
     pub fn get_handle_path<H: Into<HandleId>>(&self, handle: H) -> Option<AssetPath<'_>> {
         self.server
@@ -279,6 +527,136 @@ impl AssetServer {
         load_state
     }
 
+    /// Gets the progress of loading a group of assets along with their dependencies, for example
+    /// to display a progress bar on a loading screen.
+    ///
+    /// An asset counts as loaded once [`get_recursive_dependency_load_state`] returns
+    /// [`LoadState::Loaded`] for it, and as failed once it returns [`LoadState::Failed`].
+    ///
+    /// [`get_recursive_dependency_load_state`]: AssetServer::get_recursive_dependency_load_state
+    pub fn load_progress<H: Into<HandleId>>(
+        &self,
+        handles: impl IntoIterator<Item = H>,
+    ) -> LoadProgress {
+        let mut progress = LoadProgress::default();
+        for handle in handles {
+            progress.total += 1;
+            match self.get_recursive_dependency_load_state(handle) {
+                LoadState::Loaded => progress.loaded += 1,
+                LoadState::Failed => progress.failed += 1,
+                LoadState::NotLoaded | LoadState::Loading | LoadState::Unloaded => {}
+            }
+        }
+        progress
+    }
+
+    /// Gets the number of assets being loaded.
+    pub fn loads_in_flight(&self) -> usize {
+        self.server.load_stats.loads_in_flight()
+    }
+
+    /// Gets the total number of bytes read through the [`AssetIo`] to load assets.
+    pub fn bytes_read(&self) -> u64 {
+        self.server.load_stats.bytes_read()
+    }
+
+    /// Gets the load statistics of each [`AssetLoader`] that loaded assets, with the type name of
+    /// the loader.
+    pub fn loader_stats(&self) -> Vec<(&'static str, LoaderStats)> {
+        self.server.load_stats.loader_stats()
+    }
+
+    pub(crate) fn load_stats(&self) -> &LoadStats {
+        &self.server.load_stats
+    }
+
+    /// Gets the load state of an asset and all of its dependencies, recursively.
+    ///
+    /// The dependencies of an asset are the assets added by its loader with
+    /// [`LoadedAsset::add_dependency`](crate::LoadedAsset::add_dependency), and their own
+    /// dependencies.
+    ///
+    /// This method returns [`LoadState::Failed`] if the asset or any of its dependencies failed to
+    /// load, and only returns [`LoadState::Loaded`] once all of them are loaded. If the asset is
+    /// loaded but some of its dependencies aren't yet, it returns [`LoadState::Loading`].
+    pub fn get_recursive_dependency_load_state<H: Into<HandleId>>(&self, handle: H) -> LoadState {
+        let id = match handle.into() {
+            HandleId::AssetPathId(id) => id,
+            HandleId::Id(_, _) => return LoadState::NotLoaded,
+        };
+
+        let asset_sources = self.server.asset_sources.read();
+        let root_load_state = asset_sources
+            .get(&id.source_path_id())
+            .map_or(LoadState::NotLoaded, |info| info.load_state);
+        if root_load_state != LoadState::Loaded {
+            return root_load_state;
+        }
+
+        let mut load_state = LoadState::Loaded;
+        let mut visited = HashSet::default();
+        let mut pending = vec![id.source_path_id()];
+        while let Some(source_path_id) = pending.pop() {
+            if !visited.insert(source_path_id) {
+                continue;
+            }
+
+            // dependencies are queued once their dependent is loaded, so their source may not be
+            // tracked yet
+            let source_info = match asset_sources.get(&source_path_id) {
+                Some(source_info) => source_info,
+                None => {
+                    load_state = LoadState::Loading;
+                    continue;
+                }
+            };
+            match source_info.load_state {
+                LoadState::Loaded => {}
+                LoadState::Failed => return LoadState::Failed,
+                _ => load_state = LoadState::Loading,
+            }
+
+            if let Some(meta) = &source_info.meta {
+                for asset_meta in &meta.assets {
+                    pending.extend(
+                        asset_meta
+                            .dependencies
+                            .iter()
+                            .map(|dependency| dependency.get_id().source_path_id()),
+                    );
+                }
+            }
+        }
+
+        load_state
+    }
+
+    /// Gets the overall recursive dependency load state of a group of assets from the provided
+    /// handles.
+    ///
+    /// This method will only return [`LoadState::Loaded`] if all assets in the group and all of
+    /// their dependencies were loaded successfully.
+    /// See [`AssetServer::get_recursive_dependency_load_state`].
+    pub fn get_group_recursive_dependency_load_state(
+        &self,
+        handles: impl IntoIterator<Item = HandleId>,
+    ) -> LoadState {
+        let mut load_state = LoadState::Loaded;
+        for handle_id in handles {
+            match self.get_recursive_dependency_load_state(handle_id) {
+                LoadState::Loaded => continue,
+                LoadState::Loading => {
+                    load_state = LoadState::Loading;
+                }
+                LoadState::Failed => return LoadState::Failed,
+                LoadState::NotLoaded => return LoadState::NotLoaded,
+                LoadState::Unloaded => return LoadState::Unloaded,
+            }
+        }
+
+        load_state
+    }
+
     /// Queues an [`Asset`] at the provided relative path for asynchronous loading.
     ///
     /// The absolute path to the asset is `"ROOT/ASSET_FOLDER_NAME/path"`. Its extension is then
@@ -346,6 +724,7 @@ impl AssetServer {
             source_info.meta = None;
             source_info.version
         };
+        let _in_flight_load = self.server.load_stats.start_load();
 
         let set_asset_failed = || {
             let mut asset_sources = self.server.asset_sources.write();
@@ -355,41 +734,16 @@ impl AssetServer {
             source_info.load_state = LoadState::Failed;
         };
 
-        // get the according asset loader
-        let asset_loader = match self.get_path_asset_loader(asset_path.path()) {
-            Ok(loader) => loader,
+        let mut load_context = match self
+            .run_loader(asset_path.path(), version, Vec::new())
+            .await
+        {
+            Ok(load_context) => load_context,
             Err(err) => {
                 set_asset_failed();
                 return Err(err);
             }
         };
-
-        // load the asset bytes
-        let bytes = match self.asset_io().load_path(asset_path.path()).await {
-            Ok(bytes) => bytes,
-            Err(err) => {
-                set_asset_failed();
-                return Err(AssetServerError::AssetIoError(err));
-            }
-        };
-
-        // load the asset source using the corresponding AssetLoader
-        let mut load_context = LoadContext::new(
-            asset_path.path(),
-            &self.server.asset_ref_counter.channel,
-            self.asset_io(),
-            version,
-        );
-
-        if let Err(err) = asset_loader
-            .load(&bytes, &mut load_context)
-            .await
-            .map_err(AssetServerError::AssetLoaderError)
-        {
-            set_asset_failed();
-            return Err(err);
-        }
-
         // if version has changed since we loaded and grabbed a lock, return. theres is a newer
         // version being loaded
         let mut asset_sources = self.server.asset_sources.write();
@@ -424,6 +778,20 @@ impl AssetServer {
             }
         }
 
+        // reload this asset when the assets it loaded directly change
+        let mut direct_dependants = self.server.direct_dependants.write();
+        let dependant = AssetPath::new(asset_path.path().to_owned(), None);
+        for dependants in direct_dependants.values_mut() {
+            dependants.remove(&dependant);
+        }
+        for dependency in load_context.direct_dependencies.drain(..) {
+            direct_dependants
+                .entry(dependency)
+                .or_default()
+                .insert(dependant.clone());
+        }
+        drop(direct_dependants);
+
         self.asset_io()
             .watch_path_for_changes(asset_path.path())
             .unwrap();
@@ -431,6 +799,47 @@ impl AssetServer {
         Ok(asset_path_id)
     }
 
+    /// Reads the settings and the bytes of the asset source at `path` and loads them with the
+    /// loader of the source, returning the context holding the loaded assets.
+    ///
+    /// `load_stack` holds the paths of the assets loading this asset with
+    /// [`LoadContext::load_direct`], outermost first.
+    pub(crate) async fn run_loader<'a>(
+        &'a self,
+        path: &'a Path,
+        version: usize,
+        mut load_stack: Vec<PathBuf>,
+    ) -> Result<LoadContext<'a>, AssetServerError> {
+        // load the settings of the asset
+        let meta = self.load_meta(path).await?;
+
+        // load the asset bytes, which may be an artifact processed into another format
+        let (bytes, artifact_extension) = self.asset_io().load_path_with_extension(path).await?;
+        self.server.load_stats.add_bytes_read(bytes.len());
+
+        // get the according asset loader, preferring the one of the format of a processed
+        // artifact, then the one selected by the settings
+        let settings_loader = meta.as_ref().and_then(|meta| meta.loader.as_deref());
+        let asset_loader = match (artifact_extension, settings_loader) {
+            (Some(extension), _) => self.get_asset_loader(&extension)?,
+            (None, Some(type_name)) => self.get_asset_loader_by_type_name(type_name)?,
+            (None, None) => self.get_path_asset_loader(path)?,
+        };
+
+        // load the asset source using the corresponding AssetLoader
+        let mut load_context = LoadContext::new(path, self, version);
+        load_stack.push(path.to_owned());
+        load_context.load_stack = load_stack;
+        load_context.settings = meta.and_then(|meta| meta.settings);
+        let start = Instant::now();
+        let result = asset_loader.load(&bytes, &mut load_context).await;
+        self.server
+            .load_stats
+            .add_load_time(asset_loader.type_name(), start.elapsed());
+        result.map_err(AssetServerError::AssetLoaderError)?;
+        Ok(load_context)
+    }
+
     /// Queues the [`Asset`] at the provided path for loading and returns an untyped handle.
     ///
     /// See [`load`](AssetServer::load).
@@ -449,12 +858,40 @@ impl AssetServer {
     }
 
     pub(crate) fn load_untracked(&self, asset_path: AssetPath<'_>, force: bool) -> HandleId {
+        if force {
+            let dependants: Vec<_> = self
+                .server
+                .direct_dependants
+                .read()
+                .get(asset_path.path())
+                .map(|dependants| dependants.iter().cloned().collect())
+                .unwrap_or_default();
+            let is_tracked = self
+                .server
+                .asset_sources
+                .read()
+                .contains_key(&asset_path.get_id().source_path_id());
+            for dependant in &dependants {
+                self.load_untracked(dependant.clone(), true);
+            }
+            // assets only loaded directly by other assets aren't stored
+            if !dependants.is_empty() && !is_tracked {
+                return asset_path.into();
+            }
+        }
+
         let server = self.clone();
         let owned_path = asset_path.to_owned();
         IoTaskPool::get()
             .spawn(async move {
-                if let Err(err) = server.load_async(owned_path, force).await {
+                if let Err(err) = server.load_async(owned_path.clone(), force).await {
                     warn!("{}", err);
+                    // the receiver is owned by the server, so sending can't fail
+                    let _ = server.server.load_failed_sender.send(AssetLoadFailedEvent {
+                        id: owned_path.get_id().into(),
+                        path: owned_path,
+                        error: Arc::new(err),
+                    });
                 }
             })
             .detach();
@@ -611,6 +1048,9 @@ impl AssetServer {
                         }
                     }
 
+                    if let HandleId::AssetPathId(_) = result.id {
+                        assets.waiting_for_dependencies.insert(result.id);
+                    }
                     assets.set_untracked(result.id, *result.asset);
                 }
                 Ok(AssetLifecycleEvent::Free(handle_id)) => {
@@ -622,7 +1062,8 @@ impl AssetServer {
                             source_info.load_state = LoadState::Unloaded;
                         }
                     }
-                    assets.remove(handle_id);
+                    assets.waiting_for_dependencies.remove(&handle_id);
+                    assets.free(handle_id);
                 }
                 Err(TryRecvError::Empty) => {
                     break;
@@ -630,15 +1071,33 @@ impl AssetServer {
                 Err(TryRecvError::Disconnected) => panic!("AssetChannel disconnected."),
             }
         }
+
+        // release the lock on the sources before reading the load states of the dependencies
+        drop(asset_sources_guard);
+        // check before dereferencing mutably, which triggers change detection
+        if !assets.waiting_for_dependencies.is_empty() {
+            assets.send_loaded_with_dependencies_events(self);
+        }
     }
 }
 
 fn free_unused_assets_system_impl(asset_server: &AssetServer) {
-    asset_server.free_unused_assets();
     asset_server.mark_unused_assets();
+    asset_server.free_unused_assets();
+}
+
+/// A system sending an [`AssetLoadFailedEvent`] for each asset that failed to load.
+pub fn asset_load_failed_event_system(
+    asset_server: Res<AssetServer>,
+    mut events: EventWriter<AssetLoadFailedEvent>,
+) {
+    events.send_batch(asset_server.server.load_failed_receiver.try_iter());
 }
 
 /// A system for freeing assets that have no active handles.
+///
+/// Runs at the end of the frame, before the asset storages are updated, so that assets are removed
+/// in the same frame their last Strong handle is dropped.
 pub fn free_unused_assets_system(asset_server: Res<AssetServer>) {
     free_unused_assets_system_impl(&asset_server);
 }
@@ -646,10 +1105,10 @@ pub fn free_unused_assets_system(asset_server: Res<AssetServer>) {
 #[cfg(test)]
 mod test {
     use super::*;
-    use crate::{loader::LoadedAsset, update_asset_storage_system};
+    use crate::{loader::LoadedAsset, update_asset_storage_system, AssetEvent};
     use bevy_app::App;
     use bevy_ecs::prelude::*;
-    use bevy_reflect::TypeUuid;
+    use bevy_reflect::{FromReflect, Reflect, TypeUuid};
     use bevy_utils::BoxedFuture;
 
     #[derive(Debug, TypeUuid)]
@@ -812,6 +1271,227 @@ mod test {
         assert_eq!(asset_server.get_load_state(handle), LoadState::Failed);
     }
 
+    #[derive(Reflect, FromReflect, Debug, PartialEq)]
+    struct ScaleSettings {
+        scale: u32,
+    }
+
+    struct SettingsLoader;
+    impl AssetLoader for SettingsLoader {
+        fn load<'a>(
+            &'a self,
+            _: &'a [u8],
+            ctx: &'a mut LoadContext,
+        ) -> BoxedFuture<'a, Result<(), anyhow::Error>> {
+            Box::pin(async move {
+                anyhow::ensure!(
+                    ctx.settings::<ScaleSettings>() == Some(ScaleSettings { scale: 2 })
+                );
+                Ok(())
+            })
+        }
+
+        fn extensions(&self) -> &[&str] {
+            &["scaled"]
+        }
+    }
+
+    #[test]
+    fn test_meta_settings() {
+        let dir = create_dir_and_file("file.fail");
+        let meta = format!(
+            "(meta_format_version: 1, loader: Some(\"{}\"), settings: {{\"{}\": (scale: 2)}})",
+            std::any::type_name::<SettingsLoader>(),
+            std::any::type_name::<ScaleSettings>(),
+        );
+        std::fs::write(dir.path().join("file.fail.meta"), meta).unwrap();
+
+        let asset_server = setup(dir.path());
+        asset_server.add_loader(FailingLoader);
+        asset_server.add_loader(SettingsLoader);
+        let type_registry = TypeRegistryArc::default();
+        type_registry.write().register::<ScaleSettings>();
+        asset_server.set_type_registry(type_registry);
+
+        let path: AssetPath = "file.fail".into();
+        futures_lite::future::block_on(asset_server.load_async(path, true)).unwrap();
+    }
+
+    #[cfg(all(
+        feature = "filesystem_watcher",
+        all(not(target_arch = "wasm32"), not(target_os = "android"))
+    ))]
+    #[derive(Debug, TypeUuid)]
+    #[uuid = "0f5a3cb4-5b5d-4bd4-a4e8-1a1e08c7c3d9"]
+    struct ScaledAsset(u32);
+
+    #[cfg(all(
+        feature = "filesystem_watcher",
+        all(not(target_arch = "wasm32"), not(target_os = "android"))
+    ))]
+    struct ScaledLoader;
+    #[cfg(all(
+        feature = "filesystem_watcher",
+        all(not(target_arch = "wasm32"), not(target_os = "android"))
+    ))]
+    impl AssetLoader for ScaledLoader {
+        fn load<'a>(
+            &'a self,
+            _: &'a [u8],
+            ctx: &'a mut LoadContext,
+        ) -> BoxedFuture<'a, Result<(), anyhow::Error>> {
+            Box::pin(async move {
+                let scale = ctx
+                    .settings::<ScaleSettings>()
+                    .map_or(1, |settings| settings.scale);
+                ctx.set_default_asset(LoadedAsset::new(ScaledAsset(scale)));
+                Ok(())
+            })
+        }
+
+        fn extensions(&self) -> &[&str] {
+            &["scaled"]
+        }
+    }
+
+    #[cfg(all(
+        feature = "filesystem_watcher",
+        all(not(target_arch = "wasm32"), not(target_os = "android"))
+    ))]
+    #[test]
+    fn test_meta_file_changes_reload_asset() {
+        use crate::{io::filesystem_watcher_system, FileAssetIo};
+
+        let dir = create_dir_and_file("file.scaled");
+        let meta = |scale: u32| {
+            format!(
+                "(meta_format_version: 1, settings: {{\"{}\": (scale: {})}})",
+                std::any::type_name::<ScaleSettings>(),
+                scale
+            )
+        };
+        std::fs::write(dir.path().join("file.scaled.meta"), meta(2)).unwrap();
+
+        IoTaskPool::init(Default::default);
+        let asset_server = AssetServer::new(FileAssetIo::new(dir.path(), true));
+        asset_server.add_loader(ScaledLoader);
+        let type_registry = TypeRegistryArc::default();
+        type_registry.write().register::<ScaleSettings>();
+        asset_server.set_type_registry(type_registry);
+        let assets = asset_server.register_asset_type::<ScaledAsset>();
+
+        let mut app = App::new();
+        app.insert_resource(assets)
+            .insert_resource(asset_server)
+            .add_event::<AssetEvent<ScaledAsset>>()
+            .add_system(filesystem_watcher_system)
+            .add_system(
+                update_asset_storage_system::<ScaledAsset>.after(filesystem_watcher_system),
+            );
+
+        let handle: Handle<ScaledAsset> = app.world.resource::<AssetServer>().load("file.scaled");
+        let update_until_scale = |app: &mut App, scale: u32| {
+            for _ in 0..500 {
+                app.update();
+                let assets = app.world.resource::<Assets<ScaledAsset>>();
+                if assets.get(&handle).map(|asset| asset.0) == Some(scale) {
+                    return true;
+                }
+                std::thread::sleep(std::time::Duration::from_millis(10));
+            }
+            false
+        };
+        assert!(update_until_scale(&mut app, 2));
+
+        std::fs::write(dir.path().join("file.scaled.meta"), meta(3)).unwrap();
+        assert!(update_until_scale(&mut app, 3));
+    }
+
+    #[test]
+    fn test_invalid_meta_file() {
+        let dir = create_dir_and_file("file.png");
+        std::fs::write(dir.path().join("file.png.meta"), "(meta_format_version: 0)").unwrap();
+
+        let asset_server = setup(dir.path());
+        asset_server.add_loader(FakePngLoader);
+
+        let path: AssetPath = "file.png".into();
+        let handle = asset_server.get_handle_untyped(path.get_id());
+
+        let err = futures_lite::future::block_on(asset_server.load_async(path.clone(), true))
+            .unwrap_err();
+        assert!(matches!(err, AssetServerError::InvalidMetaFile { .. }));
+
+        assert_eq!(asset_server.get_load_state(handle), LoadState::Failed);
+    }
+
+    /// Serves files like a web server, answering the requests for missing files with an error
+    /// page and the `missing_status` code.
+    struct HttpAssetIo {
+        files: crate::MemoryAssetIo,
+        missing_status: u16,
+    }
+
+    impl AssetIo for HttpAssetIo {
+        fn load_path<'a>(
+            &'a self,
+            path: &'a Path,
+        ) -> BoxedFuture<'a, Result<Vec<u8>, AssetIoError>> {
+            Box::pin(async move {
+                let (status, body) = match self.files.load_path(path).await {
+                    Ok(bytes) => (200, bytes),
+                    Err(_) => (self.missing_status, b"<html>Not Found</html>".to_vec()),
+                };
+                if status != 200 {
+                    return Err(AssetIoError::from_http_status(path.to_owned(), status));
+                }
+                Ok(body)
+            })
+        }
+
+        fn read_directory(
+            &self,
+            path: &Path,
+        ) -> Result<Box<dyn Iterator<Item = PathBuf>>, AssetIoError> {
+            self.files.read_directory(path)
+        }
+
+        fn get_metadata(&self, path: &Path) -> Result<crate::Metadata, AssetIoError> {
+            self.files.get_metadata(path)
+        }
+
+        fn watch_path_for_changes(&self, _path: &Path) -> Result<(), AssetIoError> {
+            Ok(())
+        }
+
+        fn watch_for_changes(&self) -> Result<(), AssetIoError> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_missing_meta_file_over_http() {
+        let load = |missing_status| {
+            IoTaskPool::init(Default::default);
+            let asset_server = AssetServer::new(HttpAssetIo {
+                files: crate::MemoryAssetIo::default().with_file("file.png", b"png".to_vec()),
+                missing_status,
+            });
+            asset_server.add_loader(FakePngLoader);
+            asset_server.register_asset_type::<PngAsset>();
+            futures_lite::future::block_on(asset_server.load_async("file.png".into(), true))
+        };
+
+        // a missing `.meta` file is optional
+        assert!(load(404).is_ok());
+        // the error page of any other status is not parsed as a `.meta` file
+        let err = load(500).unwrap_err();
+        assert!(matches!(
+            err,
+            AssetServerError::AssetIoError(AssetIoError::Io(_))
+        ));
+    }
+
     #[test]
     fn test_invalid_asset_path() {
         let asset_server = setup(".");
@@ -898,14 +1578,9 @@ mod test {
         assert_eq!(LoadState::Loaded, get_load_state(&handle, &app.world));
         assert!(get_asset(&handle, &app.world).is_some());
 
-        // after dropping the handle, next call to `tick` will prepare the assets for removal.
+        // after dropping the handle, the next call to `tick` removes the asset.
         drop(handle);
         app.update();
-        assert_eq!(LoadState::Loaded, get_load_state(&weak_handle, &app.world));
-        assert!(get_asset(&weak_handle, &app.world).is_some());
-
-        // second call to tick will actually remove the asset.
-        app.update();
         assert_eq!(
             LoadState::Unloaded,
             get_load_state(&weak_handle, &app.world)
@@ -920,6 +1595,165 @@ mod test {
         assert!(get_asset(&handle, &app.world).is_some());
     }
 
+    struct DependentLoader;
+    impl AssetLoader for DependentLoader {
+        fn load<'a>(
+            &'a self,
+            bytes: &'a [u8],
+            ctx: &'a mut LoadContext,
+        ) -> BoxedFuture<'a, Result<(), anyhow::Error>> {
+            Box::pin(async move {
+                let dependency = AssetPath::from(std::str::from_utf8(bytes)?).to_owned();
+                ctx.set_default_asset(LoadedAsset::new(PngAsset).with_dependency(dependency));
+                Ok(())
+            })
+        }
+
+        fn extensions(&self) -> &[&str] {
+            &["dep"]
+        }
+    }
+
+    fn setup_dependencies(dependency: &str) -> (tempfile::TempDir, App) {
+        let dir = create_dir_and_file("fake.png");
+        std::fs::write(dir.path().join("fake.dep"), dependency).unwrap();
+
+        let asset_server = setup(dir.path());
+        asset_server.add_loader(FakePngLoader);
+        asset_server.add_loader(DependentLoader);
+        let assets = asset_server.register_asset_type::<PngAsset>();
+
+        let mut app = App::new();
+        app.insert_resource(assets)
+            .insert_resource(asset_server)
+            .add_event::<AssetEvent<PngAsset>>()
+            .add_event::<AssetLoadFailedEvent>()
+            .add_system(update_asset_storage_system::<PngAsset>)
+            .add_system(
+                Assets::<PngAsset>::asset_event_system
+                    .after(update_asset_storage_system::<PngAsset>),
+            )
+            .add_system(asset_load_failed_event_system);
+        (dir, app)
+    }
+
+    /// Updates the app until the recursive dependency load state of `handle` is no longer
+    /// loading, since assets and their dependencies are loaded in the background.
+    fn update_until_loaded(app: &mut App, handle: &HandleUntyped) -> LoadState {
+        for _ in 0..500 {
+            app.update();
+            let load_state = app
+                .world
+                .resource::<AssetServer>()
+                .get_recursive_dependency_load_state(handle);
+            // the load task may not have started yet
+            if !matches!(load_state, LoadState::Loading | LoadState::NotLoaded) {
+                return load_state;
+            }
+            std::thread::sleep(std::time::Duration::from_millis(10));
+        }
+        LoadState::Loading
+    }
+
+    #[test]
+    fn test_recursive_dependency_load_state() {
+        let (_dir, mut app) = setup_dependencies("fake.png");
+        let mut reader = app
+            .world
+            .resource::<Events<AssetEvent<PngAsset>>>()
+            .get_reader();
+
+        let handle = app.world.resource::<AssetServer>().load_untyped("fake.dep");
+        assert_eq!(update_until_loaded(&mut app, &handle), LoadState::Loaded);
+        app.update();
+
+        let asset_server = app.world.resource::<AssetServer>();
+        assert_eq!(asset_server.get_load_state("fake.png"), LoadState::Loaded);
+        assert_eq!(
+            asset_server.get_group_recursive_dependency_load_state([handle.id, "fake.png".into()]),
+            LoadState::Loaded
+        );
+
+        let events = app.world.resource::<Events<AssetEvent<PngAsset>>>();
+        let loaded: Vec<_> = reader
+            .iter(events)
+            .filter_map(|event| match event {
+                AssetEvent::LoadedWithDependencies { handle } => Some(handle.id()),
+                _ => None,
+            })
+            .collect();
+        assert!(loaded.contains(&handle.id));
+        assert!(loaded.contains(&"fake.png".into()));
+    }
+
+    #[test]
+    fn test_failed_dependency() {
+        let (_dir, mut app) = setup_dependencies("missing.png");
+        let mut reader = app
+            .world
+            .resource::<Events<AssetLoadFailedEvent>>()
+            .get_reader();
+
+        let handle = app.world.resource::<AssetServer>().load_untyped("fake.dep");
+        assert_eq!(update_until_loaded(&mut app, &handle), LoadState::Failed);
+        assert_eq!(
+            app.world.resource::<AssetServer>().get_load_state(&handle),
+            LoadState::Loaded
+        );
+        app.update();
+
+        let events = app.world.resource::<Events<AssetLoadFailedEvent>>();
+        let failed: Vec<_> = reader.iter(events).collect();
+        assert_eq!(failed.len(), 1);
+        assert_eq!(failed[0].path, AssetPath::from("missing.png"));
+        assert!(matches!(
+            *failed[0].error,
+            AssetServerError::AssetIoError(AssetIoError::NotFound(_))
+        ));
+    }
+
+    #[test]
+    fn test_load_stats() {
+        let (dir, mut app) = setup_dependencies("fake.png");
+        let asset_server = app.world.resource::<AssetServer>().clone();
+
+        let handles = [
+            asset_server.load_untyped("fake.dep"),
+            asset_server.load_untyped("missing.png"),
+        ];
+        assert_eq!(asset_server.load_progress(&handles).total, 2);
+        update_until_loaded(&mut app, &handles[0]);
+        update_until_loaded(&mut app, &handles[1]);
+
+        let progress = asset_server.load_progress(&handles);
+        assert_eq!(
+            progress,
+            LoadProgress {
+                loaded: 1,
+                failed: 1,
+                total: 2
+            }
+        );
+        assert!(progress.is_done());
+        assert_eq!(asset_server.loads_in_flight(), 0);
+
+        let file_len = |path| std::fs::metadata(dir.path().join(path)).unwrap().len();
+        assert_eq!(
+            asset_server.bytes_read(),
+            file_len("fake.dep") + file_len("fake.png")
+        );
+
+        let loader_stats = asset_server.loader_stats();
+        let loads = |loader| {
+            loader_stats
+                .iter()
+                .find(|(type_name, _)| *type_name == loader)
+                .map_or(0, |(_, stats)| stats.loads)
+        };
+        assert_eq!(loads(std::any::type_name::<DependentLoader>()), 1);
+        assert_eq!(loads(std::any::type_name::<FakePngLoader>()), 1);
+    }
+
     #[test]
     fn test_get_handle_path() {
         const PATH: &str = "path/file.png";
@@ -949,4 +1783,235 @@ mod test {
         let invalid_path = AssetPath::new("some/path.ext".into(), None);
         assert!(server.get_handle_path(invalid_path).is_none());
     }
+
+    #[derive(Debug, TypeUuid)]
+    #[uuid = "5f3f0c1c-5a0e-4c46-8a3f-7bde0b3e6c1a"]
+    struct TextAsset(String);
+
+    struct TextSaver;
+    impl AssetSaver for TextSaver {
+        type Asset = TextAsset;
+
+        fn save(&self, asset: &TextAsset, _path: &Path) -> Result<Vec<u8>, anyhow::Error> {
+            Ok(asset.0.as_bytes().to_vec())
+        }
+
+        fn extensions(&self) -> &[&str] {
+            &["txt"]
+        }
+    }
+
+    #[test]
+    fn test_save() {
+        IoTaskPool::init(Default::default);
+        let asset_server = AssetServer::new(crate::MemoryAssetIo::default());
+        asset_server.add_saver(TextSaver);
+
+        let text = TextAsset("hello".to_string());
+        futures_lite::future::block_on(asset_server.save("texts/hello.txt", &text)).unwrap();
+        let bytes = futures_lite::future::block_on(
+            asset_server
+                .asset_io()
+                .load_path(Path::new("texts/hello.txt")),
+        )
+        .unwrap();
+        assert_eq!(bytes, b"hello");
+
+        assert!(matches!(
+            futures_lite::future::block_on(asset_server.save("texts/hello.md", &text)),
+            Err(AssetServerError::MissingAssetSaver { extensions }) if extensions == ["md"]
+        ));
+        // savers are selected by asset type
+        assert!(matches!(
+            futures_lite::future::block_on(asset_server.save("hello.txt", &PngAsset)),
+            Err(AssetServerError::MissingAssetSaver { .. })
+        ));
+    }
+
+    /// Saves text assets in upper case, with more extensions than the `TextSaver`.
+    struct UppercaseTextSaver;
+    impl AssetSaver for UppercaseTextSaver {
+        type Asset = TextAsset;
+
+        fn save(&self, asset: &TextAsset, _path: &Path) -> Result<Vec<u8>, anyhow::Error> {
+            Ok(asset.0.to_uppercase().into_bytes())
+        }
+
+        fn extensions(&self) -> &[&str] {
+            &["txt", "text"]
+        }
+    }
+
+    #[test]
+    fn test_later_savers_take_precedence() {
+        IoTaskPool::init(Default::default);
+        let asset_server = AssetServer::new(crate::MemoryAssetIo::default());
+        asset_server.add_saver(UppercaseTextSaver);
+        asset_server.add_saver(TextSaver);
+
+        let text = TextAsset("hello".to_string());
+        let save = |path: &str| {
+            futures_lite::future::block_on(asset_server.save(path, &text)).unwrap();
+            futures_lite::future::block_on(asset_server.asset_io().load_path(Path::new(path)))
+                .unwrap()
+        };
+        assert_eq!(save("hello.txt"), b"hello");
+        // the earlier saver is still used for the extensions only it supports
+        assert_eq!(save("hello.text"), b"HELLO");
+    }
+
+    #[test]
+    fn test_saved_content_is_not_reloaded() {
+        let dir = tempfile::tempdir().unwrap();
+        let asset_server = setup(dir.path());
+        asset_server.add_saver(TextSaver);
+
+        let text = TextAsset("saved".to_string());
+        futures_lite::future::block_on(asset_server.save("hello.txt", &text)).unwrap();
+        let path = Path::new("hello.txt");
+        let full_path = dir.path().join(path);
+        let read = || std::fs::read(&full_path).ok();
+        assert!(asset_server.is_saved_content(path, read));
+        assert!(!asset_server.is_saved_content(Path::new("other.txt"), read));
+
+        // later modifications of the file reload it
+        std::fs::write(&full_path, b"modified").unwrap();
+        assert!(!asset_server.is_saved_content(path, read));
+        std::fs::write(&full_path, b"saved").unwrap();
+        assert!(!asset_server.is_saved_content(path, read));
+    }
+
+    struct TextLoader;
+    impl AssetLoader for TextLoader {
+        fn load<'a>(
+            &'a self,
+            bytes: &'a [u8],
+            ctx: &'a mut LoadContext,
+        ) -> BoxedFuture<'a, Result<(), anyhow::Error>> {
+            Box::pin(async move {
+                let text = std::str::from_utf8(bytes)?.to_string();
+                ctx.set_default_asset(LoadedAsset::new(TextAsset(text)));
+                Ok(())
+            })
+        }
+
+        fn extensions(&self) -> &[&str] {
+            &["txt"]
+        }
+    }
+
+    /// Loads a text asset derived from the text asset at the path it contains.
+    struct DirectLoader;
+    impl AssetLoader for DirectLoader {
+        fn load<'a>(
+            &'a self,
+            bytes: &'a [u8],
+            ctx: &'a mut LoadContext,
+        ) -> BoxedFuture<'a, Result<(), anyhow::Error>> {
+            Box::pin(async move {
+                let path = std::str::from_utf8(bytes)?.to_string();
+                let text = ctx.load_direct::<TextAsset, _>(path.as_str()).await?;
+                ctx.set_default_asset(LoadedAsset::new(TextAsset(format!("direct {}", text.0))));
+                Ok(())
+            })
+        }
+
+        fn extensions(&self) -> &[&str] {
+            &["direct"]
+        }
+    }
+
+    #[test]
+    fn test_load_direct() {
+        let dir = create_dir_and_file("hello.txt");
+        std::fs::write(dir.path().join("hello.txt"), "hello").unwrap();
+        std::fs::write(dir.path().join("derived.direct"), "hello.txt").unwrap();
+        std::fs::write(dir.path().join("missing.direct"), "missing.txt").unwrap();
+
+        let asset_server = setup(dir.path());
+        asset_server.add_loader(TextLoader);
+        asset_server.add_loader(DirectLoader);
+        let assets = asset_server.register_asset_type::<TextAsset>();
+        let mut app = App::new();
+        app.insert_resource(assets)
+            .insert_resource(asset_server)
+            .add_event::<AssetEvent<TextAsset>>()
+            .add_system(update_asset_storage_system::<TextAsset>);
+        let text = |app: &App, handle: &HandleUntyped| {
+            app.world
+                .resource::<Assets<TextAsset>>()
+                .get(&handle.clone_weak().typed::<TextAsset>())
+                .map(|text| text.0.clone())
+        };
+
+        let handle = app
+            .world
+            .resource::<AssetServer>()
+            .load_untyped("derived.direct");
+        assert_eq!(update_until_loaded(&mut app, &handle), LoadState::Loaded);
+        assert_eq!(text(&app, &handle).as_deref(), Some("direct hello"));
+        // directly loaded assets aren't stored
+        let asset_server = app.world.resource::<AssetServer>().clone();
+        assert_eq!(
+            asset_server.get_load_state("hello.txt"),
+            LoadState::NotLoaded
+        );
+
+        // changing the directly loaded asset reloads the assets derived from it
+        std::fs::write(dir.path().join("hello.txt"), "world").unwrap();
+        asset_server.reload_asset("hello.txt");
+        for _ in 0..500 {
+            app.update();
+            if text(&app, &handle).as_deref() == Some("direct world") {
+                break;
+            }
+            std::thread::sleep(std::time::Duration::from_millis(10));
+        }
+        assert_eq!(text(&app, &handle).as_deref(), Some("direct world"));
+        assert_eq!(
+            asset_server.get_load_state("hello.txt"),
+            LoadState::NotLoaded
+        );
+
+        let handle = asset_server.load_untyped("missing.direct");
+        assert_eq!(update_until_loaded(&mut app, &handle), LoadState::Failed);
+    }
+
+    #[test]
+    fn test_load_direct_errors() {
+        let dir = create_dir_and_file("fake.png");
+        std::fs::write(dir.path().join("a.direct"), "b.direct").unwrap();
+        std::fs::write(dir.path().join("b.direct"), "a.direct").unwrap();
+        std::fs::write(dir.path().join("png.direct"), "fake.png").unwrap();
+
+        let asset_server = setup(dir.path());
+        asset_server.add_loader(FakePngLoader);
+        asset_server.add_loader(DirectLoader);
+        // returns the error of the innermost loader
+        let load_error = |path: &str| {
+            let mut err =
+                futures_lite::future::block_on(asset_server.load_async(path.into(), false))
+                    .unwrap_err();
+            while let AssetServerError::AssetLoaderError(loader_err) = err {
+                err = loader_err.downcast::<AssetServerError>().unwrap();
+            }
+            err
+        };
+
+        match load_error("a.direct") {
+            AssetServerError::LoadDirectCycle { cycle } => assert_eq!(
+                cycle,
+                vec![
+                    PathBuf::from("a.direct"),
+                    PathBuf::from("b.direct"),
+                    PathBuf::from("a.direct")
+                ]
+            ),
+            err => panic!("unexpected error {err:?}"),
+        }
+        assert!(matches!(
+            load_error("png.direct"),
+            AssetServerError::IncorrectAssetType { .. }
+        ));
+    }
 }
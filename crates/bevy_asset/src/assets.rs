@@ -1,16 +1,21 @@
 use crate::{
-    update_asset_storage_system, Asset, AssetLoader, AssetServer, AssetStage, Handle, HandleId,
-    RefChange,
+    free_unused_assets_system, update_asset_storage_system, Asset, AssetLoader, AssetSaver,
+    AssetServer, AssetStage, Handle, HandleId, LoadState, RefChange,
 };
 use bevy_app::App;
 use bevy_ecs::{
     event::{EventWriter, Events},
+    schedule::IntoSystemDescriptor,
     system::{ResMut, Resource},
     world::FromWorld,
 };
-use bevy_utils::HashMap;
+use bevy_utils::{HashMap, HashSet};
 use crossbeam_channel::Sender;
-use std::fmt::Debug;
+use std::{
+    fmt::Debug,
+    hash::{Hash, Hasher},
+    marker::PhantomData,
+};
 
 /// Events that involve assets of type `T`.
 ///
@@ -23,6 +28,11 @@ pub enum AssetEvent<T: Asset> {
     Modified { handle: Handle<T> },
     #[allow(missing_docs)]
     Removed { handle: Handle<T> },
+    /// Sent once an asset loaded by the [`AssetServer`] and all of its dependencies are loaded.
+    ///
+    /// See [`AssetServer::get_recursive_dependency_load_state`].
+    #[allow(missing_docs)]
+    LoadedWithDependencies { handle: Handle<T> },
 }
 
 impl<T: Asset> Debug for AssetEvent<T> {
@@ -49,15 +59,102 @@ impl<T: Asset> Debug for AssetEvent<T> {
                 ))
                 .field("handle", &handle.id())
                 .finish(),
+            AssetEvent::LoadedWithDependencies { handle } => f
+                .debug_struct(&format!(
+                    "AssetEvent<{}>::LoadedWithDependencies",
+                    std::any::type_name::<T>()
+                ))
+                .field("handle", &handle.id())
+                .finish(),
+        }
+    }
+}
+
+/// A compact, typed index of an asset stored in an [`Assets`] collection.
+///
+/// [`Handle`]s resolve to the `AssetId` of their asset the first time they are used to access
+/// it, after which [`Assets::get`] indexes the storage directly instead of looking up the
+/// [`HandleId`]. The storage slot of an asset is reused once the asset is freed, but each reuse
+/// bumps the generation of the slot, so `AssetId`s and handles of a freed asset are detected as
+/// stale instead of pointing to another asset.
+///
+/// `AssetId`s don't keep their asset loaded, use a Strong [`Handle`] for that.
+pub struct AssetId<T: Asset> {
+    index: u32,
+    generation: u32,
+    marker: PhantomData<fn() -> T>,
+}
+
+impl<T: Asset> AssetId<T> {
+    /// The index of the slot of the asset in the storage.
+    pub fn index(&self) -> u32 {
+        self.index
+    }
+
+    /// The generation of the slot when the asset was stored.
+    pub fn generation(&self) -> u32 {
+        self.generation
+    }
+
+    pub(crate) fn to_bits(self) -> u64 {
+        (u64::from(self.generation) << 32) | u64::from(self.index)
+    }
+
+    pub(crate) fn from_bits(bits: u64) -> Self {
+        AssetId {
+            index: bits as u32,
+            generation: (bits >> 32) as u32,
+            marker: PhantomData,
         }
     }
 }
 
+impl<T: Asset> Clone for AssetId<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T: Asset> Copy for AssetId<T> {}
+
+impl<T: Asset> PartialEq for AssetId<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.index == other.index && self.generation == other.generation
+    }
+}
+
+impl<T: Asset> Eq for AssetId<T> {}
+
+impl<T: Asset> Hash for AssetId<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.index.hash(state);
+        self.generation.hash(state);
+    }
+}
+
+impl<T: Asset> Debug for AssetId<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct(&format!("AssetId<{}>", std::any::type_name::<T>()))
+            .field("index", &self.index)
+            .field("generation", &self.generation)
+            .finish()
+    }
+}
+
+/// A storage slot of an [`Assets`] collection, reserved for the asset of a [`HandleId`].
+#[derive(Debug)]
+struct AssetSlot<T> {
+    generation: u32,
+    handle_id: Option<HandleId>,
+    asset: Option<T>,
+}
+
 /// Stores Assets of a given type and tracks changes to them.
 ///
 /// Each asset is mapped by a unique [`HandleId`], allowing any [`Handle`] with the same
 /// [`HandleId`] to access it. These assets remain loaded for as long as a Strong handle to that
-/// asset exists.
+/// asset exists, and are freed at the end of the frame where their last Strong handle is
+/// dropped.
 ///
 /// To store a reference to an asset without forcing it to stay loaded, you can use a Weak handle.
 /// To make a Weak handle a Strong one, use [`Assets::get_handle`] or pass the `Assets` collection
@@ -66,20 +163,109 @@ impl<T: Asset> Debug for AssetEvent<T> {
 /// Remember, if there are no Strong handles for an asset (i.e. they have all been dropped), the
 /// asset will unload. Make sure you always have a Strong handle when you want to keep an asset
 /// loaded!
+///
+/// Assets are stored in slots indexed by an [`AssetId`]. A slot is reserved for the [`HandleId`]
+/// of its asset until the asset is freed, even if the asset is [removed](Assets::remove) and set
+/// again in the meantime. Once freed, the handles that accessed the asset are stale: they don't
+/// access the asset that may be stored again with the same [`HandleId`] later.
 #[derive(Debug, Resource)]
 pub struct Assets<T: Asset> {
-    assets: HashMap<HandleId, T>,
+    slots: Vec<AssetSlot<T>>,
+    free_slots: Vec<u32>,
+    indices: HashMap<HandleId, u32>,
+    /// The generation of the new slots, chosen randomly so that an [`AssetId`] resolved by
+    /// another collection is unlikely to be taken for a stale one.
+    first_generation: u32,
+    len: usize,
     events: Events<AssetEvent<T>>,
     pub(crate) ref_change_sender: Sender<RefChange>,
+    pub(crate) waiting_for_dependencies: HashSet<HandleId>,
 }
 
 impl<T: Asset> Assets<T> {
     pub(crate) fn new(ref_change_sender: Sender<RefChange>) -> Self {
         Assets {
-            assets: HashMap::default(),
+            slots: Vec::new(),
+            free_slots: Vec::new(),
+            indices: HashMap::default(),
+            first_generation: fastrand::u32(..),
+            len: 0,
             events: Events::default(),
             ref_change_sender,
+            waiting_for_dependencies: HashSet::default(),
+        }
+    }
+
+    /// Stores the asset in the slot of `id`, reserving a slot if there is none yet, and returns
+    /// the [`AssetId`] of the slot along with the previous asset of the slot.
+    fn insert(&mut self, id: HandleId, asset: T) -> (AssetId<T>, Option<T>) {
+        let index = match self.indices.get(&id) {
+            Some(&index) => index,
+            None => {
+                let index = match self.free_slots.pop() {
+                    Some(index) => index,
+                    None => {
+                        self.slots.push(AssetSlot {
+                            generation: self.first_generation,
+                            handle_id: None,
+                            asset: None,
+                        });
+                        (self.slots.len() - 1) as u32
+                    }
+                };
+                self.slots[index as usize].handle_id = Some(id);
+                self.indices.insert(id, index);
+                index
+            }
+        };
+
+        let slot = &mut self.slots[index as usize];
+        let previous = slot.asset.replace(asset);
+        if previous.is_none() {
+            self.len += 1;
         }
+        let asset_id = AssetId {
+            index,
+            generation: slot.generation,
+            marker: PhantomData,
+        };
+        (asset_id, previous)
+    }
+
+    /// Gets the index of the slot of the handle, resolving the handle to its [`AssetId`] if it
+    /// wasn't yet.
+    ///
+    /// Returns `None` if the handle is stale.
+    fn index(&self, handle: &Handle<T>) -> Option<u32> {
+        if let Some(asset_id) = handle.cached_asset_id() {
+            if let Some(slot) = self.slots.get(asset_id.index as usize) {
+                if slot.generation == asset_id.generation {
+                    if slot.handle_id == Some(handle.id()) {
+                        return Some(asset_id.index);
+                    }
+                } else if asset_id.generation.wrapping_sub(self.first_generation)
+                    < slot.generation.wrapping_sub(self.first_generation)
+                {
+                    // the slot was freed since the handle was resolved
+                    return None;
+                }
+            }
+        }
+
+        // the handle wasn't resolved yet, or was resolved by another collection
+        let index = *self.indices.get(&handle.id())?;
+        handle.cache_asset_id(AssetId::<T> {
+            index,
+            generation: self.slots[index as usize].generation,
+            marker: PhantomData,
+        });
+        Some(index)
+    }
+
+    fn slot(&self, id: AssetId<T>) -> Option<&AssetSlot<T>> {
+        self.slots
+            .get(id.index as usize)
+            .filter(|slot| slot.generation == id.generation)
     }
 
     /// Adds an asset to the collection, returning a Strong handle to that asset.
@@ -89,11 +275,13 @@ impl<T: Asset> Assets<T> {
     /// * [`AssetEvent::Created`]
     pub fn add(&mut self, asset: T) -> Handle<T> {
         let id = HandleId::random::<T>();
-        self.assets.insert(id, asset);
+        let (asset_id, _) = self.insert(id, asset);
         self.events.send(AssetEvent::Created {
             handle: Handle::weak(id),
         });
-        self.get_handle(id)
+        let handle = self.get_handle(id);
+        handle.cache_asset_id(asset_id);
+        handle
     }
 
     /// Add/modify the asset pointed to by the given handle.
@@ -120,7 +308,7 @@ impl<T: Asset> Assets<T> {
     /// * [`AssetEvent::Modified`]: Sent if the asset with given handle already existed.
     pub fn set_untracked<H: Into<HandleId>>(&mut self, handle: H, asset: T) {
         let id: HandleId = handle.into();
-        if self.assets.insert(id, asset).is_some() {
+        if self.insert(id, asset).1.is_some() {
             self.events.send(AssetEvent::Modified {
                 handle: Handle::weak(id),
             });
@@ -135,13 +323,17 @@ impl<T: Asset> Assets<T> {
     ///
     /// This is the main method for accessing asset data from an [Assets] collection. If you need
     /// mutable access to the asset, use [`get_mut`](Assets::get_mut).
+    ///
+    /// Returns `None` if the asset was freed since the handle was last used to access it, even if
+    /// an asset has been stored with the same [`HandleId`] since.
     pub fn get(&self, handle: &Handle<T>) -> Option<&T> {
-        self.assets.get(&handle.into())
+        let index = self.index(handle)?;
+        self.slots[index as usize].asset.as_ref()
     }
 
     /// Checks if an asset exists for the given handle
     pub fn contains(&self, handle: &Handle<T>) -> bool {
-        self.assets.contains_key(&handle.into())
+        self.get(handle).is_some()
     }
 
     /// Get mutable access to the asset for the given handle.
@@ -153,7 +345,58 @@ impl<T: Asset> Assets<T> {
         self.events.send(AssetEvent::Modified {
             handle: Handle::weak(id),
         });
-        self.assets.get_mut(&id)
+        let index = self.index(handle)?;
+        self.slots[index as usize].asset.as_mut()
+    }
+
+    /// Gets the [`AssetId`] of the asset of the given handle, if it exists.
+    pub fn asset_id(&self, handle: &Handle<T>) -> Option<AssetId<T>> {
+        let index = self.index(handle)?;
+        let slot = &self.slots[index as usize];
+        slot.asset.as_ref().map(|_| AssetId {
+            index,
+            generation: slot.generation,
+            marker: PhantomData,
+        })
+    }
+
+    /// Gets the [`HandleId`] of the asset with the given [`AssetId`].
+    ///
+    /// Returns `None` if the asset was freed.
+    pub fn handle_id(&self, id: AssetId<T>) -> Option<HandleId> {
+        self.slot(id).and_then(|slot| slot.handle_id)
+    }
+
+    /// Gets the asset with the given [`AssetId`].
+    ///
+    /// Returns `None` if the asset was freed, even if another asset has been stored in its slot
+    /// since.
+    pub fn get_by_id(&self, id: AssetId<T>) -> Option<&T> {
+        self.slot(id).and_then(|slot| slot.asset.as_ref())
+    }
+
+    /// Checks if an asset exists for the given [`AssetId`].
+    pub fn contains_id(&self, id: AssetId<T>) -> bool {
+        self.get_by_id(id).is_some()
+    }
+
+    /// Get mutable access to the asset with the given [`AssetId`].
+    ///
+    /// # Events
+    ///
+    /// * [`AssetEvent::Modified`]: Sent if the asset exists.
+    pub fn get_mut_by_id(&mut self, id: AssetId<T>) -> Option<&mut T> {
+        let slot = self
+            .slots
+            .get_mut(id.index as usize)
+            .filter(|slot| slot.generation == id.generation)?;
+        let asset = slot.asset.as_mut()?;
+        if let Some(handle_id) = slot.handle_id {
+            self.events.send(AssetEvent::Modified {
+                handle: Handle::weak(handle_id),
+            });
+        }
+        Some(asset)
     }
 
     /// Gets a _Strong_ handle pointing to the same asset as the given one.
@@ -171,52 +414,57 @@ impl<T: Asset> Assets<T> {
         handle: H,
         insert_fn: impl FnOnce() -> T,
     ) -> &mut T {
-        let mut event = None;
         let id: HandleId = handle.into();
-        let borrowed = self.assets.entry(id).or_insert_with(|| {
-            event = Some(AssetEvent::Created {
-                handle: Handle::weak(id),
-            });
-            insert_fn()
-        });
-
-        if let Some(event) = event {
-            self.events.send(event);
-        }
-        borrowed
+        let index = match self.indices.get(&id) {
+            Some(&index) if self.slots[index as usize].asset.is_some() => index,
+            _ => {
+                self.events.send(AssetEvent::Created {
+                    handle: Handle::weak(id),
+                });
+                self.insert(id, insert_fn()).0.index
+            }
+        };
+        self.slots[index as usize].asset.as_mut().unwrap()
     }
 
     /// Gets an iterator over all assets in the collection.
     pub fn iter(&self) -> impl Iterator<Item = (HandleId, &T)> {
-        self.assets.iter().map(|(k, v)| (*k, v))
+        self.slots
+            .iter()
+            .filter_map(|slot| Some((slot.handle_id?, slot.asset.as_ref()?)))
     }
 
     /// Gets a mutable iterator over all assets in the collection.
     pub fn iter_mut(&mut self) -> impl Iterator<Item = (HandleId, &mut T)> {
-        self.assets.iter_mut().map(|(k, v)| {
+        self.slots.iter_mut().filter_map(|slot| {
+            let id = slot.handle_id?;
+            let asset = slot.asset.as_mut()?;
             self.events.send(AssetEvent::Modified {
-                handle: Handle::weak(*k),
+                handle: Handle::weak(id),
             });
-            (*k, v)
+            Some((id, asset))
         })
     }
 
     /// Gets an iterator over all [`HandleId`]'s in the collection.
     pub fn ids(&self) -> impl Iterator<Item = HandleId> + '_ {
-        self.assets.keys().cloned()
+        self.iter().map(|(id, _)| id)
     }
 
     /// Removes an asset for the given handle.
     ///
-    /// The asset is returned if it existed in the collection, otherwise `None`.
+    /// The asset is returned if it existed in the collection, otherwise `None`. The slot of the
+    /// asset stays reserved for its handles until the asset is freed.
     ///
     /// # Events
     ///
     /// * [`AssetEvent::Removed`]
     pub fn remove<H: Into<HandleId>>(&mut self, handle: H) -> Option<T> {
         let id: HandleId = handle.into();
-        let asset = self.assets.remove(&id);
+        let index = *self.indices.get(&id)?;
+        let asset = self.slots[index as usize].asset.take();
         if asset.is_some() {
+            self.len -= 1;
             self.events.send(AssetEvent::Removed {
                 handle: Handle::weak(id),
             });
@@ -224,18 +472,35 @@ impl<T: Asset> Assets<T> {
         asset
     }
 
+    /// Frees the asset of a handle whose last Strong handle was dropped, releasing its slot so
+    /// that the handles of the asset become stale.
+    pub(crate) fn free(&mut self, id: HandleId) {
+        self.remove(id);
+        if let Some(index) = self.indices.remove(&id) {
+            let slot = &mut self.slots[index as usize];
+            slot.generation = slot.generation.wrapping_add(1);
+            slot.handle_id = None;
+            self.free_slots.push(index);
+        }
+    }
+
     /// Clears the inner asset map, removing all key-value pairs.
     ///
     /// Keeps the allocated memory for reuse.
     pub fn clear(&mut self) {
-        self.assets.clear();
+        for slot in &mut self.slots {
+            slot.asset = None;
+        }
+        self.len = 0;
     }
 
     /// Reserves capacity for at least additional more elements to be inserted into the assets.
     ///
     /// The collection may reserve more space to avoid frequent reallocations.
     pub fn reserve(&mut self, additional: usize) {
-        self.assets.reserve(additional);
+        self.slots
+            .reserve(additional.saturating_sub(self.free_slots.len()));
+        self.indices.reserve(additional);
     }
 
     /// Shrinks the capacity of the asset map as much as possible.
@@ -243,7 +508,9 @@ impl<T: Asset> Assets<T> {
     /// It will drop down as much as possible while maintaining the internal rules and possibly
     /// leaving some space in accordance with the resize policy.
     pub fn shrink_to_fit(&mut self) {
-        self.assets.shrink_to_fit();
+        self.slots.shrink_to_fit();
+        self.free_slots.shrink_to_fit();
+        self.indices.shrink_to_fit();
     }
 
     /// A system that creates [`AssetEvent`]s at the end of the frame based on changes in the
@@ -259,14 +526,33 @@ impl<T: Asset> Assets<T> {
         }
     }
 
+    /// Sends [`AssetEvent::LoadedWithDependencies`] for the loaded assets whose dependencies
+    /// finished loading.
+    pub(crate) fn send_loaded_with_dependencies_events(&mut self, asset_server: &AssetServer) {
+        let events = &mut self.events;
+        self.waiting_for_dependencies.retain(|id| {
+            match asset_server.get_recursive_dependency_load_state(*id) {
+                LoadState::Loaded => {
+                    events.send(AssetEvent::LoadedWithDependencies {
+                        handle: Handle::weak(*id),
+                    });
+                    false
+                }
+                LoadState::Loading => true,
+                // the asset or one of its dependencies failed to load or was unloaded
+                LoadState::NotLoaded | LoadState::Failed | LoadState::Unloaded => false,
+            }
+        });
+    }
+
     /// Gets the number of assets in the collection.
     pub fn len(&self) -> usize {
-        self.assets.len()
+        self.len
     }
 
     /// Returns `true` if there are no stored assets.
     pub fn is_empty(&self) -> bool {
-        self.assets.is_empty()
+        self.len == 0
     }
 }
 
@@ -282,7 +568,9 @@ pub trait AddAsset {
     /// Registers `T` as a supported internal asset in the application.
     ///
     /// Internal assets (e.g. shaders) are bundled directly into the app and can't be hot reloaded
-    /// using the conventional API. See `DebugAssetServerPlugin`.
+    /// using the conventional API. Internal assets of this type loaded with
+    /// [`load_internal_asset`](crate::load_internal_asset) are reloaded from their
+    /// [`EmbeddedAssets`](crate::EmbeddedAssets) when those are watched for changes.
     ///
     /// Adding the same type again after it has been added does nothing.
     fn add_debug_asset<T: Clone>(&mut self) -> &mut Self
@@ -296,20 +584,33 @@ pub trait AddAsset {
     where
         T: AssetLoader + FromWorld;
 
-    /// Adds an asset loader `T` for internal assets using default values.
-    ///
-    /// Internal assets (e.g. shaders) are bundled directly into the app and can't be hot reloaded
-    /// using the conventional API. See `DebugAssetServerPlugin`.
+    /// Adds the provided asset loader to the application.
+    fn add_asset_loader<T>(&mut self, loader: T) -> &mut Self
+    where
+        T: AssetLoader;
+
+    /// Adds an asset saver `T` using default values.
     ///
     /// The default values may come from the `World` or from `T::default()`.
-    fn init_debug_asset_loader<T>(&mut self) -> &mut Self
+    fn init_asset_saver<T>(&mut self) -> &mut Self
     where
-        T: AssetLoader + FromWorld;
+        T: AssetSaver + FromWorld;
 
-    /// Adds the provided asset loader to the application.
-    fn add_asset_loader<T>(&mut self, loader: T) -> &mut Self
+    /// Adds the provided asset saver to the application.
+    fn add_asset_saver<T>(&mut self, saver: T) -> &mut Self
     where
-        T: AssetLoader;
+        T: AssetSaver;
+
+    /// Adds the provided asset processor to the application's [`AssetPipeline`].
+    ///
+    /// Does nothing if asset processing isn't enabled with [`AssetProcessorSettings`].
+    ///
+    /// [`AssetPipeline`]: crate::AssetPipeline
+    /// [`AssetProcessorSettings`]: crate::AssetProcessorSettings
+    #[cfg(all(not(target_arch = "wasm32"), not(target_os = "android")))]
+    fn add_asset_processor<T>(&mut self, processor: T) -> &mut Self
+    where
+        T: crate::AssetProcessor;
 }
 
 impl AddAsset for App {
@@ -325,9 +626,18 @@ impl AddAsset for App {
             asset_server.register_asset_type::<T>()
         };
 
+        // the storage is also updated at the end of the frame, to remove the assets freed during
+        // the frame before their events are sent
         self.insert_resource(assets)
-            .add_system_to_stage(AssetStage::AssetEvents, Assets::<T>::asset_event_system)
             .add_system_to_stage(AssetStage::LoadAssets, update_asset_storage_system::<T>)
+            .add_system_to_stage(
+                AssetStage::AssetEvents,
+                update_asset_storage_system::<T>.after(free_unused_assets_system),
+            )
+            .add_system_to_stage(
+                AssetStage::AssetEvents,
+                Assets::<T>::asset_event_system.after(update_asset_storage_system::<T>),
+            )
             .register_type::<Handle<T>>()
             .add_event::<AssetEvent<T>>()
     }
@@ -336,14 +646,13 @@ impl AddAsset for App {
     where
         T: Asset,
     {
-        #[cfg(feature = "debug_asset_server")]
+        #[cfg(all(
+            feature = "filesystem_watcher",
+            all(not(target_arch = "wasm32"), not(target_os = "android"))
+        ))]
         {
-            self.add_system(crate::debug_asset_server::sync_debug_assets::<T>);
-            let mut app = self
-                .world
-                .non_send_resource_mut::<crate::debug_asset_server::DebugAssetApp>();
-            app.add_asset::<T>()
-                .init_resource::<crate::debug_asset_server::HandleMap<T>>();
+            self.add_system(crate::io::sync_internal_assets::<T>)
+                .init_resource::<crate::io::InternalAssetHandles<T>>();
         }
         self
     }
@@ -356,62 +665,54 @@ impl AddAsset for App {
         self.add_asset_loader(result)
     }
 
-    fn init_debug_asset_loader<T>(&mut self) -> &mut Self
+    fn add_asset_loader<T>(&mut self, loader: T) -> &mut Self
     where
-        T: AssetLoader + FromWorld,
+        T: AssetLoader,
     {
-        #[cfg(feature = "debug_asset_server")]
-        {
-            let mut app = self
-                .world
-                .non_send_resource_mut::<crate::debug_asset_server::DebugAssetApp>();
-            app.init_asset_loader::<T>();
-        }
+        self.world.resource_mut::<AssetServer>().add_loader(loader);
         self
     }
 
-    fn add_asset_loader<T>(&mut self, loader: T) -> &mut Self
+    fn init_asset_saver<T>(&mut self) -> &mut Self
     where
-        T: AssetLoader,
+        T: AssetSaver + FromWorld,
     {
-        self.world.resource_mut::<AssetServer>().add_loader(loader);
+        let result = T::from_world(&mut self.world);
+        self.add_asset_saver(result)
+    }
+
+    fn add_asset_saver<T>(&mut self, saver: T) -> &mut Self
+    where
+        T: AssetSaver,
+    {
+        self.world.resource::<AssetServer>().add_saver(saver);
         self
     }
-}
 
-/// Loads an internal asset.
-///
-/// Internal assets (e.g. shaders) are bundled directly into the app and can't be hot reloaded
-/// using the conventional API. See `DebugAssetServerPlugin`.
-#[cfg(feature = "debug_asset_server")]
-#[macro_export]
-macro_rules! load_internal_asset {
-    ($app: ident, $handle: ident, $path_str: expr, $loader: expr) => {{
-        {
-            let mut debug_app = $app
-                .world
-                .non_send_resource_mut::<$crate::debug_asset_server::DebugAssetApp>();
-            $crate::debug_asset_server::register_handle_with_loader(
-                $loader,
-                &mut debug_app,
-                $handle,
-                file!(),
-                $path_str,
-            );
+    #[cfg(all(not(target_arch = "wasm32"), not(target_os = "android")))]
+    fn add_asset_processor<T>(&mut self, processor: T) -> &mut Self
+    where
+        T: crate::AssetProcessor,
+    {
+        if let Some(mut asset_pipeline) = self.world.get_resource_mut::<crate::AssetPipeline>() {
+            asset_pipeline.add_processor(processor);
         }
-        let mut assets = $app.world.resource_mut::<$crate::Assets<_>>();
-        assets.set_untracked($handle, ($loader)(include_str!($path_str)));
-    }};
+        self
+    }
 }
 
 /// Loads an internal asset.
 ///
 /// Internal assets (e.g. shaders) are bundled directly into the app and can't be hot reloaded
-/// using the conventional API. See `DebugAssetServerPlugin`.
-#[cfg(not(feature = "debug_asset_server"))]
+/// using the conventional API. The file is embedded with [`embedded_asset`](crate::embedded_asset)
+/// and, for asset types added with [`add_debug_asset`](crate::AddAsset::add_debug_asset), reloaded
+/// into `$handle` from the `embedded` asset source when its source file changes. See
+/// `DebugAssetServerPlugin`.
 #[macro_export]
 macro_rules! load_internal_asset {
     ($app: ident, $handle: ident, $path_str: expr, $loader: expr) => {{
+        let path = $crate::embedded_asset!($app, $path_str);
+        $crate::watch_internal_asset($loader, &mut $app.world, $handle, path);
         let mut assets = $app.world.resource_mut::<$crate::Assets<_>>();
         assets.set_untracked($handle, ($loader)(include_str!($path_str)));
     }};
@@ -421,7 +722,7 @@ macro_rules! load_internal_asset {
 mod tests {
     use bevy_app::App;
 
-    use crate::{AddAsset, Assets};
+    use crate::{AddAsset, Assets, Handle, HandleId};
 
     #[test]
     fn asset_overwriting() {
@@ -438,4 +739,90 @@ mod tests {
         let assets_after = app.world.resource_mut::<Assets<MyAsset>>();
         assert!(assets_after.get(&handle).is_some());
     }
+
+    #[derive(bevy_reflect::TypeUuid, Debug, PartialEq)]
+    #[uuid = "9c7c6f4e-7c4c-4a8e-9df1-1fbb2b0a5d3e"]
+    struct Counter(usize);
+
+    #[test]
+    fn asset_freed_in_the_frame_its_handle_is_dropped() {
+        let mut app = App::new();
+        app.add_plugin(bevy_core::CorePlugin)
+            .add_plugin(crate::AssetPlugin)
+            .add_asset::<Counter>();
+        let handle = app.world.resource_mut::<Assets<Counter>>().add(Counter(0));
+        let weak_handle = handle.clone_weak();
+
+        app.update();
+        assert!(app
+            .world
+            .resource::<Assets<Counter>>()
+            .contains(&weak_handle));
+
+        drop(handle);
+        app.update();
+        assert!(!app
+            .world
+            .resource::<Assets<Counter>>()
+            .contains(&weak_handle));
+    }
+
+    #[test]
+    fn dangling_handle_is_stale_after_its_asset_is_set_again() {
+        let mut app = App::new();
+        app.add_plugin(bevy_core::CorePlugin)
+            .add_plugin(crate::AssetPlugin)
+            .add_asset::<Counter>();
+        let id = HandleId::random::<Counter>();
+        let handle = app
+            .world
+            .resource_mut::<Assets<Counter>>()
+            .set(id, Counter(0));
+        let weak_handle = Handle::<Counter>::weak(id);
+        let asset_id = {
+            let assets = app.world.resource::<Assets<Counter>>();
+            assert_eq!(assets.get(&weak_handle), Some(&Counter(0)));
+            assets.asset_id(&handle).unwrap()
+        };
+        assert_eq!(weak_handle.asset_id(), Some(asset_id));
+
+        drop(handle);
+        app.update();
+        let mut assets = app.world.resource_mut::<Assets<Counter>>();
+        let new_handle = assets.set(id, Counter(1));
+        assert_eq!(assets.get(&new_handle), Some(&Counter(1)));
+        assert_eq!(assets.get(&weak_handle), None);
+        assert_eq!(assets.get_by_id(asset_id), None);
+        assert_eq!(assets.get(&Handle::weak(id)), Some(&Counter(1)));
+
+        let new_asset_id = new_handle.asset_id().unwrap();
+        assert_eq!(new_asset_id.index(), asset_id.index());
+        assert_eq!(
+            new_asset_id.generation(),
+            asset_id.generation().wrapping_add(1)
+        );
+        assert_eq!(assets.handle_id(new_asset_id), Some(id));
+    }
+
+    #[test]
+    fn removed_asset_keeps_its_slot_until_freed() {
+        let mut app = App::new();
+        app.add_plugin(bevy_core::CorePlugin)
+            .add_plugin(crate::AssetPlugin)
+            .add_asset::<Counter>();
+        let mut assets = app.world.resource_mut::<Assets<Counter>>();
+        let handle = assets.add(Counter(0));
+        let asset_id = handle.asset_id().unwrap();
+
+        assert_eq!(assets.remove(&handle), Some(Counter(0)));
+        assert!(!assets.contains(&handle));
+        assert!(assets.is_empty());
+
+        assets.set_untracked(&handle, Counter(1));
+        assert_eq!(assets.get(&handle), Some(&Counter(1)));
+        assert_eq!(assets.asset_id(&handle), Some(asset_id));
+        *assets.get_mut_by_id(asset_id).unwrap() = Counter(2);
+        assert_eq!(assets.get_by_id(asset_id), Some(&Counter(2)));
+        assert_eq!(assets.len(), 1);
+    }
 }
@@ -0,0 +1,130 @@
+use crate::{AssetServer, LoaderStats};
+use bevy_app::prelude::*;
+use bevy_diagnostic::{fit_diagnostic_name, Diagnostic, DiagnosticId, Diagnostics};
+use bevy_ecs::system::{Local, Res, ResMut};
+use bevy_utils::{HashMap, Instant};
+
+/// Adds diagnostics of the loads of the [`AssetServer`] to an [`App`].
+///
+/// The diagnostics are the number of loads in flight, the number of bytes read per second through
+/// the [`AssetIo`](crate::AssetIo) and, for each [`AssetLoader`](crate::AssetLoader) that loaded
+/// assets, the average time the loader took to load an asset.
+#[derive(Default)]
+pub struct AssetLoadDiagnosticsPlugin;
+
+impl Plugin for AssetLoadDiagnosticsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_startup_system(Self::setup_system)
+            .add_system(Self::diagnostic_system);
+    }
+}
+
+/// The load statistics of the last measurements.
+#[derive(Default)]
+pub struct LastLoadStats {
+    bytes_read: Option<(Instant, u64)>,
+    loaders: HashMap<&'static str, LoaderStats>,
+}
+
+impl AssetLoadDiagnosticsPlugin {
+    /// The number of assets being loaded.
+    pub const LOADS_IN_FLIGHT: DiagnosticId =
+        DiagnosticId::from_u128(198611728393740581744092810384755391290);
+    /// The number of bytes read per second to load assets.
+    pub const BYTES_READ_PER_SECOND: DiagnosticId =
+        DiagnosticId::from_u128(266019432417836209476637915398240532613);
+
+    /// Gets the id of the average load time diagnostic of the loader with the given type name,
+    /// which is the same in every run of the app.
+    pub fn loader_diagnostic_id(loader_type_name: &str) -> DiagnosticId {
+        /// Distinguishes the ids of the loader diagnostics from the other diagnostic ids.
+        const NAMESPACE: u128 = 0x9d3f_0c61_5b2e_4a7d_0000_0000_0000_0000;
+
+        DiagnosticId::from_name(NAMESPACE, loader_type_name)
+    }
+
+    /// Registers the load diagnostics for the current application.
+    pub fn setup_system(mut diagnostics: ResMut<Diagnostics>) {
+        diagnostics.add(Diagnostic::new(
+            Self::LOADS_IN_FLIGHT,
+            "asset_loads_in_flight",
+            20,
+        ));
+        diagnostics.add(
+            Diagnostic::new(Self::BYTES_READ_PER_SECOND, "asset_bytes_read", 20).with_suffix("B/s"),
+        );
+    }
+
+    /// Updates the load diagnostics, registering the diagnostics of the loaders as they load
+    /// assets.
+    pub fn diagnostic_system(
+        mut diagnostics: ResMut<Diagnostics>,
+        asset_server: Res<AssetServer>,
+        mut last: Local<LastLoadStats>,
+    ) {
+        diagnostics.add_measurement(Self::LOADS_IN_FLIGHT, || {
+            asset_server.loads_in_flight() as f64
+        });
+
+        let now = Instant::now();
+        let bytes_read = asset_server.bytes_read();
+        if let Some((last_time, last_bytes_read)) = last.bytes_read {
+            let elapsed = (now - last_time).as_secs_f64();
+            if elapsed > 0.0 {
+                diagnostics.add_measurement(Self::BYTES_READ_PER_SECOND, || {
+                    (bytes_read - last_bytes_read) as f64 / elapsed
+                });
+            }
+        }
+        last.bytes_read = Some((now, bytes_read));
+
+        for (loader, stats) in asset_server.loader_stats() {
+            let id = Self::loader_diagnostic_id(loader);
+            let last_stats = match last.loaders.get(loader) {
+                Some(last_stats) => *last_stats,
+                None => {
+                    diagnostics.add(
+                        Diagnostic::new(id, loader_diagnostic_name(loader), 20).with_suffix("ms"),
+                    );
+                    LoaderStats::default()
+                }
+            };
+
+            // measure the average load time of the loads that finished since the last update
+            let loads = stats.loads - last_stats.loads;
+            if loads > 0 {
+                let load_time = stats.total_load_time - last_stats.total_load_time;
+                diagnostics.add_measurement(id, || load_time.as_secs_f64() * 1000.0 / loads as f64);
+            }
+            last.loaders.insert(loader, stats);
+        }
+    }
+}
+
+/// Names the diagnostic of a loader after the type name of the loader, without its module path.
+fn loader_diagnostic_name(loader_type_name: &str) -> String {
+    let loader_name = loader_type_name
+        .split('<')
+        .next()
+        .and_then(|path| path.rsplit("::").next())
+        .unwrap_or(loader_type_name);
+    fit_diagnostic_name(loader_name, "load_time")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_loader_diagnostic_name() {
+        assert_eq!(
+            loader_diagnostic_name("bevy_render::texture::ImageTextureLoader"),
+            "ImageTextureLoader load_time"
+        );
+        assert_eq!(
+            loader_diagnostic_name("my_game::loaders::ALoaderWithAVeryVeryLongName"),
+            "…WithAVeryVeryLongName load_time"
+        );
+        assert_eq!(loader_diagnostic_name("a::B<c::D>"), "B load_time");
+    }
+}
@@ -1,4 +1,6 @@
 //! Diagnostic providers for `bevy_diagnostic`.
 
 mod asset_count_diagnostics_plugin;
+mod asset_load_diagnostics_plugin;
 pub use asset_count_diagnostics_plugin::AssetCountDiagnosticsPlugin;
+pub use asset_load_diagnostics_plugin::{AssetLoadDiagnosticsPlugin, LastLoadStats};
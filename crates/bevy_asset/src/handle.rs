@@ -1,37 +1,26 @@
 use std::{
+    cell::RefCell,
     cmp::Ordering,
     fmt::Debug,
     hash::{Hash, Hasher},
     marker::PhantomData,
+    sync::atomic::{AtomicU64, Ordering as AtomicOrdering},
 };
 
 use crate::{
     path::{AssetPath, AssetPathId},
-    Asset, Assets,
+    Asset, AssetId, Assets,
 };
 use bevy_ecs::{component::Component, reflect::ReflectComponent};
 use bevy_reflect::{
     std_traits::ReflectDefault, FromReflect, Reflect, ReflectDeserialize, ReflectSerialize,
 };
-use bevy_utils::Uuid;
+use bevy_utils::{HashMap, Uuid};
 use crossbeam_channel::{Receiver, Sender};
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
 /// A unique, stable asset id.
-#[derive(
-    Debug,
-    Clone,
-    Copy,
-    Eq,
-    PartialEq,
-    Hash,
-    Ord,
-    PartialOrd,
-    Serialize,
-    Deserialize,
-    Reflect,
-    FromReflect,
-)]
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, Ord, PartialOrd, Reflect, FromReflect)]
 #[reflect_value(Serialize, Deserialize, PartialEq, Hash)]
 pub enum HandleId {
     /// A handle id of a loaded asset.
@@ -53,6 +42,90 @@ impl<'a> From<AssetPath<'a>> for HandleId {
     }
 }
 
+/// The serialized form of a [`HandleId`].
+#[derive(Serialize, Deserialize)]
+#[serde(rename = "HandleId")]
+enum SerializedHandleId {
+    Id(Uuid, u64),
+    AssetPathId(AssetPathId),
+    /// An asset path, as written by [`with_handle_paths`].
+    Path(String),
+}
+
+thread_local! {
+    static HANDLE_PATHS: RefCell<Option<HashMap<HandleId, AssetPath<'static>>>> =
+        RefCell::new(None);
+}
+
+/// Runs `f` with `handle_paths` as the asset paths of the [`HandleId`]s it (de)serializes.
+///
+/// Within `f`, a [`HandleId`] found in `handle_paths` serializes as its asset path, like
+/// `Path("models/cube.gltf#Mesh0")`, instead of as an opaque id. A [`HandleId`] deserialized from
+/// an asset path is the id the [`AssetServer`](crate::AssetServer) loads that path with, and is
+/// added to `handle_paths`. Scenes use this to store their [`Handle`]s as editable paths and to
+/// load them again.
+pub fn with_handle_paths<R>(
+    handle_paths: &mut HashMap<HandleId, AssetPath<'static>>,
+    f: impl FnOnce() -> R,
+) -> R {
+    // Hands the paths back and restores the enclosing scope even if `f` panics
+    struct Restore<'a> {
+        handle_paths: &'a mut HashMap<HandleId, AssetPath<'static>>,
+        previous: Option<HashMap<HandleId, AssetPath<'static>>>,
+    }
+
+    impl Drop for Restore<'_> {
+        fn drop(&mut self) {
+            let handle_paths = HANDLE_PATHS.with(|paths| paths.replace(self.previous.take()));
+            *self.handle_paths = handle_paths.unwrap_or_default();
+        }
+    }
+
+    let previous = HANDLE_PATHS.with(|paths| paths.replace(Some(std::mem::take(handle_paths))));
+    let _restore = Restore {
+        handle_paths,
+        previous,
+    };
+    f()
+}
+
+impl Serialize for HandleId {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let path = HANDLE_PATHS.with(|paths| {
+            paths
+                .borrow()
+                .as_ref()
+                .and_then(|paths| paths.get(self))
+                .map(ToString::to_string)
+        });
+        let serialized = match (path, *self) {
+            (Some(path), _) => SerializedHandleId::Path(path),
+            (None, HandleId::Id(type_uuid, id)) => SerializedHandleId::Id(type_uuid, id),
+            (None, HandleId::AssetPathId(id)) => SerializedHandleId::AssetPathId(id),
+        };
+        serialized.serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for HandleId {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Ok(match SerializedHandleId::deserialize(deserializer)? {
+            SerializedHandleId::Id(type_uuid, id) => HandleId::Id(type_uuid, id),
+            SerializedHandleId::AssetPathId(id) => HandleId::AssetPathId(id),
+            SerializedHandleId::Path(path) => {
+                let path = AssetPath::from(path.as_str()).to_owned();
+                let id = path.get_id().into();
+                HANDLE_PATHS.with(|paths| {
+                    if let Some(paths) = paths.borrow_mut().as_mut() {
+                        paths.insert(id, path);
+                    }
+                });
+                id
+            }
+        })
+    }
+}
+
 impl HandleId {
     /// Creates a random id for an asset of type `T`.
     #[inline]
@@ -101,6 +174,10 @@ impl HandleId {
 /// handle to the unloaded asset, but it will not be able to retrieve the image data, resulting in
 /// collisions no longer being detected for that entity.
 ///
+/// A handle resolves to the [`AssetId`] of its asset the first time it is used to access it.
+/// Once the asset is freed, the handle stays dangling: it won't access any asset stored with the
+/// same [`HandleId`] afterwards, and a new handle must be obtained for it.
+///
 #[derive(Component, Reflect, FromReflect)]
 #[reflect(Component, Default)]
 pub struct Handle<T>
@@ -111,6 +188,8 @@ where
     #[reflect(ignore)]
     handle_type: HandleType,
     #[reflect(ignore)]
+    asset_id: CachedAssetId,
+    #[reflect(ignore)]
     // NOTE: PhantomData<fn() -> T> gives this safe Send/Sync impls
     marker: PhantomData<fn() -> T>,
 }
@@ -123,6 +202,38 @@ enum HandleType {
     Strong(Sender<RefChange>),
 }
 
+/// The [`AssetId`] a [`Handle`] resolved to, packed in an atomic so that it can be cached through
+/// a shared reference.
+pub(crate) struct CachedAssetId(AtomicU64);
+
+impl CachedAssetId {
+    const UNRESOLVED: u64 = u64::MAX;
+
+    fn get<T: Asset>(&self) -> Option<AssetId<T>> {
+        match self.0.load(AtomicOrdering::Relaxed) {
+            Self::UNRESOLVED => None,
+            bits => Some(AssetId::from_bits(bits)),
+        }
+    }
+
+    fn set<T: Asset>(&self, asset_id: AssetId<T>) {
+        self.0.store(asset_id.to_bits(), AtomicOrdering::Relaxed);
+    }
+
+    fn copy_from(&self, other: &CachedAssetId) {
+        self.0.store(
+            other.0.load(AtomicOrdering::Relaxed),
+            AtomicOrdering::Relaxed,
+        );
+    }
+}
+
+impl Default for CachedAssetId {
+    fn default() -> Self {
+        CachedAssetId(AtomicU64::new(Self::UNRESOLVED))
+    }
+}
+
 impl Debug for HandleType {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -138,16 +249,21 @@ impl<T: Asset> Handle<T> {
         Self {
             id,
             handle_type: HandleType::Strong(ref_change_sender),
+            asset_id: CachedAssetId::default(),
             marker: PhantomData,
         }
     }
 
     /// Creates a weak handle into an Asset identified by `id`.
+    ///
+    /// The handle doesn't keep the asset loaded: once the asset is freed,
+    /// [`Assets::get`](crate::Assets::get) returns `None` for it.
     #[inline]
     pub fn weak(id: HandleId) -> Self {
         Self {
             id,
             handle_type: HandleType::Weak,
+            asset_id: CachedAssetId::default(),
             marker: PhantomData,
         }
     }
@@ -158,11 +274,28 @@ impl<T: Asset> Handle<T> {
         self.id
     }
 
+    /// The [`AssetId`] this handle resolved to, if it was already used to access its asset.
+    ///
+    /// Use [`Assets::asset_id`] to resolve it.
+    #[inline]
+    pub fn asset_id(&self) -> Option<AssetId<T>> {
+        self.asset_id.get()
+    }
+
+    pub(crate) fn cached_asset_id(&self) -> Option<AssetId<T>> {
+        self.asset_id.get()
+    }
+
+    pub(crate) fn cache_asset_id(&self, asset_id: AssetId<T>) {
+        self.asset_id.set(asset_id);
+    }
+
     /// Recasts this handle as a weak handle of an Asset `U`.
     pub fn as_weak<U: Asset>(&self) -> Handle<U> {
         Handle {
             id: self.id,
             handle_type: HandleType::Weak,
+            asset_id: CachedAssetId::default(),
             marker: PhantomData,
         }
     }
@@ -193,7 +326,9 @@ impl<T: Asset> Handle<T> {
     #[inline]
     #[must_use]
     pub fn clone_weak(&self) -> Self {
-        Self::weak(self.id)
+        let handle = Self::weak(self.id);
+        handle.asset_id.copy_from(&self.asset_id);
+        handle
     }
 
     /// Creates an untyped copy of this handle.
@@ -300,10 +435,12 @@ impl<T: Asset> Debug for Handle<T> {
 
 impl<T: Asset> Clone for Handle<T> {
     fn clone(&self) -> Self {
-        match self.handle_type {
+        let handle = match self.handle_type {
             HandleType::Strong(ref sender) => Handle::strong(self.id, sender.clone()),
             HandleType::Weak => Handle::weak(self.id),
-        }
+        };
+        handle.asset_id.copy_from(&self.asset_id);
+        handle
     }
 }
 
@@ -392,6 +529,7 @@ impl HandleUntyped {
         Handle {
             handle_type,
             id: self.id,
+            asset_id: CachedAssetId::default(),
             marker: PhantomData::default(),
         }
     }
@@ -0,0 +1,459 @@
+use crate::{AssetIo, AssetIoError, Metadata};
+use anyhow::Result;
+use bevy_utils::{BoxedFuture, HashMap, HashSet};
+use std::{
+    borrow::Cow,
+    path::{Path, PathBuf},
+};
+
+/// Returns `true` if `name` can name an asset source in the scheme of a path.
+///
+/// Names are at least two characters long, so that the drive letters of Windows paths aren't
+/// taken for sources.
+fn is_asset_source_name(name: &str) -> bool {
+    name.len() > 1
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+}
+
+/// Splits the path of an asset into the name of its asset source and its path in that source.
+///
+/// The source is named by the scheme of the path, as in `mods://textures/player.png`. Paths
+/// without a scheme belong to the default source, including paths like `player:1.png`.
+pub(crate) fn split_asset_source(path: &Path) -> (Option<&str>, &Path) {
+    let path_str = match path.to_str() {
+        Some(path_str) => path_str,
+        None => return (None, path),
+    };
+    match path_str.split_once("://") {
+        Some((source, rest)) if is_asset_source_name(source) => (Some(source), Path::new(rest)),
+        _ => (None, path),
+    }
+}
+
+/// Restores the `://` after the scheme of a path whose separators were collapsed.
+///
+/// Joining paths collapses them: the parent of `mods://scene.gltf` is `mods:`, so the textures
+/// referenced by the scene would be loaded from `mods:/texture.png` instead of the `mods` source.
+pub(crate) fn normalize_asset_source(path: Cow<Path>) -> Cow<Path> {
+    let path_str = match path.to_str() {
+        Some(path_str) => path_str,
+        None => return path,
+    };
+    match path_str.split_once(':') {
+        Some((source, rest))
+            if is_asset_source_name(source)
+                && rest.starts_with(['/', '\\'])
+                && !rest.starts_with("//") =>
+        {
+            let rest = rest.trim_start_matches(['/', '\\']);
+            Cow::Owned(PathBuf::from(format!("{source}://{rest}")))
+        }
+        _ => path,
+    }
+}
+
+/// I/O implementation serving assets from several named sources.
+///
+/// The source of an asset is selected from the scheme of its path: `mods://textures/player.png`
+/// is loaded from the `mods` source at `textures/player.png`, while paths without a scheme are
+/// loaded from the default source.
+///
+/// ```
+/// # use bevy_asset::{AssetServer, AssetSources, LayeredAssetIo, MemoryAssetIo};
+/// let base_game = MemoryAssetIo::default().with_file("player.png", b"base".to_vec());
+/// let mods = MemoryAssetIo::default().with_file("player.png", b"mod".to_vec());
+/// let asset_sources = AssetSources::new(Box::new(
+///     // files of `mods` override the ones of the base game
+///     LayeredAssetIo::new(Box::new(base_game)).with_layer(mods),
+/// ))
+/// .with_source("memory", MemoryAssetIo::default());
+///
+/// let asset_server = AssetServer::new(asset_sources);
+/// ```
+pub struct AssetSources {
+    default_source: Box<dyn AssetIo>,
+    sources: HashMap<String, Box<dyn AssetIo>>,
+}
+
+impl AssetSources {
+    /// Creates asset sources loading the paths without a scheme from `default_source`.
+    pub fn new(default_source: Box<dyn AssetIo>) -> Self {
+        Self {
+            default_source,
+            sources: HashMap::default(),
+        }
+    }
+
+    /// Adds a source loading the paths with the scheme `name://`, replacing the source previously
+    /// added with that name.
+    pub fn add_source<T: AssetIo>(&mut self, name: impl Into<String>, source: T) {
+        self.sources.insert(name.into(), Box::new(source));
+    }
+
+    /// Adds a source loading the paths with the scheme `name://` and returns `self`.
+    #[must_use]
+    pub fn with_source<T: AssetIo>(mut self, name: impl Into<String>, source: T) -> Self {
+        self.add_source(name, source);
+        self
+    }
+
+    /// Returns the source with the given name, or the default source for `None`.
+    pub fn source(&self, name: Option<&str>) -> Option<&dyn AssetIo> {
+        match name {
+            Some(name) => self.sources.get(name).map(|source| &**source),
+            None => Some(&*self.default_source),
+        }
+    }
+
+    /// Returns the named sources.
+    #[cfg(all(
+        feature = "filesystem_watcher",
+        all(not(target_arch = "wasm32"), not(target_os = "android"))
+    ))]
+    pub(crate) fn named_sources(&self) -> impl Iterator<Item = (&str, &dyn AssetIo)> {
+        self.sources
+            .iter()
+            .map(|(name, source)| (name.as_str(), &**source))
+    }
+
+    fn route<'a>(&self, path: &'a Path) -> Result<(&dyn AssetIo, &'a Path), AssetIoError> {
+        let (name, source_path) = split_asset_source(path);
+        self.source(name)
+            .map(|source| (source, source_path))
+            .ok_or_else(|| AssetIoError::MissingAssetSource(name.unwrap().to_string()))
+    }
+}
+
+impl AssetIo for AssetSources {
+    fn load_path<'a>(&'a self, path: &'a Path) -> BoxedFuture<'a, Result<Vec<u8>, AssetIoError>> {
+        Box::pin(async move {
+            let (source, source_path) = self.route(path)?;
+            source.load_path(source_path).await
+        })
+    }
+
+    fn load_path_with_extension<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> BoxedFuture<'a, Result<(Vec<u8>, Option<String>), AssetIoError>> {
+        Box::pin(async move {
+            let (source, source_path) = self.route(path)?;
+            source.load_path_with_extension(source_path).await
+        })
+    }
+
+    fn write_path<'a>(
+        &'a self,
+        path: &'a Path,
+        bytes: &'a [u8],
+    ) -> BoxedFuture<'a, Result<(), AssetIoError>> {
+        Box::pin(async move {
+            let (source, source_path) = self.route(path)?;
+            source.write_path(source_path, bytes).await
+        })
+    }
+
+    fn read_directory(
+        &self,
+        path: &Path,
+    ) -> Result<Box<dyn Iterator<Item = PathBuf>>, AssetIoError> {
+        let (source, source_path) = self.route(path)?;
+        let entries = source.read_directory(source_path)?;
+        match split_asset_source(path).0 {
+            // prefix the entries with the scheme of their source
+            Some(name) => {
+                let name = name.to_string();
+                Ok(Box::new(entries.map(move |entry| {
+                    PathBuf::from(format!("{}://{}", name, entry.display()))
+                })))
+            }
+            None => Ok(entries),
+        }
+    }
+
+    fn get_metadata(&self, path: &Path) -> Result<Metadata, AssetIoError> {
+        let (source, source_path) = self.route(path)?;
+        source.get_metadata(source_path)
+    }
+
+    fn watch_path_for_changes(&self, path: &Path) -> Result<(), AssetIoError> {
+        let (source, source_path) = self.route(path)?;
+        source.watch_path_for_changes(source_path)
+    }
+
+    fn watch_for_changes(&self) -> Result<(), AssetIoError> {
+        self.default_source.watch_for_changes()?;
+        for source in self.sources.values() {
+            source.watch_for_changes()?;
+        }
+        Ok(())
+    }
+}
+
+/// I/O implementation overlaying several layers of assets.
+///
+/// Each asset is loaded from the last layer which contains it, so layers override the files of
+/// the layers added before them, for example to let mods replace the assets of the base game.
+/// Directories list the entries of all layers.
+pub struct LayeredAssetIo {
+    layers: Vec<Box<dyn AssetIo>>,
+}
+
+impl LayeredAssetIo {
+    /// Creates layered assets on top of the `base` layer.
+    pub fn new(base: Box<dyn AssetIo>) -> Self {
+        Self { layers: vec![base] }
+    }
+
+    /// Adds a layer overriding the files of the previous layers.
+    pub fn add_layer<T: AssetIo>(&mut self, layer: T) {
+        self.layers.push(Box::new(layer));
+    }
+
+    /// Adds a layer overriding the files of the previous layers and returns `self`.
+    #[must_use]
+    pub fn with_layer<T: AssetIo>(mut self, layer: T) -> Self {
+        self.add_layer(layer);
+        self
+    }
+
+    /// Returns the layers, from the bottom one.
+    #[cfg(all(
+        feature = "filesystem_watcher",
+        all(not(target_arch = "wasm32"), not(target_os = "android"))
+    ))]
+    pub(crate) fn layers(&self) -> impl Iterator<Item = &dyn AssetIo> {
+        self.layers.iter().map(|layer| &**layer)
+    }
+
+    /// Returns the topmost layer containing `path`.
+    fn find_layer(&self, path: &Path) -> Option<&dyn AssetIo> {
+        self.layers
+            .iter()
+            .rev()
+            .find(|layer| layer.get_metadata(path).is_ok())
+            .map(|layer| &**layer)
+    }
+}
+
+impl AssetIo for LayeredAssetIo {
+    fn load_path<'a>(&'a self, path: &'a Path) -> BoxedFuture<'a, Result<Vec<u8>, AssetIoError>> {
+        Box::pin(async move {
+            for layer in self.layers.iter().rev() {
+                match layer.load_path(path).await {
+                    Err(AssetIoError::NotFound(_)) => continue,
+                    result => return result,
+                }
+            }
+            Err(AssetIoError::NotFound(path.to_owned()))
+        })
+    }
+
+    fn load_path_with_extension<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> BoxedFuture<'a, Result<(Vec<u8>, Option<String>), AssetIoError>> {
+        Box::pin(async move {
+            for layer in self.layers.iter().rev() {
+                match layer.load_path_with_extension(path).await {
+                    Err(AssetIoError::NotFound(_)) => continue,
+                    result => return result,
+                }
+            }
+            Err(AssetIoError::NotFound(path.to_owned()))
+        })
+    }
+
+    fn write_path<'a>(
+        &'a self,
+        path: &'a Path,
+        bytes: &'a [u8],
+    ) -> BoxedFuture<'a, Result<(), AssetIoError>> {
+        // files are written to the topmost layer, which then overrides the other layers
+        self.layers.last().unwrap().write_path(path, bytes)
+    }
+
+    fn read_directory(
+        &self,
+        path: &Path,
+    ) -> Result<Box<dyn Iterator<Item = PathBuf>>, AssetIoError> {
+        let layers: Vec<_> = self
+            .layers
+            .iter()
+            .filter(|layer| layer.is_dir(path))
+            .collect();
+        if layers.is_empty() {
+            return Err(AssetIoError::NotFound(path.to_owned()));
+        }
+
+        let mut entries = Vec::new();
+        let mut seen = HashSet::default();
+        for layer in layers {
+            for entry in layer.read_directory(path)? {
+                if seen.insert(entry.clone()) {
+                    entries.push(entry);
+                }
+            }
+        }
+        Ok(Box::new(entries.into_iter()))
+    }
+
+    fn get_metadata(&self, path: &Path) -> Result<Metadata, AssetIoError> {
+        match self.find_layer(path) {
+            Some(layer) => layer.get_metadata(path),
+            None => Err(AssetIoError::NotFound(path.to_owned())),
+        }
+    }
+
+    fn watch_path_for_changes(&self, path: &Path) -> Result<(), AssetIoError> {
+        match self.find_layer(path) {
+            Some(layer) => layer.watch_path_for_changes(path),
+            None => Err(AssetIoError::NotFound(path.to_owned())),
+        }
+    }
+
+    fn watch_for_changes(&self) -> Result<(), AssetIoError> {
+        for layer in &self.layers {
+            layer.watch_for_changes()?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MemoryAssetIo;
+
+    fn load(asset_io: &dyn AssetIo, path: &str) -> Result<Vec<u8>, AssetIoError> {
+        futures_lite::future::block_on(asset_io.load_path(Path::new(path)))
+    }
+
+    #[test]
+    fn split_source() {
+        assert_eq!(
+            split_asset_source(Path::new("mods://textures/player.png")),
+            (Some("mods"), Path::new("textures/player.png"))
+        );
+        assert_eq!(
+            split_asset_source(Path::new("textures/player.png")),
+            (None, Path::new("textures/player.png"))
+        );
+        assert_eq!(
+            split_asset_source(Path::new("textures/player:1.png")),
+            (None, Path::new("textures/player:1.png"))
+        );
+        // only the `name://` form names a source
+        assert_eq!(
+            split_asset_source(Path::new("player:1.png")),
+            (None, Path::new("player:1.png"))
+        );
+        assert_eq!(
+            split_asset_source(Path::new("mods:/textures/player.png")),
+            (None, Path::new("mods:/textures/player.png"))
+        );
+        assert_eq!(
+            split_asset_source(Path::new("C://textures/player.png")),
+            (None, Path::new("C://textures/player.png"))
+        );
+    }
+
+    #[test]
+    fn normalize_collapsed_source() {
+        fn normalize(path: &str) -> Cow<'_, Path> {
+            normalize_asset_source(Cow::Borrowed(Path::new(path)))
+        }
+        assert_eq!(
+            normalize("mods:/textures/player.png"),
+            Path::new("mods://textures/player.png")
+        );
+        let joined_path = || {
+            Path::new("mods://scene.gltf")
+                .parent()
+                .unwrap()
+                .join("texture.png")
+        };
+        assert_eq!(
+            normalize_asset_source(Cow::Owned(joined_path())),
+            Path::new("mods://texture.png")
+        );
+        for path in [
+            "mods://textures/player.png",
+            "player:1.png",
+            "C:/textures/player.png",
+            "C:\\textures\\player.png",
+            "textures/player.png",
+        ] {
+            assert!(matches!(normalize(path), Cow::Borrowed(_)), "{path}");
+        }
+
+        let asset_path = crate::AssetPath::from(joined_path());
+        assert_eq!(asset_path.path(), Path::new("mods://texture.png"));
+        assert_eq!(asset_path.source(), Some("mods"));
+        assert_eq!(crate::AssetPath::from("player:1.png").source(), None);
+    }
+
+    #[test]
+    fn route_to_sources() {
+        let asset_sources = AssetSources::new(Box::new(
+            MemoryAssetIo::default().with_file("player.png", b"default".to_vec()),
+        ))
+        .with_source(
+            "memory",
+            MemoryAssetIo::default().with_file("textures/player.png", b"memory".to_vec()),
+        );
+
+        assert_eq!(load(&asset_sources, "player.png").unwrap(), b"default");
+        assert_eq!(
+            load(&asset_sources, "memory://textures/player.png").unwrap(),
+            b"memory"
+        );
+        assert!(matches!(
+            load(&asset_sources, "memory://player.png"),
+            Err(AssetIoError::NotFound(_))
+        ));
+        assert!(matches!(
+            load(&asset_sources, "mods://player.png"),
+            Err(AssetIoError::MissingAssetSource(name)) if name == "mods"
+        ));
+
+        assert!(asset_sources.is_dir(Path::new("memory://textures")));
+        let entries: Vec<_> = asset_sources
+            .read_directory(Path::new("memory://textures"))
+            .unwrap()
+            .collect();
+        assert_eq!(entries, [Path::new("memory://textures/player.png")]);
+    }
+
+    #[test]
+    fn override_layers() {
+        let base = MemoryAssetIo::default()
+            .with_file("player.png", b"base".to_vec())
+            .with_file("enemy.png", b"base".to_vec());
+        let mods = MemoryAssetIo::default()
+            .with_file("player.png", b"mod".to_vec())
+            .with_file("boss.png", b"mod".to_vec());
+        let layers = LayeredAssetIo::new(Box::new(base)).with_layer(mods);
+
+        assert_eq!(load(&layers, "player.png").unwrap(), b"mod");
+        assert_eq!(load(&layers, "enemy.png").unwrap(), b"base");
+        assert_eq!(load(&layers, "boss.png").unwrap(), b"mod");
+        assert!(matches!(
+            load(&layers, "missing.png"),
+            Err(AssetIoError::NotFound(_))
+        ));
+
+        let mut entries: Vec<_> = layers.read_directory(Path::new("")).unwrap().collect();
+        entries.sort();
+        assert_eq!(
+            entries,
+            [
+                Path::new("boss.png"),
+                Path::new("enemy.png"),
+                Path::new("player.png")
+            ]
+        );
+    }
+}
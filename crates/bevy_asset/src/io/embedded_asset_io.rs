@@ -0,0 +1,597 @@
+#[cfg(all(
+    feature = "filesystem_watcher",
+    all(not(target_arch = "wasm32"), not(target_os = "android"))
+))]
+use crate::{
+    filesystem_watcher::FilesystemWatcher, AssetEvent, AssetServer, AssetSources, Assets, Handle,
+};
+use crate::{
+    io::memory_asset_io::{implied_metadata, read_implied_directory},
+    Asset, AssetIo, AssetIoError, AssetPath, HandleUntyped, Metadata,
+};
+use anyhow::Result;
+#[cfg(all(
+    feature = "filesystem_watcher",
+    all(not(target_arch = "wasm32"), not(target_os = "android"))
+))]
+use bevy_ecs::{
+    event::EventReader,
+    system::{Res, ResMut},
+};
+use bevy_ecs::{system::Resource, world::World};
+#[cfg(all(
+    feature = "filesystem_watcher",
+    all(not(target_arch = "wasm32"), not(target_os = "android"))
+))]
+use bevy_utils::HashSet;
+use bevy_utils::{BoxedFuture, HashMap};
+#[cfg(all(
+    feature = "filesystem_watcher",
+    all(not(target_arch = "wasm32"), not(target_os = "android"))
+))]
+use crossbeam_channel::TryRecvError;
+use parking_lot::RwLock;
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+/// The name of the asset source serving the [`EmbeddedAssets`].
+///
+/// Embedded assets are loaded from paths such as `embedded://bevy_pbr/render/pbr.wgsl`.
+pub const EMBEDDED_ASSET_SOURCE: &str = "embedded";
+
+struct EmbeddedAsset {
+    bytes: &'static [u8],
+    // only read in debug builds and when watching for changes
+    #[cfg_attr(
+        any(
+            target_arch = "wasm32",
+            target_os = "android",
+            not(any(debug_assertions, feature = "filesystem_watcher"))
+        ),
+        allow(dead_code)
+    )]
+    source_path: Option<PathBuf>,
+}
+
+/// Assets compiled into the application, registered with the
+/// [`embedded_asset`](crate::embedded_asset) macro.
+///
+/// Embedded assets are served by the [`EmbeddedAssetIo`] of the `embedded` asset source. In debug
+/// builds they are read from their source file when it exists, so that editing the file on disk
+/// and reloading the asset picks up the changes without recompiling. Source files are watched for
+/// changes when [`watch_for_changes`](EmbeddedAssets::watch_for_changes) is enabled.
+///
+/// Clones of this resource share the same assets.
+#[derive(Resource, Clone, Default)]
+pub struct EmbeddedAssets {
+    assets: Arc<RwLock<HashMap<PathBuf, EmbeddedAsset>>>,
+    #[cfg(all(
+        feature = "filesystem_watcher",
+        all(not(target_arch = "wasm32"), not(target_os = "android"))
+    ))]
+    filesystem_watcher: Arc<RwLock<Option<FilesystemWatcher>>>,
+}
+
+impl EmbeddedAssets {
+    /// Registers the `bytes` of an asset at `path` in the `embedded` source, replacing the asset
+    /// previously registered there.
+    ///
+    /// `source_path` is the file the bytes were read from, if they should be read from disk
+    /// instead in debug builds.
+    pub fn insert<P: Into<PathBuf>>(
+        &self,
+        path: P,
+        bytes: &'static [u8],
+        source_path: Option<PathBuf>,
+    ) {
+        self.assets
+            .write()
+            .insert(path.into(), EmbeddedAsset { bytes, source_path });
+    }
+
+    /// Returns `true` if an asset is registered at `path`.
+    pub fn contains(&self, path: &Path) -> bool {
+        self.assets.read().contains_key(path)
+    }
+
+    /// Returns the asset path loading the embedded asset registered at `path`.
+    pub fn asset_path(path: &Path) -> AssetPath<'static> {
+        AssetPath::new(
+            PathBuf::from(format!("{}://{}", EMBEDDED_ASSET_SOURCE, path.display())),
+            None,
+        )
+    }
+
+    /// Returns the path in the `embedded` source of the file at `path`, relative to the source
+    /// file `file` of the module `module_path`.
+    ///
+    /// The path starts with the name of the crate, followed by the directory of `file` inside
+    /// its `src` folder: `path` next to `bevy_pbr/src/render/mod.rs` is embedded at
+    /// `bevy_pbr/render/path`.
+    pub fn embedded_path(module_path: &str, file: &str, path: &str) -> PathBuf {
+        let crate_name = module_path.split("::").next().unwrap_or(module_path);
+        let directory = Path::new(file).parent().unwrap_or_else(|| Path::new(""));
+        let directory: PathBuf = directory
+            .components()
+            .skip_while(|component| component.as_os_str() != "src")
+            .skip(1)
+            .collect();
+        Path::new(crate_name).join(directory).join(path)
+    }
+
+    /// Returns the location on disk of the file at `path`, relative to the source file `file` of
+    /// the crate at `manifest_dir`.
+    ///
+    /// `file` is the output of [`file!`], which may be relative to the root of the workspace of
+    /// the crate rather than to the crate itself.
+    pub fn source_path(manifest_dir: &str, file: &str, path: &str) -> PathBuf {
+        let manifest_dir = Path::new(manifest_dir);
+        let file = Path::new(file);
+        // strip the path from the workspace root to the crate, if any
+        let file = file
+            .ancestors()
+            .filter(|prefix| !prefix.as_os_str().is_empty() && manifest_dir.ends_with(prefix))
+            .find_map(|prefix| file.strip_prefix(prefix).ok())
+            .unwrap_or(file);
+        let directory = file.parent().unwrap_or_else(|| Path::new(""));
+        manifest_dir.join(directory).join(path)
+    }
+
+    /// Watches the source files of the embedded assets, reloading the assets when they change.
+    ///
+    /// Source files are only read in debug builds or with the `debug_asset_server` feature.
+    pub fn watch_for_changes(&self) {
+        #[cfg(all(
+            feature = "filesystem_watcher",
+            all(not(target_arch = "wasm32"), not(target_os = "android"))
+        ))]
+        {
+            let mut filesystem_watcher = self.filesystem_watcher.write();
+            if filesystem_watcher.is_none() {
+                *filesystem_watcher = Some(FilesystemWatcher::default());
+            }
+        }
+        #[cfg(not(all(
+            feature = "filesystem_watcher",
+            all(not(target_arch = "wasm32"), not(target_os = "android"))
+        )))]
+        bevy_log::warn!("Watching for changes is not supported when the `filesystem_watcher` feature is disabled");
+    }
+
+    /// Returns `true` if the source files of the embedded assets are watched for changes.
+    pub fn is_watching_for_changes(&self) -> bool {
+        #[cfg(all(
+            feature = "filesystem_watcher",
+            all(not(target_arch = "wasm32"), not(target_os = "android"))
+        ))]
+        return self.filesystem_watcher.read().is_some();
+        #[cfg(not(all(
+            feature = "filesystem_watcher",
+            all(not(target_arch = "wasm32"), not(target_os = "android"))
+        )))]
+        false
+    }
+
+    fn load(&self, path: &Path) -> Result<Vec<u8>, AssetIoError> {
+        let assets = self.assets.read();
+        let asset = assets
+            .get(path)
+            .ok_or_else(|| AssetIoError::NotFound(path.to_owned()))?;
+        #[cfg(all(
+            any(debug_assertions, feature = "debug_asset_server"),
+            all(not(target_arch = "wasm32"), not(target_os = "android"))
+        ))]
+        if let Some(source_path) = &asset.source_path {
+            // the source file is missing when the application doesn't run from its workspace
+            if let Ok(bytes) = std::fs::read(source_path) {
+                return Ok(bytes);
+            }
+        }
+        Ok(asset.bytes.to_vec())
+    }
+}
+
+/// I/O implementation serving the [`EmbeddedAssets`].
+///
+/// The [`AssetPlugin`](crate::AssetPlugin) adds it as the `embedded` source of the default
+/// [`AssetIo`].
+pub struct EmbeddedAssetIo {
+    assets: EmbeddedAssets,
+}
+
+impl EmbeddedAssetIo {
+    /// Creates an asset I/O serving `assets`.
+    pub fn new(assets: EmbeddedAssets) -> Self {
+        Self { assets }
+    }
+
+    /// Returns the served assets.
+    pub fn assets(&self) -> &EmbeddedAssets {
+        &self.assets
+    }
+}
+
+impl AssetIo for EmbeddedAssetIo {
+    fn load_path<'a>(&'a self, path: &'a Path) -> BoxedFuture<'a, Result<Vec<u8>, AssetIoError>> {
+        Box::pin(async move { self.assets.load(path) })
+    }
+
+    fn read_directory(
+        &self,
+        path: &Path,
+    ) -> Result<Box<dyn Iterator<Item = PathBuf>>, AssetIoError> {
+        read_implied_directory(self.assets.assets.read().keys(), path)
+    }
+
+    fn get_metadata(&self, path: &Path) -> Result<Metadata, AssetIoError> {
+        implied_metadata(self.assets.assets.read().keys(), path)
+    }
+
+    fn watch_path_for_changes(&self, _path: &Path) -> Result<(), AssetIoError> {
+        #[cfg(all(
+            feature = "filesystem_watcher",
+            all(not(target_arch = "wasm32"), not(target_os = "android"))
+        ))]
+        {
+            let mut watcher = self.assets.filesystem_watcher.write();
+            if let Some(ref mut watcher) = *watcher {
+                let assets = self.assets.assets.read();
+                let source_path = assets
+                    .get(_path)
+                    .and_then(|asset| asset.source_path.as_ref())
+                    .filter(|source_path| source_path.exists());
+                if let Some(source_path) = source_path {
+                    watcher
+                        .watch(source_path)
+                        .map_err(|_error| AssetIoError::PathWatchError(source_path.clone()))?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn watch_for_changes(&self) -> Result<(), AssetIoError> {
+        self.assets.watch_for_changes();
+        Ok(())
+    }
+}
+
+/// Returns `true` if `asset_io` serves the `embedded` asset source.
+#[cfg(all(
+    feature = "filesystem_watcher",
+    all(not(target_arch = "wasm32"), not(target_os = "android"))
+))]
+pub(crate) fn serves_embedded_assets(asset_io: &dyn AssetIo) -> bool {
+    asset_io
+        .downcast_ref::<AssetSources>()
+        .and_then(|sources| sources.source(Some(EMBEDDED_ASSET_SOURCE)))
+        .is_some()
+}
+
+/// Reloads the embedded assets whose source files changed.
+///
+/// Does nothing if the [`AssetServer`] doesn't serve the `embedded` asset source.
+#[cfg(all(
+    feature = "filesystem_watcher",
+    all(not(target_arch = "wasm32"), not(target_os = "android"))
+))]
+pub fn embedded_watcher_system(
+    embedded_assets: Option<Res<EmbeddedAssets>>,
+    asset_server: Res<AssetServer>,
+) {
+    let embedded_assets = match embedded_assets {
+        Some(embedded_assets) => embedded_assets,
+        None => return,
+    };
+    if !serves_embedded_assets(asset_server.asset_io()) {
+        return;
+    }
+    let watcher = embedded_assets.filesystem_watcher.read();
+    let watcher = match *watcher {
+        Some(ref watcher) => watcher,
+        None => return,
+    };
+    let mut changed = HashSet::default();
+    loop {
+        let event = match watcher.receiver.try_recv() {
+            Ok(result) => result.unwrap(),
+            Err(TryRecvError::Empty) => break,
+            Err(TryRecvError::Disconnected) => panic!("FilesystemWatcher disconnected."),
+        };
+        if let notify::event::Event {
+            kind: notify::event::EventKind::Modify(_),
+            paths,
+            ..
+        } = event
+        {
+            changed.extend(paths);
+        }
+    }
+    if changed.is_empty() {
+        return;
+    }
+
+    let assets = embedded_assets.assets.read();
+    for (path, asset) in assets.iter() {
+        if let Some(source_path) = &asset.source_path {
+            if changed.contains(source_path) {
+                let _ = asset_server.load_untracked(EmbeddedAssets::asset_path(path), true);
+            }
+        }
+    }
+}
+
+/// Maps the embedded assets loaded for internal assets to the handles of the internal assets.
+#[cfg(all(
+    feature = "filesystem_watcher",
+    all(not(target_arch = "wasm32"), not(target_os = "android"))
+))]
+#[derive(Resource)]
+pub(crate) struct InternalAssetHandles<T: Asset> {
+    handles: HashMap<Handle<T>, Handle<T>>,
+}
+
+#[cfg(all(
+    feature = "filesystem_watcher",
+    all(not(target_arch = "wasm32"), not(target_os = "android"))
+))]
+impl<T: Asset> Default for InternalAssetHandles<T> {
+    fn default() -> Self {
+        Self {
+            handles: Default::default(),
+        }
+    }
+}
+
+/// Loads the embedded asset at `path` into the internal asset at `handle` whenever the source file
+/// of the embedded asset changes.
+///
+/// The type of the asset is inferred from `_loader`, which is the loader given to the
+/// [`load_internal_asset`](crate::load_internal_asset) macro. Does nothing unless the
+/// [`EmbeddedAssets`] are watched for changes and served by the [`AssetServer`], and the asset
+/// type was added with [`add_debug_asset`](crate::AddAsset::add_debug_asset).
+#[doc(hidden)]
+pub fn watch_internal_asset<A: Asset>(
+    _loader: fn(&'static str) -> A,
+    _world: &mut World,
+    _handle: HandleUntyped,
+    _path: AssetPath<'static>,
+) {
+    #[cfg(all(
+        feature = "filesystem_watcher",
+        all(not(target_arch = "wasm32"), not(target_os = "android"))
+    ))]
+    {
+        let watching = _world
+            .get_resource::<EmbeddedAssets>()
+            .map_or(false, EmbeddedAssets::is_watching_for_changes);
+        if !watching
+            || !_world.contains_resource::<InternalAssetHandles<A>>()
+            || !serves_embedded_assets(_world.resource::<AssetServer>().asset_io())
+        {
+            return;
+        }
+        let embedded_handle = _world.resource::<AssetServer>().load(_path);
+        _world
+            .resource_mut::<InternalAssetHandles<A>>()
+            .handles
+            .insert(embedded_handle, _handle.typed_weak());
+    }
+}
+
+/// Copies the embedded assets loaded by [`watch_internal_asset`] into their internal assets.
+#[cfg(all(
+    feature = "filesystem_watcher",
+    all(not(target_arch = "wasm32"), not(target_os = "android"))
+))]
+pub(crate) fn sync_internal_assets<T: Asset + Clone>(
+    mut events: EventReader<AssetEvent<T>>,
+    internal_handles: Res<InternalAssetHandles<T>>,
+    mut assets: ResMut<Assets<T>>,
+) {
+    for event in events.iter() {
+        let embedded_handle = match event {
+            AssetEvent::Created { handle } | AssetEvent::Modified { handle } => handle,
+            AssetEvent::Removed { .. } | AssetEvent::LoadedWithDependencies { .. } => continue,
+        };
+        if let Some(handle) = internal_handles.handles.get(embedded_handle) {
+            if let Some(embedded_asset) = assets.get(embedded_handle).cloned() {
+                assets.set_untracked(handle, embedded_asset);
+            }
+        }
+    }
+}
+
+/// Embeds the file at `path`, relative to the current source file, into the application.
+///
+/// The file is registered in the [`EmbeddedAssets`] of the app with [`include_bytes`], and the
+/// macro returns the [`AssetPath`] loading it from the `embedded` asset source. The path is
+/// made of the name of the crate and the location of the file inside its `src` folder:
+///
+/// ```ignore
+/// // in `my_crate/src/render/mod.rs`
+/// let path = embedded_asset!(app, "outline.wgsl");
+/// assert_eq!(path, AssetPath::from("embedded://my_crate/render/outline.wgsl"));
+/// let outline_shader: Handle<Shader> = asset_server.load(path);
+/// ```
+///
+/// In debug builds the asset is read from the file on disk when it exists, so that editing it
+/// takes effect when the asset is reloaded, or immediately when watching for changes.
+#[macro_export]
+macro_rules! embedded_asset {
+    ($app: ident, $path_str: expr) => {{
+        let embedded_path =
+            $crate::EmbeddedAssets::embedded_path(module_path!(), file!(), $path_str);
+        let source_path =
+            $crate::EmbeddedAssets::source_path(env!("CARGO_MANIFEST_DIR"), file!(), $path_str);
+        $app.world
+            .get_resource_or_insert_with($crate::EmbeddedAssets::default)
+            .insert(
+                embedded_path.clone(),
+                include_bytes!($path_str),
+                Some(source_path),
+            );
+        $crate::EmbeddedAssets::asset_path(&embedded_path)
+    }};
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn embedded_paths() {
+        assert_eq!(
+            EmbeddedAssets::embedded_path(
+                "bevy_pbr::render::mesh",
+                "crates/bevy_pbr/src/render/mesh.rs",
+                "mesh.wgsl"
+            ),
+            Path::new("bevy_pbr/render/mesh.wgsl")
+        );
+        assert_eq!(
+            EmbeddedAssets::embedded_path("my_game", "src/main.rs", "shaders/outline.wgsl"),
+            Path::new("my_game/shaders/outline.wgsl")
+        );
+        assert_eq!(
+            EmbeddedAssets::asset_path(Path::new("my_game/outline.wgsl")),
+            AssetPath::from("embedded://my_game/outline.wgsl")
+        );
+    }
+
+    #[test]
+    fn source_paths() {
+        // crate in a workspace
+        assert_eq!(
+            EmbeddedAssets::source_path(
+                "/bevy/crates/bevy_pbr",
+                "crates/bevy_pbr/src/render/mesh.rs",
+                "mesh.wgsl"
+            ),
+            Path::new("/bevy/crates/bevy_pbr/src/render/mesh.wgsl")
+        );
+        // standalone crate
+        assert_eq!(
+            EmbeddedAssets::source_path("/my_game", "src/main.rs", "outline.wgsl"),
+            Path::new("/my_game/src/outline.wgsl")
+        );
+    }
+
+    #[test]
+    fn load_embedded_assets() {
+        let source_path = std::env::temp_dir().join("bevy_asset_embedded_test.txt");
+        std::fs::write(&source_path, b"source").unwrap();
+
+        let assets = EmbeddedAssets::default();
+        assets.insert("my_crate/bytes.txt", b"bytes", None);
+        assets.insert(
+            "my_crate/data/source.txt",
+            b"bytes",
+            Some(source_path.clone()),
+        );
+        assets.insert(
+            "my_crate/data/missing_source.txt",
+            b"bytes",
+            Some(PathBuf::from("/missing_source.txt")),
+        );
+        let asset_io = EmbeddedAssetIo::new(assets.clone());
+
+        let load = |path: &str| futures_lite::future::block_on(asset_io.load_path(Path::new(path)));
+        assert_eq!(load("my_crate/bytes.txt").unwrap(), b"bytes");
+        assert_eq!(load("my_crate/data/missing_source.txt").unwrap(), b"bytes");
+        let expected: &[u8] = if cfg!(any(debug_assertions, feature = "debug_asset_server")) {
+            b"source"
+        } else {
+            b"bytes"
+        };
+        assert_eq!(load("my_crate/data/source.txt").unwrap(), expected);
+        assert!(matches!(
+            load("my_crate/missing.txt"),
+            Err(AssetIoError::NotFound(_))
+        ));
+
+        assert!(asset_io.is_dir(Path::new("my_crate/data")));
+        let entries: Vec<_> = asset_io
+            .read_directory(Path::new("my_crate"))
+            .unwrap()
+            .collect();
+        assert_eq!(
+            entries,
+            [Path::new("my_crate/bytes.txt"), Path::new("my_crate/data")]
+        );
+
+        // assets registered later are visible through clones
+        assets.insert("my_crate/late.txt", b"late", None);
+        assert_eq!(load("my_crate/late.txt").unwrap(), b"late");
+
+        std::fs::remove_file(source_path).unwrap();
+    }
+
+    #[cfg(all(
+        feature = "filesystem_watcher",
+        all(not(target_arch = "wasm32"), not(target_os = "android"))
+    ))]
+    #[test]
+    fn internal_assets_are_loaded_from_the_embedded_source() {
+        use crate::{AddAsset, AssetLoader, AssetPlugin, LoadContext, LoadedAsset};
+        use bevy_app::App;
+        use bevy_reflect::TypeUuid;
+
+        #[derive(Clone, Debug, TypeUuid)]
+        #[uuid = "7f3ab2e1-0a64-4d55-9a0a-d7c2ae0b6f31"]
+        struct TextAsset(String);
+
+        struct TextLoader;
+        impl AssetLoader for TextLoader {
+            fn load<'a>(
+                &'a self,
+                bytes: &'a [u8],
+                load_context: &'a mut LoadContext,
+            ) -> BoxedFuture<'a, Result<(), anyhow::Error>> {
+                Box::pin(async move {
+                    let text = String::from_utf8(bytes.to_vec())?;
+                    load_context.set_default_asset(LoadedAsset::new(TextAsset(text)));
+                    Ok(())
+                })
+            }
+
+            fn extensions(&self) -> &[&str] {
+                &["txt"]
+            }
+        }
+
+        bevy_tasks::IoTaskPool::init(Default::default);
+        let mut app = App::new();
+        let embedded_assets = EmbeddedAssets::default();
+        embedded_assets.insert("test/text.txt", b"embedded", None);
+        embedded_assets.watch_for_changes();
+        app.insert_resource(embedded_assets)
+            .add_plugin(AssetPlugin)
+            .add_asset::<TextAsset>()
+            .add_debug_asset::<TextAsset>()
+            .add_asset_loader(TextLoader);
+
+        let handle = HandleUntyped::weak_from_u64(TextAsset::TYPE_UUID, 1);
+        watch_internal_asset(
+            |text| TextAsset(text.to_string()),
+            &mut app.world,
+            handle.clone_weak(),
+            EmbeddedAssets::asset_path(Path::new("test/text.txt")),
+        );
+
+        let handle = handle.typed::<TextAsset>();
+        for _ in 0..500 {
+            app.update();
+            let assets = app.world.resource::<Assets<TextAsset>>();
+            if let Some(asset) = assets.get(&handle) {
+                assert_eq!(asset.0, "embedded");
+                return;
+            }
+            std::thread::sleep(std::time::Duration::from_millis(10));
+        }
+        panic!("the internal asset was not loaded from the embedded source");
+    }
+}
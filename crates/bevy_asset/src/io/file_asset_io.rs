@@ -1,5 +1,5 @@
 #[cfg(feature = "filesystem_watcher")]
-use crate::{filesystem_watcher::FilesystemWatcher, AssetServer};
+use crate::{filesystem_watcher::FilesystemWatcher, AssetLoaderMeta, AssetServer};
 use crate::{AssetIo, AssetIoError, Metadata};
 use anyhow::Result;
 #[cfg(feature = "filesystem_watcher")]
@@ -105,6 +105,21 @@ impl AssetIo for FileAssetIo {
         })
     }
 
+    fn write_path<'a>(
+        &'a self,
+        path: &'a Path,
+        bytes: &'a [u8],
+    ) -> BoxedFuture<'a, Result<(), AssetIoError>> {
+        Box::pin(async move {
+            let full_path = self.root_path.join(path);
+            if let Some(parent) = full_path.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            fs::write(full_path, bytes)?;
+            Ok(())
+        })
+    }
+
     fn read_directory(
         &self,
         path: &Path,
@@ -159,41 +174,120 @@ impl AssetIo for FileAssetIo {
     }
 }
 
+/// Collects the [`FileAssetIo`]s serving `asset_io`, with the name of the asset source they
+/// serve, looking through the asset I/O wrappers of this crate.
+#[cfg(all(
+    feature = "filesystem_watcher",
+    all(not(target_arch = "wasm32"), not(target_os = "android"))
+))]
+fn find_file_asset_ios<'a>(
+    asset_io: &'a dyn AssetIo,
+    source: Option<&'a str>,
+    file_asset_ios: &mut Vec<(Option<&'a str>, &'a FileAssetIo)>,
+) {
+    if let Some(asset_io) = asset_io.downcast_ref::<FileAssetIo>() {
+        file_asset_ios.push((source, asset_io));
+    } else if let Some(asset_sources) = asset_io.downcast_ref::<crate::AssetSources>() {
+        if let Some(default_source) = asset_sources.source(None) {
+            find_file_asset_ios(default_source, source, file_asset_ios);
+        }
+        for (name, named_source) in asset_sources.named_sources() {
+            find_file_asset_ios(named_source, Some(name), file_asset_ios);
+        }
+    } else if let Some(layered) = asset_io.downcast_ref::<crate::LayeredAssetIo>() {
+        for layer in layered.layers() {
+            find_file_asset_ios(layer, source, file_asset_ios);
+        }
+    } else if let Some(processed) = asset_io.downcast_ref::<crate::ProcessedAssetIo>() {
+        find_file_asset_ios(processed.source(), source, file_asset_ios);
+    }
+}
+
 /// Watches for file changes in the local file system.
 #[cfg(all(
     feature = "filesystem_watcher",
     all(not(target_arch = "wasm32"), not(target_os = "android"))
 ))]
 pub fn filesystem_watcher_system(asset_server: Res<AssetServer>) {
-    let mut changed = HashSet::default();
-    let asset_io =
-        if let Some(asset_io) = asset_server.server.asset_io.downcast_ref::<FileAssetIo>() {
-            asset_io
-        } else {
-            return;
-        };
-    let watcher = asset_io.filesystem_watcher.read();
-    if let Some(ref watcher) = *watcher {
-        loop {
-            let event = match watcher.receiver.try_recv() {
-                Ok(result) => result.unwrap(),
-                Err(TryRecvError::Empty) => break,
-                Err(TryRecvError::Disconnected) => panic!("FilesystemWatcher disconnected."),
-            };
-            if let notify::event::Event {
-                kind: notify::event::EventKind::Modify(_),
-                paths,
-                ..
-            } = event
-            {
-                for path in &paths {
-                    if !changed.contains(path) {
-                        let relative_path = path.strip_prefix(&asset_io.root_path).unwrap();
-                        let _ = asset_server.load_untracked(relative_path.into(), true);
+    let mut file_asset_ios = Vec::new();
+    find_file_asset_ios(&*asset_server.server.asset_io, None, &mut file_asset_ios);
+    for (source, asset_io) in file_asset_ios {
+        let mut changed = HashSet::default();
+        let watcher = asset_io.filesystem_watcher.read();
+        if let Some(ref watcher) = *watcher {
+            loop {
+                let event = match watcher.receiver.try_recv() {
+                    Ok(result) => result.unwrap(),
+                    Err(TryRecvError::Empty) => break,
+                    Err(TryRecvError::Disconnected) => panic!("FilesystemWatcher disconnected."),
+                };
+                if let notify::event::Event {
+                    kind: notify::event::EventKind::Modify(_),
+                    paths,
+                    ..
+                } = event
+                {
+                    for path in &paths {
+                        if !changed.contains(path) {
+                            let relative_path = path.strip_prefix(&asset_io.root_path).unwrap();
+                            let asset_path = match source {
+                                Some(source) => PathBuf::from(format!(
+                                    "{}://{}",
+                                    source,
+                                    relative_path.display()
+                                )),
+                                None => relative_path.to_owned(),
+                            };
+                            // saving an asset doesn't reload it
+                            if asset_server.is_saved_content(&asset_path, || fs::read(path).ok()) {
+                                continue;
+                            }
+                            // editing the settings of an asset reloads the asset
+                            let asset_path =
+                                AssetLoaderMeta::asset_path(&asset_path).unwrap_or(asset_path);
+                            let _ = asset_server.load_untracked(asset_path.as_path().into(), true);
+                        }
                     }
+                    changed.extend(paths);
                 }
-                changed.extend(paths);
             }
         }
     }
 }
+
+#[cfg(all(
+    test,
+    feature = "filesystem_watcher",
+    all(not(target_arch = "wasm32"), not(target_os = "android"))
+))]
+mod tests {
+    use super::*;
+    use crate::{AssetSources, LayeredAssetIo, MemoryAssetIo};
+
+    #[test]
+    fn watch_every_file_source() {
+        let asset_sources = AssetSources::new(Box::new(
+            LayeredAssetIo::new(Box::new(FileAssetIo::new("base", false)))
+                .with_layer(MemoryAssetIo::default())
+                .with_layer(FileAssetIo::new("overrides", false)),
+        ))
+        .with_source("mods", FileAssetIo::new("mods", false))
+        .with_source("memory", MemoryAssetIo::default());
+
+        let mut file_asset_ios = Vec::new();
+        find_file_asset_ios(&asset_sources, None, &mut file_asset_ios);
+        let mut sources = file_asset_ios
+            .iter()
+            .map(|(source, asset_io)| (*source, asset_io.root_path().file_name().unwrap()))
+            .collect::<Vec<_>>();
+        sources.sort();
+        assert_eq!(
+            sources,
+            [
+                (None, "base".as_ref()),
+                (None, "overrides".as_ref()),
+                (Some("mods"), "mods".as_ref())
+            ]
+        );
+    }
+}
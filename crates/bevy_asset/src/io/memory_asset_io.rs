@@ -0,0 +1,164 @@
+use crate::{AssetIo, AssetIoError, FileType, Metadata};
+use anyhow::Result;
+use bevy_utils::{BoxedFuture, HashMap};
+use parking_lot::RwLock;
+use std::{
+    collections::BTreeSet,
+    path::{Path, PathBuf},
+};
+
+/// I/O implementation for assets stored in memory.
+///
+/// Directories are implied by the paths of the stored files. This is useful to load assets in
+/// tests without touching the file system, or to serve assets generated at runtime.
+///
+/// Changes aren't watched: use [`AssetServer::reload_asset`](crate::AssetServer::reload_asset)
+/// after replacing a file.
+#[derive(Default)]
+pub struct MemoryAssetIo {
+    files: RwLock<HashMap<PathBuf, Vec<u8>>>,
+}
+
+impl MemoryAssetIo {
+    /// Stores a file at `path`, replacing the file previously stored there.
+    pub fn insert<P: Into<PathBuf>, B: Into<Vec<u8>>>(&self, path: P, bytes: B) {
+        self.files.write().insert(path.into(), bytes.into());
+    }
+
+    /// Stores a file at `path` and returns `self`.
+    #[must_use]
+    pub fn with_file<P: Into<PathBuf>, B: Into<Vec<u8>>>(self, path: P, bytes: B) -> Self {
+        self.insert(path, bytes);
+        self
+    }
+
+    /// Removes the file at `path`, returning its content if it was stored.
+    pub fn remove(&self, path: &Path) -> Option<Vec<u8>> {
+        self.files.write().remove(path)
+    }
+}
+
+impl AssetIo for MemoryAssetIo {
+    fn load_path<'a>(&'a self, path: &'a Path) -> BoxedFuture<'a, Result<Vec<u8>, AssetIoError>> {
+        Box::pin(async move {
+            self.files
+                .read()
+                .get(path)
+                .cloned()
+                .ok_or_else(|| AssetIoError::NotFound(path.to_owned()))
+        })
+    }
+
+    fn write_path<'a>(
+        &'a self,
+        path: &'a Path,
+        bytes: &'a [u8],
+    ) -> BoxedFuture<'a, Result<(), AssetIoError>> {
+        Box::pin(async move {
+            self.insert(path, bytes);
+            Ok(())
+        })
+    }
+
+    fn read_directory(
+        &self,
+        path: &Path,
+    ) -> Result<Box<dyn Iterator<Item = PathBuf>>, AssetIoError> {
+        read_implied_directory(self.files.read().keys(), path)
+    }
+
+    fn get_metadata(&self, path: &Path) -> Result<Metadata, AssetIoError> {
+        implied_metadata(self.files.read().keys(), path)
+    }
+
+    fn watch_path_for_changes(&self, _path: &Path) -> Result<(), AssetIoError> {
+        Ok(())
+    }
+
+    fn watch_for_changes(&self) -> Result<(), AssetIoError> {
+        Ok(())
+    }
+}
+
+/// Lists the children of the directory at `path`, where directories are implied by `files`.
+pub(crate) fn read_implied_directory<'a>(
+    files: impl Iterator<Item = &'a PathBuf>,
+    path: &Path,
+) -> Result<Box<dyn Iterator<Item = PathBuf>>, AssetIoError> {
+    let mut is_dir = path.as_os_str().is_empty();
+    let mut children = BTreeSet::new();
+    for file in files {
+        if let Ok(relative_path) = file.strip_prefix(path) {
+            if let Some(child) = relative_path.components().next() {
+                is_dir = true;
+                children.insert(path.join(child));
+            }
+        }
+    }
+    if !is_dir {
+        return Err(AssetIoError::NotFound(path.to_owned()));
+    }
+    Ok(Box::new(children.into_iter()))
+}
+
+/// Returns the metadata of `path`, where directories are implied by `files`.
+pub(crate) fn implied_metadata<'a>(
+    mut files: impl Iterator<Item = &'a PathBuf>,
+    path: &Path,
+) -> Result<Metadata, AssetIoError> {
+    let mut is_dir = path.as_os_str().is_empty();
+    for file in &mut files {
+        if file == path {
+            return Ok(Metadata::new(FileType::File));
+        }
+        is_dir |= file.starts_with(path);
+    }
+    if is_dir {
+        Ok(Metadata::new(FileType::Directory))
+    } else {
+        Err(AssetIoError::NotFound(path.to_owned()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn load_and_read_directories() {
+        let asset_io = MemoryAssetIo::default()
+            .with_file("textures/player.png", b"player".to_vec())
+            .with_file("textures/enemies/slime.png", b"slime".to_vec())
+            .with_file("level.scn", b"level".to_vec());
+
+        let bytes =
+            futures_lite::future::block_on(asset_io.load_path(Path::new("textures/player.png")))
+                .unwrap();
+        assert_eq!(bytes, b"player");
+        assert!(matches!(
+            futures_lite::future::block_on(asset_io.load_path(Path::new("missing.png"))),
+            Err(AssetIoError::NotFound(_))
+        ));
+
+        assert!(asset_io.is_file(Path::new("level.scn")));
+        assert!(asset_io.is_dir(Path::new("textures/enemies")));
+        assert!(!asset_io.is_dir(Path::new("text")));
+
+        let root: Vec<_> = asset_io.read_directory(Path::new("")).unwrap().collect();
+        assert_eq!(root, [Path::new("level.scn"), Path::new("textures")]);
+        let textures: Vec<_> = asset_io
+            .read_directory(Path::new("textures"))
+            .unwrap()
+            .collect();
+        assert_eq!(
+            textures,
+            [
+                Path::new("textures/enemies"),
+                Path::new("textures/player.png")
+            ]
+        );
+
+        asset_io.remove(Path::new("level.scn"));
+        assert!(!asset_io.is_file(Path::new("level.scn")));
+    }
+}
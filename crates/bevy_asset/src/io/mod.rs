@@ -2,19 +2,31 @@
 mod android_asset_io;
 #[cfg(all(not(target_arch = "wasm32"), not(target_os = "android")))]
 mod file_asset_io;
+#[cfg(all(not(target_arch = "wasm32"), not(target_os = "android")))]
+mod processed_asset_io;
 #[cfg(target_arch = "wasm32")]
 mod wasm_asset_io;
 
+mod asset_sources;
+mod embedded_asset_io;
+mod memory_asset_io;
 mod metadata;
+mod pack_asset_io;
 
 #[cfg(target_os = "android")]
 pub use android_asset_io::*;
 #[cfg(all(not(target_arch = "wasm32"), not(target_os = "android")))]
 pub use file_asset_io::*;
+#[cfg(all(not(target_arch = "wasm32"), not(target_os = "android")))]
+pub use processed_asset_io::*;
 #[cfg(target_arch = "wasm32")]
 pub use wasm_asset_io::*;
 
+pub use asset_sources::*;
+pub use embedded_asset_io::*;
+pub use memory_asset_io::*;
 pub use metadata::*;
+pub use pack_asset_io::*;
 
 use anyhow::Result;
 use bevy_utils::BoxedFuture;
@@ -39,6 +51,31 @@ pub enum AssetIoError {
     /// Failed to watch path.
     #[error("failed to watch path: {0}")]
     PathWatchError(PathBuf),
+
+    /// The asset I/O doesn't support writing files.
+    #[error("writing is not supported for path: {0}")]
+    WriteNotSupported(PathBuf),
+
+    /// The asset source named by the scheme of the path is not registered.
+    #[error("no asset source named {0}")]
+    MissingAssetSource(String),
+}
+
+impl AssetIoError {
+    /// The error of a request for `path` answered with the HTTP `status` code, for the asset I/O
+    /// implementations fetching assets over HTTP.
+    ///
+    /// The missing files must be reported as [`AssetIoError::NotFound`] rather than as the body of
+    /// the error page, as the asset server probes for the optional `.meta` file of every asset.
+    pub fn from_http_status(path: PathBuf, status: u16) -> Self {
+        match status {
+            404 | 410 => AssetIoError::NotFound(path),
+            _ => AssetIoError::Io(io::Error::new(
+                io::ErrorKind::Other,
+                format!("request for {path:?} failed with HTTP status {status}"),
+            )),
+        }
+    }
 }
 
 /// A storage provider for an [`AssetServer`].
@@ -56,6 +93,31 @@ pub trait AssetIo: Downcast + Send + Sync + 'static {
     /// Returns a future to load the full file data at the provided path.
     fn load_path<'a>(&'a self, path: &'a Path) -> BoxedFuture<'a, Result<Vec<u8>, AssetIoError>>;
 
+    /// Returns a future to load the full file data at the provided path, along with the extension
+    /// of the loader to read it with when it isn't the extension of the path.
+    ///
+    /// This is the case for the processed artifacts whose
+    /// [`AssetProcessor`](crate::AssetProcessor) converted them to another format.
+    #[allow(clippy::type_complexity)]
+    fn load_path_with_extension<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> BoxedFuture<'a, Result<(Vec<u8>, Option<String>), AssetIoError>> {
+        Box::pin(async move { Ok((self.load_path(path).await?, None)) })
+    }
+
+    /// Returns a future to write `bytes` to the file at the provided path, replacing its content.
+    ///
+    /// Asset I/O which can't be written to, which is the default, fails with
+    /// [`AssetIoError::WriteNotSupported`].
+    fn write_path<'a>(
+        &'a self,
+        path: &'a Path,
+        _bytes: &'a [u8],
+    ) -> BoxedFuture<'a, Result<(), AssetIoError>> {
+        Box::pin(async move { Err(AssetIoError::WriteNotSupported(path.to_owned())) })
+    }
+
     /// Returns an iterator of directory entry names at the provided path.
     fn read_directory(
         &self,
@@ -0,0 +1,474 @@
+use crate::{AssetIo, AssetIoError, FileType, Metadata};
+use anyhow::Result;
+use bevy_utils::{BoxedFuture, HashMap};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    io::{self, Read, Write},
+    path::{Component, Path, PathBuf},
+};
+use thiserror::Error;
+
+/// The bytes starting every asset pack.
+pub const PACK_MAGIC: &[u8; 8] = b"BEVYPACK";
+
+/// The version of the asset pack format written and read by this version of Bevy.
+pub const PACK_FORMAT_VERSION: u32 = 1;
+
+/// The compression of a file in an asset pack.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum PackCompression {
+    /// The file is stored as is.
+    None,
+    /// The file is compressed with Zstandard. Reading it requires the `zstd` feature.
+    Zstd,
+}
+
+impl PackCompression {
+    fn to_byte(self) -> u8 {
+        match self {
+            PackCompression::None => 0,
+            PackCompression::Zstd => 1,
+        }
+    }
+
+    fn from_byte(byte: u8) -> Result<Self, PackError> {
+        match byte {
+            0 => Ok(PackCompression::None),
+            1 => Ok(PackCompression::Zstd),
+            _ => Err(PackError::UnknownCompression(byte)),
+        }
+    }
+}
+
+/// Errors that occur while reading or writing asset packs.
+#[derive(Error, Debug)]
+pub enum PackError {
+    /// Encountered an I/O error while reading or writing the pack.
+    #[error("encountered an io error while reading the asset pack: {0}")]
+    Io(#[from] io::Error),
+
+    /// The data doesn't start with the [`PACK_MAGIC`] bytes.
+    #[error("not an asset pack")]
+    InvalidMagic,
+
+    /// The pack was written with another version of the format.
+    #[error("unsupported asset pack version {0}, expected {}", PACK_FORMAT_VERSION)]
+    UnsupportedVersion(u32),
+
+    /// A file of the pack has an unknown compression.
+    #[error("unknown asset pack compression {0}")]
+    UnknownCompression(u8),
+
+    /// A file of the pack has a path which isn't valid UTF-8, or which isn't relative.
+    #[error("invalid path in asset pack: {0}")]
+    InvalidPath(String),
+
+    /// A file of the pack uses a compression whose feature is disabled.
+    #[error("the {0:?} compression of asset packs requires the `zstd` feature")]
+    UnsupportedCompression(PackCompression),
+
+    /// A file of the pack failed to decompress.
+    #[error("failed to decompress asset pack file: {0}")]
+    Decompression(String),
+}
+
+impl From<PackError> for AssetIoError {
+    fn from(error: PackError) -> Self {
+        match error {
+            PackError::Io(error) => AssetIoError::Io(error),
+            error => AssetIoError::Io(io::Error::new(io::ErrorKind::InvalidData, error)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct PackEntry {
+    compression: PackCompression,
+    offset: u64,
+    size: u64,
+}
+
+enum PackData {
+    #[cfg(not(target_arch = "wasm32"))]
+    File(PathBuf),
+    Memory(Vec<u8>),
+}
+
+/// I/O implementation reading assets from a single packed archive.
+///
+/// An asset pack stores many files in one archive, starting with an index of their paths, which
+/// is faster to read than loose files and easier to distribute. Files can be compressed with
+/// [`PackCompression::Zstd`]. [`get_metadata`](AssetIo::get_metadata) and
+/// [`read_directory`](AssetIo::read_directory) answer from the index, so
+/// [`AssetServer::load_folder`](crate::AssetServer::load_folder) works on packs.
+///
+/// Packs are written with a [`PackWriter`], for example with the `build-asset-pack` tool of the
+/// repository:
+///
+/// ```no_run
+/// # use bevy_asset::{AssetServer, FileAssetIo, PackAssetIo};
+/// let pack = PackAssetIo::open(FileAssetIo::get_base_path().join("assets.pack")).unwrap();
+/// let asset_server = AssetServer::new(pack);
+/// ```
+///
+/// Changes aren't watched.
+pub struct PackAssetIo {
+    data: PackData,
+    entries: HashMap<PathBuf, PackEntry>,
+    directories: HashMap<PathBuf, BTreeSet<PathBuf>>,
+}
+
+impl PackAssetIo {
+    /// Opens the asset pack at `path`, reading its index.
+    ///
+    /// Files are read from the pack when they are loaded.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn open<P: Into<PathBuf>>(path: P) -> Result<Self, PackError> {
+        let path = path.into();
+        let mut reader = io::BufReader::new(std::fs::File::open(&path)?);
+        let entries = read_index(&mut reader)?;
+        Ok(Self::new(PackData::File(path), entries))
+    }
+
+    /// Reads an asset pack held in memory.
+    pub fn from_bytes(bytes: Vec<u8>) -> Result<Self, PackError> {
+        let entries = read_index(&mut io::Cursor::new(&bytes))?;
+        Ok(Self::new(PackData::Memory(bytes), entries))
+    }
+
+    fn new(data: PackData, entries: HashMap<PathBuf, PackEntry>) -> Self {
+        let mut directories: HashMap<PathBuf, BTreeSet<PathBuf>> = HashMap::default();
+        directories.insert(PathBuf::new(), BTreeSet::new());
+        for path in entries.keys() {
+            let mut child = path.as_path();
+            while let Some(parent) = child.parent() {
+                directories
+                    .entry(parent.to_owned())
+                    .or_default()
+                    .insert(child.to_owned());
+                child = parent;
+            }
+        }
+        Self {
+            data,
+            entries,
+            directories,
+        }
+    }
+
+    /// Returns the paths of the files in the pack.
+    pub fn paths(&self) -> impl Iterator<Item = &Path> {
+        self.entries.keys().map(PathBuf::as_path)
+    }
+
+    fn read_entry(&self, entry: &PackEntry) -> Result<Vec<u8>, PackError> {
+        let size = usize::try_from(entry.size)
+            .map_err(|_| io::Error::new(io::ErrorKind::InvalidData, "file too large"))?;
+        let bytes = match &self.data {
+            #[cfg(not(target_arch = "wasm32"))]
+            PackData::File(path) => {
+                use std::io::{Seek, SeekFrom};
+                let mut file = std::fs::File::open(path)?;
+                file.seek(SeekFrom::Start(entry.offset))?;
+                let mut bytes = vec![0; size];
+                file.read_exact(&mut bytes)?;
+                bytes
+            }
+            PackData::Memory(data) => usize::try_from(entry.offset)
+                .ok()
+                .and_then(|offset| data.get(offset..offset.checked_add(size)?))
+                .ok_or_else(|| io::Error::from(io::ErrorKind::UnexpectedEof))?
+                .to_vec(),
+        };
+        decompress(entry.compression, bytes)
+    }
+}
+
+fn decompress(compression: PackCompression, bytes: Vec<u8>) -> Result<Vec<u8>, PackError> {
+    match compression {
+        PackCompression::None => Ok(bytes),
+        #[cfg(feature = "zstd")]
+        PackCompression::Zstd => {
+            let mut cursor = io::Cursor::new(bytes);
+            let mut decoder =
+                ruzstd::StreamingDecoder::new(&mut cursor).map_err(PackError::Decompression)?;
+            let mut decompressed = Vec::new();
+            decoder
+                .read_to_end(&mut decompressed)
+                .map_err(|err| PackError::Decompression(err.to_string()))?;
+            Ok(decompressed)
+        }
+        #[cfg(not(feature = "zstd"))]
+        PackCompression::Zstd => Err(PackError::UnsupportedCompression(compression)),
+    }
+}
+
+impl AssetIo for PackAssetIo {
+    fn load_path<'a>(&'a self, path: &'a Path) -> BoxedFuture<'a, Result<Vec<u8>, AssetIoError>> {
+        Box::pin(async move {
+            let entry = self
+                .entries
+                .get(path)
+                .ok_or_else(|| AssetIoError::NotFound(path.to_owned()))?;
+            Ok(self.read_entry(entry)?)
+        })
+    }
+
+    fn read_directory(
+        &self,
+        path: &Path,
+    ) -> Result<Box<dyn Iterator<Item = PathBuf>>, AssetIoError> {
+        let children = self
+            .directories
+            .get(path)
+            .ok_or_else(|| AssetIoError::NotFound(path.to_owned()))?;
+        Ok(Box::new(children.clone().into_iter()))
+    }
+
+    fn get_metadata(&self, path: &Path) -> Result<Metadata, AssetIoError> {
+        if self.entries.contains_key(path) {
+            Ok(Metadata::new(FileType::File))
+        } else if self.directories.contains_key(path) {
+            Ok(Metadata::new(FileType::Directory))
+        } else {
+            Err(AssetIoError::NotFound(path.to_owned()))
+        }
+    }
+
+    fn watch_path_for_changes(&self, _path: &Path) -> Result<(), AssetIoError> {
+        Ok(())
+    }
+
+    fn watch_for_changes(&self) -> Result<(), AssetIoError> {
+        bevy_log::warn!("Watching for changes is not supported by asset packs");
+        Ok(())
+    }
+}
+
+/// Writes asset packs read by the [`PackAssetIo`].
+///
+/// ```
+/// # use bevy_asset::{PackAssetIo, PackWriter};
+/// let mut writer = PackWriter::default();
+/// writer.add_file("textures/player.png", b"player".to_vec()).unwrap();
+///
+/// let mut pack = Vec::new();
+/// writer.write(&mut pack).unwrap();
+/// let pack = PackAssetIo::from_bytes(pack).unwrap();
+/// ```
+#[derive(Default)]
+pub struct PackWriter {
+    files: BTreeMap<String, (PackCompression, Vec<u8>)>,
+}
+
+impl PackWriter {
+    /// Adds the file at `path` with its uncompressed `bytes`, replacing the file previously added
+    /// there.
+    pub fn add_file<P: AsRef<Path>>(&mut self, path: P, bytes: Vec<u8>) -> Result<(), PackError> {
+        self.add_compressed_file(path, PackCompression::None, bytes)
+    }
+
+    /// Adds the file at `path` with its `bytes` compressed with `compression`, replacing the file
+    /// previously added there.
+    pub fn add_compressed_file<P: AsRef<Path>>(
+        &mut self,
+        path: P,
+        compression: PackCompression,
+        bytes: Vec<u8>,
+    ) -> Result<(), PackError> {
+        let path = pack_path(path.as_ref())?;
+        self.files.insert(path, (compression, bytes));
+        Ok(())
+    }
+
+    /// Writes the pack to `writer`.
+    pub fn write<W: Write>(&self, mut writer: W) -> Result<(), PackError> {
+        let index_size: usize = self
+            .files
+            .keys()
+            .map(|path| 4 + path.len() + 1 + 8 + 8)
+            .sum();
+        let mut offset = (PACK_MAGIC.len() + 4 + 4 + index_size) as u64;
+
+        writer.write_all(PACK_MAGIC)?;
+        writer.write_all(&PACK_FORMAT_VERSION.to_le_bytes())?;
+        writer.write_all(&(self.files.len() as u32).to_le_bytes())?;
+        for (path, (compression, bytes)) in &self.files {
+            writer.write_all(&(path.len() as u32).to_le_bytes())?;
+            writer.write_all(path.as_bytes())?;
+            writer.write_all(&[compression.to_byte()])?;
+            writer.write_all(&offset.to_le_bytes())?;
+            writer.write_all(&(bytes.len() as u64).to_le_bytes())?;
+            offset += bytes.len() as u64;
+        }
+        for (_, bytes) in self.files.values() {
+            writer.write_all(bytes)?;
+        }
+        Ok(())
+    }
+}
+
+/// Returns `path` with `/` separators, as stored in the index of packs.
+fn pack_path(path: &Path) -> Result<String, PackError> {
+    let mut components = Vec::new();
+    for component in path.components() {
+        match component {
+            Component::Normal(component) => components.push(
+                component
+                    .to_str()
+                    .ok_or_else(|| PackError::InvalidPath(path.display().to_string()))?,
+            ),
+            Component::CurDir => {}
+            _ => return Err(PackError::InvalidPath(path.display().to_string())),
+        }
+    }
+    Ok(components.join("/"))
+}
+
+fn read_u32<R: Read>(reader: &mut R) -> Result<u32, PackError> {
+    let mut bytes = [0; 4];
+    reader.read_exact(&mut bytes)?;
+    Ok(u32::from_le_bytes(bytes))
+}
+
+fn read_u64<R: Read>(reader: &mut R) -> Result<u64, PackError> {
+    let mut bytes = [0; 8];
+    reader.read_exact(&mut bytes)?;
+    Ok(u64::from_le_bytes(bytes))
+}
+
+fn read_index<R: Read>(reader: &mut R) -> Result<HashMap<PathBuf, PackEntry>, PackError> {
+    let mut magic = [0; 8];
+    reader.read_exact(&mut magic)?;
+    if &magic != PACK_MAGIC {
+        return Err(PackError::InvalidMagic);
+    }
+    let version = read_u32(reader)?;
+    if version != PACK_FORMAT_VERSION {
+        return Err(PackError::UnsupportedVersion(version));
+    }
+
+    let entry_count = read_u32(reader)?;
+    let mut entries = HashMap::default();
+    for _ in 0..entry_count {
+        let path_len = read_u32(reader)? as usize;
+        let mut path = vec![0; path_len];
+        reader.read_exact(&mut path)?;
+        let path =
+            String::from_utf8(path).map_err(|error| PackError::InvalidPath(error.to_string()))?;
+        let mut compression = [0];
+        reader.read_exact(&mut compression)?;
+        let entry = PackEntry {
+            compression: PackCompression::from_byte(compression[0])?,
+            offset: read_u64(reader)?,
+            size: read_u64(reader)?,
+        };
+        entries.insert(PathBuf::from(path), entry);
+    }
+    Ok(entries)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_pack() -> Vec<u8> {
+        let mut writer = PackWriter::default();
+        writer
+            .add_file("textures/player.png", b"player".to_vec())
+            .unwrap();
+        writer
+            .add_file(
+                Path::new("textures").join("enemies/slime.png"),
+                b"slime".to_vec(),
+            )
+            .unwrap();
+        writer.add_file("level.scn", b"level".to_vec()).unwrap();
+        let mut pack = Vec::new();
+        writer.write(&mut pack).unwrap();
+        pack
+    }
+
+    fn check_pack(asset_io: &PackAssetIo) {
+        let load = |path: &str| futures_lite::future::block_on(asset_io.load_path(Path::new(path)));
+        assert_eq!(load("textures/player.png").unwrap(), b"player");
+        assert_eq!(load("textures/enemies/slime.png").unwrap(), b"slime");
+        assert_eq!(load("level.scn").unwrap(), b"level");
+        assert!(matches!(
+            load("missing.png"),
+            Err(AssetIoError::NotFound(_))
+        ));
+
+        assert!(asset_io.is_file(Path::new("level.scn")));
+        assert!(asset_io.is_dir(Path::new("textures/enemies")));
+        assert!(!asset_io.is_dir(Path::new("text")));
+
+        let root: Vec<_> = asset_io.read_directory(Path::new("")).unwrap().collect();
+        assert_eq!(root, [Path::new("level.scn"), Path::new("textures")]);
+        let textures: Vec<_> = asset_io
+            .read_directory(Path::new("textures"))
+            .unwrap()
+            .collect();
+        assert_eq!(
+            textures,
+            [
+                Path::new("textures/enemies"),
+                Path::new("textures/player.png")
+            ]
+        );
+    }
+
+    #[test]
+    fn read_pack_from_memory() {
+        let asset_io = PackAssetIo::from_bytes(test_pack()).unwrap();
+        check_pack(&asset_io);
+    }
+
+    #[test]
+    fn read_pack_from_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("assets.pack");
+        std::fs::write(&path, test_pack()).unwrap();
+
+        let asset_io = PackAssetIo::open(path).unwrap();
+        check_pack(&asset_io);
+    }
+
+    #[test]
+    fn reject_invalid_packs() {
+        assert!(matches!(
+            PackAssetIo::from_bytes(b"NOTAPACK".to_vec()),
+            Err(PackError::InvalidMagic)
+        ));
+
+        let mut pack = test_pack();
+        pack[PACK_MAGIC.len()] = 2;
+        assert!(matches!(
+            PackAssetIo::from_bytes(pack),
+            Err(PackError::UnsupportedVersion(2))
+        ));
+
+        let mut writer = PackWriter::default();
+        assert!(matches!(
+            writer.add_file("../player.png", Vec::new()),
+            Err(PackError::InvalidPath(_))
+        ));
+    }
+
+    #[cfg(not(feature = "zstd"))]
+    #[test]
+    fn zstd_requires_feature() {
+        let mut writer = PackWriter::default();
+        writer
+            .add_compressed_file("player.png", PackCompression::Zstd, Vec::new())
+            .unwrap();
+        let mut pack = Vec::new();
+        writer.write(&mut pack).unwrap();
+
+        let asset_io = PackAssetIo::from_bytes(pack).unwrap();
+        assert!(matches!(
+            futures_lite::future::block_on(asset_io.load_path(Path::new("player.png"))),
+            Err(AssetIoError::Io(_))
+        ));
+    }
+}
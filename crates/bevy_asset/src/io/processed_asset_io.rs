@@ -0,0 +1,140 @@
+use crate::{
+    processor::{hash_bytes, record_path, source_hash, ProcessRecord},
+    AssetIo, AssetIoError, AssetLoaderMeta, FileAssetIo, Metadata,
+};
+use anyhow::Result;
+use bevy_utils::BoxedFuture;
+use std::{
+    path::{Path, PathBuf},
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+/// I/O implementation serving the artifacts of an [`AssetPipeline`] in place of their source.
+///
+/// Assets are loaded from the imported asset folder when they have been processed, and from the
+/// wrapped source [`AssetIo`] otherwise. Every other operation is delegated to the source.
+/// Artifacts whose processor converted them to another format are served with the extension
+/// recorded in their `.import` file, so that they are read by the loader of that format.
+///
+/// When watching for changes, an artifact is only served if its source, settings and
+/// dependencies didn't change since it was processed, so that a modified source is reloaded
+/// instead of its stale artifact until the pipeline runs again.
+///
+/// [`AssetPipeline`]: crate::AssetPipeline
+pub struct ProcessedAssetIo {
+    source: Box<dyn AssetIo>,
+    processed: FileAssetIo,
+    check_sources: AtomicBool,
+}
+
+impl ProcessedAssetIo {
+    /// Creates a new `ProcessedAssetIo` serving the artifacts of `processed` in place of the
+    /// assets of `source`.
+    pub fn new(source: Box<dyn AssetIo>, processed: FileAssetIo) -> Self {
+        Self {
+            source,
+            processed,
+            check_sources: AtomicBool::new(false),
+        }
+    }
+
+    /// Returns the [`AssetIo`] providing the asset sources.
+    pub fn source(&self) -> &dyn AssetIo {
+        &*self.source
+    }
+
+    /// Returns the [`FileAssetIo`] providing the processed artifacts.
+    pub fn processed(&self) -> &FileAssetIo {
+        &self.processed
+    }
+
+    /// Reads the record of the artifact of the asset at `path`.
+    async fn load_record(&self, path: &Path) -> Option<ProcessRecord> {
+        let record = self.processed.load_path(&record_path(path)).await.ok()?;
+        ron::de::from_bytes(&record).ok()
+    }
+
+    /// Returns `true` if the inputs of the artifact of the asset at `path` match its `record`.
+    async fn is_artifact_up_to_date(&self, path: &Path, record: Option<&ProcessRecord>) -> bool {
+        let record = match record {
+            Some(record) => record,
+            None => return false,
+        };
+        let source = match self.source.load_path(path).await {
+            Ok(source) => source,
+            Err(_) => return false,
+        };
+        let meta = self
+            .source
+            .load_path(&AssetLoaderMeta::meta_path(path))
+            .await
+            .ok();
+        if source_hash(&source, meta.as_deref()) != record.source_hash {
+            return false;
+        }
+
+        for (dependency, hash) in &record.dependencies {
+            match self.source.load_path(dependency).await {
+                Ok(bytes) if hash_bytes(&bytes) == *hash => {}
+                _ => return false,
+            }
+        }
+        true
+    }
+}
+
+impl AssetIo for ProcessedAssetIo {
+    fn load_path<'a>(&'a self, path: &'a Path) -> BoxedFuture<'a, Result<Vec<u8>, AssetIoError>> {
+        Box::pin(async move { Ok(self.load_path_with_extension(path).await?.0) })
+    }
+
+    fn load_path_with_extension<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> BoxedFuture<'a, Result<(Vec<u8>, Option<String>), AssetIoError>> {
+        Box::pin(async move {
+            let artifact = match self.processed.load_path(path).await {
+                Err(AssetIoError::NotFound(_)) => {
+                    return self.source.load_path_with_extension(path).await
+                }
+                result => result?,
+            };
+            let record = self.load_record(path).await;
+            if self.check_sources.load(Ordering::Relaxed)
+                && !self.is_artifact_up_to_date(path, record.as_ref()).await
+            {
+                return self.source.load_path_with_extension(path).await;
+            }
+            let extension = record.and_then(|record| record.artifact_extension);
+            Ok((artifact, extension))
+        })
+    }
+
+    fn write_path<'a>(
+        &'a self,
+        path: &'a Path,
+        bytes: &'a [u8],
+    ) -> BoxedFuture<'a, Result<(), AssetIoError>> {
+        self.source.write_path(path, bytes)
+    }
+
+    fn read_directory(
+        &self,
+        path: &Path,
+    ) -> Result<Box<dyn Iterator<Item = PathBuf>>, AssetIoError> {
+        self.source.read_directory(path)
+    }
+
+    fn get_metadata(&self, path: &Path) -> Result<Metadata, AssetIoError> {
+        self.source.get_metadata(path)
+    }
+
+    fn watch_path_for_changes(&self, path: &Path) -> Result<(), AssetIoError> {
+        self.source.watch_path_for_changes(path)
+    }
+
+    fn watch_for_changes(&self) -> Result<(), AssetIoError> {
+        self.check_sources.store(true, Ordering::Relaxed);
+        self.source.watch_for_changes()
+    }
+}
@@ -14,7 +14,8 @@ use web_sys::Response;
 ///
 /// Implementation details:
 ///
-/// - `load_path` makes [fetch()] requests.
+/// - `load_path` makes [fetch()] requests. The responses with an error status are reported as
+///   [`AssetIoError::from_http_status`].
 /// - `read_directory` always returns an empty iterator.
 /// - `get_metadata` will always return an error.
 /// - Watching for changes is not supported. The watcher methods will do nothing.
@@ -42,6 +43,9 @@ impl AssetIo for WasmAssetIo {
                 .await
                 .unwrap();
             let resp: Response = resp_value.dyn_into().unwrap();
+            if !resp.ok() {
+                return Err(AssetIoError::from_http_status(path, resp.status()));
+            }
             let data = JsFuture::from(resp.array_buffer().unwrap()).await.unwrap();
             let bytes = Uint8Array::new(&data).to_vec();
             Ok(bytes)
@@ -23,8 +23,13 @@ mod filesystem_watcher;
 mod handle;
 mod info;
 mod io;
+mod load_stats;
 mod loader;
+mod meta;
 mod path;
+#[cfg(all(not(target_arch = "wasm32"), not(target_os = "android")))]
+mod processor;
+mod saver;
 
 /// The `bevy_asset` prelude.
 pub mod prelude {
@@ -41,8 +46,13 @@ pub use bevy_utils::BoxedFuture;
 pub use handle::*;
 pub use info::*;
 pub use io::*;
+pub use load_stats::{LoadProgress, LoaderStats};
 pub use loader::*;
+pub use meta::*;
 pub use path::*;
+#[cfg(all(not(target_arch = "wasm32"), not(target_os = "android")))]
+pub use processor::*;
+pub use saver::*;
 
 use bevy_app::{prelude::Plugin, App};
 use bevy_ecs::{
@@ -55,7 +65,7 @@ use bevy_ecs::{
 pub enum AssetStage {
     /// The stage where asset storages are updated.
     LoadAssets,
-    /// The stage where asset events are generated.
+    /// The stage where unused assets are freed and asset events are generated.
     AssetEvents,
 }
 
@@ -91,7 +101,13 @@ impl Default for AssetServerSettings {
 ///
 /// This is useful when providing a custom `AssetIo` instance that needs to
 /// delegate to the default `AssetIo` for the platform.
+///
+/// The [`EmbeddedAssets`] of the app are served from the `embedded` asset source.
 pub fn create_platform_default_asset_io(app: &mut App) -> Box<dyn AssetIo> {
+    let embedded_assets = app
+        .world
+        .get_resource_or_insert_with(EmbeddedAssets::default)
+        .clone();
     let settings = app
         .world
         .get_resource_or_insert_with(AssetServerSettings::default);
@@ -103,7 +119,30 @@ pub fn create_platform_default_asset_io(app: &mut App) -> Box<dyn AssetIo> {
     #[cfg(target_os = "android")]
     let source = AndroidAssetIo::new(&settings.asset_folder);
 
-    Box::new(source)
+    #[cfg(all(
+        debug_assertions,
+        feature = "filesystem_watcher",
+        all(not(target_arch = "wasm32"), not(target_os = "android"))
+    ))]
+    if settings.watch_for_changes {
+        embedded_assets.watch_for_changes();
+    }
+
+    #[cfg(all(not(target_arch = "wasm32"), not(target_os = "android")))]
+    let source: Box<dyn AssetIo> = match app.world.get_resource::<AssetProcessorSettings>() {
+        Some(processor_settings) => {
+            let processed = FileAssetIo::new(&processor_settings.imported_asset_folder, false);
+            Box::new(ProcessedAssetIo::new(Box::new(source), processed))
+        }
+        None => Box::new(source),
+    };
+    #[cfg(any(target_arch = "wasm32", target_os = "android"))]
+    let source: Box<dyn AssetIo> = Box::new(source);
+
+    Box::new(
+        AssetSources::new(source)
+            .with_source(EMBEDDED_ASSET_SOURCE, EmbeddedAssetIo::new(embedded_assets)),
+    )
 }
 
 impl Plugin for AssetPlugin {
@@ -114,6 +153,28 @@ impl Plugin for AssetPlugin {
             app.insert_resource(asset_server);
         }
 
+        if let Some(type_registry) = app.world.get_resource::<bevy_app::AppTypeRegistry>() {
+            app.world
+                .resource::<AssetServer>()
+                .set_type_registry(type_registry.0.clone());
+        }
+
+        #[cfg(all(not(target_arch = "wasm32"), not(target_os = "android")))]
+        if let Some(processor_settings) = app.world.get_resource::<AssetProcessorSettings>() {
+            let process_on_startup = processor_settings.process_on_startup;
+            let asset_pipeline = AssetPipeline::new(
+                &app.world.resource::<AssetServerSettings>().asset_folder,
+                &processor_settings.imported_asset_folder,
+            );
+            app.insert_resource(asset_pipeline);
+            if process_on_startup {
+                app.add_startup_system_to_stage(
+                    bevy_app::StartupStage::PreStartup,
+                    processor::process_assets_system,
+                );
+            }
+        }
+
         app.add_stage_before(
             bevy_app::CoreStage::PreUpdate,
             AssetStage::LoadAssets,
@@ -125,15 +186,21 @@ impl Plugin for AssetPlugin {
             SystemStage::parallel(),
         )
         .register_type::<HandleId>()
+        .add_event::<AssetLoadFailedEvent>()
         .add_system_to_stage(
-            bevy_app::CoreStage::PreUpdate,
+            AssetStage::AssetEvents,
             asset_server::free_unused_assets_system,
+        )
+        .add_system_to_stage(
+            AssetStage::AssetEvents,
+            asset_server::asset_load_failed_event_system,
         );
 
         #[cfg(all(
             feature = "filesystem_watcher",
             all(not(target_arch = "wasm32"), not(target_os = "android"))
         ))]
-        app.add_system_to_stage(AssetStage::LoadAssets, io::filesystem_watcher_system);
+        app.add_system_to_stage(AssetStage::LoadAssets, io::filesystem_watcher_system)
+            .add_system_to_stage(AssetStage::LoadAssets, io::embedded_watcher_system);
     }
 }
@@ -0,0 +1,108 @@
+use bevy_utils::{Duration, HashMap};
+use parking_lot::Mutex;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+
+/// Load statistics of an [`AssetLoader`](crate::AssetLoader).
+///
+/// See [`AssetServer::loader_stats`](crate::AssetServer::loader_stats).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct LoaderStats {
+    /// The number of assets loaded by the loader, including failed loads.
+    pub loads: u64,
+    /// The total time spent in the loader.
+    pub total_load_time: Duration,
+}
+
+impl LoaderStats {
+    /// The average time the loader took to load an asset.
+    ///
+    /// Returns `None` if the loader hasn't loaded any asset yet.
+    pub fn average_load_time(&self) -> Option<Duration> {
+        if self.loads == 0 {
+            return None;
+        }
+        Some(self.total_load_time.div_f64(self.loads as f64))
+    }
+}
+
+/// The progress of loading a group of assets and their dependencies.
+///
+/// See [`AssetServer::load_progress`](crate::AssetServer::load_progress).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct LoadProgress {
+    /// The number of assets loaded along with all of their dependencies.
+    pub loaded: usize,
+    /// The number of assets that failed to load, or with a dependency that failed to load.
+    pub failed: usize,
+    /// The number of assets in the group.
+    pub total: usize,
+}
+
+impl LoadProgress {
+    /// The fraction of the assets that are done loading, successfully or not, between `0.0` and
+    /// `1.0`.
+    ///
+    /// An empty group is done loading.
+    pub fn fraction(&self) -> f32 {
+        if self.total == 0 {
+            return 1.0;
+        }
+        (self.loaded + self.failed) as f32 / self.total as f32
+    }
+
+    /// Returns `true` once all the assets are done loading, successfully or not.
+    pub fn is_done(&self) -> bool {
+        self.loaded + self.failed == self.total
+    }
+}
+
+/// Statistics of the loads of an asset server, shared with its load tasks.
+#[derive(Default)]
+pub(crate) struct LoadStats {
+    loads_in_flight: AtomicUsize,
+    bytes_read: AtomicU64,
+    loaders: Mutex<HashMap<&'static str, LoaderStats>>,
+}
+
+impl LoadStats {
+    /// Counts a load as in flight until the returned guard is dropped.
+    pub(crate) fn start_load(&self) -> InFlightLoad<'_> {
+        self.loads_in_flight.fetch_add(1, Ordering::Relaxed);
+        InFlightLoad(&self.loads_in_flight)
+    }
+
+    pub(crate) fn loads_in_flight(&self) -> usize {
+        self.loads_in_flight.load(Ordering::Relaxed)
+    }
+
+    pub(crate) fn add_bytes_read(&self, bytes: usize) {
+        self.bytes_read.fetch_add(bytes as u64, Ordering::Relaxed);
+    }
+
+    pub(crate) fn bytes_read(&self) -> u64 {
+        self.bytes_read.load(Ordering::Relaxed)
+    }
+
+    pub(crate) fn add_load_time(&self, loader: &'static str, load_time: Duration) {
+        let mut loaders = self.loaders.lock();
+        let stats = loaders.entry(loader).or_default();
+        stats.loads += 1;
+        stats.total_load_time += load_time;
+    }
+
+    pub(crate) fn loader_stats(&self) -> Vec<(&'static str, LoaderStats)> {
+        self.loaders
+            .lock()
+            .iter()
+            .map(|(loader, stats)| (*loader, *stats))
+            .collect()
+    }
+}
+
+pub(crate) struct InFlightLoad<'a>(&'a AtomicUsize);
+
+impl<'a> Drop for InFlightLoad<'a> {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::Relaxed);
+    }
+}
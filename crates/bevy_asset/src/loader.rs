@@ -1,15 +1,15 @@
 use crate::{
-    path::AssetPath, AssetIo, AssetIoError, AssetMeta, AssetServer, Assets, Handle, HandleId,
-    RefChangeChannel,
+    path::AssetPath, AssetIo, AssetIoError, AssetMeta, AssetServer, AssetServerError, Assets,
+    Handle, HandleId, RefChangeChannel,
 };
 use anyhow::Error;
 use anyhow::Result;
 use bevy_ecs::system::{Res, ResMut};
-use bevy_reflect::{TypeUuid, TypeUuidDynamic};
+use bevy_reflect::{FromReflect, Reflect, TypeUuid, TypeUuidDynamic};
 use bevy_utils::{BoxedFuture, HashMap};
 use crossbeam_channel::{Receiver, Sender};
 use downcast_rs::{impl_downcast, Downcast};
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 /// A loader for an asset source.
 ///
@@ -25,6 +25,12 @@ pub trait AssetLoader: Send + Sync + 'static {
 
     /// Returns a list of extensions supported by this asset loader, without the preceding dot.
     fn extensions(&self) -> &[&str];
+
+    /// Returns the type name of this asset loader, which identifies it in the load statistics of
+    /// the [`AssetServer`](crate::AssetServer).
+    fn type_name(&self) -> &'static str {
+        std::any::type_name::<Self>()
+    }
 }
 
 /// An essential piece of data of an application.
@@ -125,21 +131,24 @@ pub struct LoadContext<'a> {
     pub(crate) labeled_assets: HashMap<Option<String>, BoxedLoadedAsset>,
     pub(crate) path: &'a Path,
     pub(crate) version: usize,
+    pub(crate) settings: Option<Box<dyn Reflect>>,
+    pub(crate) asset_server: &'a AssetServer,
+    pub(crate) direct_dependencies: Vec<PathBuf>,
+    pub(crate) load_stack: Vec<PathBuf>,
 }
 
 impl<'a> LoadContext<'a> {
-    pub(crate) fn new(
-        path: &'a Path,
-        ref_change_channel: &'a RefChangeChannel,
-        asset_io: &'a dyn AssetIo,
-        version: usize,
-    ) -> Self {
+    pub(crate) fn new(path: &'a Path, asset_server: &'a AssetServer, version: usize) -> Self {
         Self {
-            ref_change_channel,
-            asset_io,
+            ref_change_channel: &asset_server.server.asset_ref_counter.channel,
+            asset_io: asset_server.asset_io(),
             labeled_assets: Default::default(),
             version,
             path,
+            settings: None,
+            asset_server,
+            direct_dependencies: Vec::new(),
+            load_stack: Vec::new(),
         }
     }
 
@@ -148,6 +157,19 @@ impl<'a> LoadContext<'a> {
         self.path
     }
 
+    /// Returns the settings of the asset from its `.meta` file as `T`, if they are present and of
+    /// that type.
+    ///
+    /// See [`AssetLoaderMeta`](crate::AssetLoaderMeta) for the format of `.meta` files.
+    pub fn settings<T: FromReflect>(&self) -> Option<T> {
+        self.settings.as_deref().and_then(T::from_reflect)
+    }
+
+    /// Returns the reflected settings of the asset from its `.meta` file, if any.
+    pub fn reflect_settings(&self) -> Option<&dyn Reflect> {
+        self.settings.as_deref()
+    }
+
     /// Returns `true` if the load context contains an asset with the specified label.
     pub fn has_labeled_asset(&self, label: &str) -> bool {
         self.labeled_assets.contains_key(&Some(label.to_string()))
@@ -174,7 +196,72 @@ impl<'a> LoadContext<'a> {
     /// Reads the contents of the file at the specified path through the [`AssetIo`] associated
     /// with this context.
     pub async fn read_asset_bytes<P: AsRef<Path>>(&self, path: P) -> Result<Vec<u8>, AssetIoError> {
-        self.asset_io.load_path(path.as_ref()).await
+        let bytes = self.asset_io.load_path(path.as_ref()).await?;
+        self.asset_server.load_stats().add_bytes_read(bytes.len());
+        Ok(bytes)
+    }
+
+    /// Loads the asset at `path` and returns its value, for assets derived from the content of
+    /// other assets.
+    ///
+    /// Unlike the dependencies of a [`LoadedAsset`], which are loaded in the background into
+    /// their own storage, the asset is loaded right away with the loader of its path and returned
+    /// to this loader. Only the asset selected by the label of the path is kept, the other assets
+    /// of its source are dropped. The source of this asset is reloaded when the source of the
+    /// directly loaded asset changes.
+    ///
+    /// Returns [`AssetServerError::LoadDirectCycle`] if the asset at `path` is already being
+    /// loaded by this loader or by an asset loading it directly.
+    pub async fn load_direct<'b, T: Asset, P: Into<AssetPath<'b>>>(
+        &mut self,
+        path: P,
+    ) -> Result<T, AssetServerError> {
+        let asset_path: AssetPath = path.into();
+        if let Some(index) = self
+            .load_stack
+            .iter()
+            .position(|path| path == asset_path.path())
+        {
+            let mut cycle = self.load_stack[index..].to_vec();
+            cycle.push(asset_path.path().to_owned());
+            return Err(AssetServerError::LoadDirectCycle { cycle });
+        }
+
+        let asset_server = self.asset_server;
+        let mut load_context = asset_server
+            .run_loader(asset_path.path(), 0, self.load_stack.clone())
+            .await?;
+
+        let label = asset_path.label().map(str::to_string);
+        let loaded_asset = load_context.labeled_assets.remove(&label);
+        let (value, dependencies) = match loaded_asset {
+            Some(BoxedLoadedAsset {
+                value: Some(value),
+                dependencies,
+            }) => (value, dependencies),
+            _ => {
+                return Err(AssetServerError::MissingLabeledAsset {
+                    path: asset_path.to_owned(),
+                })
+            }
+        };
+        for dependency in dependencies {
+            asset_server.load_untracked(dependency, false);
+        }
+
+        asset_server
+            .asset_io()
+            .watch_path_for_changes(asset_path.path())?;
+        self.direct_dependencies.push(asset_path.path().to_owned());
+        self.direct_dependencies
+            .append(&mut load_context.direct_dependencies);
+
+        value.downcast::<T>().map(|value| *value).map_err(|_| {
+            AssetServerError::IncorrectAssetType {
+                path: asset_path.to_owned(),
+                expected: std::any::type_name::<T>(),
+            }
+        })
     }
 
     /// Generates metadata for the assets managed by this load context.
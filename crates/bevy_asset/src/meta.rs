@@ -0,0 +1,330 @@
+use bevy_reflect::{
+    serde::{ReflectSerializer, UntypedReflectDeserializer},
+    FromReflect, Reflect, TypeRegistry,
+};
+use serde::{
+    de::{DeserializeSeed, Error, MapAccess, Visitor},
+    ser::SerializeStruct,
+    Deserialize, Serialize,
+};
+use std::{
+    ffi::OsString,
+    path::{Path, PathBuf},
+};
+
+/// The extension of the settings file of an asset, without the preceding dot.
+pub const META_EXTENSION: &str = "meta";
+
+/// The version of the `.meta` file format written and read by this version of Bevy.
+pub const META_FORMAT_VERSION: u32 = 1;
+
+/// The settings of an asset, read from the `.meta` file next to its source.
+///
+/// The `.meta` file of `textures/player.png` is `textures/player.png.meta`. It can select the
+/// [`AssetLoader`](crate::AssetLoader) used for the asset, overriding the one registered for its
+/// extension, and carry reflected settings which the loader reads with
+/// [`LoadContext::settings`](crate::LoadContext::settings).
+///
+/// Settings files are written in RON:
+///
+/// ```ron
+/// (
+///     meta_format_version: 1,
+///     loader: Some("bevy_render::texture::image_texture_loader::ImageTextureLoader"),
+///     settings: {
+///         "bevy_render::texture::image_texture_loader::ImageLoaderSettings": (
+///             is_srgb: false,
+///             sampler: Nearest,
+///         ),
+///     },
+/// )
+/// ```
+///
+/// The `loader` and `settings` fields are optional. Settings types must be registered in the
+/// [`AppTypeRegistry`](bevy_app::AppTypeRegistry).
+/// Editing a `.meta` file reloads its asset when watching for changes.
+#[derive(Debug)]
+pub struct AssetLoaderMeta {
+    /// The version of the format of the file. Must be [`META_FORMAT_VERSION`].
+    pub meta_format_version: u32,
+    /// The [type name](std::any::type_name) of the loader to use for the asset, if any.
+    pub loader: Option<String>,
+    /// The settings passed to the loader, if any.
+    pub settings: Option<Box<dyn Reflect>>,
+}
+
+impl AssetLoaderMeta {
+    /// Creates new settings for an asset, with the given loader settings.
+    pub fn new<T: Reflect>(settings: T) -> Self {
+        Self {
+            meta_format_version: META_FORMAT_VERSION,
+            loader: None,
+            settings: Some(Box::new(settings)),
+        }
+    }
+
+    /// Selects the loader `T` for the asset.
+    #[must_use]
+    pub fn with_loader<T: crate::AssetLoader>(mut self) -> Self {
+        self.loader = Some(std::any::type_name::<T>().to_string());
+        self
+    }
+
+    /// Returns the settings as `T`, if they are present and of that type.
+    pub fn settings<T: FromReflect>(&self) -> Option<T> {
+        self.settings
+            .as_ref()
+            .and_then(|settings| T::from_reflect(&**settings))
+    }
+
+    /// Returns the path of the `.meta` file of the asset at `path`.
+    pub fn meta_path(path: &Path) -> PathBuf {
+        let mut meta_path = OsString::from(path.as_os_str());
+        meta_path.push(".");
+        meta_path.push(META_EXTENSION);
+        meta_path.into()
+    }
+
+    /// Returns the path of the asset described by the `.meta` file at `meta_path`, or `None` if
+    /// it isn't a `.meta` file.
+    pub fn asset_path(meta_path: &Path) -> Option<PathBuf> {
+        if meta_path.extension()? == META_EXTENSION {
+            Some(meta_path.with_extension(""))
+        } else {
+            None
+        }
+    }
+}
+
+const META_STRUCT: &str = "AssetLoaderMeta";
+const META_FIELD_META_FORMAT_VERSION: &str = "meta_format_version";
+const META_FIELD_LOADER: &str = "loader";
+const META_FIELD_SETTINGS: &str = "settings";
+
+/// Serializer for [`AssetLoaderMeta`], writing the settings with their reflected type name.
+pub struct AssetLoaderMetaSerializer<'a> {
+    /// The settings to serialize.
+    pub meta: &'a AssetLoaderMeta,
+    /// The registry of the settings type.
+    pub registry: &'a TypeRegistry,
+}
+
+impl<'a> AssetLoaderMetaSerializer<'a> {
+    /// Creates a serializer for `meta`.
+    pub fn new(meta: &'a AssetLoaderMeta, registry: &'a TypeRegistry) -> Self {
+        AssetLoaderMetaSerializer { meta, registry }
+    }
+}
+
+impl<'a> Serialize for AssetLoaderMetaSerializer<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let field_count = 2 + self.meta.settings.is_some() as usize;
+        let mut state = serializer.serialize_struct(META_STRUCT, field_count)?;
+        state.serialize_field(
+            META_FIELD_META_FORMAT_VERSION,
+            &self.meta.meta_format_version,
+        )?;
+        state.serialize_field(META_FIELD_LOADER, &self.meta.loader)?;
+        if let Some(settings) = &self.meta.settings {
+            state.serialize_field(
+                META_FIELD_SETTINGS,
+                &ReflectSerializer::new(&**settings, self.registry),
+            )?;
+        }
+        state.end()
+    }
+}
+
+/// Deserializer for [`AssetLoaderMeta`].
+///
+/// Fails if the file wasn't written with the current [`META_FORMAT_VERSION`].
+pub struct AssetLoaderMetaDeserializer<'a> {
+    /// The registry of the settings types.
+    pub registry: &'a TypeRegistry,
+}
+
+impl<'a> AssetLoaderMetaDeserializer<'a> {
+    /// Creates a deserializer using the types of `registry`.
+    pub fn new(registry: &'a TypeRegistry) -> Self {
+        AssetLoaderMetaDeserializer { registry }
+    }
+}
+
+impl<'a, 'de> DeserializeSeed<'de> for AssetLoaderMetaDeserializer<'a> {
+    type Value = AssetLoaderMeta;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        deserializer.deserialize_struct(
+            META_STRUCT,
+            &[
+                META_FIELD_META_FORMAT_VERSION,
+                META_FIELD_LOADER,
+                META_FIELD_SETTINGS,
+            ],
+            AssetLoaderMetaVisitor {
+                registry: self.registry,
+            },
+        )
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(field_identifier, rename_all = "snake_case")]
+enum MetaField {
+    MetaFormatVersion,
+    Loader,
+    Settings,
+}
+
+struct AssetLoaderMetaVisitor<'a> {
+    registry: &'a TypeRegistry,
+}
+
+impl<'a, 'de> Visitor<'de> for AssetLoaderMetaVisitor<'a> {
+    type Value = AssetLoaderMeta;
+
+    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        formatter.write_str("asset meta struct")
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let mut meta_format_version = None;
+        let mut loader = None;
+        let mut settings = None;
+        while let Some(key) = map.next_key()? {
+            match key {
+                MetaField::MetaFormatVersion => {
+                    if meta_format_version.is_some() {
+                        return Err(Error::duplicate_field(META_FIELD_META_FORMAT_VERSION));
+                    }
+                    let version = map.next_value::<u32>()?;
+                    if version != META_FORMAT_VERSION {
+                        return Err(Error::custom(format_args!(
+                            "unsupported meta format version {}, expected {}",
+                            version, META_FORMAT_VERSION
+                        )));
+                    }
+                    meta_format_version = Some(version);
+                }
+                MetaField::Loader => {
+                    if loader.is_some() {
+                        return Err(Error::duplicate_field(META_FIELD_LOADER));
+                    }
+                    loader = Some(map.next_value::<Option<String>>()?);
+                }
+                MetaField::Settings => {
+                    if settings.is_some() {
+                        return Err(Error::duplicate_field(META_FIELD_SETTINGS));
+                    }
+                    settings =
+                        Some(map.next_value_seed(UntypedReflectDeserializer::new(self.registry))?);
+                }
+            }
+        }
+
+        let meta_format_version = meta_format_version
+            .ok_or_else(|| Error::missing_field(META_FIELD_META_FORMAT_VERSION))?;
+
+        Ok(AssetLoaderMeta {
+            meta_format_version,
+            loader: loader.flatten(),
+            settings,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Reflect, FromReflect, Debug, PartialEq)]
+    struct TextureSettings {
+        is_srgb: bool,
+        filter: String,
+    }
+
+    fn registry() -> TypeRegistry {
+        let mut registry = TypeRegistry::default();
+        registry.register::<TextureSettings>();
+        registry.register::<bool>();
+        registry.register::<String>();
+        registry
+    }
+
+    #[test]
+    fn meta_path() {
+        let meta_path = AssetLoaderMeta::meta_path(Path::new("textures/player.png"));
+        assert_eq!(meta_path, Path::new("textures/player.png.meta"));
+        assert_eq!(
+            AssetLoaderMeta::asset_path(&meta_path).unwrap(),
+            Path::new("textures/player.png")
+        );
+        assert_eq!(AssetLoaderMeta::asset_path(Path::new("player.png")), None);
+    }
+
+    #[test]
+    fn serialize_and_deserialize() {
+        let registry = registry();
+        let meta = AssetLoaderMeta {
+            loader: Some("my_game::TextureLoader".to_string()),
+            ..AssetLoaderMeta::new(TextureSettings {
+                is_srgb: false,
+                filter: "nearest".to_string(),
+            })
+        };
+
+        let serialized = ron::ser::to_string_pretty(
+            &AssetLoaderMetaSerializer::new(&meta, &registry),
+            Default::default(),
+        )
+        .unwrap();
+        let deserialized = ron::Options::default()
+            .from_bytes_seed(
+                serialized.as_bytes(),
+                AssetLoaderMetaDeserializer::new(&registry),
+            )
+            .unwrap();
+
+        assert_eq!(deserialized.meta_format_version, META_FORMAT_VERSION);
+        assert_eq!(deserialized.loader, meta.loader);
+        assert_eq!(
+            deserialized.settings::<TextureSettings>().unwrap(),
+            TextureSettings {
+                is_srgb: false,
+                filter: "nearest".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn deserialize_optional_fields() {
+        let registry = registry();
+        let meta = ron::Options::default()
+            .from_bytes_seed(
+                b"(meta_format_version: 1)",
+                AssetLoaderMetaDeserializer::new(&registry),
+            )
+            .unwrap();
+        assert_eq!(meta.loader, None);
+        assert!(meta.settings.is_none());
+    }
+
+    #[test]
+    fn reject_unsupported_version() {
+        let registry = registry();
+        let result = ron::Options::default().from_bytes_seed(
+            b"(meta_format_version: 2, loader: None)",
+            AssetLoaderMetaDeserializer::new(&registry),
+        );
+        assert!(result.is_err());
+    }
+}
@@ -1,8 +1,10 @@
+use crate::io::normalize_asset_source;
 use bevy_reflect::{Reflect, ReflectDeserialize, ReflectSerialize};
 use bevy_utils::AHasher;
 use serde::{Deserialize, Serialize};
 use std::{
     borrow::Cow,
+    fmt::{self, Display},
     hash::{Hash, Hasher},
     path::{Path, PathBuf},
 };
@@ -19,7 +21,7 @@ impl<'a> AssetPath<'a> {
     #[inline]
     pub fn new_ref(path: &'a Path, label: Option<&'a str>) -> AssetPath<'a> {
         AssetPath {
-            path: Cow::Borrowed(path),
+            path: normalize_asset_source(Cow::Borrowed(path)),
             label: label.map(Cow::Borrowed),
         }
     }
@@ -28,7 +30,7 @@ impl<'a> AssetPath<'a> {
     #[inline]
     pub fn new(path: PathBuf, label: Option<String>) -> AssetPath<'a> {
         AssetPath {
-            path: Cow::Owned(path),
+            path: normalize_asset_source(Cow::Owned(path)),
             label: label.map(Cow::Owned),
         }
     }
@@ -51,6 +53,14 @@ impl<'a> AssetPath<'a> {
         &self.path
     }
 
+    /// Gets the name of the asset source of this path, from its scheme.
+    ///
+    /// Returns `None` for the default source. See [`AssetSources`](crate::AssetSources).
+    #[inline]
+    pub fn source(&self) -> Option<&str> {
+        crate::io::split_asset_source(&self.path).0
+    }
+
     /// Converts the borrowed path data to owned.
     #[inline]
     pub fn to_owned(&self) -> AssetPath<'static> {
@@ -152,13 +162,24 @@ impl<'a, 'b> From<&'a AssetPath<'b>> for AssetPathId {
     }
 }
 
+/// Formats the path as `path#label`, the form parsed by `AssetPath::from(&str)`.
+impl<'a> Display for AssetPath<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.path.display())?;
+        if let Some(label) = &self.label {
+            write!(f, "#{label}")?;
+        }
+        Ok(())
+    }
+}
+
 impl<'a> From<&'a str> for AssetPath<'a> {
     fn from(asset_path: &'a str) -> Self {
         let mut parts = asset_path.splitn(2, '#');
         let path = Path::new(parts.next().expect("Path must be set."));
         let label = parts.next();
         AssetPath {
-            path: Cow::Borrowed(path),
+            path: normalize_asset_source(Cow::Borrowed(path)),
             label: label.map(Cow::Borrowed),
         }
     }
@@ -173,7 +194,7 @@ impl<'a> From<&'a String> for AssetPath<'a> {
 impl<'a> From<&'a Path> for AssetPath<'a> {
     fn from(path: &'a Path) -> Self {
         AssetPath {
-            path: Cow::Borrowed(path),
+            path: normalize_asset_source(Cow::Borrowed(path)),
             label: None,
         }
     }
@@ -182,7 +203,7 @@ impl<'a> From<&'a Path> for AssetPath<'a> {
 impl<'a> From<PathBuf> for AssetPath<'a> {
     fn from(path: PathBuf) -> Self {
         AssetPath {
-            path: Cow::Owned(path),
+            path: normalize_asset_source(Cow::Owned(path)),
             label: None,
         }
     }
@@ -0,0 +1,697 @@
+use crate::{AssetIoError, AssetLoaderMeta, FileAssetIo};
+use anyhow::Error;
+use bevy_ecs::system::{Res, Resource};
+use bevy_log::{error, info};
+use bevy_tasks::IoTaskPool;
+use bevy_utils::{BoxedFuture, HashMap};
+use serde::{Deserialize, Serialize};
+use std::{
+    fs,
+    hash::Hasher,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+use thiserror::Error;
+
+/// The extension of the files recording how an artifact was processed.
+const RECORD_EXTENSION: &str = "import";
+
+/// A processor for an asset source.
+///
+/// Types implementing this trait are run ahead of time by the [`AssetPipeline`] to transform the
+/// source bytes of an asset into an artifact that is faster to load, such as a compressed texture
+/// or a baked mesh. The artifact is loaded in place of the source by the [`AssetLoader`] registered
+/// for the asset's extension, or for the [`artifact_extension`](Self::artifact_extension) of the
+/// processor when it converts its sources to another format.
+///
+/// [`AssetLoader`]: crate::AssetLoader
+pub trait AssetProcessor: Send + Sync + 'static {
+    /// Processes the source bytes of an asset in an asynchronous closure, returning the bytes of
+    /// the artifact.
+    fn process<'a>(
+        &'a self,
+        bytes: &'a [u8],
+        process_context: &'a mut ProcessContext,
+    ) -> BoxedFuture<'a, Result<Vec<u8>, Error>>;
+
+    /// Returns a list of extensions supported by this asset processor, without the preceding dot.
+    fn extensions(&self) -> &[&str];
+
+    /// Returns the version of this asset processor.
+    ///
+    /// Changing the version causes the assets processed with a previous version to be processed
+    /// again.
+    fn version(&self) -> u32 {
+        0
+    }
+
+    /// Returns the extension of the loader reading the artifacts of this processor, without the
+    /// preceding dot.
+    ///
+    /// By default, artifacts are read by the loader of their source's extension. A processor
+    /// converting its sources to another format, like PNG textures to KTX2, returns the extension
+    /// of that format, which is recorded in the `.import` file of each artifact.
+    fn artifact_extension(&self) -> Option<&str> {
+        None
+    }
+}
+
+/// A context for processing an asset with an [`AssetProcessor`].
+pub struct ProcessContext<'a> {
+    path: &'a Path,
+    source_root: &'a Path,
+    dependencies: HashMap<PathBuf, u64>,
+}
+
+impl<'a> ProcessContext<'a> {
+    /// Returns the path of the asset being processed, relative to the asset folder.
+    pub fn path(&self) -> &Path {
+        self.path
+    }
+
+    /// Reads the source bytes of another asset and records it as a dependency.
+    ///
+    /// The asset being processed will be processed again whenever one of its dependencies changes.
+    pub async fn read_dependency<P: AsRef<Path>>(
+        &mut self,
+        path: P,
+    ) -> Result<Vec<u8>, AssetIoError> {
+        let path = path.as_ref();
+        let bytes = read_source(self.source_root, path)?;
+        self.dependencies
+            .insert(path.to_owned(), hash_bytes(&bytes));
+        Ok(bytes)
+    }
+}
+
+/// Errors that occur while processing assets with an [`AssetPipeline`].
+#[derive(Error, Debug)]
+pub enum AssetProcessError {
+    /// Encountered an error while reading a source or writing an artifact.
+    #[error("encountered an io error while processing an asset: {0}")]
+    Io(#[from] AssetIoError),
+
+    /// Encountered an error in the [`AssetProcessor`].
+    #[error("encountered an error while processing an asset: {0}")]
+    AssetProcessorError(Error),
+
+    /// Failed to write the record of a processed asset.
+    #[error("failed to write the record of a processed asset: {0}")]
+    RecordError(#[from] ron::Error),
+}
+
+impl From<std::io::Error> for AssetProcessError {
+    fn from(error: std::io::Error) -> Self {
+        AssetProcessError::Io(error.into())
+    }
+}
+
+/// The outcome of a run of the [`AssetPipeline`].
+#[derive(Debug, Default)]
+pub struct ProcessSummary {
+    /// The assets whose artifact was written during this run.
+    pub processed: Vec<PathBuf>,
+    /// The assets whose artifact was already up to date.
+    pub up_to_date: Vec<PathBuf>,
+    /// The artifacts that were removed because their source no longer exists.
+    pub removed: Vec<PathBuf>,
+    /// The assets that failed to be processed.
+    pub failed: Vec<(PathBuf, AssetProcessError)>,
+}
+
+/// The record written next to a processed artifact, used to detect changes to its inputs.
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+pub(crate) struct ProcessRecord {
+    /// The hash of the source and of the processor that produced the artifact.
+    pub hash: u64,
+    /// The hash of the source bytes and settings, see [`source_hash`].
+    pub source_hash: u64,
+    /// The hash of each dependency read while processing the asset.
+    pub dependencies: HashMap<PathBuf, u64>,
+    /// The extension of the loader reading the artifact, if it isn't the extension of the source.
+    #[serde(default)]
+    pub artifact_extension: Option<String>,
+}
+
+struct ProcessorEntry {
+    processor: Arc<dyn AssetProcessor>,
+    type_name: &'static str,
+}
+
+/// Processes asset sources ahead of time with the registered [`AssetProcessor`]s.
+///
+/// Artifacts are written to the imported asset folder at the same path as their source, along with
+/// a `.import` record of the hashes of their inputs. Only the assets whose source, dependencies or
+/// processor changed since the last run are processed again.
+/// The artifacts are served in place of their source by a [`ProcessedAssetIo`].
+///
+/// When [`AssetProcessorSettings`] are provided, the pipeline is added as a resource by the
+/// [`AssetPlugin`](crate::AssetPlugin) and runs once on startup, before any startup system.
+/// Processors are added with [`AddAsset::add_asset_processor`](crate::AddAsset::add_asset_processor).
+///
+/// [`ProcessedAssetIo`]: crate::ProcessedAssetIo
+#[derive(Resource)]
+pub struct AssetPipeline {
+    source_root: PathBuf,
+    imported_root: PathBuf,
+    processors: Vec<ProcessorEntry>,
+    extension_to_processor_index: HashMap<String, usize>,
+}
+
+impl AssetPipeline {
+    /// Creates a new pipeline processing the assets of `source_folder` into `imported_folder`.
+    ///
+    /// Both paths are relative to the [base path](FileAssetIo::get_base_path).
+    pub fn new<S: AsRef<Path>, I: AsRef<Path>>(source_folder: S, imported_folder: I) -> Self {
+        let base_path = FileAssetIo::get_base_path();
+        Self {
+            source_root: base_path.join(source_folder.as_ref()),
+            imported_root: base_path.join(imported_folder.as_ref()),
+            processors: Vec::new(),
+            extension_to_processor_index: HashMap::default(),
+        }
+    }
+
+    /// Adds the provided asset processor to the pipeline.
+    pub fn add_processor<T>(&mut self, processor: T)
+    where
+        T: AssetProcessor,
+    {
+        let processor_index = self.processors.len();
+        for extension in processor.extensions() {
+            self.extension_to_processor_index
+                .insert(extension.to_string(), processor_index);
+        }
+        self.processors.push(ProcessorEntry {
+            processor: Arc::new(processor),
+            type_name: std::any::type_name::<T>(),
+        });
+    }
+
+    /// Returns the root directory where processed artifacts are written.
+    pub fn imported_root(&self) -> &Path {
+        &self.imported_root
+    }
+
+    /// Processes every asset of the source folder that changed since the last run, and removes
+    /// the artifacts of the sources that no longer exist.
+    ///
+    /// Assets are processed in parallel on the [`IoTaskPool`], and this method blocks until all of
+    /// them are processed.
+    pub fn process_all(&self) -> ProcessSummary {
+        let mut summary = ProcessSummary::default();
+
+        let mut sources = Vec::new();
+        if let Err(error) = self.collect_files(&self.source_root, Path::new(""), &mut sources) {
+            summary.failed.push((PathBuf::new(), error));
+            return summary;
+        }
+
+        let results = IoTaskPool::get().scope(|scope| {
+            for path in sources {
+                if let Some(processor) = self.get_path_processor(&path) {
+                    scope.spawn(async move {
+                        let result = self.process_path(&path, processor).await;
+                        (path, result)
+                    });
+                }
+            }
+        });
+
+        for (path, result) in results {
+            match result {
+                Ok(true) => summary.processed.push(path),
+                Ok(false) => summary.up_to_date.push(path),
+                Err(error) => summary.failed.push((path, error)),
+            }
+        }
+
+        match self.remove_stale_artifacts() {
+            Ok(removed) => summary.removed = removed,
+            Err(error) => summary.failed.push((PathBuf::new(), error)),
+        }
+
+        summary
+    }
+
+    /// Processes the asset at `path` if it changed since the last run.
+    ///
+    /// Returns `true` if the artifact was written, or `false` if it was already up to date.
+    async fn process_path(
+        &self,
+        path: &Path,
+        entry: &ProcessorEntry,
+    ) -> Result<bool, AssetProcessError> {
+        let bytes = read_source(&self.source_root, path)?;
+        // changing the settings of an asset processes it again
+        let meta = read_source(&self.source_root, &AssetLoaderMeta::meta_path(path)).ok();
+        let source_hash = source_hash(&bytes, meta.as_deref());
+        let artifact_extension = entry.processor.artifact_extension();
+        let hash = {
+            let mut hasher = StableHasher::default();
+            hasher.write(entry.type_name.as_bytes());
+            hasher.write_u32(entry.processor.version());
+            hasher.write(artifact_extension.unwrap_or_default().as_bytes());
+            hasher.write_u64(source_hash);
+            hasher.finish()
+        };
+
+        let artifact_path = self.imported_root.join(path);
+        let record_path = record_path(&artifact_path);
+        if artifact_path.is_file() && self.is_up_to_date(&record_path, hash) {
+            return Ok(false);
+        }
+
+        let mut process_context = ProcessContext {
+            path,
+            source_root: &self.source_root,
+            dependencies: HashMap::default(),
+        };
+        let artifact = entry
+            .processor
+            .process(&bytes, &mut process_context)
+            .await
+            .map_err(AssetProcessError::AssetProcessorError)?;
+
+        let record = ProcessRecord {
+            hash,
+            source_hash,
+            dependencies: process_context.dependencies,
+            artifact_extension: artifact_extension.map(str::to_string),
+        };
+        if let Some(parent) = artifact_path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&artifact_path, artifact)?;
+        fs::write(&record_path, ron::to_string(&record)?)?;
+
+        Ok(true)
+    }
+
+    /// Returns `true` if the record at `record_path` matches `hash` and the current hash of the
+    /// recorded dependencies.
+    fn is_up_to_date(&self, record_path: &Path, hash: u64) -> bool {
+        let record = fs::read_to_string(record_path)
+            .ok()
+            .and_then(|record| ron::from_str::<ProcessRecord>(&record).ok());
+
+        match record {
+            Some(record) if record.hash == hash => {
+                record.dependencies.iter().all(|(path, dependency_hash)| {
+                    read_source(&self.source_root, path)
+                        .map(|bytes| hash_bytes(&bytes) == *dependency_hash)
+                        .unwrap_or(false)
+                })
+            }
+            _ => false,
+        }
+    }
+
+    /// Removes the artifacts whose source no longer exists or is no longer processed.
+    fn remove_stale_artifacts(&self) -> Result<Vec<PathBuf>, AssetProcessError> {
+        let mut records = Vec::new();
+        if self.imported_root.is_dir() {
+            self.collect_files(&self.imported_root, Path::new(""), &mut records)?;
+        }
+
+        let mut removed = Vec::new();
+        for record in records {
+            if record.extension().and_then(|extension| extension.to_str()) != Some(RECORD_EXTENSION)
+            {
+                continue;
+            }
+
+            let path = record.with_extension("");
+            if self.source_root.join(&path).is_file() && self.get_path_processor(&path).is_some() {
+                continue;
+            }
+
+            let artifact_path = self.imported_root.join(&path);
+            if artifact_path.is_file() {
+                fs::remove_file(artifact_path)?;
+            }
+            fs::remove_file(self.imported_root.join(&record))?;
+            removed.push(path);
+        }
+
+        Ok(removed)
+    }
+
+    /// Recursively collects the files of `root.join(path)`, relative to `root`.
+    fn collect_files(
+        &self,
+        root: &Path,
+        path: &Path,
+        files: &mut Vec<PathBuf>,
+    ) -> Result<(), AssetProcessError> {
+        for entry in fs::read_dir(root.join(path))? {
+            let entry = entry?;
+            let entry_path = path.join(entry.file_name());
+            if entry.file_type()?.is_dir() {
+                self.collect_files(root, &entry_path, files)?;
+            } else {
+                files.push(entry_path);
+            }
+        }
+        Ok(())
+    }
+
+    fn get_path_processor(&self, path: &Path) -> Option<&ProcessorEntry> {
+        let file_name = path.file_name()?.to_str()?.to_lowercase();
+
+        let mut extension = file_name.as_str();
+        while let Some(index) = extension.find('.') {
+            extension = &extension[index + 1..];
+            if let Some(index) = self.extension_to_processor_index.get(extension) {
+                return Some(&self.processors[*index]);
+            }
+        }
+        None
+    }
+}
+
+/// Settings enabling asset processing.
+///
+/// When this resource is added before the [`AssetPlugin`](crate::AssetPlugin), the assets of the
+/// [asset folder](crate::AssetServerSettings::asset_folder) are processed by an [`AssetPipeline`],
+/// and the [`AssetServer`](crate::AssetServer) loads the processed artifacts when they exist.
+#[derive(Resource)]
+pub struct AssetProcessorSettings {
+    /// The folder where processed artifacts are written, relative to the executable.
+    pub imported_asset_folder: String,
+    /// Whether to run the [`AssetPipeline`] on startup.
+    ///
+    /// Shipped builds which only contain already processed artifacts can disable it.
+    pub process_on_startup: bool,
+}
+
+impl Default for AssetProcessorSettings {
+    fn default() -> Self {
+        Self {
+            imported_asset_folder: "imported_assets".to_string(),
+            process_on_startup: true,
+        }
+    }
+}
+
+/// Runs the [`AssetPipeline`], logging its outcome.
+pub fn process_assets_system(asset_pipeline: Res<AssetPipeline>) {
+    let summary = asset_pipeline.process_all();
+    for (path, error) in &summary.failed {
+        error!("Failed to process asset {:?}: {}", path, error);
+    }
+    info!(
+        "Processed {} assets ({} up to date, {} removed)",
+        summary.processed.len(),
+        summary.up_to_date.len(),
+        summary.removed.len()
+    );
+}
+
+fn read_source(source_root: &Path, path: &Path) -> Result<Vec<u8>, AssetIoError> {
+    let full_path = source_root.join(path);
+    fs::read(&full_path).map_err(|e| {
+        if e.kind() == std::io::ErrorKind::NotFound {
+            AssetIoError::NotFound(full_path)
+        } else {
+            e.into()
+        }
+    })
+}
+
+/// Returns the path of the record of the artifact at `artifact_path`.
+pub(crate) fn record_path(artifact_path: &Path) -> PathBuf {
+    let mut record_path = artifact_path.as_os_str().to_owned();
+    record_path.push(".");
+    record_path.push(RECORD_EXTENSION);
+    record_path.into()
+}
+
+/// A 64-bit FNV-1a hasher.
+///
+/// The hashes of the `.import` records are compared between runs, and possibly between machines,
+/// so they use this fully specified algorithm. Integers are hashed as their little-endian bytes.
+struct StableHasher(u64);
+
+impl Default for StableHasher {
+    fn default() -> Self {
+        StableHasher(0xcbf2_9ce4_8422_2325)
+    }
+}
+
+impl Hasher for StableHasher {
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 ^= u64::from(*byte);
+            self.0 = self.0.wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+
+    fn write_u32(&mut self, i: u32) {
+        self.write(&i.to_le_bytes());
+    }
+
+    fn write_u64(&mut self, i: u64) {
+        self.write(&i.to_le_bytes());
+    }
+
+    fn finish(&self) -> u64 {
+        self.0
+    }
+}
+
+/// Hashes the source bytes of an asset along with the bytes of its `.meta` file, if any.
+pub(crate) fn source_hash(bytes: &[u8], meta: Option<&[u8]>) -> u64 {
+    let mut hasher = StableHasher::default();
+    hasher.write(bytes);
+    if let Some(meta) = meta {
+        hasher.write(meta);
+    }
+    hasher.finish()
+}
+
+pub(crate) fn hash_bytes(bytes: &[u8]) -> u64 {
+    let mut hasher = StableHasher::default();
+    hasher.write(bytes);
+    hasher.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{AssetIo, AssetLoader, AssetServer, LoadContext, LoadedAsset, ProcessedAssetIo};
+    use bevy_reflect::TypeUuid;
+
+    struct UppercaseProcessor;
+
+    impl AssetProcessor for UppercaseProcessor {
+        fn process<'a>(
+            &'a self,
+            bytes: &'a [u8],
+            process_context: &'a mut ProcessContext,
+        ) -> BoxedFuture<'a, Result<Vec<u8>, Error>> {
+            Box::pin(async move {
+                let mut artifact = bytes.to_ascii_uppercase();
+                if process_context.path().ends_with("with_header.txt") {
+                    let header = process_context.read_dependency("header.bin").await?;
+                    artifact.splice(0..0, header);
+                }
+                Ok(artifact)
+            })
+        }
+
+        fn extensions(&self) -> &[&str] {
+            &["txt"]
+        }
+    }
+
+    /// Processes `.shout` files into uppercase text artifacts.
+    struct ShoutProcessor;
+
+    impl AssetProcessor for ShoutProcessor {
+        fn process<'a>(
+            &'a self,
+            bytes: &'a [u8],
+            _process_context: &'a mut ProcessContext,
+        ) -> BoxedFuture<'a, Result<Vec<u8>, Error>> {
+            Box::pin(async move { Ok(bytes.to_ascii_uppercase()) })
+        }
+
+        fn extensions(&self) -> &[&str] {
+            &["shout"]
+        }
+
+        fn artifact_extension(&self) -> Option<&str> {
+            Some("txt")
+        }
+    }
+
+    #[derive(TypeUuid)]
+    #[uuid = "0e9f3d52-7a61-4b8e-a2c4-5d1f6b3e8c90"]
+    struct TextAsset;
+
+    struct TextLoader;
+
+    impl AssetLoader for TextLoader {
+        fn load<'a>(
+            &'a self,
+            bytes: &'a [u8],
+            load_context: &'a mut LoadContext,
+        ) -> BoxedFuture<'a, Result<(), Error>> {
+            Box::pin(async move {
+                std::str::from_utf8(bytes)?;
+                load_context.set_default_asset(LoadedAsset::new(TextAsset));
+                Ok(())
+            })
+        }
+
+        fn extensions(&self) -> &[&str] {
+            &["txt"]
+        }
+    }
+
+    fn setup() -> (tempfile::TempDir, AssetPipeline) {
+        IoTaskPool::init(Default::default);
+        let dir = tempfile::tempdir().unwrap();
+        fs::create_dir_all(dir.path().join("assets/nested")).unwrap();
+        fs::write(dir.path().join("assets/nested/a.txt"), "a").unwrap();
+        fs::write(dir.path().join("assets/with_header.txt"), "b").unwrap();
+        fs::write(dir.path().join("assets/header.bin"), "#").unwrap();
+
+        let mut pipeline =
+            AssetPipeline::new(dir.path().join("assets"), dir.path().join("imported"));
+        pipeline.add_processor(UppercaseProcessor);
+        (dir, pipeline)
+    }
+
+    fn sorted(mut paths: Vec<PathBuf>) -> Vec<PathBuf> {
+        paths.sort();
+        paths
+    }
+
+    #[test]
+    fn process_changed_assets() {
+        let (dir, pipeline) = setup();
+        let imported = dir.path().join("imported");
+
+        let summary = pipeline.process_all();
+        assert!(summary.failed.is_empty());
+        assert_eq!(
+            sorted(summary.processed),
+            [Path::new("nested/a.txt"), Path::new("with_header.txt")]
+        );
+        assert_eq!(fs::read(imported.join("nested/a.txt")).unwrap(), b"A");
+        assert_eq!(fs::read(imported.join("with_header.txt")).unwrap(), b"#B");
+        assert!(!imported.join("header.bin").exists());
+
+        let summary = pipeline.process_all();
+        assert!(summary.processed.is_empty());
+        assert_eq!(summary.up_to_date.len(), 2);
+
+        fs::write(dir.path().join("assets/header.bin"), "$").unwrap();
+        let summary = pipeline.process_all();
+        assert_eq!(summary.processed, [Path::new("with_header.txt")]);
+        assert_eq!(fs::read(imported.join("with_header.txt")).unwrap(), b"$B");
+
+        fs::remove_file(dir.path().join("assets/nested/a.txt")).unwrap();
+        let summary = pipeline.process_all();
+        assert_eq!(summary.removed, [Path::new("nested/a.txt")]);
+        assert!(!imported.join("nested/a.txt").exists());
+        assert!(!imported.join("nested/a.txt.import").exists());
+    }
+
+    #[test]
+    fn serve_processed_assets() {
+        let (dir, pipeline) = setup();
+        pipeline.process_all();
+
+        let asset_io = ProcessedAssetIo::new(
+            Box::new(FileAssetIo::new(dir.path().join("assets"), false)),
+            FileAssetIo::new(dir.path().join("imported"), false),
+        );
+
+        let load = |path: &str| {
+            futures_lite::future::block_on(asset_io.load_path(Path::new(path))).unwrap()
+        };
+        assert_eq!(load("nested/a.txt"), b"A");
+        assert_eq!(load("header.bin"), b"#");
+
+        // the artifacts are only checked against their source when watching for changes
+        fs::write(dir.path().join("assets/nested/a.txt"), "c").unwrap();
+        assert_eq!(load("nested/a.txt"), b"A");
+    }
+
+    #[test]
+    fn serve_changed_sources_when_watching_for_changes() {
+        let (dir, pipeline) = setup();
+        pipeline.process_all();
+
+        let asset_io = ProcessedAssetIo::new(
+            Box::new(FileAssetIo::new(dir.path().join("assets"), false)),
+            FileAssetIo::new(dir.path().join("imported"), false),
+        );
+        asset_io.watch_for_changes().unwrap();
+
+        let load = |path: &str| {
+            futures_lite::future::block_on(asset_io.load_path(Path::new(path))).unwrap()
+        };
+        assert_eq!(load("nested/a.txt"), b"A");
+        assert_eq!(load("with_header.txt"), b"#B");
+
+        fs::write(dir.path().join("assets/nested/a.txt"), "c").unwrap();
+        assert_eq!(load("nested/a.txt"), b"c");
+
+        fs::write(dir.path().join("assets/header.bin"), "$").unwrap();
+        assert_eq!(load("with_header.txt"), b"b");
+
+        pipeline.process_all();
+        assert_eq!(load("nested/a.txt"), b"C");
+        assert_eq!(load("with_header.txt"), b"$B");
+    }
+
+    #[test]
+    fn load_artifacts_converted_to_another_format() {
+        let (dir, mut pipeline) = setup();
+        fs::write(dir.path().join("assets/b.shout"), "hey").unwrap();
+        pipeline.add_processor(ShoutProcessor);
+        pipeline.process_all();
+
+        let record = fs::read_to_string(dir.path().join("imported/b.shout.import")).unwrap();
+        let record = ron::from_str::<ProcessRecord>(&record).unwrap();
+        assert_eq!(record.artifact_extension.as_deref(), Some("txt"));
+
+        let asset_io = ProcessedAssetIo::new(
+            Box::new(FileAssetIo::new(dir.path().join("assets"), false)),
+            FileAssetIo::new(dir.path().join("imported"), false),
+        );
+        let load = |path: &str| {
+            futures_lite::future::block_on(asset_io.load_path_with_extension(Path::new(path)))
+                .unwrap()
+        };
+        assert_eq!(load("b.shout"), (b"HEY".to_vec(), Some("txt".to_string())));
+        assert_eq!(load("nested/a.txt"), (b"A".to_vec(), None));
+        assert_eq!(load("header.bin"), (b"#".to_vec(), None));
+
+        // the artifact is read by the loader of its format, though `.shout` files have none
+        let asset_server = AssetServer::new(asset_io);
+        asset_server.add_loader(TextLoader);
+        let load_context = futures_lite::future::block_on(asset_server.run_loader(
+            Path::new("b.shout"),
+            0,
+            Vec::new(),
+        ))
+        .unwrap();
+        assert_eq!(
+            load_context.get_asset_metas()[0].type_uuid,
+            TextAsset::TYPE_UUID
+        );
+    }
+
+    #[test]
+    fn stable_hashes() {
+        // FNV-1a test vectors
+        assert_eq!(hash_bytes(b""), 0xcbf2_9ce4_8422_2325);
+        assert_eq!(hash_bytes(b"a"), 0xaf63_dc4c_8601_ec8c);
+        assert_eq!(hash_bytes(b"foobar"), 0x8594_4171_f739_67e8);
+    }
+}
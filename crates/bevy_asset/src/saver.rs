@@ -0,0 +1,52 @@
+use crate::{Asset, AssetDynamic};
+use anyhow::Error;
+use bevy_utils::AHasher;
+use std::{
+    hash::{Hash, Hasher},
+    path::Path,
+};
+
+/// A saver for assets, the counterpart of an [`AssetLoader`](crate::AssetLoader).
+///
+/// Types implementing this trait are used by [`AssetServer::save`](crate::AssetServer::save) to
+/// serialize assets before writing them through the [`AssetIo`](crate::AssetIo) of the server.
+/// The bytes written by a saver should be readable by the loader of the same extensions.
+///
+/// Bevy provides savers for `Image`s, `DynamicScene`s, `Mesh`es and `StandardMaterial`s.
+pub trait AssetSaver: Send + Sync + 'static {
+    /// The type of the saved assets.
+    type Asset: Asset;
+
+    /// Serializes the `asset` which is saved at `path`.
+    fn save(&self, asset: &Self::Asset, path: &Path) -> Result<Vec<u8>, Error>;
+
+    /// Returns a list of extensions supported by this asset saver, without the preceding dot.
+    fn extensions(&self) -> &[&str];
+}
+
+/// An [`AssetSaver`] with its asset type erased, stored by the asset server.
+pub(crate) trait ErasedAssetSaver: Send + Sync + 'static {
+    fn save(&self, asset: &dyn AssetDynamic, path: &Path) -> Result<Vec<u8>, Error>;
+
+    fn extensions(&self) -> &[&str];
+}
+
+impl<T: AssetSaver> ErasedAssetSaver for T {
+    fn save(&self, asset: &dyn AssetDynamic, path: &Path) -> Result<Vec<u8>, Error> {
+        let asset = asset
+            .downcast_ref::<T::Asset>()
+            .expect("the asset server only passes assets of the type of the saver");
+        AssetSaver::save(self, asset, path)
+    }
+
+    fn extensions(&self) -> &[&str] {
+        AssetSaver::extensions(self)
+    }
+}
+
+/// Hashes the bytes written by a save, to recognize the file changes caused by the save.
+pub(crate) fn saved_hash(bytes: &[u8]) -> u64 {
+    let mut hasher = AHasher::new_with_keys(42, 23);
+    bytes.hash(&mut hasher);
+    hasher.finish()
+}
@@ -0,0 +1,613 @@
+use crate::{core_2d, core_3d, fullscreen_vertex_shader::fullscreen_shader_vertex_state};
+use bevy_app::prelude::*;
+use bevy_asset::{load_internal_asset, HandleUntyped};
+use bevy_ecs::prelude::*;
+use bevy_math::UVec2;
+use bevy_reflect::{Reflect, TypeUuid};
+use bevy_render::{
+    camera::{Camera, ExtractedCamera},
+    extract_component::{ComponentUniforms, DynamicUniformIndex, UniformComponentPlugin},
+    render_graph::{Node, NodeRunError, RenderGraph, RenderGraphContext, SlotInfo, SlotType},
+    render_resource::*,
+    renderer::{RenderContext, RenderDevice},
+    texture::{CachedTexture, TextureCache},
+    view::{ExtractedView, ViewTarget},
+    Extract, RenderApp, RenderStage,
+};
+#[cfg(feature = "trace")]
+use bevy_utils::tracing::info_span;
+use std::num::NonZeroU32;
+
+const BLOOM_SHADER_HANDLE: HandleUntyped =
+    HandleUntyped::weak_from_u64(Shader::TYPE_UUID, 929599476923908);
+
+/// Adds the [`BloomSettings`] camera setting and the bloom pass of the core 2d and 3d render
+/// graphs.
+pub struct BloomPlugin;
+
+impl Plugin for BloomPlugin {
+    fn build(&self, app: &mut App) {
+        load_internal_asset!(app, BLOOM_SHADER_HANDLE, "bloom.wgsl", Shader::from_wgsl);
+
+        app.register_type::<BloomSettings>()
+            .add_plugin(UniformComponentPlugin::<BloomUniform>::default());
+
+        let render_app = match app.get_sub_app_mut(RenderApp) {
+            Ok(render_app) => render_app,
+            Err(_) => return,
+        };
+
+        render_app
+            .init_resource::<BloomPipelines>()
+            .add_system_to_stage(RenderStage::Extract, extract_bloom_settings)
+            .add_system_to_stage(RenderStage::Prepare, prepare_bloom_textures);
+
+        {
+            let bloom_node = BloomNode::new(&mut render_app.world);
+            let mut graph = render_app.world.resource_mut::<RenderGraph>();
+            let draw_3d_graph = graph.get_sub_graph_mut(core_3d::graph::NAME).unwrap();
+            draw_3d_graph.add_node(core_3d::graph::node::BLOOM, bloom_node);
+            draw_3d_graph
+                .add_slot_edge(
+                    draw_3d_graph.input_node().unwrap().id,
+                    core_3d::graph::input::VIEW_ENTITY,
+                    core_3d::graph::node::BLOOM,
+                    BloomNode::IN_VIEW,
+                )
+                .unwrap();
+            // MAIN_PASS -> BLOOM -> TONEMAPPING
+            draw_3d_graph
+                .add_node_edge(core_3d::graph::node::MAIN_PASS, core_3d::graph::node::BLOOM)
+                .unwrap();
+            draw_3d_graph
+                .add_node_edge(
+                    core_3d::graph::node::BLOOM,
+                    core_3d::graph::node::TONEMAPPING,
+                )
+                .unwrap();
+        }
+
+        {
+            let bloom_node = BloomNode::new(&mut render_app.world);
+            let mut graph = render_app.world.resource_mut::<RenderGraph>();
+            let draw_2d_graph = graph.get_sub_graph_mut(core_2d::graph::NAME).unwrap();
+            draw_2d_graph.add_node(core_2d::graph::node::BLOOM, bloom_node);
+            draw_2d_graph
+                .add_slot_edge(
+                    draw_2d_graph.input_node().unwrap().id,
+                    core_2d::graph::input::VIEW_ENTITY,
+                    core_2d::graph::node::BLOOM,
+                    BloomNode::IN_VIEW,
+                )
+                .unwrap();
+            // MAIN_PASS -> BLOOM -> TONEMAPPING
+            draw_2d_graph
+                .add_node_edge(core_2d::graph::node::MAIN_PASS, core_2d::graph::node::BLOOM)
+                .unwrap();
+            draw_2d_graph
+                .add_node_edge(
+                    core_2d::graph::node::BLOOM,
+                    core_2d::graph::node::TONEMAPPING,
+                )
+                .unwrap();
+        }
+    }
+}
+
+/// Applies a bloom effect to a "high dynamic range" camera, making bright areas of the image
+/// glow.
+///
+/// The bright areas above the threshold are blurred by downsampling them to a chain of mips
+/// of decreasing size, which are then upsampled and added back to the image before tonemapping.
+///
+/// Bloom is only applied to cameras with [`Camera::hdr`] set.
+#[derive(Component, Reflect, Clone, Debug)]
+#[reflect(Component)]
+pub struct BloomSettings {
+    /// Baseline of the threshold curve (default: 1.0).
+    ///
+    /// Colors whose brightest channel is under the threshold curve don't bloom.
+    pub threshold: f32,
+    /// Knee of the threshold curve (default: 0.1), softening the transition between the colors
+    /// that bloom and the ones that don't.
+    pub knee: f32,
+    /// Scale of the upsampling filter (default: 1.0), which spreads the bloom further when
+    /// increased.
+    pub scale: f32,
+    /// Intensity of the bloom added to the image (default: 0.3).
+    pub intensity: f32,
+}
+
+impl Default for BloomSettings {
+    fn default() -> Self {
+        Self {
+            threshold: 1.0,
+            knee: 0.1,
+            scale: 1.0,
+            intensity: 0.3,
+        }
+    }
+}
+
+/// The [`BloomSettings`] of a view, in the layout of the bloom shader.
+#[derive(Component, ShaderType, Clone, Debug, PartialEq)]
+pub struct BloomUniform {
+    threshold: f32,
+    knee: f32,
+    scale: f32,
+    intensity: f32,
+}
+
+impl From<&BloomSettings> for BloomUniform {
+    fn from(settings: &BloomSettings) -> Self {
+        Self {
+            threshold: settings.threshold,
+            // the knee is a divisor in the threshold curve
+            knee: settings.knee.max(0.0001),
+            scale: settings.scale,
+            intensity: settings.intensity,
+        }
+    }
+}
+
+fn extract_bloom_settings(
+    mut commands: Commands,
+    cameras: Extract<Query<(Entity, &Camera, &BloomSettings)>>,
+) {
+    for (entity, camera, settings) in &cameras {
+        if camera.is_active && camera.hdr {
+            commands
+                .get_or_spawn(entity)
+                .insert(BloomUniform::from(settings));
+        }
+    }
+}
+
+#[derive(Resource)]
+pub struct BloomPipelines {
+    bind_group_layout: BindGroupLayout,
+    sampler: Sampler,
+    downsample_prefilter_pipeline: CachedRenderPipelineId,
+    downsample_pipeline: CachedRenderPipelineId,
+    upsample_pipeline: CachedRenderPipelineId,
+    upsample_final_pipeline: CachedRenderPipelineId,
+}
+
+impl FromWorld for BloomPipelines {
+    fn from_world(world: &mut World) -> Self {
+        let render_device = world.resource::<RenderDevice>();
+
+        let sampler = render_device.create_sampler(&SamplerDescriptor {
+            label: Some("bloom_sampler"),
+            min_filter: FilterMode::Linear,
+            mag_filter: FilterMode::Linear,
+            address_mode_u: AddressMode::ClampToEdge,
+            address_mode_v: AddressMode::ClampToEdge,
+            ..Default::default()
+        });
+
+        let texture_entry = |binding| BindGroupLayoutEntry {
+            binding,
+            visibility: ShaderStages::FRAGMENT,
+            ty: BindingType::Texture {
+                sample_type: TextureSampleType::Float { filterable: true },
+                view_dimension: TextureViewDimension::D2,
+                multisampled: false,
+            },
+            count: None,
+        };
+        let bind_group_layout =
+            render_device.create_bind_group_layout(&BindGroupLayoutDescriptor {
+                label: Some("bloom_bind_group_layout"),
+                entries: &[
+                    // the texture being sampled
+                    texture_entry(0),
+                    BindGroupLayoutEntry {
+                        binding: 1,
+                        visibility: ShaderStages::FRAGMENT,
+                        ty: BindingType::Sampler(SamplerBindingType::Filtering),
+                        count: None,
+                    },
+                    BindGroupLayoutEntry {
+                        binding: 2,
+                        visibility: ShaderStages::FRAGMENT,
+                        ty: BindingType::Buffer {
+                            ty: BufferBindingType::Uniform,
+                            has_dynamic_offset: true,
+                            min_binding_size: Some(BloomUniform::min_size()),
+                        },
+                        count: None,
+                    },
+                    // the larger mip an upsampled mip is added to
+                    texture_entry(3),
+                ],
+            });
+
+        let descriptor =
+            |label: &'static str, entry_point: &'static str, blend| RenderPipelineDescriptor {
+                label: Some(label.into()),
+                layout: Some(vec![bind_group_layout.clone()]),
+                vertex: fullscreen_shader_vertex_state(),
+                fragment: Some(FragmentState {
+                    shader: BLOOM_SHADER_HANDLE.typed::<Shader>(),
+                    shader_defs: vec![],
+                    entry_point: entry_point.into(),
+                    targets: vec![Some(ColorTargetState {
+                        format: ViewTarget::TEXTURE_FORMAT_HDR,
+                        blend,
+                        write_mask: ColorWrites::ALL,
+                    })],
+                }),
+                primitive: PrimitiveState::default(),
+                depth_stencil: None,
+                multisample: MultisampleState::default(),
+            };
+
+        let mut pipeline_cache = world.resource_mut::<PipelineCache>();
+        let downsample_prefilter_pipeline = pipeline_cache.queue_render_pipeline(descriptor(
+            "bloom_downsample_prefilter_pipeline",
+            "downsample_prefilter",
+            None,
+        ));
+        let downsample_pipeline = pipeline_cache.queue_render_pipeline(descriptor(
+            "bloom_downsample_pipeline",
+            "downsample",
+            None,
+        ));
+        let upsample_pipeline = pipeline_cache.queue_render_pipeline(descriptor(
+            "bloom_upsample_pipeline",
+            "upsample",
+            None,
+        ));
+        // the bloom is added to the main texture of the view
+        let upsample_final_pipeline = pipeline_cache.queue_render_pipeline(descriptor(
+            "bloom_upsample_final_pipeline",
+            "upsample_final",
+            Some(BlendState {
+                color: BlendComponent {
+                    src_factor: BlendFactor::One,
+                    dst_factor: BlendFactor::One,
+                    operation: BlendOperation::Add,
+                },
+                alpha: BlendComponent {
+                    src_factor: BlendFactor::Zero,
+                    dst_factor: BlendFactor::One,
+                    operation: BlendOperation::Add,
+                },
+            }),
+        ));
+
+        BloomPipelines {
+            bind_group_layout,
+            sampler,
+            downsample_prefilter_pipeline,
+            downsample_pipeline,
+            upsample_pipeline,
+            upsample_final_pipeline,
+        }
+    }
+}
+
+/// The mip chains of the bloom pass of a view.
+#[derive(Component)]
+pub struct BloomTextures {
+    /// The downsampled mips, `mip_count` mips starting at half the size of the view.
+    downsampled: CachedTexture,
+    /// The upsampled mips, `mip_count - 1` mips starting at half the size of the view.
+    upsampled: Option<CachedTexture>,
+    mip_count: u32,
+}
+
+impl BloomTextures {
+    fn downsampled_mip(&self, mip: u32) -> TextureView {
+        mip_view(&self.downsampled, mip)
+    }
+
+    fn upsampled_mip(&self, mip: u32) -> TextureView {
+        mip_view(self.upsampled.as_ref().unwrap(), mip)
+    }
+}
+
+fn mip_view(texture: &CachedTexture, mip: u32) -> TextureView {
+    texture.texture.create_view(&TextureViewDescriptor {
+        base_mip_level: mip,
+        mip_level_count: NonZeroU32::new(1),
+        ..Default::default()
+    })
+}
+
+/// The number of mips of the bloom chain of a view whose half size is `size`, stopping a few
+/// mips before a single pixel as the smallest mips barely contribute to the bloom.
+fn bloom_mip_count(size: UVec2) -> u32 {
+    let min_element = size.x.min(size.y).max(1);
+    ((min_element as f32).log2().round() as i32 - 3).max(1) as u32
+}
+
+/// The size of the first mip of the bloom chain of a view, half the size of its target.
+fn bloom_texture_size(target_size: UVec2) -> UVec2 {
+    (target_size / 2).max(UVec2::ONE)
+}
+
+fn prepare_bloom_textures(
+    mut commands: Commands,
+    mut texture_cache: ResMut<TextureCache>,
+    render_device: Res<RenderDevice>,
+    views: Query<(Entity, &ExtractedCamera), With<BloomUniform>>,
+) {
+    for (entity, camera) in &views {
+        if let Some(target_size) = camera.physical_target_size {
+            let size = bloom_texture_size(target_size);
+            let mip_count = bloom_mip_count(size);
+
+            let descriptor = TextureDescriptor {
+                label: Some("bloom_downsampled_texture"),
+                size: Extent3d {
+                    width: size.x,
+                    height: size.y,
+                    depth_or_array_layers: 1,
+                },
+                mip_level_count: mip_count,
+                sample_count: 1,
+                dimension: TextureDimension::D2,
+                format: ViewTarget::TEXTURE_FORMAT_HDR,
+                usage: TextureUsages::RENDER_ATTACHMENT | TextureUsages::TEXTURE_BINDING,
+            };
+            let downsampled = texture_cache.get(&render_device, descriptor.clone());
+            let upsampled = (mip_count > 1).then(|| {
+                texture_cache.get(
+                    &render_device,
+                    TextureDescriptor {
+                        label: Some("bloom_upsampled_texture"),
+                        mip_level_count: mip_count - 1,
+                        ..descriptor
+                    },
+                )
+            });
+
+            commands.entity(entity).insert(BloomTextures {
+                downsampled,
+                upsampled,
+                mip_count,
+            });
+        }
+    }
+}
+
+/// Blurs the bright areas of "high dynamic range" views with [`BloomSettings`], adding them back
+/// to the main texture of the view.
+pub struct BloomNode {
+    query: QueryState<
+        (
+            &'static ViewTarget,
+            &'static BloomTextures,
+            &'static DynamicUniformIndex<BloomUniform>,
+        ),
+        With<ExtractedView>,
+    >,
+}
+
+impl BloomNode {
+    pub const IN_VIEW: &'static str = "view";
+
+    pub fn new(world: &mut World) -> Self {
+        Self {
+            query: world.query_filtered(),
+        }
+    }
+}
+
+impl Node for BloomNode {
+    fn input(&self) -> Vec<SlotInfo> {
+        vec![SlotInfo::new(Self::IN_VIEW, SlotType::Entity)]
+    }
+
+    fn update(&mut self, world: &mut World) {
+        self.query.update_archetypes(world);
+    }
+
+    fn run(
+        &self,
+        graph: &mut RenderGraphContext,
+        render_context: &mut RenderContext,
+        world: &World,
+    ) -> Result<(), NodeRunError> {
+        #[cfg(feature = "trace")]
+        let _bloom_span = info_span!("bloom").entered();
+
+        let view_entity = graph.get_input_entity(Self::IN_VIEW)?;
+        let (view_target, textures, uniform_index) = match self.query.get_manual(world, view_entity)
+        {
+            Ok(result) => result,
+            Err(_) => return Ok(()),
+        };
+        let pipelines = world.resource::<BloomPipelines>();
+        let pipeline_cache = world.resource::<PipelineCache>();
+        let uniforms = match world
+            .resource::<ComponentUniforms<BloomUniform>>()
+            .binding()
+        {
+            Some(uniforms) => uniforms,
+            None => return Ok(()),
+        };
+        let (
+            downsample_prefilter_pipeline,
+            downsample_pipeline,
+            upsample_pipeline,
+            upsample_final_pipeline,
+        ) = match (
+            pipeline_cache.get_render_pipeline(pipelines.downsample_prefilter_pipeline),
+            pipeline_cache.get_render_pipeline(pipelines.downsample_pipeline),
+            pipeline_cache.get_render_pipeline(pipelines.upsample_pipeline),
+            pipeline_cache.get_render_pipeline(pipelines.upsample_final_pipeline),
+        ) {
+            (Some(a), Some(b), Some(c), Some(d)) => (a, b, c, d),
+            _ => return Ok(()),
+        };
+
+        let mut bloom_pass = |label: &'static str,
+                              pipeline: &RenderPipeline,
+                              source: &TextureView,
+                              up: &TextureView,
+                              destination: &TextureView,
+                              clear: bool| {
+            let bind_group = render_context
+                .render_device
+                .create_bind_group(&BindGroupDescriptor {
+                    label: Some("bloom_bind_group"),
+                    layout: &pipelines.bind_group_layout,
+                    entries: &[
+                        BindGroupEntry {
+                            binding: 0,
+                            resource: BindingResource::TextureView(source),
+                        },
+                        BindGroupEntry {
+                            binding: 1,
+                            resource: BindingResource::Sampler(&pipelines.sampler),
+                        },
+                        BindGroupEntry {
+                            binding: 2,
+                            resource: uniforms.clone(),
+                        },
+                        BindGroupEntry {
+                            binding: 3,
+                            resource: BindingResource::TextureView(up),
+                        },
+                    ],
+                });
+            let mut pass =
+                render_context
+                    .command_encoder
+                    .begin_render_pass(&RenderPassDescriptor {
+                        label: Some(label),
+                        color_attachments: &[Some(RenderPassColorAttachment {
+                            view: destination,
+                            resolve_target: None,
+                            ops: Operations {
+                                load: if clear {
+                                    LoadOp::Clear(Default::default())
+                                } else {
+                                    LoadOp::Load
+                                },
+                                store: true,
+                            },
+                        })],
+                        depth_stencil_attachment: None,
+                    });
+            pass.set_pipeline(pipeline);
+            pass.set_bind_group(0, &bind_group, &[uniform_index.index()]);
+            pass.draw(0..3, 0..1);
+        };
+
+        // the bright areas of the main texture are written to the first downsampled mip, which
+        // is then downsampled down the chain
+        let main_texture = view_target.main_texture();
+        let mut downsampled = textures.downsampled_mip(0);
+        bloom_pass(
+            "bloom_downsample_prefilter_pass",
+            downsample_prefilter_pipeline,
+            main_texture,
+            main_texture,
+            &downsampled,
+            true,
+        );
+        for mip in 1..textures.mip_count {
+            let destination = textures.downsampled_mip(mip);
+            bloom_pass(
+                "bloom_downsample_pass",
+                downsample_pipeline,
+                &downsampled,
+                &downsampled,
+                &destination,
+                true,
+            );
+            downsampled = destination;
+        }
+
+        // each upsampled mip is added to the downsampled mip of its size, from the smallest mip
+        // back up to the first one
+        let mut upsampled = downsampled;
+        for mip in (1..textures.mip_count).rev() {
+            let destination = textures.upsampled_mip(mip - 1);
+            bloom_pass(
+                "bloom_upsample_pass",
+                upsample_pipeline,
+                &upsampled,
+                &textures.downsampled_mip(mip - 1),
+                &destination,
+                true,
+            );
+            upsampled = destination;
+        }
+
+        bloom_pass(
+            "bloom_upsample_final_pass",
+            upsample_final_pipeline,
+            &upsampled,
+            &upsampled,
+            main_texture,
+            false,
+        );
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bloom_uniform() {
+        let settings = BloomSettings {
+            threshold: 2.5,
+            knee: 0.5,
+            scale: 1.5,
+            intensity: 0.05,
+        };
+        assert_eq!(
+            BloomUniform::from(&settings),
+            BloomUniform {
+                threshold: 2.5,
+                knee: 0.5,
+                scale: 1.5,
+                intensity: 0.05,
+            }
+        );
+    }
+
+    #[test]
+    fn bloom_knee_is_never_zero() {
+        let settings = BloomSettings {
+            knee: 0.0,
+            ..Default::default()
+        };
+        assert!(BloomUniform::from(&settings).knee > 0.0);
+    }
+
+    #[test]
+    fn bloom_mip_counts() {
+        // the chain stops 3 mips before a single pixel
+        assert_eq!(bloom_mip_count(UVec2::new(960, 540)), 6);
+        assert_eq!(bloom_mip_count(UVec2::new(1024, 2048)), 7);
+        // small views still get a mip
+        assert_eq!(bloom_mip_count(UVec2::new(4, 4)), 1);
+        assert_eq!(bloom_mip_count(UVec2::ZERO), 1);
+    }
+
+    #[test]
+    fn bloom_mip_sizes_of_odd_targets() {
+        // every mip of the chain of a non-square target with odd sides is at least a pixel wide
+        let size = bloom_texture_size(UVec2::new(1921, 1081));
+        assert_eq!(size, UVec2::new(960, 540));
+        let mip_count = bloom_mip_count(size);
+        assert_eq!(mip_count, 6);
+        let smallest_mip = UVec2::new(size.x >> (mip_count - 1), size.y >> (mip_count - 1));
+        assert_eq!(smallest_mip, UVec2::new(30, 16));
+
+        // thin targets get a single mip
+        let size = bloom_texture_size(UVec2::new(4097, 3));
+        assert_eq!(size, UVec2::new(2048, 1));
+        assert_eq!(bloom_mip_count(size), 1);
+        assert_eq!(bloom_texture_size(UVec2::ONE), UVec2::ONE);
+    }
+}
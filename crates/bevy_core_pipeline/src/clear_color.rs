@@ -1,7 +1,7 @@
 use bevy_derive::{Deref, DerefMut};
 use bevy_ecs::prelude::*;
 use bevy_reflect::{Reflect, ReflectDeserialize, ReflectSerialize};
-use bevy_render::{color::Color, extract_resource::ExtractResource};
+use bevy_render::{camera::CameraOutputMode, color::Color, extract_resource::ExtractResource};
 use serde::{Deserialize, Serialize};
 
 #[derive(Reflect, Serialize, Deserialize, Clone, Debug, Default)]
@@ -13,6 +13,26 @@ pub enum ClearColorConfig {
     None,
 }
 
+impl ClearColorConfig {
+    /// Returns the color the main texture of a camera with the given `output_mode` is cleared
+    /// to, or `None` if its contents are kept.
+    ///
+    /// The main texture of a [`CameraOutputMode::Composite`] camera is not shared with other
+    /// cameras, so it is cleared to transparent instead of being kept or cleared to `default`.
+    pub fn clear_color(
+        &self,
+        default: &ClearColor,
+        output_mode: CameraOutputMode,
+    ) -> Option<Color> {
+        match (self, output_mode) {
+            (ClearColorConfig::Custom(color), _) => Some(*color),
+            (_, CameraOutputMode::Composite(_)) => Some(Color::NONE),
+            (ClearColorConfig::Default, CameraOutputMode::Write) => Some(default.0),
+            (ClearColorConfig::None, CameraOutputMode::Write) => None,
+        }
+    }
+}
+
 /// A [`Resource`] that stores the color that is used to clear the screen between frames.
 ///
 /// This color appears as the "background" color for simple apps,
@@ -26,3 +46,43 @@ impl Default for ClearColor {
         Self(Color::rgb(0.4, 0.4, 0.4))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{ClearColor, ClearColorConfig};
+    use bevy_render::{
+        camera::{CameraBlendMode, CameraOutputMode},
+        color::Color,
+    };
+
+    #[test]
+    fn clear_color_of_output_modes() {
+        let default = ClearColor(Color::BLUE);
+        let write = CameraOutputMode::Write;
+        let composite = CameraOutputMode::Composite(CameraBlendMode::Additive);
+
+        assert_eq!(
+            ClearColorConfig::Default.clear_color(&default, write),
+            Some(Color::BLUE)
+        );
+        assert_eq!(ClearColorConfig::None.clear_color(&default, write), None);
+        assert_eq!(
+            ClearColorConfig::Custom(Color::RED).clear_color(&default, write),
+            Some(Color::RED)
+        );
+
+        // the main texture of a compositing camera is only its own, so it is always cleared
+        assert_eq!(
+            ClearColorConfig::Default.clear_color(&default, composite),
+            Some(Color::NONE)
+        );
+        assert_eq!(
+            ClearColorConfig::None.clear_color(&default, composite),
+            Some(Color::NONE)
+        );
+        assert_eq!(
+            ClearColorConfig::Custom(Color::RED).clear_color(&default, composite),
+            Some(Color::RED)
+        );
+    }
+}
@@ -1,4 +1,7 @@
-use crate::clear_color::ClearColorConfig;
+use crate::{
+    clear_color::ClearColorConfig,
+    tonemapping::{DebandDither, Tonemapping},
+};
 use bevy_ecs::{prelude::*, query::QueryItem};
 use bevy_reflect::Reflect;
 use bevy_render::{
@@ -34,6 +37,8 @@ pub struct Camera2dBundle {
     pub transform: Transform,
     pub global_transform: GlobalTransform,
     pub camera_2d: Camera2d,
+    pub tonemapping: Tonemapping,
+    pub deband_dither: DebandDither,
 }
 
 impl Default for Camera2dBundle {
@@ -74,6 +79,10 @@ impl Camera2dBundle {
             global_transform: Default::default(),
             camera: Camera::default(),
             camera_2d: Camera2d::default(),
+            // 2d colors are displayed as is, unless the camera is "high dynamic range" and opts
+            // into tonemapping
+            tonemapping: Tonemapping::None,
+            deband_dither: DebandDither::Disabled,
         }
     }
 }
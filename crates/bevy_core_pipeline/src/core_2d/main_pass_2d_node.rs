@@ -1,5 +1,5 @@
 use crate::{
-    clear_color::{ClearColor, ClearColorConfig},
+    clear_color::ClearColor,
     core_2d::{camera_2d::Camera2d, Transparent2d},
 };
 use bevy_ecs::prelude::*;
@@ -64,16 +64,18 @@ impl Node for MainPass2dNode {
             let _main_pass_2d = info_span!("main_pass_2d").entered();
             let pass_descriptor = RenderPassDescriptor {
                 label: Some("main_pass_2d"),
-                color_attachments: &[Some(target.get_color_attachment(Operations {
-                    load: match camera_2d.clear_color {
-                        ClearColorConfig::Default => {
-                            LoadOp::Clear(world.resource::<ClearColor>().0.into())
-                        }
-                        ClearColorConfig::Custom(color) => LoadOp::Clear(color.into()),
-                        ClearColorConfig::None => LoadOp::Load,
-                    },
-                    store: true,
-                }))],
+                color_attachments: &[Some(
+                    target.get_color_attachment(Operations {
+                        load: match camera_2d
+                            .clear_color
+                            .clear_color(world.resource::<ClearColor>(), camera.output_mode)
+                        {
+                            Some(color) => LoadOp::Clear(color.into()),
+                            None => LoadOp::Load,
+                        },
+                        store: true,
+                    }),
+                )],
                 depth_stencil_attachment: None,
             };
 
@@ -6,14 +6,25 @@ pub mod graph {
     pub mod input {
         pub const VIEW_ENTITY: &str = "view_entity";
     }
+    /// The nodes of the graph, which run in the order `MAIN_PASS`, `BLOOM`, `TONEMAPPING`, then
+    /// `UPSCALING`.
+    ///
+    /// Post-processing nodes reading the "high dynamic range" colors of the view are ordered
+    /// between `BLOOM` and `TONEMAPPING`, the ones reading the tonemapped colors between
+    /// `TONEMAPPING` and `UPSCALING`. See
+    /// [`ViewTarget::post_process_write`](bevy_render::view::ViewTarget::post_process_write).
     pub mod node {
         pub const MAIN_PASS: &str = "main_pass";
+        pub const BLOOM: &str = "bloom";
+        pub const TONEMAPPING: &str = "tonemapping";
+        pub const UPSCALING: &str = "upscaling";
     }
 }
 
 pub use camera_2d::*;
 pub use main_pass_2d_node::*;
 
+use crate::{tonemapping::TonemappingNode, upscaling::UpscalingNode};
 use bevy_app::{App, Plugin};
 use bevy_ecs::prelude::*;
 use bevy_render::{
@@ -52,10 +63,14 @@ impl Plugin for Core2dPlugin {
             );
 
         let pass_node_2d = MainPass2dNode::new(&mut render_app.world);
+        let tonemapping = TonemappingNode::new(&mut render_app.world);
+        let upscaling = UpscalingNode::new(&mut render_app.world);
         let mut graph = render_app.world.resource_mut::<RenderGraph>();
 
         let mut draw_2d_graph = RenderGraph::default();
         draw_2d_graph.add_node(graph::node::MAIN_PASS, pass_node_2d);
+        draw_2d_graph.add_node(graph::node::TONEMAPPING, tonemapping);
+        draw_2d_graph.add_node(graph::node::UPSCALING, upscaling);
         let input_node_id = draw_2d_graph.set_input(vec![SlotInfo::new(
             graph::input::VIEW_ENTITY,
             SlotType::Entity,
@@ -68,6 +83,28 @@ impl Plugin for Core2dPlugin {
                 MainPass2dNode::IN_VIEW,
             )
             .unwrap();
+        draw_2d_graph
+            .add_slot_edge(
+                input_node_id,
+                graph::input::VIEW_ENTITY,
+                graph::node::TONEMAPPING,
+                TonemappingNode::IN_VIEW,
+            )
+            .unwrap();
+        draw_2d_graph
+            .add_slot_edge(
+                input_node_id,
+                graph::input::VIEW_ENTITY,
+                graph::node::UPSCALING,
+                UpscalingNode::IN_VIEW,
+            )
+            .unwrap();
+        draw_2d_graph
+            .add_node_edge(graph::node::MAIN_PASS, graph::node::TONEMAPPING)
+            .unwrap();
+        draw_2d_graph
+            .add_node_edge(graph::node::TONEMAPPING, graph::node::UPSCALING)
+            .unwrap();
         graph.add_sub_graph(graph::NAME, draw_2d_graph);
     }
 }
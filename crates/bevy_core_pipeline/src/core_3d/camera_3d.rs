@@ -1,4 +1,7 @@
-use crate::clear_color::ClearColorConfig;
+use crate::{
+    clear_color::ClearColorConfig,
+    tonemapping::{DebandDither, Tonemapping},
+};
 use bevy_ecs::{prelude::*, query::QueryItem};
 use bevy_reflect::{Reflect, ReflectDeserialize, ReflectSerialize};
 use bevy_render::{
@@ -19,6 +22,9 @@ pub struct Camera3d {
     pub clear_color: ClearColorConfig,
     /// The depth clear operation to perform for the main 3d pass.
     pub depth_load_op: Camera3dDepthLoadOp,
+    /// How the transparent meshes of the [`Transparent3d`](super::Transparent3d) phase are
+    /// blended in the main 3d pass.
+    pub transparency: Camera3dTransparency,
 }
 
 /// The depth clear operation to perform for the main 3d pass.
@@ -47,6 +53,35 @@ impl From<Camera3dDepthLoadOp> for LoadOp<f32> {
     }
 }
 
+/// How the transparent meshes of a 3d camera are blended.
+#[derive(Reflect, Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[reflect(Serialize, Deserialize)]
+pub enum Camera3dTransparency {
+    /// The meshes are sorted back-to-front by the view depth of their origin, then alpha blended.
+    ///
+    /// Intersecting meshes and meshes whose origins are not in the same order as their
+    /// triangles are blended in the wrong order, which can be fixed with a
+    /// [`TransparentSortOverride`](super::TransparentSortOverride).
+    Sorted,
+    /// Weighted blended order-independent transparency, by McGuire and Bavoil.
+    ///
+    /// The meshes are not sorted: their colors are accumulated with weights decreasing with their
+    /// distance to the camera, then composited over the opaque meshes. Intersecting meshes are
+    /// blended without artifacts, but the result only approximates the sorted blending: the
+    /// colors of overlapping meshes with a high alpha are mixed instead of occluding each other.
+    ///
+    /// The fragment shaders of the transparent materials have to return the
+    /// `OitFragmentOutput` of `bevy_core_pipeline::oit` when the `WEIGHTED_BLENDED_OIT` shader
+    /// def is set, like the shader of the `StandardMaterial` does.
+    WeightedBlendedOit,
+}
+
+impl Default for Camera3dTransparency {
+    fn default() -> Self {
+        Camera3dTransparency::Sorted
+    }
+}
+
 impl ExtractComponent for Camera3d {
     type Query = &'static Self;
     type Filter = With<Camera>;
@@ -66,6 +101,8 @@ pub struct Camera3dBundle {
     pub transform: Transform,
     pub global_transform: GlobalTransform,
     pub camera_3d: Camera3d,
+    pub tonemapping: Tonemapping,
+    pub deband_dither: DebandDither,
 }
 
 // NOTE: ideally Perspective and Orthographic defaults can share the same impl, but sadly it breaks rust's type inference
@@ -80,6 +117,8 @@ impl Default for Camera3dBundle {
             transform: Default::default(),
             global_transform: Default::default(),
             camera_3d: Default::default(),
+            tonemapping: Default::default(),
+            deband_dither: Default::default(),
         }
     }
 }
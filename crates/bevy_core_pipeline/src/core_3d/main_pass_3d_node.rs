@@ -1,13 +1,17 @@
 use crate::{
-    clear_color::{ClearColor, ClearColorConfig},
+    clear_color::ClearColor,
     core_3d::{AlphaMask3d, Camera3d, Opaque3d, Transparent3d},
+    oit::{is_oit_pipeline, OitCompositePipeline, ViewOitCompositePipeline, ViewOitTextures},
 };
 use bevy_ecs::prelude::*;
 use bevy_render::{
     camera::ExtractedCamera,
     render_graph::{Node, NodeRunError, RenderGraphContext, SlotInfo, SlotType},
     render_phase::{DrawFunctions, RenderPhase, TrackedRenderPass},
-    render_resource::{LoadOp, Operations, RenderPassDepthStencilAttachment, RenderPassDescriptor},
+    render_resource::{
+        BindGroupDescriptor, BindGroupEntry, BindingResource, LoadOp, Operations, PipelineCache,
+        RenderPassDepthStencilAttachment, RenderPassDescriptor,
+    },
     renderer::RenderContext,
     view::{ExtractedView, ViewDepthTexture, ViewTarget},
 };
@@ -24,6 +28,8 @@ pub struct MainPass3dNode {
             &'static Camera3d,
             &'static ViewTarget,
             &'static ViewDepthTexture,
+            Option<&'static ViewOitTextures>,
+            Option<&'static ViewOitCompositePipeline>,
         ),
         With<ExtractedView>,
     >,
@@ -55,13 +61,22 @@ impl Node for MainPass3dNode {
         world: &World,
     ) -> Result<(), NodeRunError> {
         let view_entity = graph.get_input_entity(Self::IN_VIEW)?;
-        let (camera, opaque_phase, alpha_mask_phase, transparent_phase, camera_3d, target, depth) =
-            match self.query.get_manual(world, view_entity) {
-                Ok(query) => query,
-                Err(_) => {
-                    return Ok(());
-                } // No window
-            };
+        let (
+            camera,
+            opaque_phase,
+            alpha_mask_phase,
+            transparent_phase,
+            camera_3d,
+            target,
+            depth,
+            oit_textures,
+            oit_composite_pipeline,
+        ) = match self.query.get_manual(world, view_entity) {
+            Ok(query) => query,
+            Err(_) => {
+                return Ok(());
+            } // No window
+        };
 
         // Always run opaque pass to ensure screen is cleared
         {
@@ -73,16 +88,18 @@ impl Node for MainPass3dNode {
                 label: Some("main_opaque_pass_3d"),
                 // NOTE: The opaque pass loads the color
                 // buffer as well as writing to it.
-                color_attachments: &[Some(target.get_color_attachment(Operations {
-                    load: match camera_3d.clear_color {
-                        ClearColorConfig::Default => {
-                            LoadOp::Clear(world.resource::<ClearColor>().0.into())
-                        }
-                        ClearColorConfig::Custom(color) => LoadOp::Clear(color.into()),
-                        ClearColorConfig::None => LoadOp::Load,
-                    },
-                    store: true,
-                }))],
+                color_attachments: &[Some(
+                    target.get_color_attachment(Operations {
+                        load: match camera_3d
+                            .clear_color
+                            .clear_color(world.resource::<ClearColor>(), camera.output_mode)
+                        {
+                            Some(color) => LoadOp::Clear(color.into()),
+                            None => LoadOp::Load,
+                        },
+                        store: true,
+                    }),
+                )],
                 depth_stencil_attachment: Some(RenderPassDepthStencilAttachment {
                     view: &depth.view,
                     // NOTE: The opaque main pass loads the depth buffer and possibly overwrites it
@@ -150,7 +167,109 @@ impl Node for MainPass3dNode {
             }
         }
 
-        if !transparent_phase.items.is_empty() {
+        // Only the items whose pipeline draws into the OIT textures are accumulated, the others
+        // are drawn by the sorted transparent pass, after the accumulated items are composited
+        let (oit_items, sorted_items): (Vec<&Transparent3d>, Vec<&Transparent3d>) =
+            if oit_textures.is_some() && oit_composite_pipeline.is_some() {
+                let pipeline_cache = world.resource::<PipelineCache>();
+                transparent_phase.items.iter().partition(|item| {
+                    is_oit_pipeline(pipeline_cache.get_render_pipeline_descriptor(item.pipeline))
+                })
+            } else {
+                (Vec::new(), transparent_phase.items.iter().collect())
+            };
+
+        if let (false, Some(oit_textures), Some(oit_composite_pipeline)) =
+            (oit_items.is_empty(), oit_textures, oit_composite_pipeline)
+        {
+            // Run the transparent pass with weighted blended order-independent transparency,
+            // accumulating the transparent meshes in any order before compositing them
+            // NOTE: Scoped to drop the mutable borrow of render_context
+            #[cfg(feature = "trace")]
+            let _main_oit_pass_3d_span = info_span!("main_oit_pass_3d").entered();
+            let pass_descriptor = RenderPassDescriptor {
+                label: Some("main_oit_pass_3d"),
+                color_attachments: &oit_textures.color_attachments(),
+                depth_stencil_attachment: Some(RenderPassDepthStencilAttachment {
+                    view: &depth.view,
+                    // NOTE: Like the sorted transparent pass, the depth buffer is loaded so that
+                    // opaque meshes occlude transparent ones, and stored as a workaround for
+                    // issue #3776.
+                    depth_ops: Some(Operations {
+                        load: LoadOp::Load,
+                        store: true,
+                    }),
+                    stencil_ops: None,
+                }),
+            };
+
+            let draw_functions = world.resource::<DrawFunctions<Transparent3d>>();
+
+            let render_pass = render_context
+                .command_encoder
+                .begin_render_pass(&pass_descriptor);
+            let mut draw_functions = draw_functions.write();
+            let mut tracked_pass = TrackedRenderPass::new(render_pass);
+            if let Some(viewport) = camera.viewport.as_ref() {
+                tracked_pass.set_camera_viewport(viewport);
+            }
+            for item in oit_items {
+                let draw_function = draw_functions.get_mut(item.draw_function).unwrap();
+                draw_function.draw(world, &mut tracked_pass, view_entity, item);
+            }
+            drop(tracked_pass);
+
+            // the composite pipeline may still be compiling, skipping the transparent meshes
+            if let Some(pipeline) = world
+                .resource::<PipelineCache>()
+                .get_render_pipeline(oit_composite_pipeline.0)
+            {
+                #[cfg(feature = "trace")]
+                let _oit_composite_pass_3d_span = info_span!("oit_composite_pass_3d").entered();
+                let bind_group =
+                    render_context
+                        .render_device
+                        .create_bind_group(&BindGroupDescriptor {
+                            label: Some("oit_composite_texture_bind_group"),
+                            layout: &world.resource::<OitCompositePipeline>().texture_bind_group,
+                            entries: &[
+                                BindGroupEntry {
+                                    binding: 0,
+                                    resource: BindingResource::TextureView(
+                                        &oit_textures.accum.default_view,
+                                    ),
+                                },
+                                BindGroupEntry {
+                                    binding: 1,
+                                    resource: BindingResource::TextureView(
+                                        &oit_textures.revealage.default_view,
+                                    ),
+                                },
+                            ],
+                        });
+                let pass_descriptor = RenderPassDescriptor {
+                    label: Some("oit_composite_pass_3d"),
+                    color_attachments: &[Some(target.get_color_attachment(Operations {
+                        load: LoadOp::Load,
+                        store: true,
+                    }))],
+                    depth_stencil_attachment: None,
+                };
+
+                let render_pass = render_context
+                    .command_encoder
+                    .begin_render_pass(&pass_descriptor);
+                let mut tracked_pass = TrackedRenderPass::new(render_pass);
+                if let Some(viewport) = camera.viewport.as_ref() {
+                    tracked_pass.set_camera_viewport(viewport);
+                }
+                tracked_pass.set_render_pipeline(pipeline);
+                tracked_pass.set_bind_group(0, &bind_group, &[]);
+                tracked_pass.draw(0..3, 0..1);
+            }
+        }
+
+        if !sorted_items.is_empty() {
             // Run the transparent pass, sorted back-to-front
             // NOTE: Scoped to drop the mutable borrow of render_context
             #[cfg(feature = "trace")]
@@ -188,7 +307,7 @@ impl Node for MainPass3dNode {
             if let Some(viewport) = camera.viewport.as_ref() {
                 tracked_pass.set_camera_viewport(viewport);
             }
-            for item in &transparent_phase.items {
+            for item in sorted_items {
                 let draw_function = draw_functions.get_mut(item.draw_function).unwrap();
                 draw_function.draw(world, &mut tracked_pass, view_entity, item);
             }
@@ -6,26 +6,38 @@ pub mod graph {
     pub mod input {
         pub const VIEW_ENTITY: &str = "view_entity";
     }
+    /// The nodes of the graph, which run in the order `MAIN_PASS`, `BLOOM`, `TONEMAPPING`, then
+    /// `UPSCALING`.
+    ///
+    /// Post-processing nodes reading the "high dynamic range" colors of the view are ordered
+    /// between `BLOOM` and `TONEMAPPING`, the ones reading the tonemapped colors between
+    /// `TONEMAPPING` and `UPSCALING`. See
+    /// [`ViewTarget::post_process_write`](bevy_render::view::ViewTarget::post_process_write).
     pub mod node {
         pub const MAIN_PASS: &str = "main_pass";
+        pub const BLOOM: &str = "bloom";
+        pub const TONEMAPPING: &str = "tonemapping";
+        pub const UPSCALING: &str = "upscaling";
     }
 }
 
-use std::cmp::Reverse;
+use std::{cmp::Reverse, ops::Range};
 
 pub use camera_3d::*;
 pub use main_pass_3d_node::*;
 
+use crate::{tonemapping::TonemappingNode, upscaling::UpscalingNode};
 use bevy_app::{App, Plugin};
-use bevy_ecs::prelude::*;
+use bevy_ecs::{prelude::*, query::QueryItem};
+use bevy_reflect::Reflect;
 use bevy_render::{
     camera::{Camera, ExtractedCamera},
-    extract_component::ExtractComponentPlugin,
+    extract_component::{ExtractComponent, ExtractComponentPlugin},
     prelude::Msaa,
     render_graph::{RenderGraph, SlotInfo, SlotType},
     render_phase::{
-        sort_phase_system, CachedRenderPipelinePhaseItem, DrawFunctionId, DrawFunctions,
-        EntityPhaseItem, PhaseItem, RenderPhase,
+        sort_phase_system, BatchedPhaseItem, CachedRenderPipelinePhaseItem, DrawFunctionId,
+        DrawFunctions, EntityPhaseItem, PhaseItem, RenderPhase,
     },
     render_resource::{
         CachedRenderPipelineId, Extent3d, TextureDescriptor, TextureDimension, TextureFormat,
@@ -44,7 +56,10 @@ impl Plugin for Core3dPlugin {
     fn build(&self, app: &mut App) {
         app.register_type::<Camera3d>()
             .register_type::<Camera3dDepthLoadOp>()
-            .add_plugin(ExtractComponentPlugin::<Camera3d>::default());
+            .register_type::<Camera3dTransparency>()
+            .register_type::<TransparentSortOverride>()
+            .add_plugin(ExtractComponentPlugin::<Camera3d>::default())
+            .add_plugin(ExtractComponentPlugin::<TransparentSortOverride>::default());
 
         let render_app = match app.get_sub_app_mut(RenderApp) {
             Ok(render_app) => render_app,
@@ -62,10 +77,14 @@ impl Plugin for Core3dPlugin {
             .add_system_to_stage(RenderStage::PhaseSort, sort_phase_system::<Transparent3d>);
 
         let pass_node_3d = MainPass3dNode::new(&mut render_app.world);
+        let tonemapping = TonemappingNode::new(&mut render_app.world);
+        let upscaling = UpscalingNode::new(&mut render_app.world);
         let mut graph = render_app.world.resource_mut::<RenderGraph>();
 
         let mut draw_3d_graph = RenderGraph::default();
         draw_3d_graph.add_node(graph::node::MAIN_PASS, pass_node_3d);
+        draw_3d_graph.add_node(graph::node::TONEMAPPING, tonemapping);
+        draw_3d_graph.add_node(graph::node::UPSCALING, upscaling);
         let input_node_id = draw_3d_graph.set_input(vec![SlotInfo::new(
             graph::input::VIEW_ENTITY,
             SlotType::Entity,
@@ -78,6 +97,28 @@ impl Plugin for Core3dPlugin {
                 MainPass3dNode::IN_VIEW,
             )
             .unwrap();
+        draw_3d_graph
+            .add_slot_edge(
+                input_node_id,
+                graph::input::VIEW_ENTITY,
+                graph::node::TONEMAPPING,
+                TonemappingNode::IN_VIEW,
+            )
+            .unwrap();
+        draw_3d_graph
+            .add_slot_edge(
+                input_node_id,
+                graph::input::VIEW_ENTITY,
+                graph::node::UPSCALING,
+                UpscalingNode::IN_VIEW,
+            )
+            .unwrap();
+        draw_3d_graph
+            .add_node_edge(graph::node::MAIN_PASS, graph::node::TONEMAPPING)
+            .unwrap();
+        draw_3d_graph
+            .add_node_edge(graph::node::TONEMAPPING, graph::node::UPSCALING)
+            .unwrap();
         graph.add_sub_graph(graph::NAME, draw_3d_graph);
     }
 }
@@ -87,6 +128,8 @@ pub struct Opaque3d {
     pub pipeline: CachedRenderPipelineId,
     pub entity: Entity,
     pub draw_function: DrawFunctionId,
+    /// Range of the instances drawn by this item, if it is drawn as a batch of instances.
+    pub batch_range: Option<Range<u32>>,
 }
 
 impl PhaseItem for Opaque3d {
@@ -124,11 +167,23 @@ impl CachedRenderPipelinePhaseItem for Opaque3d {
     }
 }
 
+impl BatchedPhaseItem for Opaque3d {
+    fn batch_range(&self) -> &Option<Range<u32>> {
+        &self.batch_range
+    }
+
+    fn batch_range_mut(&mut self) -> &mut Option<Range<u32>> {
+        &mut self.batch_range
+    }
+}
+
 pub struct AlphaMask3d {
     pub distance: f32,
     pub pipeline: CachedRenderPipelineId,
     pub entity: Entity,
     pub draw_function: DrawFunctionId,
+    /// Range of the instances drawn by this item, if it is drawn as a batch of instances.
+    pub batch_range: Option<Range<u32>>,
 }
 
 impl PhaseItem for AlphaMask3d {
@@ -166,11 +221,23 @@ impl CachedRenderPipelinePhaseItem for AlphaMask3d {
     }
 }
 
+impl BatchedPhaseItem for AlphaMask3d {
+    fn batch_range(&self) -> &Option<Range<u32>> {
+        &self.batch_range
+    }
+
+    fn batch_range_mut(&mut self) -> &mut Option<Range<u32>> {
+        &mut self.batch_range
+    }
+}
+
 pub struct Transparent3d {
     pub distance: f32,
     pub pipeline: CachedRenderPipelineId,
     pub entity: Entity,
     pub draw_function: DrawFunctionId,
+    /// Range of the instances drawn by this item, if it is drawn as a batch of instances.
+    pub batch_range: Option<Range<u32>>,
 }
 
 impl PhaseItem for Transparent3d {
@@ -207,6 +274,51 @@ impl CachedRenderPipelinePhaseItem for Transparent3d {
     }
 }
 
+impl BatchedPhaseItem for Transparent3d {
+    fn batch_range(&self) -> &Option<Range<u32>> {
+        &self.batch_range
+    }
+
+    fn batch_range_mut(&mut self) -> &mut Option<Range<u32>> {
+        &mut self.batch_range
+    }
+}
+
+/// Overrides the distance used to sort a transparent entity in the [`Transparent3d`] phase of
+/// every view.
+///
+/// Transparent entities are sorted back-to-front by the view depth of their origin, which is
+/// wrong for intersecting meshes or for layers that must always be drawn in the same order,
+/// like particle layers. The `distance` of the [`Transparent3d`] items of the entity is
+/// [`TransparentSortOverride::distance`].
+#[derive(Component, Reflect, Clone, Copy, Debug, Default, PartialEq)]
+#[reflect(Component)]
+pub struct TransparentSortOverride {
+    /// Replaces the view depth of the entity when set. Like the view depth, the values increase
+    /// towards the camera, so entities with greater values are drawn later, on top of the others.
+    pub sort_key: Option<f32>,
+    /// Added to the view depth of the entity, or to its `sort_key` when set. Positive values
+    /// draw the entity later, negative values earlier.
+    pub depth_bias: f32,
+}
+
+impl TransparentSortOverride {
+    /// Returns the distance sorting the entity, given its view depth.
+    #[inline]
+    pub fn distance(&self, view_depth: f32) -> f32 {
+        self.sort_key.unwrap_or(view_depth) + self.depth_bias
+    }
+}
+
+impl ExtractComponent for TransparentSortOverride {
+    type Query = &'static Self;
+    type Filter = ();
+
+    fn extract_component(item: QueryItem<Self::Query>) -> Self {
+        *item
+    }
+}
+
 pub fn extract_core_3d_camera_phases(
     mut commands: Commands,
     cameras_3d: Extract<Query<(Entity, &Camera), With<Camera3d>>>,
@@ -268,3 +380,32 @@ pub fn prepare_core_3d_depth_textures(
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::TransparentSortOverride;
+
+    #[test]
+    fn transparent_sort_override_distance() {
+        assert_eq!(TransparentSortOverride::default().distance(-4.0), -4.0);
+
+        let bias = TransparentSortOverride {
+            sort_key: None,
+            depth_bias: 0.5,
+        };
+        assert_eq!(bias.distance(-4.0), -3.5);
+
+        let sort_key = TransparentSortOverride {
+            sort_key: Some(10.0),
+            depth_bias: 0.0,
+        };
+        assert_eq!(sort_key.distance(-4.0), 10.0);
+        assert_eq!(sort_key.distance(-100.0), 10.0);
+
+        let both = TransparentSortOverride {
+            sort_key: Some(10.0),
+            depth_bias: -1.0,
+        };
+        assert_eq!(both.distance(-4.0), 9.0);
+    }
+}
@@ -0,0 +1,371 @@
+use crate::{
+    core_3d::{Camera3d, Camera3dTransparency, Transparent3d},
+    fullscreen_vertex_shader::fullscreen_shader_vertex_state,
+};
+use bevy_app::prelude::*;
+use bevy_asset::{load_internal_asset, HandleUntyped};
+use bevy_ecs::prelude::*;
+use bevy_reflect::TypeUuid;
+use bevy_render::{
+    camera::ExtractedCamera,
+    color::Color,
+    prelude::Msaa,
+    render_phase::RenderPhase,
+    render_resource::*,
+    renderer::RenderDevice,
+    texture::{CachedTexture, TextureCache},
+    view::ViewTarget,
+    RenderApp, RenderStage,
+};
+
+/// The shader importable as `bevy_core_pipeline::oit` by the fragment shaders of transparent
+/// materials, which return its `OitFragmentOutput` when the `WEIGHTED_BLENDED_OIT` shader def
+/// is set.
+pub const OIT_SHADER_HANDLE: HandleUntyped =
+    HandleUntyped::weak_from_u64(Shader::TYPE_UUID, 11253465939120813706);
+
+const OIT_COMPOSITE_SHADER_HANDLE: HandleUntyped =
+    HandleUntyped::weak_from_u64(Shader::TYPE_UUID, 3390581406213741895);
+
+/// Adds the weighted blended order-independent transparency of the 3d cameras with
+/// [`Camera3dTransparency::WeightedBlendedOit`].
+pub struct OitPlugin;
+
+impl Plugin for OitPlugin {
+    fn build(&self, app: &mut App) {
+        load_internal_asset!(app, OIT_SHADER_HANDLE, "oit.wgsl", Shader::from_wgsl);
+        load_internal_asset!(
+            app,
+            OIT_COMPOSITE_SHADER_HANDLE,
+            "oit_composite.wgsl",
+            Shader::from_wgsl
+        );
+
+        if let Ok(render_app) = app.get_sub_app_mut(RenderApp) {
+            render_app
+                .init_resource::<OitCompositePipeline>()
+                .init_resource::<SpecializedRenderPipelines<OitCompositePipeline>>()
+                .add_system_to_stage(RenderStage::Prepare, prepare_oit_textures)
+                .add_system_to_stage(RenderStage::Queue, queue_view_oit_composite_pipelines);
+        }
+    }
+}
+
+/// The format of the texture accumulating the weighted colors of the transparent meshes.
+pub const OIT_ACCUM_TEXTURE_FORMAT: TextureFormat = TextureFormat::Rgba16Float;
+/// The format of the texture accumulating the revealage of the transparent meshes.
+pub const OIT_REVEALAGE_TEXTURE_FORMAT: TextureFormat = TextureFormat::R8Unorm;
+
+/// The color targets of the pipelines drawing transparent meshes into the [`ViewOitTextures`],
+/// in the order of the fields of the `OitFragmentOutput` of `bevy_core_pipeline::oit`.
+pub fn oit_color_target_states() -> Vec<Option<ColorTargetState>> {
+    vec![
+        Some(ColorTargetState {
+            format: OIT_ACCUM_TEXTURE_FORMAT,
+            blend: Some(BlendState {
+                color: BlendComponent {
+                    src_factor: BlendFactor::One,
+                    dst_factor: BlendFactor::One,
+                    operation: BlendOperation::Add,
+                },
+                alpha: BlendComponent {
+                    src_factor: BlendFactor::One,
+                    dst_factor: BlendFactor::One,
+                    operation: BlendOperation::Add,
+                },
+            }),
+            write_mask: ColorWrites::ALL,
+        }),
+        Some(ColorTargetState {
+            format: OIT_REVEALAGE_TEXTURE_FORMAT,
+            blend: Some(BlendState {
+                color: BlendComponent {
+                    src_factor: BlendFactor::Zero,
+                    dst_factor: BlendFactor::OneMinusSrc,
+                    operation: BlendOperation::Add,
+                },
+                alpha: BlendComponent {
+                    src_factor: BlendFactor::Zero,
+                    dst_factor: BlendFactor::OneMinusSrcAlpha,
+                    operation: BlendOperation::Add,
+                },
+            }),
+            write_mask: ColorWrites::ALL,
+        }),
+    ]
+}
+
+/// Returns `true` if the pipeline draws into the [`ViewOitTextures`], with the
+/// [`oit_color_target_states`].
+///
+/// Only the [`Transparent3d`] items with such a pipeline are accumulated in the views with
+/// weighted blended order-independent transparency. The other transparent items are sorted and
+/// blended over the composited items.
+pub fn is_oit_pipeline(descriptor: &RenderPipelineDescriptor) -> bool {
+    descriptor.fragment.as_ref().map_or(false, |fragment| {
+        fragment
+            .targets
+            .iter()
+            .map(|target| target.as_ref().map(|target| target.format))
+            .eq([
+                Some(OIT_ACCUM_TEXTURE_FORMAT),
+                Some(OIT_REVEALAGE_TEXTURE_FORMAT),
+            ])
+    })
+}
+
+/// The textures accumulating the transparent meshes of a view with weighted blended
+/// order-independent transparency, which are composited over its main texture.
+#[derive(Component)]
+pub struct ViewOitTextures {
+    pub accum: CachedTexture,
+    pub revealage: CachedTexture,
+    /// The multisampled textures resolved into `accum` and `revealage`, when MSAA is enabled.
+    pub sampled: Option<(CachedTexture, CachedTexture)>,
+}
+
+impl ViewOitTextures {
+    /// The color attachments of the pass drawing the transparent meshes, which clear the textures.
+    pub fn color_attachments(&self) -> [Option<RenderPassColorAttachment>; 2] {
+        [
+            Some(oit_color_attachment(
+                &self.accum,
+                self.sampled.as_ref().map(|(accum, _)| accum),
+                Color::NONE,
+            )),
+            Some(oit_color_attachment(
+                &self.revealage,
+                self.sampled.as_ref().map(|(_, revealage)| revealage),
+                Color::WHITE,
+            )),
+        ]
+    }
+}
+
+fn oit_color_attachment<'a>(
+    texture: &'a CachedTexture,
+    sampled: Option<&'a CachedTexture>,
+    clear_color: Color,
+) -> RenderPassColorAttachment<'a> {
+    let ops = Operations {
+        load: LoadOp::Clear(clear_color.into()),
+        store: true,
+    };
+    match sampled {
+        Some(sampled) => RenderPassColorAttachment {
+            view: &sampled.default_view,
+            resolve_target: Some(&texture.default_view),
+            ops,
+        },
+        None => RenderPassColorAttachment {
+            view: &texture.default_view,
+            resolve_target: None,
+            ops,
+        },
+    }
+}
+
+pub fn prepare_oit_textures(
+    mut commands: Commands,
+    mut texture_cache: ResMut<TextureCache>,
+    msaa: Res<Msaa>,
+    render_device: Res<RenderDevice>,
+    views: Query<(Entity, &ExtractedCamera, &Camera3d), With<RenderPhase<Transparent3d>>>,
+) {
+    for (entity, camera, camera_3d) in &views {
+        if camera_3d.transparency != Camera3dTransparency::WeightedBlendedOit {
+            continue;
+        }
+        let size = match camera.physical_target_size {
+            Some(size) => Extent3d {
+                width: size.x,
+                height: size.y,
+                depth_or_array_layers: 1,
+            },
+            None => continue,
+        };
+
+        let mut texture = |label, format, sample_count, usage| {
+            texture_cache.get(
+                &render_device,
+                TextureDescriptor {
+                    label: Some(label),
+                    size,
+                    mip_level_count: 1,
+                    sample_count,
+                    dimension: TextureDimension::D2,
+                    format,
+                    usage,
+                },
+            )
+        };
+        let resolved_usage = TextureUsages::RENDER_ATTACHMENT | TextureUsages::TEXTURE_BINDING;
+        let textures = ViewOitTextures {
+            accum: texture(
+                "oit_accum_texture",
+                OIT_ACCUM_TEXTURE_FORMAT,
+                1,
+                resolved_usage,
+            ),
+            revealage: texture(
+                "oit_revealage_texture",
+                OIT_REVEALAGE_TEXTURE_FORMAT,
+                1,
+                resolved_usage,
+            ),
+            sampled: (msaa.samples > 1).then(|| {
+                (
+                    texture(
+                        "oit_accum_texture_sampled",
+                        OIT_ACCUM_TEXTURE_FORMAT,
+                        msaa.samples,
+                        TextureUsages::RENDER_ATTACHMENT,
+                    ),
+                    texture(
+                        "oit_revealage_texture_sampled",
+                        OIT_REVEALAGE_TEXTURE_FORMAT,
+                        msaa.samples,
+                        TextureUsages::RENDER_ATTACHMENT,
+                    ),
+                )
+            }),
+        };
+        commands.entity(entity).insert(textures);
+    }
+}
+
+#[derive(Resource)]
+pub struct OitCompositePipeline {
+    pub texture_bind_group: BindGroupLayout,
+}
+
+impl FromWorld for OitCompositePipeline {
+    fn from_world(render_world: &mut World) -> Self {
+        let render_device = render_world.resource::<RenderDevice>();
+        let texture_entry = |binding| BindGroupLayoutEntry {
+            binding,
+            visibility: ShaderStages::FRAGMENT,
+            ty: BindingType::Texture {
+                sample_type: TextureSampleType::Float { filterable: false },
+                view_dimension: TextureViewDimension::D2,
+                multisampled: false,
+            },
+            count: None,
+        };
+        let texture_bind_group =
+            render_device.create_bind_group_layout(&BindGroupLayoutDescriptor {
+                label: Some("oit_composite_texture_bind_group_layout"),
+                entries: &[texture_entry(0), texture_entry(1)],
+            });
+
+        OitCompositePipeline { texture_bind_group }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct OitCompositePipelineKey {
+    pub texture_format: TextureFormat,
+    pub samples: u32,
+}
+
+impl SpecializedRenderPipeline for OitCompositePipeline {
+    type Key = OitCompositePipelineKey;
+
+    fn specialize(&self, key: Self::Key) -> RenderPipelineDescriptor {
+        RenderPipelineDescriptor {
+            label: Some("oit_composite_pipeline".into()),
+            layout: Some(vec![self.texture_bind_group.clone()]),
+            vertex: fullscreen_shader_vertex_state(),
+            fragment: Some(FragmentState {
+                shader: OIT_COMPOSITE_SHADER_HANDLE.typed(),
+                shader_defs: Vec::new(),
+                entry_point: "fs_main".into(),
+                targets: vec![Some(ColorTargetState {
+                    format: key.texture_format,
+                    blend: Some(BlendState::ALPHA_BLENDING),
+                    write_mask: ColorWrites::ALL,
+                })],
+            }),
+            primitive: PrimitiveState::default(),
+            depth_stencil: None,
+            multisample: MultisampleState {
+                count: key.samples,
+                mask: !0,
+                alpha_to_coverage_enabled: false,
+            },
+        }
+    }
+}
+
+/// The pipeline compositing the [`ViewOitTextures`] of a view over its main texture, run at the
+/// end of its main 3d pass.
+#[derive(Component)]
+pub struct ViewOitCompositePipeline(pub CachedRenderPipelineId);
+
+pub fn queue_view_oit_composite_pipelines(
+    mut commands: Commands,
+    mut pipeline_cache: ResMut<PipelineCache>,
+    mut pipelines: ResMut<SpecializedRenderPipelines<OitCompositePipeline>>,
+    oit_composite_pipeline: Res<OitCompositePipeline>,
+    msaa: Res<Msaa>,
+    views: Query<(Entity, &ViewTarget), With<ViewOitTextures>>,
+) {
+    for (entity, view_target) in &views {
+        let key = OitCompositePipelineKey {
+            texture_format: view_target.main_texture_format(),
+            samples: msaa.samples,
+        };
+        let pipeline = pipelines.specialize(&mut pipeline_cache, &oit_composite_pipeline, key);
+
+        commands
+            .entity(entity)
+            .insert(ViewOitCompositePipeline(pipeline));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{is_oit_pipeline, oit_color_target_states};
+    use bevy_render::render_resource::{
+        BlendState, ColorTargetState, ColorWrites, FragmentState, MultisampleState, PrimitiveState,
+        RenderPipelineDescriptor, TextureFormat, VertexState,
+    };
+
+    fn descriptor(targets: Vec<Option<ColorTargetState>>) -> RenderPipelineDescriptor {
+        RenderPipelineDescriptor {
+            label: None,
+            layout: None,
+            vertex: VertexState {
+                shader: Default::default(),
+                shader_defs: Vec::new(),
+                entry_point: "vertex".into(),
+                buffers: Vec::new(),
+            },
+            primitive: PrimitiveState::default(),
+            depth_stencil: None,
+            multisample: MultisampleState::default(),
+            fragment: Some(FragmentState {
+                shader: Default::default(),
+                shader_defs: Vec::new(),
+                entry_point: "fragment".into(),
+                targets,
+            }),
+        }
+    }
+
+    #[test]
+    fn only_oit_targets_are_oit_pipelines() {
+        assert!(is_oit_pipeline(&descriptor(oit_color_target_states())));
+
+        let sorted = descriptor(vec![Some(ColorTargetState {
+            format: TextureFormat::Rgba8UnormSrgb,
+            blend: Some(BlendState::ALPHA_BLENDING),
+            write_mask: ColorWrites::ALL,
+        })]);
+        assert!(!is_oit_pipeline(&sorted));
+
+        let mut without_fragment = descriptor(Vec::new());
+        without_fragment.fragment = None;
+        assert!(!is_oit_pipeline(&without_fragment));
+    }
+}
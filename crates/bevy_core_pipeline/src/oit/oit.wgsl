@@ -0,0 +1,27 @@
+#define_import_path bevy_core_pipeline::oit
+
+// The outputs of the fragment shaders of the transparent meshes of a view with weighted blended
+// order-independent transparency, accumulated into the two textures of the view.
+struct OitFragmentOutput {
+    // The sum of the weighted colors, premultiplied by their alpha, and of their weighted alpha.
+    @location(0) accum: vec4<f32>,
+    // The alpha multiplied into the product of the `1.0 - alpha` of the fragments.
+    @location(1) revealage: f32,
+};
+
+// The weight of a fragment, decreasing with its distance to the camera, from equation 9 of
+// "Weighted Blended Order-Independent Transparency" by McGuire and Bavoil.
+fn oit_weight(color: vec4<f32>, frag_coord: vec4<f32>) -> f32 {
+    // the w of the fragment coordinates is the inverse of the view depth for perspective projections
+    let view_depth = 1.0 / frag_coord.w;
+    return color.a * clamp(0.03 / (1e-5 + pow(view_depth / 200.0, 4.0)), 1e-2, 3e3);
+}
+
+// Returns the output accumulating the `color` of the fragment at `frag_coord`.
+fn oit_fragment_output(color: vec4<f32>, frag_coord: vec4<f32>) -> OitFragmentOutput {
+    let weight = oit_weight(color, frag_coord);
+    var output: OitFragmentOutput;
+    output.accum = vec4<f32>(color.rgb * color.a, color.a) * weight;
+    output.revealage = color.a;
+    return output;
+}
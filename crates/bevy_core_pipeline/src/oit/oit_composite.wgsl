@@ -0,0 +1,20 @@
+#import bevy_core_pipeline::fullscreen_vertex_shader
+
+@group(0) @binding(0)
+var accum_texture: texture_2d<f32>;
+@group(0) @binding(1)
+var revealage_texture: texture_2d<f32>;
+
+@fragment
+fn fs_main(in: FullscreenVertexOutput) -> @location(0) vec4<f32> {
+    let coords = vec2<i32>(in.position.xy);
+    let revealage = textureLoad(revealage_texture, coords, 0).r;
+    // no transparent fragment covers the pixel
+    if (revealage >= 1.0) {
+        discard;
+    }
+    let accum = textureLoad(accum_texture, coords, 0);
+    let average_color = accum.rgb / max(accum.a, 1e-5);
+    // blended over the opaque colors with the alpha blending of the pipeline
+    return vec4<f32>(average_color, 1.0 - revealage);
+}
@@ -0,0 +1,484 @@
+mod node;
+
+pub use node::TonemappingNode;
+
+use crate::fullscreen_vertex_shader::fullscreen_shader_vertex_state;
+use bevy_app::prelude::*;
+use bevy_asset::{load_internal_asset, Handle, HandleUntyped};
+use bevy_ecs::{prelude::*, query::QueryItem};
+use bevy_reflect::{Reflect, TypeUuid};
+use bevy_render::{
+    camera::Camera,
+    extract_component::{ExtractComponent, ExtractComponentPlugin, UniformComponentPlugin},
+    render_asset::RenderAssets,
+    render_resource::*,
+    renderer::RenderDevice,
+    texture::Image,
+    view::{ExtractedView, ViewTarget},
+    Extract, RenderApp, RenderStage,
+};
+
+const TONEMAPPING_SHADER_HANDLE: HandleUntyped =
+    HandleUntyped::weak_from_u64(Shader::TYPE_UUID, 17015368199668024512);
+
+const TONEMAPPING_SHARED_SHADER_HANDLE: HandleUntyped =
+    HandleUntyped::weak_from_u64(Shader::TYPE_UUID, 2499430578245347910);
+
+/// Adds the [`Tonemapping`], [`DebandDither`] and [`ColorGrading`] camera settings, and the
+/// tonemapping pass of "high dynamic range" cameras.
+pub struct TonemappingPlugin;
+
+impl Plugin for TonemappingPlugin {
+    fn build(&self, app: &mut App) {
+        load_internal_asset!(
+            app,
+            TONEMAPPING_SHADER_HANDLE,
+            "tonemapping.wgsl",
+            Shader::from_wgsl
+        );
+        load_internal_asset!(
+            app,
+            TONEMAPPING_SHARED_SHADER_HANDLE,
+            "tonemapping_shared.wgsl",
+            Shader::from_wgsl
+        );
+
+        app.register_type::<Tonemapping>()
+            .register_type::<DebandDither>()
+            .register_type::<ColorGrading>()
+            .add_plugin(ExtractComponentPlugin::<Tonemapping>::default())
+            .add_plugin(ExtractComponentPlugin::<DebandDither>::default())
+            .add_plugin(UniformComponentPlugin::<ColorGradingUniform>::default());
+
+        if let Ok(render_app) = app.get_sub_app_mut(RenderApp) {
+            render_app
+                .add_system_to_stage(RenderStage::Extract, extract_color_grading)
+                .init_resource::<TonemappingPipeline>()
+                .init_resource::<SpecializedRenderPipelines<TonemappingPipeline>>()
+                .add_system_to_stage(RenderStage::Queue, queue_view_tonemapping_pipelines);
+        }
+    }
+}
+
+/// The operator mapping the colors of a camera to the displayable range.
+///
+/// "High dynamic range" cameras apply it in the tonemapping pass, other cameras apply it in the
+/// shaders of the materials that support it, like the `StandardMaterial`. Cameras without this
+/// component use the default operator.
+#[derive(Component, Reflect, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[reflect(Component)]
+pub enum Tonemapping {
+    /// Colors are not mapped, and values above 1.0 are clamped.
+    None,
+    /// Reinhard applied to each channel, which desaturates bright colors.
+    Reinhard,
+    /// Reinhard applied to the luminance, which keeps the hue and saturation of bright colors.
+    ReinhardLuminance,
+    /// Stephen Hill's fit of the ACES reference rendering and output transforms.
+    AcesFitted,
+    /// A polynomial approximation of Troy Sobotka's AgX, which desaturates bright colors
+    /// towards white.
+    AgX,
+}
+
+impl Default for Tonemapping {
+    fn default() -> Self {
+        Tonemapping::ReinhardLuminance
+    }
+}
+
+impl Tonemapping {
+    /// Returns `true` if colors are mapped by the operator.
+    pub fn is_enabled(&self) -> bool {
+        *self != Tonemapping::None
+    }
+
+    /// The shader def selecting the operator in `bevy_core_pipeline::tonemapping::tone_mapping`.
+    pub fn shader_def(&self) -> Option<&'static str> {
+        match self {
+            Tonemapping::None => None,
+            Tonemapping::Reinhard => Some("TONEMAP_METHOD_REINHARD"),
+            Tonemapping::ReinhardLuminance => Some("TONEMAP_METHOD_REINHARD_LUMINANCE"),
+            Tonemapping::AcesFitted => Some("TONEMAP_METHOD_ACES_FITTED"),
+            Tonemapping::AgX => Some("TONEMAP_METHOD_AGX"),
+        }
+    }
+}
+
+impl ExtractComponent for Tonemapping {
+    type Query = &'static Self;
+    type Filter = With<Camera>;
+
+    fn extract_component(item: QueryItem<Self::Query>) -> Self {
+        *item
+    }
+}
+
+/// Adds a small amount of noise to the colors of a camera after tonemapping, to hide the banding
+/// of smooth gradients quantized to the 8 bits of the render target.
+///
+/// Cameras without this component are not dithered.
+#[derive(Component, Reflect, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[reflect(Component)]
+pub enum DebandDither {
+    Disabled,
+    Enabled,
+}
+
+impl Default for DebandDither {
+    fn default() -> Self {
+        DebandDither::Enabled
+    }
+}
+
+impl ExtractComponent for DebandDither {
+    type Query = &'static Self;
+    type Filter = With<Camera>;
+
+    fn extract_component(item: QueryItem<Self::Query>) -> Self {
+        *item
+    }
+}
+
+/// Adjusts the colors of a "high dynamic range" camera in its tonemapping pass.
+///
+/// The exposure and saturation are applied to the colors before tonemapping, and the lookup
+/// table to the tonemapped colors. Color grading is only applied to cameras with
+/// [`Camera::hdr`] set.
+#[derive(Component, Reflect, Clone, Debug)]
+#[reflect(Component)]
+pub struct ColorGrading {
+    /// Exposure of the camera in EV (default: 0.0), each step doubling the brightness of the
+    /// colors.
+    pub exposure: f32,
+    /// Saturation of the colors (default: 1.0). `0.0` makes the image grayscale and values above
+    /// `1.0` saturate the colors.
+    pub saturation: f32,
+    /// A 3d lookup table mapping the tonemapped colors to their graded colors (default: `None`).
+    ///
+    /// The table is indexed with sRGB-encoded colors and its texels are read as linear colors,
+    /// so tables authored in sRGB should use an sRGB texture format, like
+    /// [`TextureFormat::Rgba8UnormSrgb`]. The image must have a [`TextureDimension::D3`]
+    /// texture, and is sampled with its own sampler.
+    pub lut: Option<Handle<Image>>,
+}
+
+impl Default for ColorGrading {
+    fn default() -> Self {
+        Self {
+            exposure: 0.0,
+            saturation: 1.0,
+            lut: None,
+        }
+    }
+}
+
+/// The [`ColorGrading`] of a view, in the layout of the tonemapping shader.
+#[derive(Component, ShaderType, Clone, Debug, PartialEq)]
+pub struct ColorGradingUniform {
+    exposure: f32,
+    saturation: f32,
+}
+
+impl Default for ColorGradingUniform {
+    fn default() -> Self {
+        Self {
+            exposure: 0.0,
+            saturation: 1.0,
+        }
+    }
+}
+
+impl From<&ColorGrading> for ColorGradingUniform {
+    fn from(color_grading: &ColorGrading) -> Self {
+        Self {
+            exposure: color_grading.exposure,
+            saturation: color_grading.saturation,
+        }
+    }
+}
+
+/// The lookup table of the [`ColorGrading`] of a view.
+#[derive(Component)]
+pub struct ViewColorGradingLut(pub Handle<Image>);
+
+fn extract_color_grading(
+    mut commands: Commands,
+    cameras: Extract<Query<(Entity, &Camera, Option<&ColorGrading>)>>,
+) {
+    for (entity, camera, color_grading) in &cameras {
+        // every tonemapping pass reads the uniform, cameras without color grading use the default
+        if !camera.is_active || !camera.hdr {
+            continue;
+        }
+        let mut entity_commands = commands.get_or_spawn(entity);
+        match color_grading {
+            Some(color_grading) => {
+                entity_commands.insert(ColorGradingUniform::from(color_grading));
+                if let Some(lut) = &color_grading.lut {
+                    entity_commands.insert(ViewColorGradingLut(lut.clone_weak()));
+                }
+            }
+            None => {
+                entity_commands.insert(ColorGradingUniform::default());
+            }
+        }
+    }
+}
+
+#[derive(Resource)]
+pub struct TonemappingPipeline {
+    texture_bind_group: BindGroupLayout,
+    lut_bind_group: BindGroupLayout,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TonemappingPipelineKey {
+    pub tonemapping: Tonemapping,
+    pub deband_dither: DebandDither,
+    pub color_grading_lut: bool,
+}
+
+impl TonemappingPipelineKey {
+    /// Returns the key of the tonemapping pass of a view, or `None` if the pass would leave the
+    /// colors of the view unchanged. The lookup table is only bound once its image is loaded.
+    fn new(
+        tonemapping: Tonemapping,
+        deband_dither: DebandDither,
+        color_grading: &ColorGradingUniform,
+        lut_loaded: bool,
+    ) -> Option<Self> {
+        let key = TonemappingPipelineKey {
+            tonemapping,
+            deband_dither,
+            color_grading_lut: lut_loaded,
+        };
+        let changes_colors = key.tonemapping.is_enabled()
+            || key.deband_dither == DebandDither::Enabled
+            || key.color_grading_lut
+            || *color_grading != ColorGradingUniform::default();
+        changes_colors.then_some(key)
+    }
+
+    fn shader_defs(&self) -> Vec<ShaderDefVal> {
+        let mut shader_defs = Vec::new();
+        if let Some(shader_def) = self.tonemapping.shader_def() {
+            shader_defs.push(shader_def.into());
+        }
+        if self.deband_dither == DebandDither::Enabled {
+            shader_defs.push("DEBAND_DITHER".into());
+        }
+        if self.color_grading_lut {
+            shader_defs.push("COLOR_GRADING_LUT".into());
+        }
+        shader_defs
+    }
+}
+
+impl FromWorld for TonemappingPipeline {
+    fn from_world(render_world: &mut World) -> Self {
+        let render_device = render_world.resource::<RenderDevice>();
+        let texture_bind_group =
+            render_device.create_bind_group_layout(&BindGroupLayoutDescriptor {
+                label: Some("tonemapping_texture_bind_group_layout"),
+                entries: &[
+                    BindGroupLayoutEntry {
+                        binding: 0,
+                        visibility: ShaderStages::FRAGMENT,
+                        ty: BindingType::Texture {
+                            sample_type: TextureSampleType::Float { filterable: false },
+                            view_dimension: TextureViewDimension::D2,
+                            multisampled: false,
+                        },
+                        count: None,
+                    },
+                    BindGroupLayoutEntry {
+                        binding: 1,
+                        visibility: ShaderStages::FRAGMENT,
+                        ty: BindingType::Buffer {
+                            ty: BufferBindingType::Uniform,
+                            has_dynamic_offset: true,
+                            min_binding_size: Some(ColorGradingUniform::min_size()),
+                        },
+                        count: None,
+                    },
+                ],
+            });
+        let lut_bind_group = render_device.create_bind_group_layout(&BindGroupLayoutDescriptor {
+            label: Some("tonemapping_lut_bind_group_layout"),
+            entries: &[
+                BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: ShaderStages::FRAGMENT,
+                    ty: BindingType::Texture {
+                        sample_type: TextureSampleType::Float { filterable: true },
+                        view_dimension: TextureViewDimension::D3,
+                        multisampled: false,
+                    },
+                    count: None,
+                },
+                BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: ShaderStages::FRAGMENT,
+                    ty: BindingType::Sampler(SamplerBindingType::Filtering),
+                    count: None,
+                },
+            ],
+        });
+
+        TonemappingPipeline {
+            texture_bind_group,
+            lut_bind_group,
+        }
+    }
+}
+
+impl SpecializedRenderPipeline for TonemappingPipeline {
+    type Key = TonemappingPipelineKey;
+
+    fn specialize(&self, key: Self::Key) -> RenderPipelineDescriptor {
+        let shader_defs = key.shader_defs();
+        let mut layout = vec![self.texture_bind_group.clone()];
+        if key.color_grading_lut {
+            layout.push(self.lut_bind_group.clone());
+        }
+
+        RenderPipelineDescriptor {
+            label: Some("tonemapping_pipeline".into()),
+            layout: Some(layout),
+            vertex: fullscreen_shader_vertex_state(),
+            fragment: Some(FragmentState {
+                shader: TONEMAPPING_SHADER_HANDLE.typed(),
+                shader_defs,
+                entry_point: "fs_main".into(),
+                targets: vec![Some(ColorTargetState {
+                    format: ViewTarget::TEXTURE_FORMAT_HDR,
+                    blend: None,
+                    write_mask: ColorWrites::ALL,
+                })],
+            }),
+            primitive: PrimitiveState::default(),
+            depth_stencil: None,
+            multisample: MultisampleState::default(),
+        }
+    }
+}
+
+/// The tonemapping pipeline of a "high dynamic range" view, run by the [`TonemappingNode`].
+#[derive(Component)]
+pub struct ViewTonemappingPipeline(pub CachedRenderPipelineId);
+
+pub fn queue_view_tonemapping_pipelines(
+    mut commands: Commands,
+    mut pipeline_cache: ResMut<PipelineCache>,
+    mut pipelines: ResMut<SpecializedRenderPipelines<TonemappingPipeline>>,
+    tonemapping_pipeline: Res<TonemappingPipeline>,
+    images: Res<RenderAssets<Image>>,
+    views: Query<(
+        Entity,
+        &ExtractedView,
+        &ColorGradingUniform,
+        Option<&ViewColorGradingLut>,
+        Option<&Tonemapping>,
+        Option<&DebandDither>,
+    )>,
+) {
+    for (entity, view, color_grading, lut, tonemapping, deband_dither) in &views {
+        // other views are tonemapped by the shaders of their materials
+        if !view.hdr {
+            continue;
+        }
+        let key = match TonemappingPipelineKey::new(
+            tonemapping.copied().unwrap_or_default(),
+            deband_dither.copied().unwrap_or(DebandDither::Disabled),
+            color_grading,
+            lut.map_or(false, |lut| images.contains_key(&lut.0)),
+        ) {
+            Some(key) => key,
+            None => continue,
+        };
+
+        let pipeline = pipelines.specialize(&mut pipeline_cache, &tonemapping_pipeline, key);
+        commands
+            .entity(entity)
+            .insert(ViewTonemappingPipeline(pipeline));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn color_grading_uniform() {
+        let color_grading = ColorGrading {
+            exposure: 1.5,
+            saturation: 0.0,
+            lut: None,
+        };
+        assert_eq!(
+            ColorGradingUniform::from(&color_grading),
+            ColorGradingUniform {
+                exposure: 1.5,
+                saturation: 0.0,
+            }
+        );
+    }
+
+    #[test]
+    fn unchanged_colors_skip_the_pass() {
+        let default = ColorGradingUniform::default();
+        assert!(TonemappingPipelineKey::new(
+            Tonemapping::None,
+            DebandDither::Disabled,
+            &default,
+            false
+        )
+        .is_none());
+
+        let exposed = ColorGradingUniform::from(&ColorGrading {
+            exposure: 1.0,
+            ..Default::default()
+        });
+        let key =
+            TonemappingPipelineKey::new(Tonemapping::None, DebandDither::Disabled, &exposed, false)
+                .unwrap();
+        assert!(key.shader_defs().is_empty());
+    }
+
+    #[test]
+    fn lut_is_bound_once_loaded() {
+        let default = ColorGradingUniform::default();
+        let lut_def = ShaderDefVal::from("COLOR_GRADING_LUT");
+
+        let key =
+            TonemappingPipelineKey::new(Tonemapping::None, DebandDither::Disabled, &default, true)
+                .unwrap();
+        assert!(key.color_grading_lut);
+        assert_eq!(key.shader_defs(), vec![lut_def.clone()]);
+
+        let key =
+            TonemappingPipelineKey::new(Tonemapping::AgX, DebandDither::Enabled, &default, false)
+                .unwrap();
+        assert!(!key.color_grading_lut);
+        assert!(!key.shader_defs().contains(&lut_def));
+    }
+
+    #[test]
+    fn operator_and_dither_shader_defs() {
+        let key = TonemappingPipelineKey::new(
+            Tonemapping::AcesFitted,
+            DebandDither::Enabled,
+            &ColorGradingUniform::default(),
+            false,
+        )
+        .unwrap();
+        assert_eq!(
+            key.shader_defs(),
+            vec![
+                ShaderDefVal::from("TONEMAP_METHOD_ACES_FITTED"),
+                ShaderDefVal::from("DEBAND_DITHER"),
+            ]
+        );
+    }
+}
@@ -0,0 +1,145 @@
+use crate::tonemapping::{
+    ColorGradingUniform, TonemappingPipeline, ViewColorGradingLut, ViewTonemappingPipeline,
+};
+use bevy_ecs::prelude::*;
+use bevy_render::{
+    extract_component::{ComponentUniforms, DynamicUniformIndex},
+    render_asset::RenderAssets,
+    render_graph::{Node, NodeRunError, RenderGraphContext, SlotInfo, SlotType},
+    render_resource::{
+        BindGroupDescriptor, BindGroupEntry, BindingResource, LoadOp, Operations, PipelineCache,
+        RenderPassColorAttachment, RenderPassDescriptor,
+    },
+    renderer::RenderContext,
+    texture::Image,
+    view::{ExtractedView, ViewTarget},
+};
+
+/// Maps the colors of "high dynamic range" views to the displayable range, with the
+/// [`Tonemapping`](super::Tonemapping) operator and the [`ColorGrading`](super::ColorGrading) of
+/// the view.
+pub struct TonemappingNode {
+    query: QueryState<
+        (
+            &'static ViewTarget,
+            &'static ViewTonemappingPipeline,
+            &'static DynamicUniformIndex<ColorGradingUniform>,
+            Option<&'static ViewColorGradingLut>,
+        ),
+        With<ExtractedView>,
+    >,
+}
+
+impl TonemappingNode {
+    pub const IN_VIEW: &'static str = "view";
+
+    pub fn new(world: &mut World) -> Self {
+        Self {
+            query: world.query_filtered(),
+        }
+    }
+}
+
+impl Node for TonemappingNode {
+    fn input(&self) -> Vec<SlotInfo> {
+        vec![SlotInfo::new(TonemappingNode::IN_VIEW, SlotType::Entity)]
+    }
+
+    fn update(&mut self, world: &mut World) {
+        self.query.update_archetypes(world);
+    }
+
+    fn run(
+        &self,
+        graph: &mut RenderGraphContext,
+        render_context: &mut RenderContext,
+        world: &World,
+    ) -> Result<(), NodeRunError> {
+        let view_entity = graph.get_input_entity(Self::IN_VIEW)?;
+        let pipeline_cache = world.resource::<PipelineCache>();
+        let tonemapping_pipeline = world.resource::<TonemappingPipeline>();
+
+        let (target, tonemapping, uniform_index, lut) =
+            match self.query.get_manual(world, view_entity) {
+                Ok(result) => result,
+                // the view is not tonemapped by this pass
+                Err(_) => return Ok(()),
+            };
+        let pipeline = match pipeline_cache.get_render_pipeline(tonemapping.0) {
+            Some(pipeline) => pipeline,
+            None => return Ok(()),
+        };
+        let uniforms = match world
+            .resource::<ComponentUniforms<ColorGradingUniform>>()
+            .binding()
+        {
+            Some(uniforms) => uniforms,
+            None => return Ok(()),
+        };
+        // the pipeline only reads the lookup table once its image is loaded
+        let lut_bind_group = lut
+            .and_then(|lut| world.resource::<RenderAssets<Image>>().get(&lut.0))
+            .map(|lut| {
+                render_context
+                    .render_device
+                    .create_bind_group(&BindGroupDescriptor {
+                        label: Some("tonemapping_lut_bind_group"),
+                        layout: &tonemapping_pipeline.lut_bind_group,
+                        entries: &[
+                            BindGroupEntry {
+                                binding: 0,
+                                resource: BindingResource::TextureView(&lut.texture_view),
+                            },
+                            BindGroupEntry {
+                                binding: 1,
+                                resource: BindingResource::Sampler(&lut.sampler),
+                            },
+                        ],
+                    })
+            });
+
+        let post_process = target.post_process_write();
+        let bind_group = render_context
+            .render_device
+            .create_bind_group(&BindGroupDescriptor {
+                label: Some("tonemapping_texture_bind_group"),
+                layout: &tonemapping_pipeline.texture_bind_group,
+                entries: &[
+                    BindGroupEntry {
+                        binding: 0,
+                        resource: BindingResource::TextureView(post_process.source),
+                    },
+                    BindGroupEntry {
+                        binding: 1,
+                        resource: uniforms,
+                    },
+                ],
+            });
+
+        let pass_descriptor = RenderPassDescriptor {
+            label: Some("tonemapping_pass"),
+            color_attachments: &[Some(RenderPassColorAttachment {
+                view: post_process.destination,
+                resolve_target: None,
+                ops: Operations {
+                    load: LoadOp::Clear(Default::default()),
+                    store: true,
+                },
+            })],
+            depth_stencil_attachment: None,
+        };
+
+        let mut render_pass = render_context
+            .command_encoder
+            .begin_render_pass(&pass_descriptor);
+
+        render_pass.set_pipeline(pipeline);
+        render_pass.set_bind_group(0, &bind_group, &[uniform_index.index()]);
+        if let Some(lut_bind_group) = &lut_bind_group {
+            render_pass.set_bind_group(1, lut_bind_group, &[]);
+        }
+        render_pass.draw(0..3, 0..1);
+
+        Ok(())
+    }
+}
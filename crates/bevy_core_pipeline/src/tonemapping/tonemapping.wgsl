@@ -0,0 +1,53 @@
+#import bevy_core_pipeline::fullscreen_vertex_shader
+#import bevy_core_pipeline::tonemapping
+
+struct ColorGrading {
+    exposure: f32,
+    saturation: f32,
+};
+
+@group(0) @binding(0)
+var hdr_texture: texture_2d<f32>;
+@group(0) @binding(1)
+var<uniform> color_grading: ColorGrading;
+
+#ifdef COLOR_GRADING_LUT
+@group(1) @binding(0)
+var lut_texture: texture_3d<f32>;
+@group(1) @binding(1)
+var lut_sampler: sampler;
+
+fn linear_to_srgb(color: vec3<f32>) -> vec3<f32> {
+    let low = color * 12.92;
+    let high = 1.055 * pow(color, vec3<f32>(1.0 / 2.4)) - 0.055;
+    return select(high, low, color <= vec3<f32>(0.0031308));
+}
+
+// Maps a tonemapped color with the lookup table, sampling the centers of its edge texels for
+// the colors at the ends of the range.
+fn apply_lut(color: vec3<f32>) -> vec3<f32> {
+    let lut_size = vec3<f32>(textureDimensions(lut_texture));
+    let coords = linear_to_srgb(clamp(color, vec3<f32>(0.0), vec3<f32>(1.0)));
+    let uvw = coords * ((lut_size - 1.0) / lut_size) + 0.5 / lut_size;
+    return textureSampleLevel(lut_texture, lut_sampler, uvw, 0.0).rgb;
+}
+#endif
+
+@fragment
+fn fs_main(in: FullscreenVertexOutput) -> @location(0) vec4<f32> {
+    let hdr_color = textureLoad(hdr_texture, vec2<i32>(in.position.xy), 0);
+
+    var graded_color = hdr_color.rgb * exp2(color_grading.exposure);
+    let gray = vec3<f32>(luminance(graded_color));
+    graded_color = max(mix(gray, graded_color, color_grading.saturation), vec3<f32>(0.0));
+
+    var output_color = tone_mapping(vec4<f32>(graded_color, hdr_color.a));
+#ifdef COLOR_GRADING_LUT
+    output_color = vec4<f32>(apply_lut(output_color.rgb), output_color.a);
+#endif
+#ifdef DEBAND_DITHER
+    output_color = deband_dither(output_color, in.position.xy);
+#endif
+
+    return output_color;
+}
@@ -0,0 +1,138 @@
+#define_import_path bevy_core_pipeline::tonemapping
+
+// from https://64.github.io/tonemapping/
+// reinhard on RGB oversaturates colors
+fn reinhard(color: vec3<f32>) -> vec3<f32> {
+    return color / (1.0 + color);
+}
+
+fn reinhard_extended(color: vec3<f32>, max_white: f32) -> vec3<f32> {
+    let numerator = color * (1.0 + (color / vec3<f32>(max_white * max_white)));
+    return numerator / (1.0 + color);
+}
+
+// luminance coefficients from Rec. 709.
+// https://en.wikipedia.org/wiki/Rec._709
+fn luminance(v: vec3<f32>) -> f32 {
+    return dot(v, vec3<f32>(0.2126, 0.7152, 0.0722));
+}
+
+fn change_luminance(c_in: vec3<f32>, l_out: f32) -> vec3<f32> {
+    let l_in = luminance(c_in);
+    return c_in * (l_out / l_in);
+}
+
+fn reinhard_luminance(color: vec3<f32>) -> vec3<f32> {
+    let l_old = luminance(color);
+    let l_new = l_old / (1.0 + l_old);
+    return change_luminance(color, l_new);
+}
+
+fn reinhard_extended_luminance(color: vec3<f32>, max_white_l: f32) -> vec3<f32> {
+    let l_old = luminance(color);
+    let numerator = l_old * (1.0 + (l_old / (max_white_l * max_white_l)));
+    let l_new = numerator / (1.0 + l_old);
+    return change_luminance(color, l_new);
+}
+
+// Stephen Hill's fit of the ACES RRT and ODT
+// https://github.com/TheRealMJP/BakingLab/blob/master/BakingLab/ACES.hlsl
+fn rrt_and_odt_fit(v: vec3<f32>) -> vec3<f32> {
+    let a = v * (v + 0.0245786) - 0.000090537;
+    let b = v * (0.983729 * v + 0.4329510) + 0.238081;
+    return a / b;
+}
+
+fn aces_fitted(color: vec3<f32>) -> vec3<f32> {
+    // sRGB => XYZ => D65_2_D60 => AP1 => RRT_SAT
+    let aces_input = mat3x3<f32>(
+        vec3<f32>(0.59719, 0.07600, 0.02840),
+        vec3<f32>(0.35458, 0.90834, 0.13383),
+        vec3<f32>(0.04823, 0.01566, 0.83777)
+    );
+    // ODT_SAT => XYZ => D60_2_D65 => sRGB
+    let aces_output = mat3x3<f32>(
+        vec3<f32>(1.60475, -0.10208, -0.00327),
+        vec3<f32>(-0.53108, 1.10813, -0.07276),
+        vec3<f32>(-0.07367, -0.00605, 1.07602)
+    );
+
+    let v = rrt_and_odt_fit(aces_input * color);
+    return clamp(aces_output * v, vec3<f32>(0.0), vec3<f32>(1.0));
+}
+
+// Benjamin Wrensch's polynomial approximation of the AgX default contrast curve
+// https://iolite-engine.com/blog_posts/minimal_agx_implementation
+fn agx_default_contrast_approx(x: vec3<f32>) -> vec3<f32> {
+    let x2 = x * x;
+    let x4 = x2 * x2;
+    return 15.5 * x4 * x2
+        - 40.14 * x4 * x
+        + 31.96 * x4
+        - 6.868 * x2 * x
+        + 0.4298 * x2
+        + 0.1191 * x
+        - 0.00232;
+}
+
+fn agx(color: vec3<f32>) -> vec3<f32> {
+    let agx_mat = mat3x3<f32>(
+        vec3<f32>(0.842479062253094, 0.0423282422610123, 0.0423756549057051),
+        vec3<f32>(0.0784335999999992, 0.878468636469772, 0.0784336),
+        vec3<f32>(0.0792237451477643, 0.0791661274605434, 0.879142973793104)
+    );
+    let agx_mat_inv = mat3x3<f32>(
+        vec3<f32>(1.19687900512017, -0.0528968517574562, -0.0529716355144438),
+        vec3<f32>(-0.0980208811401368, 1.15190312990417, -0.0980434501171241),
+        vec3<f32>(-0.0990297440797205, -0.0989611768448433, 1.15107367264116)
+    );
+    let min_ev = -12.47393;
+    let max_ev = 4.026069;
+
+    // log2 encoding of the input in the AgX working space
+    var v = agx_mat * color;
+    v = clamp(log2(max(v, vec3<f32>(1e-10))), vec3<f32>(min_ev), vec3<f32>(max_ev));
+    v = (v - min_ev) / (max_ev - min_ev);
+
+    v = agx_default_contrast_approx(v);
+
+    // the curve outputs display encoded values, which are linearized for the srgb render targets
+    v = agx_mat_inv * v;
+    return pow(max(v, vec3<f32>(0.0)), vec3<f32>(2.2));
+}
+
+// Maps the color to the displayable range with the operator selected by the
+// `TONEMAP_METHOD_*` shader def, colors are unchanged without one.
+fn tone_mapping(in: vec4<f32>) -> vec4<f32> {
+    var color = in.rgb;
+#ifdef TONEMAP_METHOD_REINHARD
+    color = reinhard(color);
+#endif
+#ifdef TONEMAP_METHOD_REINHARD_LUMINANCE
+    color = reinhard_luminance(color);
+#endif
+#ifdef TONEMAP_METHOD_ACES_FITTED
+    color = aces_fitted(color);
+#endif
+#ifdef TONEMAP_METHOD_AGX
+    color = agx(color);
+#endif
+    return vec4<f32>(color, in.a);
+}
+
+// Interleaved gradient noise in [-0.5 / 255, 0.5 / 255], from
+// https://alex.vlachos.com/graphics/Alex_Vlachos_Advanced_VR_Rendering_GDC2015.pdf
+fn screen_space_dither(frag_coord: vec2<f32>) -> vec3<f32> {
+    var dither = vec3<f32>(dot(vec2<f32>(171.0, 231.0), frag_coord));
+    dither = fract(dither / vec3<f32>(103.0, 71.0, 97.0));
+    return (dither - 0.5) / 255.0;
+}
+
+// Dithers a tonemapped color written to an srgb render target. The noise is added to the
+// gamma encoded color, so that it matches the quantization steps of the render target.
+fn deband_dither(color: vec4<f32>, frag_coord: vec2<f32>) -> vec4<f32> {
+    var rgb = pow(max(color.rgb, vec3<f32>(0.0)), vec3<f32>(1.0 / 2.2));
+    rgb = rgb + screen_space_dither(frag_coord);
+    rgb = pow(max(rgb, vec3<f32>(0.0)), vec3<f32>(2.2));
+    return vec4<f32>(rgb, color.a);
+}
@@ -0,0 +1,127 @@
+mod node;
+
+pub use node::UpscalingNode;
+
+use crate::fullscreen_vertex_shader::fullscreen_shader_vertex_state;
+use bevy_app::prelude::*;
+use bevy_asset::{load_internal_asset, HandleUntyped};
+use bevy_ecs::prelude::*;
+use bevy_reflect::TypeUuid;
+use bevy_render::{
+    camera::{CameraBlendMode, CameraOutputMode, ExtractedCamera},
+    render_resource::*,
+    renderer::RenderDevice,
+    view::ViewTarget,
+    RenderApp, RenderStage,
+};
+
+const UPSCALING_SHADER_HANDLE: HandleUntyped =
+    HandleUntyped::weak_from_u64(Shader::TYPE_UUID, 14589267395627146578);
+
+/// Adds the pass writing the main texture of the views to their render target.
+pub struct UpscalingPlugin;
+
+impl Plugin for UpscalingPlugin {
+    fn build(&self, app: &mut App) {
+        load_internal_asset!(
+            app,
+            UPSCALING_SHADER_HANDLE,
+            "upscaling.wgsl",
+            Shader::from_wgsl
+        );
+
+        if let Ok(render_app) = app.get_sub_app_mut(RenderApp) {
+            render_app
+                .init_resource::<UpscalingPipeline>()
+                .init_resource::<SpecializedRenderPipelines<UpscalingPipeline>>()
+                .add_system_to_stage(RenderStage::Queue, queue_view_upscaling_pipelines);
+        }
+    }
+}
+
+#[derive(Resource)]
+pub struct UpscalingPipeline {
+    texture_bind_group: BindGroupLayout,
+}
+
+impl FromWorld for UpscalingPipeline {
+    fn from_world(render_world: &mut World) -> Self {
+        let render_device = render_world.resource::<RenderDevice>();
+        let texture_bind_group =
+            render_device.create_bind_group_layout(&BindGroupLayoutDescriptor {
+                label: Some("upscaling_texture_bind_group_layout"),
+                entries: &[BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: ShaderStages::FRAGMENT,
+                    ty: BindingType::Texture {
+                        sample_type: TextureSampleType::Float { filterable: false },
+                        view_dimension: TextureViewDimension::D2,
+                        multisampled: false,
+                    },
+                    count: None,
+                }],
+            });
+
+        UpscalingPipeline { texture_bind_group }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct UpscalingPipelineKey {
+    pub texture_format: TextureFormat,
+    /// The blend mode of the main texture over the render target, if the view is composited
+    /// over it instead of replacing it.
+    pub blend_mode: Option<CameraBlendMode>,
+}
+
+impl SpecializedRenderPipeline for UpscalingPipeline {
+    type Key = UpscalingPipelineKey;
+
+    fn specialize(&self, key: Self::Key) -> RenderPipelineDescriptor {
+        RenderPipelineDescriptor {
+            label: Some("upscaling_pipeline".into()),
+            layout: Some(vec![self.texture_bind_group.clone()]),
+            vertex: fullscreen_shader_vertex_state(),
+            fragment: Some(FragmentState {
+                shader: UPSCALING_SHADER_HANDLE.typed(),
+                shader_defs: Vec::new(),
+                entry_point: "fs_main".into(),
+                targets: vec![Some(ColorTargetState {
+                    format: key.texture_format,
+                    blend: key.blend_mode.map(CameraBlendMode::blend_state),
+                    write_mask: ColorWrites::ALL,
+                })],
+            }),
+            primitive: PrimitiveState::default(),
+            depth_stencil: None,
+            multisample: MultisampleState::default(),
+        }
+    }
+}
+
+/// The upscaling pipeline of a view, run by the [`UpscalingNode`].
+#[derive(Component)]
+pub struct ViewUpscalingPipeline(pub CachedRenderPipelineId);
+
+pub fn queue_view_upscaling_pipelines(
+    mut commands: Commands,
+    mut pipeline_cache: ResMut<PipelineCache>,
+    mut pipelines: ResMut<SpecializedRenderPipelines<UpscalingPipeline>>,
+    upscaling_pipeline: Res<UpscalingPipeline>,
+    view_targets: Query<(Entity, &ViewTarget, &ExtractedCamera)>,
+) {
+    for (entity, view_target, camera) in &view_targets {
+        let key = UpscalingPipelineKey {
+            texture_format: view_target.out_texture_format(),
+            blend_mode: match camera.output_mode {
+                CameraOutputMode::Write => None,
+                CameraOutputMode::Composite(blend_mode) => Some(blend_mode),
+            },
+        };
+        let pipeline = pipelines.specialize(&mut pipeline_cache, &upscaling_pipeline, key);
+
+        commands
+            .entity(entity)
+            .insert(ViewUpscalingPipeline(pipeline));
+    }
+}
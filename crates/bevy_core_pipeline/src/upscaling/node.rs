@@ -0,0 +1,105 @@
+use crate::upscaling::{UpscalingPipeline, ViewUpscalingPipeline};
+use bevy_ecs::prelude::*;
+use bevy_render::{
+    camera::{CameraOutputMode, ExtractedCamera},
+    render_graph::{Node, NodeRunError, RenderGraphContext, SlotInfo, SlotType},
+    render_resource::{
+        BindGroupDescriptor, BindGroupEntry, BindingResource, LoadOp, Operations, PipelineCache,
+        RenderPassColorAttachment, RenderPassDescriptor,
+    },
+    renderer::RenderContext,
+    view::{ExtractedView, ViewTarget},
+};
+
+/// Writes the main texture of views to the texture of their render target, converting it to the
+/// format of the render target.
+///
+/// The main texture of a [`CameraOutputMode::Composite`] camera is blended over the contents of
+/// the render target instead of replacing them.
+pub struct UpscalingNode {
+    query: QueryState<
+        (
+            &'static ViewTarget,
+            &'static ViewUpscalingPipeline,
+            &'static ExtractedCamera,
+        ),
+        With<ExtractedView>,
+    >,
+}
+
+impl UpscalingNode {
+    pub const IN_VIEW: &'static str = "view";
+
+    pub fn new(world: &mut World) -> Self {
+        Self {
+            query: world.query_filtered(),
+        }
+    }
+}
+
+impl Node for UpscalingNode {
+    fn input(&self) -> Vec<SlotInfo> {
+        vec![SlotInfo::new(UpscalingNode::IN_VIEW, SlotType::Entity)]
+    }
+
+    fn update(&mut self, world: &mut World) {
+        self.query.update_archetypes(world);
+    }
+
+    fn run(
+        &self,
+        graph: &mut RenderGraphContext,
+        render_context: &mut RenderContext,
+        world: &World,
+    ) -> Result<(), NodeRunError> {
+        let view_entity = graph.get_input_entity(Self::IN_VIEW)?;
+        let pipeline_cache = world.resource::<PipelineCache>();
+        let upscaling_pipeline = world.resource::<UpscalingPipeline>();
+
+        let (target, upscaling, camera) = match self.query.get_manual(world, view_entity) {
+            Ok(result) => result,
+            Err(_) => return Ok(()),
+        };
+        let pipeline = match pipeline_cache.get_render_pipeline(upscaling.0) {
+            Some(pipeline) => pipeline,
+            None => return Ok(()),
+        };
+
+        let bind_group = render_context
+            .render_device
+            .create_bind_group(&BindGroupDescriptor {
+                label: Some("upscaling_texture_bind_group"),
+                layout: &upscaling_pipeline.texture_bind_group,
+                entries: &[BindGroupEntry {
+                    binding: 0,
+                    resource: BindingResource::TextureView(target.main_texture()),
+                }],
+            });
+
+        let pass_descriptor = RenderPassDescriptor {
+            label: Some("upscaling_pass"),
+            color_attachments: &[Some(RenderPassColorAttachment {
+                view: target.out_texture(),
+                resolve_target: None,
+                ops: Operations {
+                    load: match camera.output_mode {
+                        CameraOutputMode::Write => LoadOp::Clear(Default::default()),
+                        CameraOutputMode::Composite(_) => LoadOp::Load,
+                    },
+                    store: true,
+                },
+            })],
+            depth_stencil_attachment: None,
+        };
+
+        let mut render_pass = render_context
+            .command_encoder
+            .begin_render_pass(&pass_descriptor);
+
+        render_pass.set_pipeline(pipeline);
+        render_pass.set_bind_group(0, &bind_group, &[]);
+        render_pass.draw(0..3, 0..1);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,9 @@
+#import bevy_core_pipeline::fullscreen_vertex_shader
+
+@group(0) @binding(0)
+var main_texture: texture_2d<f32>;
+
+@fragment
+fn fs_main(in: FullscreenVertexOutput) -> @location(0) vec4<f32> {
+    return textureLoad(main_texture, vec2<i32>(in.position.xy), 0);
+}
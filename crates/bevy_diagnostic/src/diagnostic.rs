@@ -13,6 +13,49 @@ impl DiagnosticId {
     pub const fn from_u128(value: u128) -> Self {
         DiagnosticId(Uuid::from_u128(value))
     }
+
+    /// Derives the id of a diagnostic added at runtime from the `name` of what it measures and a
+    /// `namespace` unique to the code adding such diagnostics.
+    ///
+    /// The id is the 128-bit FNV-1a hash of both, so it is the same in every run of the app,
+    /// whatever the platform or the Rust version.
+    pub const fn from_name(namespace: u128, name: &str) -> Self {
+        const OFFSET_BASIS: u128 = 0x6c62_272e_07bb_0142_62b8_2175_6295_c58d;
+        const PRIME: u128 = 0x0000_0000_0100_0000_0000_0000_0000_013b;
+
+        let namespace = namespace.to_le_bytes();
+        let name = name.as_bytes();
+        let mut hash = OFFSET_BASIS;
+        let mut i = 0;
+        while i < namespace.len() + name.len() {
+            let byte = if i < namespace.len() {
+                namespace[i]
+            } else {
+                name[i - namespace.len()]
+            };
+            hash = (hash ^ byte as u128).wrapping_mul(PRIME);
+            i += 1;
+        }
+        DiagnosticId::from_u128(hash)
+    }
+}
+
+/// Returns `<name> <kind>`, with the start of `name` replaced by `…` if the result would be longer
+/// than [`MAX_DIAGNOSTIC_NAME_WIDTH`].
+///
+/// This names the diagnostics added at runtime for things with arbitrarily long names, like
+/// paths or type names, whose end tells them apart.
+pub fn fit_diagnostic_name(name: &str, kind: &str) -> String {
+    let max_name_width = MAX_DIAGNOSTIC_NAME_WIDTH.saturating_sub(kind.chars().count() + 1);
+    let name_width = name.chars().count();
+    if name_width <= max_name_width {
+        return format!("{name} {kind}");
+    }
+    let end = name
+        .chars()
+        .skip(name_width - max_name_width.saturating_sub(1))
+        .collect::<String>();
+    format!("…{end} {kind}")
 }
 
 impl Default for DiagnosticId {
@@ -205,3 +248,35 @@ impl Diagnostics {
         self.diagnostics.values()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{fit_diagnostic_name, DiagnosticId};
+    use crate::MAX_DIAGNOSTIC_NAME_WIDTH;
+
+    #[test]
+    fn ids_from_names_are_stable() {
+        // the ids must not change with the Rust version, as they identify diagnostics across runs
+        assert_eq!(
+            DiagnosticId::from_name(0, ""),
+            DiagnosticId::from_u128(0xf1f9_0b7b_f705_b5ef_1517_1638_3414_5e4d)
+        );
+        let id = DiagnosticId::from_name(1, "core_3d/main_pass cpu");
+        assert_eq!(id, DiagnosticId::from_name(1, "core_3d/main_pass cpu"));
+        assert_ne!(id, DiagnosticId::from_name(2, "core_3d/main_pass cpu"));
+        assert_ne!(id, DiagnosticId::from_name(1, "core_3d/main_pass gpu"));
+    }
+
+    #[test]
+    fn fitted_names_keep_their_end() {
+        assert_eq!(
+            fit_diagnostic_name("core_3d/main_pass", "cpu"),
+            "core_3d/main_pass cpu"
+        );
+
+        let name = fit_diagnostic_name("core_3d/main_opaque_pass_with_a_long_name", "gpu");
+        assert_eq!(name.chars().count(), MAX_DIAGNOSTIC_NAME_WIDTH);
+        assert!(name.starts_with('…'));
+        assert!(name.ends_with("with_a_long_name gpu"));
+    }
+}
@@ -9,7 +9,7 @@ use crate::{
 };
 use bevy_reflect::{
     impl_from_reflect_value, impl_reflect_value, FromType, Reflect, ReflectDeserialize,
-    ReflectSerialize,
+    ReflectSerialize, TypeData, TypeRegistry,
 };
 
 /// A struct used to operate on reflected [`Component`] of a type.
@@ -401,6 +401,66 @@ impl<C: Resource + Reflect + FromWorld> FromType<C> for ReflectResource {
     }
 }
 
+/// Returns an iterator over the reflected components of `entity` whose type registration
+/// contains the [`TypeData`] of type `T`, along with that type data.
+///
+/// Only components registered in `type_registry` with a [`ReflectComponent`] are visited.
+/// Combined with a type data generated by [`#[reflect_trait]`](bevy_reflect::reflect_trait),
+/// this gives access to every component of the entity implementing a given trait,
+/// whatever its concrete type:
+///
+/// ```
+/// # use bevy_ecs::{component::Component, reflect::{reflect_components_with_data, ReflectComponent}, world::World};
+/// # use bevy_reflect::{reflect_trait, Reflect, TypeRegistry};
+/// #[reflect_trait]
+/// trait Damageable {
+///     fn health(&self) -> u32;
+/// }
+///
+/// #[derive(Component, Reflect, Default)]
+/// #[reflect(Component, Damageable)]
+/// struct Shield(u32);
+///
+/// impl Damageable for Shield {
+///     fn health(&self) -> u32 {
+///         self.0
+///     }
+/// }
+///
+/// let mut type_registry = TypeRegistry::default();
+/// type_registry.register::<Shield>();
+///
+/// let mut world = World::new();
+/// let entity = world.spawn(Shield(10)).id();
+///
+/// let health: u32 = reflect_components_with_data::<ReflectDamageable>(&world, entity, &type_registry)
+///     .filter_map(|(component, reflect_damageable)| reflect_damageable.get(component))
+///     .map(|damageable| damageable.health())
+///     .sum();
+/// assert_eq!(10, health);
+/// ```
+///
+/// # Panics
+///
+/// Panics if the `entity` does not exist.
+pub fn reflect_components_with_data<'a, T: TypeData>(
+    world: &'a World,
+    entity: Entity,
+    type_registry: &'a TypeRegistry,
+) -> impl Iterator<Item = (&'a dyn Reflect, &'a T)> {
+    world
+        .inspect_entity(entity)
+        .into_iter()
+        .filter_map(move |info| {
+            let registration = type_registry.get(info.type_id()?)?;
+            let data = registration.data::<T>()?;
+            let component = registration
+                .data::<ReflectComponent>()?
+                .reflect(world, entity)?;
+            Some((component, data))
+        })
+}
+
 impl_reflect_value!(Entity(Hash, PartialEq, Serialize, Deserialize));
 impl_from_reflect_value!(Entity);
 
@@ -433,3 +493,61 @@ impl<C: Component + MapEntities> FromType<C> for ReflectMapEntities {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{reflect_components_with_data, ReflectComponent};
+    use crate as bevy_ecs;
+    use crate::{component::Component, world::World};
+    use bevy_reflect::{reflect_trait, Reflect, TypeRegistry};
+
+    #[reflect_trait]
+    trait Damageable {
+        fn health(&self) -> u32;
+    }
+
+    #[derive(Component, Reflect, Default)]
+    #[reflect(Component, Damageable)]
+    struct Shield(u32);
+
+    impl Damageable for Shield {
+        fn health(&self) -> u32 {
+            self.0
+        }
+    }
+
+    #[derive(Component, Reflect, Default)]
+    #[reflect(Component, Damageable)]
+    struct Armor(u32);
+
+    impl Damageable for Armor {
+        fn health(&self) -> u32 {
+            self.0
+        }
+    }
+
+    #[derive(Component, Reflect, Default)]
+    #[reflect(Component)]
+    struct Name;
+
+    #[test]
+    fn iter_components_implementing_trait() {
+        let mut type_registry = TypeRegistry::default();
+        type_registry.register::<Shield>();
+        type_registry.register::<Armor>();
+        type_registry.register::<Name>();
+
+        let mut world = World::new();
+        let entity = world.spawn((Shield(10), Armor(5), Name)).id();
+
+        let mut health: Vec<u32> =
+            reflect_components_with_data::<ReflectDamageable>(&world, entity, &type_registry)
+                .map(|(component, reflect_damageable)| {
+                    reflect_damageable.get(component).unwrap().health()
+                })
+                .collect();
+        health.sort_unstable();
+
+        assert_eq!(vec![5, 10], health);
+    }
+}
@@ -1,4 +1,4 @@
-use bevy_app::{PluginGroup, PluginGroupBuilder};
+use bevy_app::{App, Plugin, PluginGroup, PluginGroupBuilder};
 
 /// This plugin group will add all the default plugins:
 /// * [`LogPlugin`](bevy_log::LogPlugin)
@@ -21,7 +21,8 @@ use bevy_app::{PluginGroup, PluginGroupBuilder};
 /// * [`GltfPlugin`](bevy_gltf::GltfPlugin) - with feature `bevy_gltf`
 /// * [`WinitPlugin`](bevy_winit::WinitPlugin) - with feature `bevy_winit`
 ///
-/// See also [`MinimalPlugins`] for a slimmed down option
+/// See also [`MinimalPlugins`] for a slimmed down option, and [`HeadlessPlugins`] to render without
+/// a window
 pub struct DefaultPlugins;
 
 impl PluginGroup for DefaultPlugins {
@@ -81,6 +82,45 @@ impl PluginGroup for DefaultPlugins {
     }
 }
 
+/// This plugin group will add the [`DefaultPlugins`] to render without a window or a surface:
+/// * [`WinitPlugin`](bevy_winit::WinitPlugin) is not added
+/// * [`WindowPlugin`](bevy_window::WindowPlugin) doesn't create a primary window, and the app
+///   doesn't exit when there are no windows, see [`WindowSettings::headless`](bevy_window::WindowSettings::headless)
+/// * [`ScheduleRunnerPlugin`](bevy_app::ScheduleRunnerPlugin) runs the app in a loop
+///
+/// Cameras must render to [`RenderTarget::Image`](bevy_render::camera::RenderTarget::Image)
+/// targets, which can be read back with the
+/// [`ScreenshotManager`](bevy_render::view::ScreenshotManager). The app runs until it sends the
+/// [`AppExit`](bevy_app::AppExit) event.
+///
+/// To render on machines without a GPU, set
+/// [`WgpuSettings::force_fallback_adapter`](bevy_render::settings::WgpuSettings::force_fallback_adapter)
+/// or the `WGPU_FORCE_FALLBACK_ADAPTER=1` environment variable to use a software adapter.
+pub struct HeadlessPlugins;
+
+impl PluginGroup for HeadlessPlugins {
+    fn build(&mut self, group: &mut PluginGroupBuilder) {
+        DefaultPlugins.build(group);
+
+        #[cfg(feature = "bevy_winit")]
+        group.disable::<bevy_winit::WinitPlugin>();
+
+        group.add_before::<bevy_window::WindowPlugin, _>(HeadlessWindowSettingsPlugin);
+        group.add(bevy_app::ScheduleRunnerPlugin::default());
+    }
+}
+
+/// Configures the [`WindowPlugin`](bevy_window::WindowPlugin) of the [`HeadlessPlugins`], unless
+/// the app already has [`WindowSettings`](bevy_window::WindowSettings).
+struct HeadlessWindowSettingsPlugin;
+
+impl Plugin for HeadlessWindowSettingsPlugin {
+    fn build(&self, app: &mut App) {
+        app.world
+            .get_resource_or_insert_with(bevy_window::WindowSettings::headless);
+    }
+}
+
 /// Minimal plugin group that will add the following plugins:
 /// * [`CorePlugin`](bevy_core::CorePlugin)
 /// * [`TimePlugin`](bevy_time::TimePlugin)
@@ -96,3 +136,57 @@ impl PluginGroup for MinimalPlugins {
         group.add(bevy_app::ScheduleRunnerPlugin::default());
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_ecs::event::Events;
+
+    fn headless_app(app: &mut App) {
+        // renders nothing, there may be no GPU in tests
+        #[cfg(feature = "bevy_render")]
+        app.insert_resource(bevy_render::settings::WgpuSettings {
+            backends: None,
+            ..Default::default()
+        });
+        app.add_plugins_with(HeadlessPlugins, |group| {
+            group.disable::<bevy_log::LogPlugin>()
+        });
+    }
+
+    #[test]
+    fn headless_plugins_run_without_windows() {
+        let mut app = App::new();
+        headless_app(&mut app);
+        let settings = app.world.resource::<bevy_window::WindowSettings>();
+        assert!(!settings.add_primary_window);
+        assert!(!settings.exit_on_all_closed);
+        // the app is run by the schedule runner instead of winit
+        assert!(app
+            .world
+            .contains_resource::<bevy_app::ScheduleRunnerSettings>());
+
+        app.update();
+        assert!(app
+            .world
+            .resource::<bevy_window::Windows>()
+            .get_primary()
+            .is_none());
+        assert!(app.world.resource::<Events<bevy_app::AppExit>>().is_empty());
+    }
+
+    #[test]
+    fn headless_plugins_keep_window_settings() {
+        let mut app = App::new();
+        app.insert_resource(bevy_window::WindowSettings {
+            close_when_requested: false,
+            ..bevy_window::WindowSettings::headless()
+        });
+        headless_app(&mut app);
+        assert!(
+            !app.world
+                .resource::<bevy_window::WindowSettings>()
+                .close_when_requested
+        );
+    }
+}
@@ -5,6 +5,8 @@ mod bundle;
 mod light;
 mod material;
 mod pbr_material;
+mod pbr_material_loader;
+mod pbr_material_saver;
 mod render;
 
 pub use alpha::*;
@@ -12,6 +14,8 @@ pub use bundle::*;
 pub use light::*;
 pub use material::*;
 pub use pbr_material::*;
+pub use pbr_material_loader::*;
+pub use pbr_material_saver::*;
 pub use render::*;
 
 use bevy_window::ModifiesWindows;
@@ -38,7 +42,7 @@ pub mod draw_3d_graph {
 }
 
 use bevy_app::prelude::*;
-use bevy_asset::{load_internal_asset, Assets, Handle, HandleUntyped};
+use bevy_asset::{load_internal_asset, AddAsset, Assets, Handle, HandleUntyped};
 use bevy_ecs::prelude::*;
 use bevy_reflect::TypeUuid;
 use bevy_render::{
@@ -129,6 +133,8 @@ impl Plugin for PbrPlugin {
             .register_type::<SpotLight>()
             .add_plugin(MeshRenderPlugin)
             .add_plugin(MaterialPlugin::<StandardMaterial>::default())
+            .init_asset_loader::<StandardMaterialLoader>()
+            .init_asset_saver::<StandardMaterialSaver>()
             .register_type::<AmbientLight>()
             .register_type::<DirectionalLightShadowMap>()
             .register_type::<PointLightShadowMap>()
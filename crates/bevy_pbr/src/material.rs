@@ -1,15 +1,23 @@
 use crate::{
-    AlphaMode, DrawMesh, MeshPipeline, MeshPipelineKey, MeshUniform, SetMeshBindGroup,
-    SetMeshViewBindGroup,
+    AlphaMode, DrawMesh, MeshPipeline, MeshPipelineKey, MeshUniform, NoAutomaticBatching,
+    SetMeshBindGroup, SetMeshViewBindGroup,
 };
 use bevy_app::{App, Plugin};
-use bevy_asset::{AddAsset, AssetEvent, AssetServer, Assets, Handle};
-use bevy_core_pipeline::core_3d::{AlphaMask3d, Opaque3d, Transparent3d};
+use bevy_asset::{AddAsset, AssetEvent, AssetServer, Assets, Handle, HandleId};
+use bevy_core_pipeline::{
+    core_3d::{
+        AlphaMask3d, Camera3d, Camera3dTransparency, Opaque3d, Transparent3d,
+        TransparentSortOverride,
+    },
+    tonemapping::{DebandDither, Tonemapping},
+};
 use bevy_derive::{Deref, DerefMut};
 use bevy_ecs::{
+    component::Component,
     entity::Entity,
     event::EventReader,
     prelude::World,
+    query::With,
     schedule::IntoSystemDescriptor,
     system::{
         lifetimeless::{Read, SQuery, SRes},
@@ -19,7 +27,7 @@ use bevy_ecs::{
 };
 use bevy_reflect::TypeUuid;
 use bevy_render::{
-    extract_component::ExtractComponentPlugin,
+    camera::CameraSampledTextures,
     mesh::{Mesh, MeshVertexBufferLayout},
     prelude::Image,
     render_asset::{PrepareAssetLabel, RenderAssets},
@@ -34,7 +42,7 @@ use bevy_render::{
     },
     renderer::RenderDevice,
     texture::FallbackImage,
-    view::{ExtractedView, Msaa, VisibleEntities},
+    view::{ComputedVisibility, ExtractedView, Msaa, VisibleEntities},
     Extract, RenderApp, RenderStage,
 };
 use bevy_utils::{tracing::error, HashMap, HashSet};
@@ -135,6 +143,30 @@ pub trait Material: AsBindGroup + Send + Sync + Clone + TypeUuid + Sized + 'stat
         0.0
     }
 
+    /// Returns `true` if the meshes using this material can be drawn in batches of instances, with
+    /// the `MESH_INSTANCING` shader def. Defaults to `true` for materials using the default mesh
+    /// shaders.
+    ///
+    /// With `MESH_INSTANCING`, the `mesh` of `bevy_pbr::mesh_bindings` is a private variable. The
+    /// vertex shader must set it to `meshes.data[instance_index]` and pass the `instance_index`
+    /// of `bevy_pbr::mesh_vertex_output` to the fragment shader, which must set it again before
+    /// reading it.
+    fn automatic_batching() -> bool {
+        matches!(Self::vertex_shader(), ShaderRef::Default)
+            && matches!(Self::fragment_shader(), ShaderRef::Default)
+    }
+
+    /// Returns `true` if the transparent meshes using this material can be drawn with weighted
+    /// blended order-independent transparency, with the `WEIGHTED_BLENDED_OIT` shader def.
+    /// Defaults to `true` for materials using the default mesh fragment shader.
+    ///
+    /// With `WEIGHTED_BLENDED_OIT`, the fragment shader must return the `OitFragmentOutput` of
+    /// `bevy_core_pipeline::oit`. The meshes of the other materials are sorted and blended over
+    /// the order-independent transparent meshes.
+    fn order_independent_transparency() -> bool {
+        matches!(Self::fragment_shader(), ShaderRef::Default)
+    }
+
     /// Customizes the default [`RenderPipelineDescriptor`] for a specific entity using the entity's
     /// [`MaterialPipelineKey`] and [`MeshVertexBufferLayout`] as input.
     #[allow(unused_variables)]
@@ -164,8 +196,7 @@ where
     M::Data: PartialEq + Eq + Hash + Clone,
 {
     fn build(&self, app: &mut App) {
-        app.add_asset::<M>()
-            .add_plugin(ExtractComponentPlugin::<Handle<M>>::extract_visible());
+        app.add_asset::<M>();
         if let Ok(render_app) = app.get_sub_app_mut(RenderApp) {
             render_app
                 .add_render_command::<Transparent3d, DrawMaterial<M>>()
@@ -175,6 +206,7 @@ where
                 .init_resource::<ExtractedMaterials<M>>()
                 .init_resource::<RenderMaterials<M>>()
                 .init_resource::<SpecializedMeshPipelines<MaterialPipeline<M>>>()
+                .add_system_to_stage(RenderStage::Extract, extract_material_meshes::<M>)
                 .add_system_to_stage(RenderStage::Extract, extract_materials::<M>)
                 .add_system_to_stage(
                     RenderStage::Prepare,
@@ -295,6 +327,27 @@ type DrawMaterial<M> = (
     DrawMesh,
 );
 
+/// The id of the material of a mesh entity in the render world. The entities sharing a mesh and a
+/// material are batched into instanced draws.
+#[derive(Component, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Debug)]
+pub struct MeshMaterialId(pub HandleId);
+
+/// Extracts the [`Handle<M>`] and the [`MeshMaterialId`] of the visible entities.
+pub fn extract_material_meshes<M: Material>(
+    mut commands: Commands,
+    mut previous_len: Local<usize>,
+    query: Extract<Query<(Entity, &ComputedVisibility, &Handle<M>)>>,
+) {
+    let mut values = Vec::with_capacity(*previous_len);
+    for (entity, computed_visibility, handle) in &query {
+        if computed_visibility.is_visible() {
+            values.push((entity, (handle.clone_weak(), MeshMaterialId(handle.id()))));
+        }
+    }
+    *previous_len = values.len();
+    commands.insert_or_spawn_batch(values);
+}
+
 /// Sets the bind group for a given [`Material`] at the configured `I` index.
 pub struct SetMaterialBindGroup<M: Material, const I: usize>(PhantomData<M>);
 impl<M: Material, const I: usize> EntityRenderCommand for SetMaterialBindGroup<M, I> {
@@ -323,19 +376,39 @@ pub fn queue_material_meshes<M: Material>(
     msaa: Res<Msaa>,
     render_meshes: Res<RenderAssets<Mesh>>,
     render_materials: Res<RenderMaterials<M>>,
-    material_meshes: Query<(&Handle<M>, &Handle<Mesh>, &MeshUniform)>,
+    material_meshes: Query<(
+        &Handle<M>,
+        &Handle<Mesh>,
+        &MeshUniform,
+        Option<&TransparentSortOverride>,
+        Option<With<NoAutomaticBatching>>,
+    )>,
     mut views: Query<(
         &ExtractedView,
         &VisibleEntities,
+        Option<&Tonemapping>,
+        Option<&DebandDither>,
+        Option<&Camera3d>,
         &mut RenderPhase<Opaque3d>,
         &mut RenderPhase<AlphaMask3d>,
         &mut RenderPhase<Transparent3d>,
+        Option<&mut CameraSampledTextures>,
     )>,
 ) where
     M::Data: PartialEq + Eq + Hash + Clone,
 {
-    for (view, visible_entities, mut opaque_phase, mut alpha_mask_phase, mut transparent_phase) in
-        &mut views
+    let automatic_batching = M::automatic_batching();
+    for (
+        view,
+        visible_entities,
+        tonemapping,
+        deband_dither,
+        camera_3d,
+        mut opaque_phase,
+        mut alpha_mask_phase,
+        mut transparent_phase,
+        mut sampled_textures,
+    ) in &mut views
     {
         let draw_opaque_pbr = opaque_draw_functions
             .read()
@@ -351,20 +424,42 @@ pub fn queue_material_meshes<M: Material>(
             .unwrap();
 
         let rangefinder = view.rangefinder3d();
-        let msaa_key = MeshPipelineKey::from_msaa_samples(msaa.samples);
+        let view_key = MeshPipelineKey::from_msaa_samples(msaa.samples)
+            | MeshPipelineKey::from_view(view, tonemapping, deband_dither);
+        let transparent_key = match camera_3d.map(|camera_3d| camera_3d.transparency) {
+            Some(Camera3dTransparency::WeightedBlendedOit)
+                if M::order_independent_transparency() =>
+            {
+                MeshPipelineKey::TRANSPARENT_MAIN_PASS | MeshPipelineKey::WEIGHTED_BLENDED_OIT
+            }
+            _ => MeshPipelineKey::TRANSPARENT_MAIN_PASS,
+        };
 
         for visible_entity in &visible_entities.entities {
-            if let Ok((material_handle, mesh_handle, mesh_uniform)) =
+            if let Ok((material_handle, mesh_handle, mesh_uniform, sort_override, no_batching)) =
                 material_meshes.get(*visible_entity)
             {
                 if let Some(material) = render_materials.get(material_handle) {
                     if let Some(mesh) = render_meshes.get(mesh_handle) {
+                        if let Some(sampled_textures) = &mut sampled_textures {
+                            sampled_textures.insert_bindings(&material.bindings);
+                        }
                         let mut mesh_key =
                             MeshPipelineKey::from_primitive_topology(mesh.primitive_topology)
-                                | msaa_key;
+                                | view_key;
                         let alpha_mode = material.properties.alpha_mode;
                         if let AlphaMode::Blend = alpha_mode {
-                            mesh_key |= MeshPipelineKey::TRANSPARENT_MAIN_PASS;
+                            mesh_key |= transparent_key;
+                        }
+                        // the instances of the batches are assigned by `batch_mesh_instances`
+                        let batch_range = (automatic_batching
+                            && no_batching.is_none()
+                            && material_pipeline
+                                .mesh_pipeline
+                                .supports_instancing(&mesh.layout))
+                        .then_some(0..0);
+                        if batch_range.is_some() {
+                            mesh_key |= MeshPipelineKey::MESH_INSTANCING;
                         }
 
                         let pipeline_id = pipelines.specialize(
@@ -393,6 +488,7 @@ pub fn queue_material_meshes<M: Material>(
                                     draw_function: draw_opaque_pbr,
                                     pipeline: pipeline_id,
                                     distance,
+                                    batch_range,
                                 });
                             }
                             AlphaMode::Mask(_) => {
@@ -401,14 +497,20 @@ pub fn queue_material_meshes<M: Material>(
                                     draw_function: draw_alpha_mask_pbr,
                                     pipeline: pipeline_id,
                                     distance,
+                                    batch_range,
                                 });
                             }
                             AlphaMode::Blend => {
+                                let distance = match sort_override {
+                                    Some(sort_override) => sort_override.distance(distance),
+                                    None => distance,
+                                };
                                 transparent_phase.add(Transparent3d {
                                     entity: *visible_entity,
                                     draw_function: draw_transparent_pbr,
                                     pipeline: pipeline_id,
                                     distance,
+                                    batch_range,
                                 });
                             }
                         }
@@ -479,6 +581,7 @@ fn extract_materials<M: Material>(
                 changed_assets.remove(handle);
                 removed.push(handle.clone_weak());
             }
+            AssetEvent::LoadedWithDependencies { .. } => {}
         }
     }
 
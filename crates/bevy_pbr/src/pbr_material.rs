@@ -416,7 +416,7 @@ impl Material for StandardMaterial {
                 .as_mut()
                 .unwrap()
                 .shader_defs
-                .push(String::from("STANDARDMATERIAL_NORMAL_MAP"));
+                .push("STANDARDMATERIAL_NORMAL_MAP".into());
         }
         descriptor.primitive.cull_mode = key.bind_group_data.cull_mode;
         if let Some(label) = &mut descriptor.label {
@@ -429,6 +429,14 @@ impl Material for StandardMaterial {
         PBR_SHADER_HANDLE.typed().into()
     }
 
+    fn automatic_batching() -> bool {
+        true
+    }
+
+    fn order_independent_transparency() -> bool {
+        true
+    }
+
     #[inline]
     fn alpha_mode(&self) -> AlphaMode {
         self.alpha_mode
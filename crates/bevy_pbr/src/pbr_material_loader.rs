@@ -0,0 +1,146 @@
+use anyhow::Result;
+use bevy_asset::{AssetLoader, AssetPath, Handle, LoadContext, LoadedAsset};
+use bevy_render::{color::Color, render_resource::Face, texture::Image};
+use bevy_utils::BoxedFuture;
+use serde::{Deserialize, Serialize};
+
+use crate::{AlphaMode, StandardMaterial};
+
+/// Loader for [`StandardMaterial`]s stored in the RON format written by the
+/// [`StandardMaterialSaver`](crate::StandardMaterialSaver).
+///
+/// Textures are referred to by their asset path and loaded as dependencies of the material.
+/// Omitted fields keep the values of [`StandardMaterial::default`]:
+/// ```ron
+/// (
+///     base_color: Rgba(red: 1.0, green: 0.5, blue: 0.5, alpha: 1.0),
+///     base_color_texture: Some("textures/brick.png"),
+///     perceptual_roughness: 0.8,
+/// )
+/// ```
+#[derive(Default)]
+pub struct StandardMaterialLoader;
+
+pub(crate) const FILE_EXTENSIONS: &[&str] = &["material.ron"];
+
+/// The content of a material file, with the textures replaced by their asset paths.
+#[derive(Serialize, Deserialize)]
+#[serde(default)]
+pub(crate) struct StandardMaterialFile {
+    pub base_color: Color,
+    pub base_color_texture: Option<String>,
+    pub emissive: Color,
+    pub emissive_texture: Option<String>,
+    pub perceptual_roughness: f32,
+    pub metallic: f32,
+    pub metallic_roughness_texture: Option<String>,
+    pub reflectance: f32,
+    pub normal_map_texture: Option<String>,
+    pub flip_normal_map_y: bool,
+    pub occlusion_texture: Option<String>,
+    pub double_sided: bool,
+    pub cull_mode: Option<MaterialFileFace>,
+    pub unlit: bool,
+    pub alpha_mode: AlphaMode,
+    pub depth_bias: f32,
+}
+
+/// The [`Face`] culled by a material file.
+#[derive(Serialize, Deserialize)]
+pub(crate) enum MaterialFileFace {
+    Front,
+    Back,
+}
+
+impl Default for StandardMaterialFile {
+    fn default() -> Self {
+        StandardMaterialFile::from_material(&StandardMaterial::default(), |_| None)
+    }
+}
+
+impl StandardMaterialFile {
+    /// Copies `material`, writing its textures as the paths returned by `texture_path`.
+    pub fn from_material(
+        material: &StandardMaterial,
+        mut texture_path: impl FnMut(&Handle<Image>) -> Option<String>,
+    ) -> Self {
+        let mut texture =
+            |texture: &Option<Handle<Image>>| texture.as_ref().and_then(&mut texture_path);
+        StandardMaterialFile {
+            base_color: material.base_color,
+            base_color_texture: texture(&material.base_color_texture),
+            emissive: material.emissive,
+            emissive_texture: texture(&material.emissive_texture),
+            perceptual_roughness: material.perceptual_roughness,
+            metallic: material.metallic,
+            metallic_roughness_texture: texture(&material.metallic_roughness_texture),
+            reflectance: material.reflectance,
+            normal_map_texture: texture(&material.normal_map_texture),
+            flip_normal_map_y: material.flip_normal_map_y,
+            occlusion_texture: texture(&material.occlusion_texture),
+            double_sided: material.double_sided,
+            cull_mode: material.cull_mode.map(|face| match face {
+                Face::Front => MaterialFileFace::Front,
+                Face::Back => MaterialFileFace::Back,
+            }),
+            unlit: material.unlit,
+            alpha_mode: material.alpha_mode,
+            depth_bias: material.depth_bias,
+        }
+    }
+
+    /// Builds the material described by the file, getting its textures from their paths with
+    /// `texture`.
+    pub fn into_material(
+        self,
+        mut texture: impl FnMut(AssetPath<'_>) -> Handle<Image>,
+    ) -> StandardMaterial {
+        let mut texture = |path: Option<String>| path.map(|path| texture(AssetPath::from(&path)));
+        StandardMaterial {
+            base_color: self.base_color,
+            base_color_texture: texture(self.base_color_texture),
+            emissive: self.emissive,
+            emissive_texture: texture(self.emissive_texture),
+            perceptual_roughness: self.perceptual_roughness,
+            metallic: self.metallic,
+            metallic_roughness_texture: texture(self.metallic_roughness_texture),
+            reflectance: self.reflectance,
+            normal_map_texture: texture(self.normal_map_texture),
+            flip_normal_map_y: self.flip_normal_map_y,
+            occlusion_texture: texture(self.occlusion_texture),
+            double_sided: self.double_sided,
+            cull_mode: self.cull_mode.map(|face| match face {
+                MaterialFileFace::Front => Face::Front,
+                MaterialFileFace::Back => Face::Back,
+            }),
+            unlit: self.unlit,
+            alpha_mode: self.alpha_mode,
+            depth_bias: self.depth_bias,
+        }
+    }
+}
+
+impl AssetLoader for StandardMaterialLoader {
+    fn load<'a>(
+        &'a self,
+        bytes: &'a [u8],
+        load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<()>> {
+        Box::pin(async move {
+            let material_file: StandardMaterialFile = ron::de::from_bytes(bytes)?;
+            let mut dependencies = Vec::new();
+            let material = material_file.into_material(|path| {
+                let texture = load_context.get_handle(path.clone());
+                dependencies.push(path.to_owned());
+                texture
+            });
+            load_context
+                .set_default_asset(LoadedAsset::new(material).with_dependencies(dependencies));
+            Ok(())
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        FILE_EXTENSIONS
+    }
+}
@@ -0,0 +1,135 @@
+use anyhow::{anyhow, Result};
+use bevy_asset::{AssetSaver, AssetServer};
+use bevy_ecs::world::{FromWorld, World};
+use ron::ser::PrettyConfig;
+use std::path::Path;
+
+use crate::{
+    pbr_material_loader::{StandardMaterialFile, FILE_EXTENSIONS},
+    StandardMaterial,
+};
+
+/// Saves [`StandardMaterial`]s in the RON format read by the
+/// [`StandardMaterialLoader`](crate::StandardMaterialLoader).
+///
+/// Textures are saved as the asset paths they were loaded from, so materials whose textures
+/// weren't loaded from a file can't be saved.
+pub struct StandardMaterialSaver {
+    asset_server: AssetServer,
+}
+
+impl FromWorld for StandardMaterialSaver {
+    fn from_world(world: &mut World) -> Self {
+        StandardMaterialSaver {
+            asset_server: world.resource::<AssetServer>().clone(),
+        }
+    }
+}
+
+impl AssetSaver for StandardMaterialSaver {
+    type Asset = StandardMaterial;
+
+    fn save(&self, material: &StandardMaterial, path: &Path) -> Result<Vec<u8>> {
+        let mut unsaved_texture = None;
+        let material_file = StandardMaterialFile::from_material(material, |texture| {
+            let texture_path = self.asset_server.get_handle_path(texture);
+            if texture_path.is_none() {
+                unsaved_texture = Some(texture.id());
+            }
+            texture_path.map(|texture_path| texture_path.to_string())
+        });
+        if let Some(texture) = unsaved_texture {
+            return Err(anyhow!(
+                "The texture {texture:?} of {} wasn't loaded from a file",
+                path.display()
+            ));
+        }
+
+        let pretty_config = PrettyConfig::default()
+            .indentor("  ".to_string())
+            .new_line("\n".to_string());
+        Ok(ron::ser::to_string_pretty(&material_file, pretty_config)?.into_bytes())
+    }
+
+    fn extensions(&self) -> &[&str] {
+        FILE_EXTENSIONS
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::StandardMaterialSaver;
+    use crate::{pbr_material_loader::StandardMaterialFile, AlphaMode, StandardMaterial};
+    use bevy_asset::{AssetSaver, AssetServer, FileAssetIo, Handle, HandleId};
+    use bevy_render::{color::Color, texture::Image};
+    use bevy_tasks::IoTaskPool;
+    use std::path::Path;
+
+    fn saver() -> StandardMaterialSaver {
+        IoTaskPool::init(Default::default);
+        StandardMaterialSaver {
+            asset_server: AssetServer::new(FileAssetIo::new("assets", false)),
+        }
+    }
+
+    #[test]
+    fn round_trip_material() {
+        let saver = saver();
+        let texture: Handle<Image> = saver.asset_server.load("textures/brick.png");
+        let material = StandardMaterial {
+            base_color: Color::rgba(1.0, 0.5, 0.5, 0.5),
+            base_color_texture: Some(texture.clone()),
+            perceptual_roughness: 0.8,
+            alpha_mode: AlphaMode::Mask(0.25),
+            cull_mode: None,
+            ..Default::default()
+        };
+
+        let bytes = saver
+            .save(&material, Path::new("brick.material.ron"))
+            .unwrap();
+        assert!(std::str::from_utf8(&bytes)
+            .unwrap()
+            .contains(r#"base_color_texture: Some("textures/brick.png")"#));
+
+        let material_file: StandardMaterialFile = ron::de::from_bytes(&bytes).unwrap();
+        let mut texture_paths = Vec::new();
+        let loaded = material_file.into_material(|path| {
+            texture_paths.push(path.to_string());
+            Handle::weak(path.into())
+        });
+        assert_eq!(texture_paths, ["textures/brick.png"]);
+        assert_eq!(loaded.base_color, material.base_color);
+        assert_eq!(loaded.base_color_texture, Some(texture));
+        assert_eq!(loaded.perceptual_roughness, 0.8);
+        assert_eq!(loaded.alpha_mode, AlphaMode::Mask(0.25));
+        assert_eq!(loaded.cull_mode, None);
+    }
+
+    #[test]
+    fn omitted_fields_are_defaults() {
+        let material_file: StandardMaterialFile =
+            ron::de::from_str("(metallic: 0.5, unlit: true)").unwrap();
+        let material = material_file.into_material(|path| Handle::weak(path.into()));
+        let default = StandardMaterial::default();
+
+        assert_eq!(material.metallic, 0.5);
+        assert!(material.unlit);
+        assert_eq!(material.base_color, default.base_color);
+        assert_eq!(material.cull_mode, default.cull_mode);
+        assert_eq!(material.reflectance, default.reflectance);
+    }
+
+    #[test]
+    fn textures_without_paths_are_not_saved() {
+        let saver = saver();
+        let material = StandardMaterial {
+            emissive_texture: Some(Handle::weak(HandleId::random::<Image>())),
+            ..Default::default()
+        };
+
+        assert!(saver
+            .save(&material, Path::new("emissive.material.ron"))
+            .is_err());
+    }
+}
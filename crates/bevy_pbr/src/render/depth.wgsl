@@ -4,8 +4,14 @@
 @group(0) @binding(0)
 var<uniform> view: View;
 
+#ifdef MESH_INSTANCING
+@group(1) @binding(0)
+var<storage> meshes: Meshes;
+var<private> mesh: Mesh;
+#else
 @group(1) @binding(0)
 var<uniform> mesh: Mesh;
+#endif
 
 #ifdef SKINNED
 @group(1) @binding(1)
@@ -22,6 +28,9 @@ struct Vertex {
     @location(4) joint_indices: vec4<u32>,
     @location(5) joint_weights: vec4<f32>,
 #endif
+#ifdef MESH_INSTANCING
+    @builtin(instance_index) instance_index: u32,
+#endif
 };
 
 struct VertexOutput {
@@ -30,6 +39,9 @@ struct VertexOutput {
 
 @vertex
 fn vertex(vertex: Vertex) -> VertexOutput {
+#ifdef MESH_INSTANCING
+    mesh = meshes.data[vertex.instance_index];
+#endif
 #ifdef SKINNED
     let model = skin_model(vertex.joint_indices, vertex.joint_weights);
 #else
@@ -1,8 +1,8 @@
 use crate::{
-    point_light_order, AmbientLight, Clusters, CubemapVisibleEntities, DirectionalLight,
-    DirectionalLightShadowMap, DrawMesh, GlobalVisiblePointLights, MeshPipeline, NotShadowCaster,
-    PointLight, PointLightShadowMap, SetMeshBindGroup, SpotLight, VisiblePointLights,
-    SHADOW_SHADER_HANDLE,
+    is_skinned, point_light_order, AmbientLight, Clusters, CubemapVisibleEntities,
+    DirectionalLight, DirectionalLightShadowMap, DrawMesh, GlobalVisiblePointLights, MeshPipeline,
+    NoAutomaticBatching, NotShadowCaster, PointLight, PointLightShadowMap, SetMeshBindGroup,
+    SpotLight, VisiblePointLights, SHADOW_SHADER_HANDLE,
 };
 use bevy_asset::Handle;
 use bevy_core_pipeline::core_3d::Transparent3d;
@@ -18,9 +18,9 @@ use bevy_render::{
     render_asset::RenderAssets,
     render_graph::{Node, NodeRunError, RenderGraphContext, SlotInfo, SlotType},
     render_phase::{
-        CachedRenderPipelinePhaseItem, DrawFunctionId, DrawFunctions, EntityPhaseItem,
-        EntityRenderCommand, PhaseItem, RenderCommandResult, RenderPhase, SetItemPipeline,
-        TrackedRenderPass,
+        BatchedPhaseItem, CachedRenderPipelinePhaseItem, DrawFunctionId, DrawFunctions,
+        EntityPhaseItem, EntityRenderCommand, PhaseItem, RenderCommandResult, RenderPhase,
+        SetItemPipeline, TrackedRenderPass,
     },
     render_resource::*,
     renderer::{RenderContext, RenderDevice, RenderQueue},
@@ -37,7 +37,10 @@ use bevy_utils::{
     tracing::{error, warn},
     HashMap,
 };
-use std::num::{NonZeroU32, NonZeroU64};
+use std::{
+    num::{NonZeroU32, NonZeroU64},
+    ops::Range,
+};
 
 #[derive(Debug, Hash, PartialEq, Eq, Clone, SystemLabel)]
 pub enum RenderLightSystems {
@@ -219,6 +222,7 @@ pub struct ShadowPipeline {
     pub view_layout: BindGroupLayout,
     pub mesh_layout: BindGroupLayout,
     pub skinned_mesh_layout: BindGroupLayout,
+    pub instanced_mesh_layout: Option<BindGroupLayout>,
     pub point_light_sampler: Sampler,
     pub directional_light_sampler: Sampler,
 }
@@ -252,6 +256,7 @@ impl FromWorld for ShadowPipeline {
             view_layout,
             mesh_layout: mesh_pipeline.mesh_layout.clone(),
             skinned_mesh_layout,
+            instanced_mesh_layout: mesh_pipeline.instanced_mesh_layout.clone(),
             point_light_sampler: render_device.create_sampler(&SamplerDescriptor {
                 address_mode_u: AddressMode::ClampToEdge,
                 address_mode_v: AddressMode::ClampToEdge,
@@ -280,6 +285,8 @@ bitflags::bitflags! {
     #[repr(transparent)]
     pub struct ShadowPipelineKey: u32 {
         const NONE               = 0;
+        /// Draws the mesh in batches of instances, see [`MeshPipelineKey::MESH_INSTANCING`](crate::MeshPipelineKey::MESH_INSTANCING).
+        const MESH_INSTANCING    = (1 << 0);
         const PRIMITIVE_TOPOLOGY_RESERVED_BITS = ShadowPipelineKey::PRIMITIVE_TOPOLOGY_MASK_BITS << ShadowPipelineKey::PRIMITIVE_TOPOLOGY_SHIFT_BITS;
     }
 }
@@ -322,13 +329,18 @@ impl SpecializedMeshPipeline for ShadowPipeline {
         let mut bind_group_layout = vec![self.view_layout.clone()];
         let mut shader_defs = Vec::new();
 
-        if layout.contains(Mesh::ATTRIBUTE_JOINT_INDEX)
-            && layout.contains(Mesh::ATTRIBUTE_JOINT_WEIGHT)
-        {
-            shader_defs.push(String::from("SKINNED"));
+        if is_skinned(layout) {
+            shader_defs.push("SKINNED".into());
             vertex_attributes.push(Mesh::ATTRIBUTE_JOINT_INDEX.at_shader_location(4));
             vertex_attributes.push(Mesh::ATTRIBUTE_JOINT_WEIGHT.at_shader_location(5));
             bind_group_layout.push(self.skinned_mesh_layout.clone());
+        } else if key.contains(ShadowPipelineKey::MESH_INSTANCING) {
+            shader_defs.push("MESH_INSTANCING".into());
+            bind_group_layout.push(
+                self.instanced_mesh_layout
+                    .clone()
+                    .expect("Mesh instancing requires storage buffers"),
+            );
         } else {
             bind_group_layout.push(self.mesh_layout.clone());
         }
@@ -1023,6 +1035,7 @@ pub fn prepare_lights(
                             ),
                         },
                         ExtractedView {
+                            hdr: false,
                             viewport: UVec4::new(
                                 0,
                                 0,
@@ -1078,6 +1091,7 @@ pub fn prepare_lights(
                         pass_name: format!("shadow pass spot light {}", light_index,),
                     },
                     ExtractedView {
+                        hdr: false,
                         viewport: UVec4::new(
                             0,
                             0,
@@ -1161,6 +1175,7 @@ pub fn prepare_lights(
                             pass_name: format!("shadow pass directional light {}", i),
                         },
                         ExtractedView {
+                            hdr: false,
                             viewport: UVec4::new(
                                 0,
                                 0,
@@ -1590,7 +1605,11 @@ pub fn queue_shadow_view_bind_group(
 pub fn queue_shadows(
     shadow_draw_functions: Res<DrawFunctions<Shadow>>,
     shadow_pipeline: Res<ShadowPipeline>,
-    casting_meshes: Query<&Handle<Mesh>, Without<NotShadowCaster>>,
+    mesh_pipeline: Res<MeshPipeline>,
+    casting_meshes: Query<
+        (&Handle<Mesh>, Option<With<NoAutomaticBatching>>),
+        Without<NotShadowCaster>,
+    >,
     render_meshes: Res<RenderAssets<Mesh>>,
     mut pipelines: ResMut<SpecializedMeshPipelines<ShadowPipeline>>,
     mut pipeline_cache: ResMut<PipelineCache>,
@@ -1626,10 +1645,17 @@ pub fn queue_shadows(
             // NOTE: Lights with shadow mapping disabled will have no visible entities
             // so no meshes will be queued
             for entity in visible_entities.iter().copied() {
-                if let Ok(mesh_handle) = casting_meshes.get(entity) {
+                if let Ok((mesh_handle, no_batching)) = casting_meshes.get(entity) {
                     if let Some(mesh) = render_meshes.get(mesh_handle) {
-                        let key =
+                        let mut key =
                             ShadowPipelineKey::from_primitive_topology(mesh.primitive_topology);
+                        // the instances of the batches are assigned by `batch_mesh_instances`
+                        let batch_range = (no_batching.is_none()
+                            && mesh_pipeline.supports_instancing(&mesh.layout))
+                        .then_some(0..0);
+                        if batch_range.is_some() {
+                            key |= ShadowPipelineKey::MESH_INSTANCING;
+                        }
                         let pipeline_id = pipelines.specialize(
                             &mut pipeline_cache,
                             &shadow_pipeline,
@@ -1650,6 +1676,7 @@ pub fn queue_shadows(
                             pipeline: pipeline_id,
                             entity,
                             distance: 0.0, // TODO: sort back-to-front
+                            batch_range,
                         });
                     }
                 }
@@ -1663,6 +1690,8 @@ pub struct Shadow {
     pub entity: Entity,
     pub pipeline: CachedRenderPipelineId,
     pub draw_function: DrawFunctionId,
+    /// Range of the instances drawn by this item, if it is drawn as a batch of instances.
+    pub batch_range: Option<Range<u32>>,
 }
 
 impl PhaseItem for Shadow {
@@ -1697,6 +1726,16 @@ impl CachedRenderPipelinePhaseItem for Shadow {
     }
 }
 
+impl BatchedPhaseItem for Shadow {
+    fn batch_range(&self) -> &Option<Range<u32>> {
+        &self.batch_range
+    }
+
+    fn batch_range_mut(&mut self) -> &mut Option<Range<u32>> {
+        &mut self.batch_range
+    }
+}
+
 pub struct ShadowPassNode {
     main_view_query: QueryState<&'static ViewLightEntities>,
     view_light_query: QueryState<(&'static ShadowView, &'static RenderPhase<Shadow>)>,
@@ -1,29 +1,43 @@
 use crate::{
-    GlobalLightMeta, GpuLights, GpuPointLights, LightMeta, NotShadowCaster, NotShadowReceiver,
-    ShadowPipeline, ViewClusterBindings, ViewLightsUniformOffset, ViewShadowBindings,
-    CLUSTERED_FORWARD_STORAGE_BUFFER_COUNT,
+    GlobalLightMeta, GpuLights, GpuPointLights, LightMeta, MeshMaterialId, NotShadowCaster,
+    NotShadowReceiver, Shadow, ShadowPipeline, ViewClusterBindings, ViewLightsUniformOffset,
+    ViewShadowBindings, CLUSTERED_FORWARD_STORAGE_BUFFER_COUNT,
 };
 use bevy_app::Plugin;
-use bevy_asset::{load_internal_asset, Assets, Handle, HandleUntyped};
+use bevy_asset::{load_internal_asset, Assets, Handle, HandleId, HandleUntyped};
+use bevy_core_pipeline::{
+    core_3d::{AlphaMask3d, Opaque3d, Transparent3d},
+    oit::oit_color_target_states,
+    tonemapping::{DebandDither, Tonemapping},
+};
 use bevy_ecs::{
     prelude::*,
+    query::QueryItem,
     system::{lifetimeless::*, SystemParamItem, SystemState},
 };
 use bevy_math::{Mat3A, Mat4, Vec2};
-use bevy_reflect::TypeUuid;
+use bevy_reflect::{std_traits::ReflectDefault, Reflect, TypeUuid};
 use bevy_render::{
-    extract_component::{ComponentUniforms, DynamicUniformIndex, UniformComponentPlugin},
+    extract_component::{
+        ComponentUniforms, DynamicUniformIndex, ExtractComponent, ExtractComponentPlugin,
+        UniformComponentPlugin,
+    },
     globals::{GlobalsBuffer, GlobalsUniform},
     mesh::{
         skinning::{SkinnedMesh, SkinnedMeshInverseBindposes},
         GpuBufferInfo, Mesh, MeshVertexBufferLayout,
     },
     render_asset::RenderAssets,
-    render_phase::{EntityRenderCommand, RenderCommandResult, TrackedRenderPass},
+    render_phase::{
+        sort_phase_system, BatchedPhaseItem, CachedRenderPipelinePhaseItem, DrawFunctionId,
+        EntityRenderCommand, RenderCommand, RenderCommandResult, RenderPhase, TrackedRenderPass,
+    },
     render_resource::*,
     renderer::{RenderDevice, RenderQueue, RenderTextureFormat},
     texture::{DefaultImageSampler, GpuImage, Image, ImageSampler, TextureFormatPixelInfo},
-    view::{ComputedVisibility, ViewUniform, ViewUniformOffset, ViewUniforms},
+    view::{
+        ComputedVisibility, ExtractedView, ViewTarget, ViewUniform, ViewUniformOffset, ViewUniforms,
+    },
     Extract, RenderApp, RenderStage,
 };
 use bevy_transform::components::GlobalTransform;
@@ -89,17 +103,28 @@ impl Plugin for MeshRenderPlugin {
         load_internal_asset!(app, MESH_SHADER_HANDLE, "mesh.wgsl", Shader::from_wgsl);
         load_internal_asset!(app, SKINNING_HANDLE, "skinning.wgsl", Shader::from_wgsl);
 
-        app.add_plugin(UniformComponentPlugin::<MeshUniform>::default());
+        app.register_type::<NoAutomaticBatching>()
+            .add_plugin(UniformComponentPlugin::<MeshUniform>::default())
+            .add_plugin(ExtractComponentPlugin::<NoAutomaticBatching>::extract_visible());
 
         if let Ok(render_app) = app.get_sub_app_mut(RenderApp) {
             render_app
                 .init_resource::<MeshPipeline>()
                 .init_resource::<SkinnedMeshUniform>()
+                .init_resource::<MeshInstances>()
                 .add_system_to_stage(RenderStage::Extract, extract_meshes)
                 .add_system_to_stage(RenderStage::Extract, extract_skinned_meshes)
                 .add_system_to_stage(RenderStage::Prepare, prepare_skinned_meshes)
                 .add_system_to_stage(RenderStage::Queue, queue_mesh_bind_group)
-                .add_system_to_stage(RenderStage::Queue, queue_mesh_view_bind_groups);
+                .add_system_to_stage(RenderStage::Queue, queue_mesh_view_bind_groups)
+                .add_system_to_stage(
+                    RenderStage::PhaseSort,
+                    batch_mesh_instances
+                        .after(sort_phase_system::<Opaque3d>)
+                        .after(sort_phase_system::<AlphaMask3d>)
+                        .after(sort_phase_system::<Transparent3d>)
+                        .after(sort_phase_system::<Shadow>),
+                );
         }
     }
 }
@@ -111,6 +136,26 @@ pub struct MeshUniform {
     pub flags: u32,
 }
 
+/// Add this component to a mesh entity to always draw it on its own, instead of in a batch of
+/// instances with the other entities sharing its mesh and material.
+///
+/// Visible entities drawing the same mesh with the same material and pipeline are otherwise
+/// drawn together, with one instanced draw call reading their [`MeshUniform`]s from a storage
+/// buffer. Skinned meshes, materials without [`Material::automatic_batching`](crate::Material::automatic_batching)
+/// and platforms without storage buffers are never batched.
+#[derive(Component, Reflect, Clone, Copy, Default)]
+#[reflect(Component, Default)]
+pub struct NoAutomaticBatching;
+
+impl ExtractComponent for NoAutomaticBatching {
+    type Query = &'static Self;
+    type Filter = ();
+
+    fn extract_component(item: QueryItem<Self::Query>) -> Self {
+        *item
+    }
+}
+
 // NOTE: These must match the bit flags in bevy_pbr/src/render/mesh_types.wgsl!
 bitflags::bitflags! {
     #[repr(transparent)]
@@ -257,6 +302,9 @@ pub struct MeshPipeline {
     pub view_layout: BindGroupLayout,
     pub mesh_layout: BindGroupLayout,
     pub skinned_mesh_layout: BindGroupLayout,
+    /// The layout binding the [`MeshInstances`] of batched meshes, or `None` if storage buffers
+    /// are not supported, in which case meshes are never batched.
+    pub instanced_mesh_layout: Option<BindGroupLayout>,
     // This dummy white texture is to be used in place of optional StandardMaterial textures
     pub dummy_white_gpu_image: GpuImage,
     pub clustered_forward_buffer_binding_type: BufferBindingType,
@@ -432,6 +480,23 @@ impl FromWorld for MeshPipeline {
                 label: Some("skinned_mesh_layout"),
             });
 
+        let instanced_mesh_layout =
+            (render_device.limits().max_storage_buffers_per_shader_stage > 0).then(|| {
+                render_device.create_bind_group_layout(&BindGroupLayoutDescriptor {
+                    entries: &[BindGroupLayoutEntry {
+                        binding: 0,
+                        visibility: ShaderStages::VERTEX | ShaderStages::FRAGMENT,
+                        ty: BindingType::Buffer {
+                            ty: BufferBindingType::Storage { read_only: true },
+                            has_dynamic_offset: false,
+                            min_binding_size: Some(GpuMeshInstances::min_size()),
+                        },
+                        count: None,
+                    }],
+                    label: Some("instanced_mesh_layout"),
+                })
+            });
+
         // A 1x1x1 'all 1.0' texture to use as a dummy texture to use in place of optional StandardMaterial textures
         let dummy_white_gpu_image = {
             let image = Image::new_fill(
@@ -485,6 +550,7 @@ impl FromWorld for MeshPipeline {
             view_layout,
             mesh_layout,
             skinned_mesh_layout,
+            instanced_mesh_layout,
             clustered_forward_buffer_binding_type,
             dummy_white_gpu_image,
         }
@@ -507,6 +573,16 @@ impl MeshPipeline {
             ))
         }
     }
+
+    /// Returns `true` if the meshes with the given vertex buffer `layout` can be drawn in
+    /// batches of instances, which excludes skinned meshes.
+    pub fn supports_instancing(&self, layout: &MeshVertexBufferLayout) -> bool {
+        self.instanced_mesh_layout.is_some() && !is_skinned(layout)
+    }
+}
+
+pub(crate) fn is_skinned(layout: &MeshVertexBufferLayout) -> bool {
+    layout.contains(Mesh::ATTRIBUTE_JOINT_INDEX) && layout.contains(Mesh::ATTRIBUTE_JOINT_WEIGHT)
 }
 
 bitflags::bitflags! {
@@ -516,8 +592,18 @@ bitflags::bitflags! {
     pub struct MeshPipelineKey: u32 {
         const NONE                        = 0;
         const TRANSPARENT_MAIN_PASS       = (1 << 0);
+        const HDR                         = (1 << 1);
+        const TONEMAP_IN_SHADER           = (1 << 2);
+        const DEBAND_DITHER               = (1 << 3);
+        /// Set with `TRANSPARENT_MAIN_PASS` for the views with weighted blended order-independent
+        /// transparency, which accumulate the transparent meshes in the `ViewOitTextures`.
+        const WEIGHTED_BLENDED_OIT        = (1 << 4);
+        /// Draws the mesh in batches of instances, reading their `Mesh` from the
+        /// [`MeshInstances`] with the `MESH_INSTANCING` shader def.
+        const MESH_INSTANCING             = (1 << 5);
         const MSAA_RESERVED_BITS          = Self::MSAA_MASK_BITS << Self::MSAA_SHIFT_BITS;
         const PRIMITIVE_TOPOLOGY_RESERVED_BITS = Self::PRIMITIVE_TOPOLOGY_MASK_BITS << Self::PRIMITIVE_TOPOLOGY_SHIFT_BITS;
+        const TONEMAP_METHOD_RESERVED_BITS = Self::TONEMAP_METHOD_MASK_BITS << Self::TONEMAP_METHOD_SHIFT_BITS;
     }
 }
 
@@ -526,6 +612,8 @@ impl MeshPipelineKey {
     const MSAA_SHIFT_BITS: u32 = 32 - Self::MSAA_MASK_BITS.count_ones();
     const PRIMITIVE_TOPOLOGY_MASK_BITS: u32 = 0b111;
     const PRIMITIVE_TOPOLOGY_SHIFT_BITS: u32 = Self::MSAA_SHIFT_BITS - 3;
+    const TONEMAP_METHOD_MASK_BITS: u32 = 0b111;
+    const TONEMAP_METHOD_SHIFT_BITS: u32 = Self::PRIMITIVE_TOPOLOGY_SHIFT_BITS - 3;
 
     pub fn from_msaa_samples(msaa_samples: u32) -> Self {
         let msaa_bits =
@@ -556,6 +644,57 @@ impl MeshPipelineKey {
             _ => PrimitiveTopology::default(),
         }
     }
+
+    pub fn from_hdr(hdr: bool) -> Self {
+        if hdr {
+            MeshPipelineKey::HDR
+        } else {
+            MeshPipelineKey::NONE
+        }
+    }
+
+    pub fn from_tonemapping(tonemapping: Tonemapping) -> Self {
+        let tonemapping_bits = ((tonemapping as u32) & Self::TONEMAP_METHOD_MASK_BITS)
+            << Self::TONEMAP_METHOD_SHIFT_BITS;
+        Self::from_bits(tonemapping_bits).unwrap()
+    }
+
+    pub fn tonemapping(&self) -> Tonemapping {
+        let tonemapping_bits =
+            (self.bits >> Self::TONEMAP_METHOD_SHIFT_BITS) & Self::TONEMAP_METHOD_MASK_BITS;
+        match tonemapping_bits {
+            x if x == Tonemapping::Reinhard as u32 => Tonemapping::Reinhard,
+            x if x == Tonemapping::ReinhardLuminance as u32 => Tonemapping::ReinhardLuminance,
+            x if x == Tonemapping::AcesFitted as u32 => Tonemapping::AcesFitted,
+            x if x == Tonemapping::AgX as u32 => Tonemapping::AgX,
+            _ => Tonemapping::None,
+        }
+    }
+
+    /// The key of the target and of the tonemapping of a view. "High dynamic range" views are
+    /// tonemapped by a separate pass, other views are tonemapped and dithered in the shader.
+    ///
+    /// A view without a [`Tonemapping`] uses the default operator, and a view without a
+    /// [`DebandDither`] is not dithered.
+    pub fn from_view(
+        view: &ExtractedView,
+        tonemapping: Option<&Tonemapping>,
+        deband_dither: Option<&DebandDither>,
+    ) -> Self {
+        if view.hdr {
+            return MeshPipelineKey::HDR;
+        }
+
+        let mut key = MeshPipelineKey::NONE;
+        let tonemapping = tonemapping.copied().unwrap_or_default();
+        if tonemapping.is_enabled() {
+            key |= MeshPipelineKey::TONEMAP_IN_SHADER | Self::from_tonemapping(tonemapping);
+        }
+        if deband_dither == Some(&DebandDither::Enabled) {
+            key |= MeshPipelineKey::DEBAND_DITHER;
+        }
+        key
+    }
 }
 
 impl SpecializedMeshPipeline for MeshPipeline {
@@ -570,54 +709,91 @@ impl SpecializedMeshPipeline for MeshPipeline {
         let mut vertex_attributes = Vec::new();
 
         if layout.contains(Mesh::ATTRIBUTE_POSITION) {
-            shader_defs.push(String::from("VERTEX_POSITIONS"));
+            shader_defs.push("VERTEX_POSITIONS".into());
             vertex_attributes.push(Mesh::ATTRIBUTE_POSITION.at_shader_location(0));
         }
 
         if layout.contains(Mesh::ATTRIBUTE_NORMAL) {
-            shader_defs.push(String::from("VERTEX_NORMALS"));
+            shader_defs.push("VERTEX_NORMALS".into());
             vertex_attributes.push(Mesh::ATTRIBUTE_NORMAL.at_shader_location(1));
         }
 
         if layout.contains(Mesh::ATTRIBUTE_UV_0) {
-            shader_defs.push(String::from("VERTEX_UVS"));
+            shader_defs.push("VERTEX_UVS".into());
             vertex_attributes.push(Mesh::ATTRIBUTE_UV_0.at_shader_location(2));
         }
 
         if layout.contains(Mesh::ATTRIBUTE_TANGENT) {
-            shader_defs.push(String::from("VERTEX_TANGENTS"));
+            shader_defs.push("VERTEX_TANGENTS".into());
             vertex_attributes.push(Mesh::ATTRIBUTE_TANGENT.at_shader_location(3));
         }
 
         if layout.contains(Mesh::ATTRIBUTE_COLOR) {
-            shader_defs.push(String::from("VERTEX_COLORS"));
+            shader_defs.push("VERTEX_COLORS".into());
             vertex_attributes.push(Mesh::ATTRIBUTE_COLOR.at_shader_location(4));
         }
 
         let mut bind_group_layout = vec![self.view_layout.clone()];
-        if layout.contains(Mesh::ATTRIBUTE_JOINT_INDEX)
-            && layout.contains(Mesh::ATTRIBUTE_JOINT_WEIGHT)
-        {
-            shader_defs.push(String::from("SKINNED"));
+        if is_skinned(layout) {
+            shader_defs.push("SKINNED".into());
             vertex_attributes.push(Mesh::ATTRIBUTE_JOINT_INDEX.at_shader_location(5));
             vertex_attributes.push(Mesh::ATTRIBUTE_JOINT_WEIGHT.at_shader_location(6));
             bind_group_layout.push(self.skinned_mesh_layout.clone());
+        } else if key.contains(MeshPipelineKey::MESH_INSTANCING) {
+            shader_defs.push("MESH_INSTANCING".into());
+            bind_group_layout.push(
+                self.instanced_mesh_layout
+                    .clone()
+                    .expect("Mesh instancing requires storage buffers"),
+            );
         } else {
             bind_group_layout.push(self.mesh_layout.clone());
         };
 
+        if key.contains(MeshPipelineKey::TONEMAP_IN_SHADER) {
+            shader_defs.push("TONEMAP_IN_SHADER".into());
+            if let Some(shader_def) = key.tonemapping().shader_def() {
+                shader_defs.push(shader_def.into());
+            }
+        }
+        if key.contains(MeshPipelineKey::DEBAND_DITHER) {
+            shader_defs.push("DEBAND_DITHER".into());
+        }
+
         let vertex_buffer_layout = layout.get_layout(&vertex_attributes)?;
 
-        let (label, blend, depth_write_enabled);
-        if key.contains(MeshPipelineKey::TRANSPARENT_MAIN_PASS) {
+        let format = if key.contains(MeshPipelineKey::HDR) {
+            ViewTarget::TEXTURE_FORMAT_HDR
+        } else {
+            self.dummy_white_gpu_image.texture_format
+        };
+        let (label, targets, depth_write_enabled);
+        if key.contains(
+            MeshPipelineKey::TRANSPARENT_MAIN_PASS | MeshPipelineKey::WEIGHTED_BLENDED_OIT,
+        ) {
+            shader_defs.push("WEIGHTED_BLENDED_OIT".into());
+            label = "oit_mesh_pipeline".into();
+            targets = oit_color_target_states();
+            // The transparent meshes are accumulated in any order, and their depth is not
+            // written to the depth buffer
+            depth_write_enabled = false;
+        } else if key.contains(MeshPipelineKey::TRANSPARENT_MAIN_PASS) {
             label = "transparent_mesh_pipeline".into();
-            blend = Some(BlendState::ALPHA_BLENDING);
+            targets = vec![Some(ColorTargetState {
+                format,
+                blend: Some(BlendState::ALPHA_BLENDING),
+                write_mask: ColorWrites::ALL,
+            })];
             // For the transparent pass, fragments that are closer will be alpha blended
             // but their depth is not written to the depth buffer
             depth_write_enabled = false;
         } else {
             label = "opaque_mesh_pipeline".into();
-            blend = Some(BlendState::REPLACE);
+            targets = vec![Some(ColorTargetState {
+                format,
+                blend: Some(BlendState::REPLACE),
+                write_mask: ColorWrites::ALL,
+            })];
             // For the opaque and alpha mask passes, fragments that are closer will replace
             // the current fragment value in the output and the depth is written to the
             // depth buffer
@@ -635,11 +811,7 @@ impl SpecializedMeshPipeline for MeshPipeline {
                 shader: MESH_SHADER_HANDLE.typed::<Shader>(),
                 shader_defs,
                 entry_point: "fragment".into(),
-                targets: vec![Some(ColorTargetState {
-                    format: self.dummy_white_gpu_image.texture_format,
-                    blend,
-                    write_mask: ColorWrites::ALL,
-                })],
+                targets,
             }),
             layout: Some(bind_group_layout),
             primitive: PrimitiveState {
@@ -768,6 +940,164 @@ pub fn prepare_skinned_meshes(
         .write_buffer(&render_device, &render_queue);
 }
 
+#[derive(ShaderType, Default)]
+pub struct GpuMeshInstances {
+    #[size(runtime)]
+    pub data: Vec<MeshUniform>,
+}
+
+/// The [`MeshUniform`]s of the batches of instances drawn this frame, bound as a storage buffer
+/// by [`SetMeshBindGroup`] for the phase items with a `batch_range`.
+#[derive(Resource, Default)]
+pub struct MeshInstances {
+    buffer: StorageBuffer<GpuMeshInstances>,
+    bind_group: Option<BindGroup>,
+}
+
+impl MeshInstances {
+    /// Returns the bind group of the instances, if any instance is drawn this frame.
+    pub fn bind_group(&self) -> Option<&BindGroup> {
+        self.bind_group.as_ref()
+    }
+}
+
+/// Merges the consecutive phase items drawing the same mesh with the same material, pipeline and
+/// draw function into batches of instances, and writes their [`MeshUniform`]s to the
+/// [`MeshInstances`].
+///
+/// Only the items queued with a `batch_range` are batched, the range of every batch is set to its
+/// instances in the [`MeshInstances`]. The items of the phases whose draw order doesn't change
+/// the output (opaque, alpha mask and shadow) are sorted by their batch key first, so that the
+/// items sharing a mesh and a material are drawn as one batch wherever they are in the view.
+pub fn batch_mesh_instances(
+    render_device: Res<RenderDevice>,
+    render_queue: Res<RenderQueue>,
+    mesh_pipeline: Res<MeshPipeline>,
+    mut mesh_instances: ResMut<MeshInstances>,
+    meshes: Query<(&MeshUniform, &Handle<Mesh>, Option<&MeshMaterialId>)>,
+    mut views: Query<(
+        &mut RenderPhase<Opaque3d>,
+        &mut RenderPhase<AlphaMask3d>,
+        &mut RenderPhase<Transparent3d>,
+    )>,
+    mut shadow_views: Query<&mut RenderPhase<Shadow>>,
+) {
+    let instanced_mesh_layout = match &mesh_pipeline.instanced_mesh_layout {
+        Some(layout) => layout,
+        None => return,
+    };
+
+    let mesh_instances = &mut *mesh_instances;
+    let instances = &mut mesh_instances.buffer.get_mut().data;
+    instances.clear();
+    for (mut opaque_phase, mut alpha_mask_phase, mut transparent_phase) in &mut views {
+        batch_phase_instances(&mut opaque_phase, &meshes, instances, true);
+        batch_phase_instances(&mut alpha_mask_phase, &meshes, instances, true);
+        // the transparent items must be drawn back to front
+        batch_phase_instances(&mut transparent_phase, &meshes, instances, false);
+    }
+    for mut shadow_phase in &mut shadow_views {
+        batch_phase_instances(&mut shadow_phase, &meshes, instances, true);
+    }
+
+    if instances.is_empty() {
+        mesh_instances.bind_group = None;
+        return;
+    }
+    mesh_instances
+        .buffer
+        .write_buffer(&render_device, &render_queue);
+    mesh_instances.bind_group = Some(render_device.create_bind_group(&BindGroupDescriptor {
+        entries: &[BindGroupEntry {
+            binding: 0,
+            resource: mesh_instances.buffer.binding().unwrap(),
+        }],
+        label: Some("instanced_mesh_bind_group"),
+        layout: instanced_mesh_layout,
+    }));
+}
+
+/// The items drawing the same mesh with the same material, pipeline and draw function in a row are
+/// drawn as one batch of instances.
+type MeshBatchKey = (
+    CachedRenderPipelineId,
+    DrawFunctionId,
+    HandleId,
+    Option<MeshMaterialId>,
+);
+
+fn batch_phase_instances<P: BatchedPhaseItem + CachedRenderPipelinePhaseItem>(
+    phase: &mut RenderPhase<P>,
+    meshes: &Query<(&MeshUniform, &Handle<Mesh>, Option<&MeshMaterialId>)>,
+    instances: &mut Vec<MeshUniform>,
+    sort_by_batch_key: bool,
+) {
+    let mut items = phase
+        .items
+        .drain(..)
+        .map(|item| {
+            // the items queued without a `batch_range` use `NoAutomaticBatching`, and the items
+            // whose mesh can't be found are drawn on their own
+            let batch: Option<(MeshBatchKey, MeshUniform)> =
+                match (item.batch_range(), meshes.get(item.entity())) {
+                    (Some(_), Ok((mesh_uniform, mesh_handle, material_id))) => Some((
+                        (
+                            item.cached_pipeline(),
+                            item.draw_function(),
+                            mesh_handle.id(),
+                            material_id.copied(),
+                        ),
+                        mesh_uniform.clone(),
+                    )),
+                    _ => None,
+                };
+            (item, batch)
+        })
+        .collect::<Vec<_>>();
+    if sort_by_batch_key {
+        // stable, so that the items of a batch and the unbatched items keep their sorted order
+        items.sort_by_key(|(_, batch)| batch.as_ref().map(|(key, _)| *key));
+    }
+    phase.items = batch_items(items, instances);
+}
+
+/// Merges the consecutive items with equal batch keys into one item per batch, and pushes the
+/// instances of every batch to `instances`. The range of every batch is set to its instances, and
+/// the range of the items without a batch key is cleared.
+fn batch_items<P: BatchedPhaseItem, K: PartialEq, I>(
+    items: impl IntoIterator<Item = (P, Option<(K, I)>)>,
+    instances: &mut Vec<I>,
+) -> Vec<P> {
+    let mut batched_items: Vec<P> = Vec::new();
+    let mut last_batch_key = None;
+    for (mut item, batch) in items {
+        let (batch_key, instance) = match batch {
+            Some(batch) => batch,
+            None => {
+                *item.batch_range_mut() = None;
+                last_batch_key = None;
+                batched_items.push(item);
+                continue;
+            }
+        };
+
+        let index = instances.len() as u32;
+        instances.push(instance);
+        match batched_items.last_mut() {
+            // the instances of the last batch end at `index`, as instances are pushed in order
+            Some(batch) if last_batch_key.as_ref() == Some(&batch_key) => {
+                batch.batch_range_mut().as_mut().unwrap().end = index + 1;
+            }
+            _ => {
+                *item.batch_range_mut() = Some(index..index + 1);
+                batched_items.push(item);
+            }
+        }
+        last_batch_key = Some(batch_key);
+    }
+    batched_items
+}
+
 #[derive(Component)]
 pub struct MeshViewBindGroup {
     pub value: BindGroup,
@@ -877,10 +1207,17 @@ impl<const I: usize> EntityRenderCommand for SetMeshViewBindGroup<I> {
     }
 }
 
+/// Sets the bind group of the [`MeshUniform`] of the item at the `I` index, or the bind group of
+/// the [`MeshInstances`] if the item is drawn as a batch of instances.
+///
+/// This is a [`RenderCommand`] of the [`BatchedPhaseItem`]s rather than an
+/// [`EntityRenderCommand`]. The custom phase items drawn with it must implement
+/// [`BatchedPhaseItem`], and return a `batch_range` of `None` to be drawn on their own.
 pub struct SetMeshBindGroup<const I: usize>;
-impl<const I: usize> EntityRenderCommand for SetMeshBindGroup<I> {
+impl<P: BatchedPhaseItem, const I: usize> RenderCommand<P> for SetMeshBindGroup<I> {
     type Param = (
         SRes<MeshBindGroup>,
+        SRes<MeshInstances>,
         SQuery<(
             Read<DynamicUniformIndex<MeshUniform>>,
             Option<Read<SkinnedMeshJoints>>,
@@ -889,11 +1226,24 @@ impl<const I: usize> EntityRenderCommand for SetMeshBindGroup<I> {
     #[inline]
     fn render<'w>(
         _view: Entity,
-        item: Entity,
-        (mesh_bind_group, mesh_query): SystemParamItem<'w, '_, Self::Param>,
+        item: &P,
+        (mesh_bind_group, mesh_instances, mesh_query): SystemParamItem<'w, '_, Self::Param>,
         pass: &mut TrackedRenderPass<'w>,
     ) -> RenderCommandResult {
-        let (mesh_index, skinned_mesh_joints) = mesh_query.get(item).unwrap();
+        if item.batch_range().is_some() {
+            return match mesh_instances.into_inner().bind_group() {
+                Some(bind_group) => {
+                    pass.set_bind_group(I, bind_group, &[]);
+                    RenderCommandResult::Success
+                }
+                None => RenderCommandResult::Failure,
+            };
+        }
+
+        let (mesh_index, skinned_mesh_joints) = match mesh_query.get(item.entity()) {
+            Ok(mesh) => mesh,
+            Err(_) => return RenderCommandResult::Failure,
+        };
         if let Some(joints) = skinned_mesh_joints {
             pass.set_bind_group(
                 I,
@@ -911,17 +1261,25 @@ impl<const I: usize> EntityRenderCommand for SetMeshBindGroup<I> {
     }
 }
 
+/// Draws the mesh of the item, or all of its instances if it is drawn as a batch of instances.
+///
+/// Like [`SetMeshBindGroup`], this is a [`RenderCommand`] of the [`BatchedPhaseItem`]s rather
+/// than an [`EntityRenderCommand`].
 pub struct DrawMesh;
-impl EntityRenderCommand for DrawMesh {
+impl<P: BatchedPhaseItem> RenderCommand<P> for DrawMesh {
     type Param = (SRes<RenderAssets<Mesh>>, SQuery<Read<Handle<Mesh>>>);
     #[inline]
     fn render<'w>(
         _view: Entity,
-        item: Entity,
+        item: &P,
         (meshes, mesh_query): SystemParamItem<'w, '_, Self::Param>,
         pass: &mut TrackedRenderPass<'w>,
     ) -> RenderCommandResult {
-        let mesh_handle = mesh_query.get(item).unwrap();
+        let mesh_handle = match mesh_query.get(item.entity()) {
+            Ok(mesh_handle) => mesh_handle,
+            Err(_) => return RenderCommandResult::Failure,
+        };
+        let instances = item.batch_range().clone().unwrap_or(0..1);
         if let Some(gpu_mesh) = meshes.into_inner().get(mesh_handle) {
             pass.set_vertex_buffer(0, gpu_mesh.vertex_buffer.slice(..));
             match &gpu_mesh.buffer_info {
@@ -931,10 +1289,10 @@ impl EntityRenderCommand for DrawMesh {
                     count,
                 } => {
                     pass.set_index_buffer(buffer.slice(..), 0, *index_format);
-                    pass.draw_indexed(0..*count, 0, 0..1);
+                    pass.draw_indexed(0..*count, 0, instances);
                 }
                 GpuBufferInfo::NonIndexed { vertex_count } => {
-                    pass.draw(0..*vertex_count, 0..1);
+                    pass.draw(0..*vertex_count, instances);
                 }
             }
             RenderCommandResult::Success
@@ -946,11 +1304,137 @@ impl EntityRenderCommand for DrawMesh {
 
 #[cfg(test)]
 mod tests {
-    use super::MeshPipelineKey;
+    use super::{batch_items, MeshPipelineKey};
+    use bevy_core_pipeline::tonemapping::Tonemapping;
+    use bevy_ecs::{entity::Entity, world::World};
+    use bevy_render::render_phase::{
+        BatchedPhaseItem, Draw, DrawFunctionId, DrawFunctions, EntityPhaseItem, PhaseItem,
+        TrackedRenderPass,
+    };
+    use std::ops::Range;
+
+    #[derive(Debug, PartialEq)]
+    struct TestPhaseItem {
+        entity: Entity,
+        draw_function: DrawFunctionId,
+        batch_range: Option<Range<u32>>,
+    }
+
+    struct DrawNothing;
+    impl Draw<TestPhaseItem> for DrawNothing {
+        fn draw<'w>(
+            &mut self,
+            _world: &'w World,
+            _pass: &mut TrackedRenderPass<'w>,
+            _view: Entity,
+            _item: &TestPhaseItem,
+        ) {
+        }
+    }
+
+    impl PhaseItem for TestPhaseItem {
+        type SortKey = ();
+
+        fn sort_key(&self) -> Self::SortKey {}
+
+        fn draw_function(&self) -> DrawFunctionId {
+            self.draw_function
+        }
+    }
+
+    impl EntityPhaseItem for TestPhaseItem {
+        fn entity(&self) -> Entity {
+            self.entity
+        }
+    }
+
+    impl BatchedPhaseItem for TestPhaseItem {
+        fn batch_range(&self) -> &Option<Range<u32>> {
+            &self.batch_range
+        }
+
+        fn batch_range_mut(&mut self) -> &mut Option<Range<u32>> {
+            &mut self.batch_range
+        }
+    }
+
+    fn item(index: u32, batch_range: Option<Range<u32>>) -> TestPhaseItem {
+        let draw_functions = DrawFunctions::<TestPhaseItem>::default();
+        let draw_function = draw_functions.write().add(DrawNothing);
+        TestPhaseItem {
+            entity: Entity::from_raw(index),
+            draw_function,
+            batch_range,
+        }
+    }
+
+    #[test]
+    fn batches_split_on_key_changes() {
+        let items = vec![
+            (item(0, Some(0..0)), Some(("a", 0))),
+            (item(1, Some(0..0)), Some(("a", 1))),
+            (item(2, Some(0..0)), Some(("b", 2))),
+            (item(3, Some(0..0)), Some(("a", 3))),
+            (item(4, Some(0..0)), Some(("a", 4))),
+        ];
+        let mut instances = vec![7];
+        let batched_items = batch_items(items, &mut instances);
+        assert_eq!(
+            batched_items,
+            vec![
+                item(0, Some(1..3)),
+                item(2, Some(3..4)),
+                item(3, Some(4..6))
+            ]
+        );
+        assert_eq!(instances, vec![7, 0, 1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn unbatched_items_are_drawn_on_their_own() {
+        // items 1 and 2 use `NoAutomaticBatching`, the mesh of item 3 can't be found
+        let items = vec![
+            (item(0, Some(0..0)), Some(("a", 0))),
+            (item(1, None), None),
+            (item(2, None), None),
+            (item(3, Some(0..0)), None),
+            (item(4, Some(0..0)), Some(("a", 4))),
+            (item(5, Some(0..0)), Some(("a", 5))),
+        ];
+        let mut instances = Vec::new();
+        let batched_items = batch_items(items, &mut instances);
+        assert_eq!(
+            batched_items,
+            vec![
+                item(0, Some(0..1)),
+                item(1, None),
+                item(2, None),
+                item(3, None),
+                item(4, Some(1..3)),
+            ]
+        );
+        assert_eq!(instances, vec![0, 4, 5]);
+    }
     #[test]
     fn mesh_key_msaa_samples() {
         for i in [1, 2, 4, 8, 16, 32, 64, 128] {
             assert_eq!(MeshPipelineKey::from_msaa_samples(i).msaa_samples(), i);
         }
     }
+
+    #[test]
+    fn mesh_key_tonemapping() {
+        for tonemapping in [
+            Tonemapping::None,
+            Tonemapping::Reinhard,
+            Tonemapping::ReinhardLuminance,
+            Tonemapping::AcesFitted,
+            Tonemapping::AgX,
+        ] {
+            let key = MeshPipelineKey::from_msaa_samples(4)
+                | MeshPipelineKey::from_tonemapping(tonemapping);
+            assert_eq!(key.tonemapping(), tonemapping);
+            assert_eq!(key.msaa_samples(), 4);
+        }
+    }
 }
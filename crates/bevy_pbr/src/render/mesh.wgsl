@@ -4,6 +4,10 @@
 // NOTE: Bindings must come before functions that use them!
 #import bevy_pbr::mesh_functions
 
+#ifdef WEIGHTED_BLENDED_OIT
+#import bevy_core_pipeline::oit
+#endif
+
 struct Vertex {
 #ifdef VERTEX_POSITIONS
     @location(0) position: vec3<f32>,
@@ -24,6 +28,9 @@ struct Vertex {
     @location(5) joint_indices: vec4<u32>,
     @location(6) joint_weights: vec4<f32>,
 #endif
+#ifdef MESH_INSTANCING
+    @builtin(instance_index) instance_index: u32,
+#endif
 };
 
 struct VertexOutput {
@@ -35,6 +42,11 @@ struct VertexOutput {
 fn vertex(vertex: Vertex) -> VertexOutput {
     var out: VertexOutput;
 
+#ifdef MESH_INSTANCING
+    mesh = meshes.data[vertex.instance_index];
+    out.instance_index = vertex.instance_index;
+#endif
+
 #ifdef VERTEX_NORMALS
 #ifdef SKINNED
     var model = skin_model(vertex.joint_indices, vertex.joint_weights);
@@ -67,14 +79,25 @@ fn vertex(vertex: Vertex) -> VertexOutput {
 
 struct FragmentInput {
     @builtin(front_facing) is_front: bool,
+    @builtin(position) frag_coord: vec4<f32>,
     #import bevy_pbr::mesh_vertex_output
 };
 
+#ifdef WEIGHTED_BLENDED_OIT
+@fragment
+fn fragment(in: FragmentInput) -> OitFragmentOutput {
+#else
 @fragment
 fn fragment(in: FragmentInput) -> @location(0) vec4<f32> {
+#endif
 #ifdef VERTEX_COLORS
-    return in.color;
+    let color = in.color;
+#else
+    let color = vec4<f32>(1.0, 0.0, 1.0, 1.0);
+#endif
+#ifdef WEIGHTED_BLENDED_OIT
+    return oit_fragment_output(color, in.frag_coord);
 #else
-    return vec4<f32>(1.0, 0.0, 1.0, 1.0);
+    return color;
 #endif
 }
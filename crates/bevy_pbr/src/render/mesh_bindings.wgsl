@@ -2,8 +2,15 @@
 
 #import bevy_pbr::mesh_types
 
+#ifdef MESH_INSTANCING
+@group(2) @binding(0)
+var<storage> meshes: Meshes;
+// Set to `meshes.data[instance_index]` by the vertex and fragment shaders
+var<private> mesh: Mesh;
+#else
 @group(2) @binding(0)
 var<uniform> mesh: Mesh;
+#endif
 #ifdef SKINNED
 @group(2) @binding(1)
 var<uniform> joint_matrices: SkinnedMesh;
@@ -7,6 +7,12 @@ struct Mesh {
     flags: u32,
 };
 
+#ifdef MESH_INSTANCING
+struct Meshes {
+    data: array<Mesh>,
+};
+#endif
+
 #ifdef SKINNED
 struct SkinnedMesh {
     data: array<mat4x4<f32>, 256u>,
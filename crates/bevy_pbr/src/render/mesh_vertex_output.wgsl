@@ -11,3 +11,6 @@
 #ifdef VERTEX_COLORS
 @location(4) color: vec4<f32>,
 #endif
+#ifdef MESH_INSTANCING
+@location(5) @interpolate(flat) instance_index: u32,
+#endif
@@ -8,14 +8,27 @@
 #import bevy_pbr::shadows
 #import bevy_pbr::pbr_functions
 
+#ifdef WEIGHTED_BLENDED_OIT
+#import bevy_core_pipeline::oit
+#endif
+
 struct FragmentInput {
     @builtin(front_facing) is_front: bool,
     @builtin(position) frag_coord: vec4<f32>,
     #import bevy_pbr::mesh_vertex_output
 };
 
+#ifdef WEIGHTED_BLENDED_OIT
+@fragment
+fn fragment(in: FragmentInput) -> OitFragmentOutput {
+#else
 @fragment
 fn fragment(in: FragmentInput) -> @location(0) vec4<f32> {
+#endif
+#ifdef MESH_INSTANCING
+    mesh = meshes.data[in.instance_index];
+#endif
+
     var output_color: vec4<f32> = material.base_color;
 #ifdef VERTEX_COLORS
     output_color = output_color * in.color;
@@ -88,10 +101,21 @@ fn fragment(in: FragmentInput) -> @location(0) vec4<f32> {
         );
         pbr_input.V = calculate_view(in.world_position, pbr_input.is_orthographic);
 
-        output_color = tone_mapping(pbr(pbr_input));
+        output_color = pbr(pbr_input);
+#ifdef TONEMAP_IN_SHADER
+        output_color = tone_mapping(output_color);
+#endif
     } else {
         output_color = alpha_discard(material, output_color);
     }
 
+#ifdef DEBAND_DITHER
+    output_color = deband_dither(output_color, in.frag_coord.xy);
+#endif
+
+#ifdef WEIGHTED_BLENDED_OIT
+    return oit_fragment_output(output_color, in.frag_coord);
+#else
     return output_color;
+#endif
 }
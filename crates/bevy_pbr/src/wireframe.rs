@@ -123,11 +123,12 @@ fn queue_wireframes(
     let msaa_key = MeshPipelineKey::from_msaa_samples(msaa.samples);
     for (view, visible_entities, mut opaque_phase) in &mut views {
         let rangefinder = view.rangefinder3d();
+        let view_key = msaa_key | MeshPipelineKey::from_hdr(view.hdr);
 
         let add_render_phase =
             |(entity, mesh_handle, mesh_uniform): (Entity, &Handle<Mesh>, &MeshUniform)| {
                 if let Some(mesh) = render_meshes.get(mesh_handle) {
-                    let key = msaa_key
+                    let key = view_key
                         | MeshPipelineKey::from_primitive_topology(mesh.primitive_topology);
                     let pipeline_id = pipelines.specialize(
                         &mut pipeline_cache,
@@ -147,6 +148,7 @@ fn queue_wireframes(
                         pipeline: pipeline_id,
                         draw_function: draw_custom,
                         distance: rangefinder.distance(&mesh_uniform.transform),
+                        batch_range: None,
                     });
                 }
             };
@@ -0,0 +1,77 @@
+//! Contains code related to custom attributes for reflected types.
+//!
+//! A custom attribute is an arbitrary expression, prefixed with `@`, inside the derive
+//! helper attribute for `Reflect`: `#[reflect(@0.0..=1.0, ...)]`. The resulting values
+//! are stored in the type's `TypeInfo` and can be retrieved by their type at runtime.
+
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::parse::{Parse, ParseStream};
+use syn::punctuated::Punctuated;
+use syn::token::Comma;
+use syn::{Expr, NestedMeta, Path, Token};
+
+/// A collection of custom attribute expressions for a type, field, or variant.
+#[derive(Default, Clone)]
+pub(crate) struct CustomAttributes {
+    attributes: Vec<Expr>,
+}
+
+impl CustomAttributes {
+    /// Adds the custom attributes of `other` to this collection.
+    pub fn extend(&mut self, other: CustomAttributes) {
+        self.attributes.extend(other.attributes);
+    }
+
+    /// Returns the `.with_custom_attributes(...)` builder call for the generated `TypeInfo`.
+    ///
+    /// If there are no custom attributes, returns `None`.
+    pub fn to_builder_call(&self, bevy_reflect_path: &Path) -> Option<TokenStream> {
+        if self.attributes.is_empty() {
+            return None;
+        }
+
+        let attributes = &self.attributes;
+        Some(quote! {
+            .with_custom_attributes(
+                #bevy_reflect_path::CustomAttributes::default()
+                    #(.with_attribute(#attributes))*
+            )
+        })
+    }
+}
+
+/// The arguments of a `#[reflect(...)]` attribute.
+///
+/// Custom attributes (`@expr`) are split from the regular metas
+/// (such as `ignore` or `PartialEq`), which are left to be parsed by the caller.
+pub(crate) struct ReflectAttributeArgs {
+    pub metas: Punctuated<NestedMeta, Comma>,
+    pub custom_attributes: CustomAttributes,
+}
+
+impl Parse for ReflectAttributeArgs {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut metas = Punctuated::new();
+        let mut custom_attributes = CustomAttributes::default();
+
+        while !input.is_empty() {
+            if input.peek(Token![@]) {
+                input.parse::<Token![@]>()?;
+                custom_attributes.attributes.push(input.parse()?);
+            } else {
+                metas.push(input.parse()?);
+            }
+
+            if input.is_empty() {
+                break;
+            }
+            input.parse::<Comma>()?;
+        }
+
+        Ok(Self {
+            metas,
+            custom_attributes,
+        })
+    }
+}
@@ -1,4 +1,5 @@
 use crate::container_attributes::ReflectTraits;
+use crate::custom_attributes::{CustomAttributes, ReflectAttributeArgs};
 use crate::field_attributes::{parse_field_attrs, ReflectFieldAttr};
 use crate::utility::members_to_serialization_denylist;
 use bit_set::BitSet;
@@ -39,6 +40,8 @@ pub(crate) struct ReflectMeta<'a> {
     generics: &'a Generics,
     /// A cached instance of the path to the `bevy_reflect` crate.
     bevy_reflect_path: Path,
+    /// Custom attributes created via `#[reflect(@...)]`.
+    custom_attributes: CustomAttributes,
     /// The documentation for this type, if any
     #[cfg(feature = "documentation")]
     docs: crate::documentation::Documentation,
@@ -101,7 +104,6 @@ pub(crate) struct EnumVariant<'a> {
     /// The fields within this variant.
     pub fields: EnumVariantFields<'a>,
     /// The reflection-based attributes on the variant.
-    #[allow(dead_code)]
     pub attrs: ReflectFieldAttr,
     /// The index of this variant within the enum.
     #[allow(dead_code)]
@@ -129,26 +131,37 @@ enum ReflectMode {
 impl<'a> ReflectDerive<'a> {
     pub fn from_input(input: &'a DeriveInput) -> Result<Self, syn::Error> {
         let mut traits = ReflectTraits::default();
+        let mut custom_attributes = CustomAttributes::default();
         // Should indicate whether `#[reflect_value]` was used
         let mut reflect_mode = None;
 
         #[cfg(feature = "documentation")]
         let mut doc = crate::documentation::Documentation::default();
 
-        for attribute in input.attrs.iter().filter_map(|attr| attr.parse_meta().ok()) {
-            match attribute {
-                Meta::List(meta_list) if meta_list.path.is_ident(REFLECT_ATTRIBUTE_NAME) => {
-                    if !matches!(reflect_mode, None | Some(ReflectMode::Normal)) {
-                        return Err(syn::Error::new(
-                            meta_list.span(),
-                            format_args!("cannot use both `#[{REFLECT_ATTRIBUTE_NAME}]` and `#[{REFLECT_VALUE_ATTRIBUTE_NAME}]`"),
-                        ));
-                    }
-
-                    reflect_mode = Some(ReflectMode::Normal);
-                    let new_traits = ReflectTraits::from_nested_metas(&meta_list.nested)?;
-                    traits = traits.merge(new_traits)?;
+        for attribute in &input.attrs {
+            // `#[reflect(...)]` may contain custom attributes, which can't be parsed as a `Meta`
+            if attribute.path.is_ident(REFLECT_ATTRIBUTE_NAME) && !attribute.tokens.is_empty() {
+                if !matches!(reflect_mode, None | Some(ReflectMode::Normal)) {
+                    return Err(syn::Error::new(
+                        attribute.span(),
+                        format_args!("cannot use both `#[{REFLECT_ATTRIBUTE_NAME}]` and `#[{REFLECT_VALUE_ATTRIBUTE_NAME}]`"),
+                    ));
                 }
+
+                reflect_mode = Some(ReflectMode::Normal);
+                let args = attribute.parse_args::<ReflectAttributeArgs>()?;
+                let new_traits = ReflectTraits::from_nested_metas(&args.metas)?;
+                traits = traits.merge(new_traits)?;
+                custom_attributes.extend(args.custom_attributes);
+                continue;
+            }
+
+            let attribute = match attribute.parse_meta() {
+                Ok(meta) => meta,
+                Err(_) => continue,
+            };
+
+            match attribute {
                 Meta::List(meta_list) if meta_list.path.is_ident(REFLECT_VALUE_ATTRIBUTE_NAME) => {
                     if !matches!(reflect_mode, None | Some(ReflectMode::Value)) {
                         return Err(syn::Error::new(
@@ -181,7 +194,8 @@ impl<'a> ReflectDerive<'a> {
             }
         }
 
-        let meta = ReflectMeta::new(&input.ident, &input.generics, traits);
+        let meta = ReflectMeta::new(&input.ident, &input.generics, traits)
+            .with_custom_attributes(custom_attributes);
 
         #[cfg(feature = "documentation")]
         let meta = meta.with_docs(doc);
@@ -284,11 +298,20 @@ impl<'a> ReflectMeta<'a> {
             type_name,
             generics,
             bevy_reflect_path: utility::get_bevy_reflect_path(),
+            custom_attributes: Default::default(),
             #[cfg(feature = "documentation")]
             docs: Default::default(),
         }
     }
 
+    /// Sets the custom attributes for this type.
+    pub fn with_custom_attributes(self, custom_attributes: CustomAttributes) -> Self {
+        Self {
+            custom_attributes,
+            ..self
+        }
+    }
+
     /// Sets the documentation for this type.
     #[cfg(feature = "documentation")]
     pub fn with_docs(self, docs: crate::documentation::Documentation) -> Self {
@@ -326,6 +349,11 @@ impl<'a> ReflectMeta<'a> {
         )
     }
 
+    /// The custom attributes of this type.
+    pub fn custom_attributes(&self) -> &CustomAttributes {
+        &self.custom_attributes
+    }
+
     /// The collection of docstrings for this type, if any.
     #[cfg(feature = "documentation")]
     pub fn doc(&self) -> &crate::documentation::Documentation {
@@ -4,6 +4,7 @@
 //! as opposed to an entire struct or enum. An example of such an attribute is
 //! the derive helper attribute for `Reflect`, which looks like: `#[reflect(ignore)]`.
 
+use crate::custom_attributes::{CustomAttributes, ReflectAttributeArgs};
 use crate::REFLECT_ATTRIBUTE_NAME;
 use quote::ToTokens;
 use syn::spanned::Spanned;
@@ -52,6 +53,8 @@ pub(crate) struct ReflectFieldAttr {
     pub ignore: ReflectIgnoreBehavior,
     /// Sets the default behavior of this field.
     pub default: DefaultBehavior,
+    /// Custom attributes created via `#[reflect(@...)]`.
+    pub custom_attributes: CustomAttributes,
 }
 
 /// Controls how the default value is determined for a field.
@@ -69,7 +72,7 @@ pub(crate) enum DefaultBehavior {
     Func(syn::ExprPath),
 }
 
-/// Parse all field attributes marked "reflect" (such as `#[reflect(ignore)]`).
+/// Parse all field attributes marked "reflect" (such as `#[reflect(ignore)]` or `#[reflect(@0.0..=1.0)]`).
 pub(crate) fn parse_field_attrs(attrs: &[Attribute]) -> Result<ReflectFieldAttr, syn::Error> {
     let mut args = ReflectFieldAttr::default();
     let mut errors: Option<syn::Error> = None;
@@ -78,12 +81,18 @@ pub(crate) fn parse_field_attrs(attrs: &[Attribute]) -> Result<ReflectFieldAttr,
         .iter()
         .filter(|a| a.path.is_ident(REFLECT_ATTRIBUTE_NAME));
     for attr in attrs {
-        let meta = attr.parse_meta()?;
-        if let Err(err) = parse_meta(&mut args, &meta) {
-            if let Some(ref mut error) = errors {
-                error.combine(err);
-            } else {
-                errors = Some(err);
+        let reflect_args = attr.parse_args::<ReflectAttributeArgs>()?;
+        args.custom_attributes
+            .extend(reflect_args.custom_attributes);
+        for nested in &reflect_args.metas {
+            if let NestedMeta::Meta(meta) = nested {
+                if let Err(err) = parse_meta(&mut args, meta) {
+                    if let Some(ref mut error) = errors {
+                        error.combine(err);
+                    } else {
+                        errors = Some(err);
+                    }
+                }
             }
         }
     }
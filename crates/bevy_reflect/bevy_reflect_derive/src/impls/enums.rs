@@ -56,19 +56,23 @@ pub(crate) fn impl_enum(reflect_enum: &ReflectEnum) -> TokenStream {
         });
 
     let string_name = enum_name.to_string();
+    let custom_attributes = reflect_enum
+        .meta()
+        .custom_attributes()
+        .to_builder_call(bevy_reflect_path);
 
     #[cfg(feature = "documentation")]
     let info_generator = {
         let doc = reflect_enum.meta().doc();
         quote! {
-            #bevy_reflect_path::EnumInfo::new::<Self>(#string_name, &variants).with_docs(#doc)
+            #bevy_reflect_path::EnumInfo::new::<Self>(#string_name, &variants) #custom_attributes.with_docs(#doc)
         }
     };
 
     #[cfg(not(feature = "documentation"))]
     let info_generator = {
         quote! {
-            #bevy_reflect_path::EnumInfo::new::<Self>(#string_name, &variants)
+            #bevy_reflect_path::EnumInfo::new::<Self>(#string_name, &variants) #custom_attributes
         }
     };
 
@@ -340,10 +344,14 @@ fn generate_impls(reflect_enum: &ReflectEnum, ref_index: &Ident, ref_name: &Iden
         }
 
         let mut push_variant =
-            |_variant: &EnumVariant, arguments: proc_macro2::TokenStream, field_len: usize| {
+            |variant: &EnumVariant, arguments: proc_macro2::TokenStream, field_len: usize| {
+                let custom_attributes = variant
+                    .attrs
+                    .custom_attributes
+                    .to_builder_call(bevy_reflect_path);
                 #[cfg(feature = "documentation")]
                 let with_docs = {
-                    let doc = quote::ToTokens::to_token_stream(&_variant.doc);
+                    let doc = quote::ToTokens::to_token_stream(&variant.doc);
                     Some(quote!(.with_docs(#doc)))
                 };
                 #[cfg(not(feature = "documentation"))]
@@ -352,6 +360,7 @@ fn generate_impls(reflect_enum: &ReflectEnum, ref_index: &Ident, ref_name: &Iden
                 variant_info.push(quote! {
                     #bevy_reflect_path::VariantInfo::#variant_type_ident(
                         #bevy_reflect_path::#variant_info_ident::new(#arguments)
+                        #custom_attributes
                         #with_docs
                     )
                 });
@@ -382,9 +391,15 @@ fn generate_impls(reflect_enum: &ReflectEnum, ref_index: &Ident, ref_name: &Iden
                     #[cfg(not(feature = "documentation"))]
                     let with_docs: Option<proc_macro2::TokenStream> = None;
 
+                    let custom_attributes = field
+                        .attrs
+                        .custom_attributes
+                        .to_builder_call(bevy_reflect_path);
+
                     let field_ty = &field.data.ty;
                     quote! {
                         #bevy_reflect_path::UnnamedField::new::<#field_ty>(#reflect_idx)
+                        #custom_attributes
                         #with_docs
                     }
                 });
@@ -417,9 +432,15 @@ fn generate_impls(reflect_enum: &ReflectEnum, ref_index: &Ident, ref_name: &Iden
                     #[cfg(not(feature = "documentation"))]
                     let with_docs: Option<proc_macro2::TokenStream> = None;
 
+                    let custom_attributes = field
+                        .attrs
+                        .custom_attributes
+                        .to_builder_call(bevy_reflect_path);
+
                     let field_ty = &field.data.ty;
                     quote! {
                         #bevy_reflect_path::NamedField::new::<#field_ty>(#field_name)
+                        #custom_attributes
                         #with_docs
                     }
                 });
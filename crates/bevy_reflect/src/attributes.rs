@@ -0,0 +1,251 @@
+use crate::Reflect;
+use bevy_utils::HashMap;
+use std::any::TypeId;
+use std::fmt::{Debug, Formatter};
+use std::sync::Arc;
+
+/// A collection of custom attributes for a type, field, or variant.
+///
+/// Custom attributes are arbitrary reflected values attached to a type's [`TypeInfo`],
+/// such as the valid range of a field or a tooltip to display in an editor.
+/// Each attribute is stored and retrieved by its type, so only one attribute of a given
+/// type may exist per collection.
+///
+/// Custom attributes can be added with the `Reflect` derive macro by prefixing
+/// any expression with `@` inside a `#[reflect(...)]` attribute:
+///
+/// ```
+/// # use std::ops::RangeInclusive;
+/// # use bevy_reflect::{Reflect, TypeInfo, Typed};
+/// #[derive(Reflect)]
+/// struct Slider {
+///     #[reflect(@0.0..=1.0_f32)]
+///     value: f32,
+/// }
+///
+/// if let TypeInfo::Struct(info) = Slider::type_info() {
+///     let field = info.field("value").unwrap();
+///     let range = field.get_attribute::<RangeInclusive<f32>>().unwrap();
+///     assert_eq!(&(0.0..=1.0), range);
+/// }
+/// ```
+///
+/// [`TypeInfo`]: crate::TypeInfo
+#[derive(Clone, Default)]
+pub struct CustomAttributes {
+    attributes: HashMap<TypeId, Arc<dyn Reflect>>,
+}
+
+impl CustomAttributes {
+    /// Adds an attribute to this collection, replacing any existing attribute of the same type.
+    #[must_use]
+    pub fn with_attribute<T: Reflect>(mut self, value: T) -> Self {
+        self.attributes.insert(TypeId::of::<T>(), Arc::new(value));
+        self
+    }
+
+    /// Returns `true` if this collection contains an attribute of type `T`.
+    pub fn contains<T: Reflect>(&self) -> bool {
+        self.attributes.contains_key(&TypeId::of::<T>())
+    }
+
+    /// Returns `true` if this collection contains an attribute with the given [`TypeId`].
+    pub fn contains_by_id(&self, id: TypeId) -> bool {
+        self.attributes.contains_key(&id)
+    }
+
+    /// Gets the attribute of type `T`, if any.
+    pub fn get<T: Reflect>(&self) -> Option<&T> {
+        self.attributes
+            .get(&TypeId::of::<T>())
+            .and_then(|value| value.as_any().downcast_ref())
+    }
+
+    /// Gets the attribute with the given [`TypeId`], if any.
+    pub fn get_by_id(&self, id: TypeId) -> Option<&dyn Reflect> {
+        self.attributes.get(&id).map(|value| &**value)
+    }
+
+    /// Returns an iterator over all attributes along with their [`TypeId`].
+    pub fn iter(&self) -> impl ExactSizeIterator<Item = (&TypeId, &dyn Reflect)> {
+        self.attributes.iter().map(|(id, value)| (id, &**value))
+    }
+
+    /// Returns the number of attributes in this collection.
+    pub fn len(&self) -> usize {
+        self.attributes.len()
+    }
+
+    /// Returns `true` if this collection contains no attributes.
+    pub fn is_empty(&self) -> bool {
+        self.attributes.is_empty()
+    }
+}
+
+impl Debug for CustomAttributes {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_set().entries(self.attributes.values()).finish()
+    }
+}
+
+/// Implements the custom attribute accessors on a container with a `custom_attributes` field.
+macro_rules! impl_custom_attribute_methods {
+    ($term:literal) => {
+        #[doc = concat!("Sets the custom attributes for this ", $term, ".")]
+        pub fn with_custom_attributes(self, custom_attributes: $crate::CustomAttributes) -> Self {
+            Self {
+                custom_attributes,
+                ..self
+            }
+        }
+
+        #[doc = concat!("The custom attributes of this ", $term, ".")]
+        pub fn custom_attributes(&self) -> &$crate::CustomAttributes {
+            &self.custom_attributes
+        }
+
+        #[doc = concat!("Gets the custom attribute of type `T` on this ", $term, ", if any.")]
+        pub fn get_attribute<T: $crate::Reflect>(&self) -> Option<&T> {
+            self.custom_attributes.get::<T>()
+        }
+
+        #[doc = concat!("Returns `true` if this ", $term, " has a custom attribute of type `T`.")]
+        pub fn has_attribute<T: $crate::Reflect>(&self) -> bool {
+            self.custom_attributes.contains::<T>()
+        }
+    };
+}
+
+pub(crate) use impl_custom_attribute_methods;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate as bevy_reflect;
+    use crate::{TypeInfo, Typed, VariantInfo};
+    use std::ops::RangeInclusive;
+
+    #[derive(Reflect, Debug, PartialEq)]
+    struct Tooltip(String);
+
+    impl Tooltip {
+        fn new(text: &str) -> Self {
+            Self(text.to_string())
+        }
+    }
+
+    #[test]
+    fn should_get_custom_attribute() {
+        let attributes = CustomAttributes::default().with_attribute(0.0..=1.0_f32);
+
+        let value = attributes.get::<RangeInclusive<f32>>().unwrap();
+        assert_eq!(&(0.0..=1.0), value);
+        assert!(attributes.contains::<RangeInclusive<f32>>());
+        assert!(!attributes.contains::<RangeInclusive<f64>>());
+        assert_eq!(1, attributes.len());
+    }
+
+    #[test]
+    fn should_derive_custom_attributes_on_struct() {
+        #[derive(Reflect)]
+        #[reflect(@Tooltip::new("A slider"))]
+        struct Slider {
+            #[reflect(@0.0..=1.0_f32, @Tooltip::new("The current value"))]
+            value: f32,
+            #[reflect(ignore)]
+            _cache: f32,
+        }
+
+        let info = match Slider::type_info() {
+            TypeInfo::Struct(info) => info,
+            _ => panic!("expected struct info"),
+        };
+
+        assert_eq!(
+            Some(&Tooltip::new("A slider")),
+            info.get_attribute::<Tooltip>()
+        );
+
+        let field = info.field("value").unwrap();
+        assert_eq!(
+            Some(&(0.0..=1.0)),
+            field.get_attribute::<RangeInclusive<f32>>()
+        );
+        assert_eq!(
+            Some(&Tooltip::new("The current value")),
+            field.get_attribute::<Tooltip>()
+        );
+        assert_eq!(2, field.custom_attributes().len());
+    }
+
+    #[test]
+    fn should_derive_custom_attributes_on_tuple_struct() {
+        #[derive(Reflect)]
+        #[reflect(@Tooltip::new("A percentage"))]
+        struct Percent(#[reflect(@0.0..=100.0_f32)] f32);
+
+        let info = match Percent::type_info() {
+            TypeInfo::TupleStruct(info) => info,
+            _ => panic!("expected tuple struct info"),
+        };
+
+        assert!(info.has_attribute::<Tooltip>());
+        assert_eq!(
+            Some(&(0.0..=100.0)),
+            info.field_at(0)
+                .unwrap()
+                .get_attribute::<RangeInclusive<f32>>()
+        );
+    }
+
+    #[test]
+    fn should_derive_custom_attributes_on_enum() {
+        #[derive(Reflect)]
+        #[reflect(@Tooltip::new("A color"))]
+        enum Color {
+            #[reflect(@Tooltip::new("Transparent"))]
+            Transparent,
+            Grayscale(#[reflect(@0.0..=1.0_f32)] f32),
+            Rgb {
+                #[reflect(@0_u8..=255)]
+                r: u8,
+                g: u8,
+                b: u8,
+            },
+        }
+
+        let info = match Color::type_info() {
+            TypeInfo::Enum(info) => info,
+            _ => panic!("expected enum info"),
+        };
+
+        assert!(info.has_attribute::<Tooltip>());
+
+        match info.variant("Transparent").unwrap() {
+            VariantInfo::Unit(variant) => assert_eq!(
+                Some(&Tooltip::new("Transparent")),
+                variant.get_attribute::<Tooltip>()
+            ),
+            _ => panic!("expected unit variant"),
+        }
+
+        match info.variant("Grayscale").unwrap() {
+            VariantInfo::Tuple(variant) => assert!(variant
+                .field_at(0)
+                .unwrap()
+                .has_attribute::<RangeInclusive<f32>>()),
+            _ => panic!("expected tuple variant"),
+        }
+
+        match info.variant("Rgb").unwrap() {
+            VariantInfo::Struct(variant) => {
+                assert!(variant
+                    .field("r")
+                    .unwrap()
+                    .has_attribute::<RangeInclusive<u8>>());
+                assert!(variant.field("g").unwrap().custom_attributes().is_empty());
+            }
+            _ => panic!("expected struct variant"),
+        }
+    }
+}
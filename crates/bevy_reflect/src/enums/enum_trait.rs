@@ -1,4 +1,5 @@
-use crate::{DynamicEnum, Reflect, VariantInfo, VariantType};
+use crate::attributes::impl_custom_attribute_methods;
+use crate::{CustomAttributes, DynamicEnum, Reflect, VariantInfo, VariantType};
 use bevy_utils::HashMap;
 use std::any::{Any, TypeId};
 use std::slice::Iter;
@@ -137,6 +138,7 @@ pub struct EnumInfo {
     type_id: TypeId,
     variants: Box<[VariantInfo]>,
     variant_indices: HashMap<&'static str, usize>,
+    custom_attributes: CustomAttributes,
     #[cfg(feature = "documentation")]
     docs: Option<&'static str>,
 }
@@ -162,6 +164,7 @@ impl EnumInfo {
             type_id: TypeId::of::<TEnum>(),
             variants: variants.to_vec().into_boxed_slice(),
             variant_indices,
+            custom_attributes: CustomAttributes::default(),
             #[cfg(feature = "documentation")]
             docs: None,
         }
@@ -173,6 +176,8 @@ impl EnumInfo {
         Self { docs, ..self }
     }
 
+    impl_custom_attribute_methods!("enum");
+
     /// Get a variant with the given name.
     pub fn variant(&self, name: &str) -> Option<&VariantInfo> {
         self.variant_indices
@@ -1,4 +1,5 @@
-use crate::{NamedField, UnnamedField};
+use crate::attributes::impl_custom_attribute_methods;
+use crate::{CustomAttributes, NamedField, UnnamedField};
 use bevy_utils::HashMap;
 use std::slice::Iter;
 
@@ -73,6 +74,15 @@ impl VariantInfo {
         }
     }
 
+    /// The custom attributes of the underlying variant.
+    pub fn custom_attributes(&self) -> &CustomAttributes {
+        match self {
+            Self::Struct(info) => info.custom_attributes(),
+            Self::Tuple(info) => info.custom_attributes(),
+            Self::Unit(info) => info.custom_attributes(),
+        }
+    }
+
     /// The docstring of the underlying variant, if any.
     #[cfg(feature = "documentation")]
     pub fn docs(&self) -> Option<&str> {
@@ -90,6 +100,7 @@ pub struct StructVariantInfo {
     name: &'static str,
     fields: Box<[NamedField]>,
     field_indices: HashMap<&'static str, usize>,
+    custom_attributes: CustomAttributes,
     #[cfg(feature = "documentation")]
     docs: Option<&'static str>,
 }
@@ -102,6 +113,7 @@ impl StructVariantInfo {
             name,
             fields: fields.to_vec().into_boxed_slice(),
             field_indices,
+            custom_attributes: CustomAttributes::default(),
             #[cfg(feature = "documentation")]
             docs: None,
         }
@@ -113,6 +125,8 @@ impl StructVariantInfo {
         Self { docs, ..self }
     }
 
+    impl_custom_attribute_methods!("variant");
+
     /// The name of this variant.
     pub fn name(&self) -> &'static str {
         self.name
@@ -165,6 +179,7 @@ impl StructVariantInfo {
 pub struct TupleVariantInfo {
     name: &'static str,
     fields: Box<[UnnamedField]>,
+    custom_attributes: CustomAttributes,
     #[cfg(feature = "documentation")]
     docs: Option<&'static str>,
 }
@@ -175,6 +190,7 @@ impl TupleVariantInfo {
         Self {
             name,
             fields: fields.to_vec().into_boxed_slice(),
+            custom_attributes: CustomAttributes::default(),
             #[cfg(feature = "documentation")]
             docs: None,
         }
@@ -186,6 +202,8 @@ impl TupleVariantInfo {
         Self { docs, ..self }
     }
 
+    impl_custom_attribute_methods!("variant");
+
     /// The name of this variant.
     pub fn name(&self) -> &'static str {
         self.name
@@ -217,6 +235,7 @@ impl TupleVariantInfo {
 #[derive(Clone, Debug)]
 pub struct UnitVariantInfo {
     name: &'static str,
+    custom_attributes: CustomAttributes,
     #[cfg(feature = "documentation")]
     docs: Option<&'static str>,
 }
@@ -226,6 +245,7 @@ impl UnitVariantInfo {
     pub fn new(name: &'static str) -> Self {
         Self {
             name,
+            custom_attributes: CustomAttributes::default(),
             #[cfg(feature = "documentation")]
             docs: None,
         }
@@ -237,6 +257,8 @@ impl UnitVariantInfo {
         Self { docs, ..self }
     }
 
+    impl_custom_attribute_methods!("variant");
+
     /// The name of this variant.
     pub fn name(&self) -> &'static str {
         self.name
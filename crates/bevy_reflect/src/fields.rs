@@ -1,4 +1,5 @@
-use crate::Reflect;
+use crate::attributes::impl_custom_attribute_methods;
+use crate::{CustomAttributes, Reflect};
 use std::any::{Any, TypeId};
 
 /// The named field of a reflected struct.
@@ -7,6 +8,7 @@ pub struct NamedField {
     name: &'static str,
     type_name: &'static str,
     type_id: TypeId,
+    custom_attributes: CustomAttributes,
     #[cfg(feature = "documentation")]
     docs: Option<&'static str>,
 }
@@ -18,6 +20,7 @@ impl NamedField {
             name,
             type_name: std::any::type_name::<T>(),
             type_id: TypeId::of::<T>(),
+            custom_attributes: CustomAttributes::default(),
             #[cfg(feature = "documentation")]
             docs: None,
         }
@@ -29,6 +32,8 @@ impl NamedField {
         Self { docs, ..self }
     }
 
+    impl_custom_attribute_methods!("field");
+
     /// The name of the field.
     pub fn name(&self) -> &'static str {
         self.name
@@ -64,6 +69,7 @@ pub struct UnnamedField {
     index: usize,
     type_name: &'static str,
     type_id: TypeId,
+    custom_attributes: CustomAttributes,
     #[cfg(feature = "documentation")]
     docs: Option<&'static str>,
 }
@@ -74,6 +80,7 @@ impl UnnamedField {
             index,
             type_name: std::any::type_name::<T>(),
             type_id: TypeId::of::<T>(),
+            custom_attributes: CustomAttributes::default(),
             #[cfg(feature = "documentation")]
             docs: None,
         }
@@ -85,6 +92,8 @@ impl UnnamedField {
         Self { docs, ..self }
     }
 
+    impl_custom_attribute_methods!("field");
+
     /// Returns the index of the field.
     pub fn index(&self) -> usize {
         self.index
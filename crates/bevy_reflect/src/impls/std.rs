@@ -1,10 +1,10 @@
 use crate::{self as bevy_reflect, ReflectFromPtr};
 use crate::{
-    map_apply, map_partial_eq, Array, ArrayInfo, ArrayIter, DynamicEnum, DynamicMap, Enum,
-    EnumInfo, FromReflect, FromType, GetTypeRegistration, List, ListInfo, Map, MapInfo, MapIter,
-    Reflect, ReflectDeserialize, ReflectMut, ReflectRef, ReflectSerialize, TupleVariantInfo,
-    TypeInfo, TypeRegistration, Typed, UnitVariantInfo, UnnamedField, ValueInfo, VariantFieldIter,
-    VariantInfo, VariantType,
+    map_apply, map_partial_eq, set_apply, set_partial_eq, Array, ArrayInfo, ArrayIter, DynamicEnum,
+    DynamicMap, DynamicSet, Enum, EnumInfo, FromReflect, FromType, GetTypeRegistration, List,
+    ListInfo, Map, MapInfo, MapIter, Reflect, ReflectDeserialize, ReflectMut, ReflectRef,
+    ReflectSerialize, Set, SetInfo, SetIter, TupleVariantInfo, TypeInfo, TypeRegistration, Typed,
+    UnitVariantInfo, UnnamedField, ValueInfo, VariantFieldIter, VariantInfo, VariantType,
 };
 
 use crate::utility::{GenericTypeInfoCell, NonGenericTypeInfoCell};
@@ -16,6 +16,7 @@ use std::ffi::OsString;
 use std::{
     any::Any,
     borrow::Cow,
+    collections::{BTreeMap, BTreeSet, VecDeque},
     hash::{Hash, Hasher},
     num::{
         NonZeroI128, NonZeroI16, NonZeroI32, NonZeroI64, NonZeroI8, NonZeroIsize, NonZeroU128,
@@ -44,7 +45,6 @@ impl_reflect_value!(f64(Debug, PartialEq, Serialize, Deserialize));
 impl_reflect_value!(String(Debug, Hash, PartialEq, Serialize, Deserialize));
 impl_reflect_value!(PathBuf(Debug, Hash, PartialEq, Serialize, Deserialize));
 impl_reflect_value!(Result<T: Clone + Reflect + 'static, E: Clone + Reflect + 'static>());
-impl_reflect_value!(HashSet<T: Hash + Eq + Clone + Send + Sync + 'static>());
 impl_reflect_value!(Range<T: Clone + Send + Sync + 'static>());
 impl_reflect_value!(RangeInclusive<T: Clone + Send + Sync + 'static>());
 impl_reflect_value!(RangeFrom<T: Clone + Send + Sync + 'static>());
@@ -88,7 +88,6 @@ impl_from_reflect_value!(isize);
 impl_from_reflect_value!(f32);
 impl_from_reflect_value!(f64);
 impl_from_reflect_value!(String);
-impl_from_reflect_value!(HashSet<T: Hash + Eq + Clone + Send + Sync + 'static>);
 impl_from_reflect_value!(Range<T: Clone + Send + Sync + 'static>);
 impl_from_reflect_value!(RangeInclusive<T: Clone + Send + Sync + 'static>);
 impl_from_reflect_value!(RangeFrom<T: Clone + Send + Sync + 'static>);
@@ -110,310 +109,471 @@ impl_from_reflect_value!(NonZeroU16);
 impl_from_reflect_value!(NonZeroU8);
 impl_from_reflect_value!(NonZeroI8);
 
-impl<T: FromReflect> Array for Vec<T> {
-    #[inline]
-    fn get(&self, index: usize) -> Option<&dyn Reflect> {
-        <[T]>::get(self, index).map(|value| value as &dyn Reflect)
-    }
+macro_rules! impl_reflect_for_veclike {
+    ($ty:ty, $push:expr, $pop:expr, $sub:ty) => {
+        impl<T: FromReflect> Array for $ty {
+            #[inline]
+            fn get(&self, index: usize) -> Option<&dyn Reflect> {
+                <$sub>::get(self, index).map(|value| value as &dyn Reflect)
+            }
 
-    #[inline]
-    fn get_mut(&mut self, index: usize) -> Option<&mut dyn Reflect> {
-        <[T]>::get_mut(self, index).map(|value| value as &mut dyn Reflect)
-    }
+            #[inline]
+            fn get_mut(&mut self, index: usize) -> Option<&mut dyn Reflect> {
+                <$sub>::get_mut(self, index).map(|value| value as &mut dyn Reflect)
+            }
 
-    #[inline]
-    fn len(&self) -> usize {
-        <[T]>::len(self)
-    }
+            #[inline]
+            fn len(&self) -> usize {
+                <$sub>::len(self)
+            }
 
-    #[inline]
-    fn iter(&self) -> ArrayIter {
-        ArrayIter {
-            array: self,
-            index: 0,
+            #[inline]
+            fn iter(&self) -> ArrayIter {
+                ArrayIter {
+                    array: self,
+                    index: 0,
+                }
+            }
+
+            #[inline]
+            fn drain(self: Box<Self>) -> Vec<Box<dyn Reflect>> {
+                self.into_iter()
+                    .map(|value| Box::new(value) as Box<dyn Reflect>)
+                    .collect()
+            }
         }
-    }
 
-    #[inline]
-    fn drain(self: Box<Self>) -> Vec<Box<dyn Reflect>> {
-        self.into_iter()
-            .map(|value| Box::new(value) as Box<dyn Reflect>)
-            .collect()
-    }
-}
+        impl<T: FromReflect> List for $ty {
+            fn push(&mut self, value: Box<dyn Reflect>) {
+                let value = value.take::<T>().unwrap_or_else(|value| {
+                    T::from_reflect(&*value).unwrap_or_else(|| {
+                        panic!(
+                            "Attempted to push invalid value of type {}.",
+                            value.type_name()
+                        )
+                    })
+                });
+                $push(self, value);
+            }
 
-impl<T: FromReflect> List for Vec<T> {
-    fn push(&mut self, value: Box<dyn Reflect>) {
-        let value = value.take::<T>().unwrap_or_else(|value| {
-            T::from_reflect(&*value).unwrap_or_else(|| {
-                panic!(
-                    "Attempted to push invalid value of type {}.",
-                    value.type_name()
-                )
-            })
-        });
-        Vec::push(self, value);
-    }
+            fn pop(&mut self) -> Option<Box<dyn Reflect>> {
+                $pop(self).map(|value| Box::new(value) as Box<dyn Reflect>)
+            }
+        }
 
-    fn pop(&mut self) -> Option<Box<dyn Reflect>> {
-        self.pop().map(|value| Box::new(value) as Box<dyn Reflect>)
-    }
-}
+        impl<T: FromReflect> Reflect for $ty {
+            fn type_name(&self) -> &str {
+                std::any::type_name::<Self>()
+            }
 
-impl<T: FromReflect> Reflect for Vec<T> {
-    fn type_name(&self) -> &str {
-        std::any::type_name::<Self>()
-    }
+            fn get_type_info(&self) -> &'static TypeInfo {
+                <Self as Typed>::type_info()
+            }
 
-    fn get_type_info(&self) -> &'static TypeInfo {
-        <Self as Typed>::type_info()
-    }
+            fn into_any(self: Box<Self>) -> Box<dyn Any> {
+                self
+            }
 
-    fn into_any(self: Box<Self>) -> Box<dyn Any> {
-        self
-    }
+            fn as_any(&self) -> &dyn Any {
+                self
+            }
 
-    fn as_any(&self) -> &dyn Any {
-        self
-    }
+            fn as_any_mut(&mut self) -> &mut dyn Any {
+                self
+            }
 
-    fn as_any_mut(&mut self) -> &mut dyn Any {
-        self
-    }
+            fn as_reflect(&self) -> &dyn Reflect {
+                self
+            }
 
-    fn as_reflect(&self) -> &dyn Reflect {
-        self
-    }
+            fn as_reflect_mut(&mut self) -> &mut dyn Reflect {
+                self
+            }
 
-    fn as_reflect_mut(&mut self) -> &mut dyn Reflect {
-        self
-    }
+            fn apply(&mut self, value: &dyn Reflect) {
+                crate::list_apply(self, value);
+            }
 
-    fn apply(&mut self, value: &dyn Reflect) {
-        crate::list_apply(self, value);
-    }
+            fn set(&mut self, value: Box<dyn Reflect>) -> Result<(), Box<dyn Reflect>> {
+                *self = value.take()?;
+                Ok(())
+            }
 
-    fn set(&mut self, value: Box<dyn Reflect>) -> Result<(), Box<dyn Reflect>> {
-        *self = value.take()?;
-        Ok(())
-    }
+            fn reflect_ref(&self) -> ReflectRef {
+                ReflectRef::List(self)
+            }
 
-    fn reflect_ref(&self) -> ReflectRef {
-        ReflectRef::List(self)
-    }
+            fn reflect_mut(&mut self) -> ReflectMut {
+                ReflectMut::List(self)
+            }
 
-    fn reflect_mut(&mut self) -> ReflectMut {
-        ReflectMut::List(self)
-    }
+            fn clone_value(&self) -> Box<dyn Reflect> {
+                Box::new(List::clone_dynamic(self))
+            }
 
-    fn clone_value(&self) -> Box<dyn Reflect> {
-        Box::new(List::clone_dynamic(self))
-    }
+            fn reflect_hash(&self) -> Option<u64> {
+                crate::array_hash(self)
+            }
 
-    fn reflect_hash(&self) -> Option<u64> {
-        crate::array_hash(self)
-    }
+            fn reflect_partial_eq(&self, value: &dyn Reflect) -> Option<bool> {
+                crate::list_partial_eq(self, value)
+            }
+        }
 
-    fn reflect_partial_eq(&self, value: &dyn Reflect) -> Option<bool> {
-        crate::list_partial_eq(self, value)
-    }
-}
+        impl<T: FromReflect> Typed for $ty {
+            fn type_info() -> &'static TypeInfo {
+                static CELL: GenericTypeInfoCell = GenericTypeInfoCell::new();
+                CELL.get_or_insert::<Self, _>(|| TypeInfo::List(ListInfo::new::<Self, T>()))
+            }
+        }
 
-impl<T: FromReflect> Typed for Vec<T> {
-    fn type_info() -> &'static TypeInfo {
-        static CELL: GenericTypeInfoCell = GenericTypeInfoCell::new();
-        CELL.get_or_insert::<Self, _>(|| TypeInfo::List(ListInfo::new::<Self, T>()))
-    }
-}
+        impl<T: FromReflect> GetTypeRegistration for $ty {
+            fn get_type_registration() -> TypeRegistration {
+                let mut registration = TypeRegistration::of::<$ty>();
+                registration.insert::<ReflectFromPtr>(FromType::<$ty>::from_type());
+                registration
+            }
+        }
 
-impl<T: FromReflect> GetTypeRegistration for Vec<T> {
-    fn get_type_registration() -> TypeRegistration {
-        let mut registration = TypeRegistration::of::<Vec<T>>();
-        registration.insert::<ReflectFromPtr>(FromType::<Vec<T>>::from_type());
-        registration
-    }
+        impl<T: FromReflect> FromReflect for $ty {
+            fn from_reflect(reflect: &dyn Reflect) -> Option<Self> {
+                if let ReflectRef::List(ref_list) = reflect.reflect_ref() {
+                    let mut new_list = Self::with_capacity(ref_list.len());
+                    for field in ref_list.iter() {
+                        $push(&mut new_list, T::from_reflect(field)?);
+                    }
+                    Some(new_list)
+                } else {
+                    None
+                }
+            }
+        }
+    };
 }
 
-impl<T: FromReflect> FromReflect for Vec<T> {
-    fn from_reflect(reflect: &dyn Reflect) -> Option<Self> {
-        if let ReflectRef::List(ref_list) = reflect.reflect_ref() {
-            let mut new_list = Self::with_capacity(ref_list.len());
-            for field in ref_list.iter() {
-                new_list.push(T::from_reflect(field)?);
+impl_reflect_for_veclike!(Vec<T>, Vec::push, Vec::pop, [T]);
+impl_reflect_for_veclike!(
+    VecDeque<T>,
+    VecDeque::push_back,
+    VecDeque::pop_back,
+    VecDeque::<T>
+);
+
+macro_rules! impl_reflect_for_map {
+    ($ty:ident, $($key_bound:ident)+, $new:expr) => {
+        impl<K: FromReflect $(+ $key_bound)+, V: FromReflect> Map for $ty<K, V> {
+            fn get(&self, key: &dyn Reflect) -> Option<&dyn Reflect> {
+                key.downcast_ref::<K>()
+                    .and_then(|key| $ty::get(self, key))
+                    .map(|value| value as &dyn Reflect)
+            }
+
+            fn get_mut(&mut self, key: &dyn Reflect) -> Option<&mut dyn Reflect> {
+                key.downcast_ref::<K>()
+                    .and_then(move |key| $ty::get_mut(self, key))
+                    .map(|value| value as &mut dyn Reflect)
+            }
+
+            fn get_at(&self, index: usize) -> Option<(&dyn Reflect, &dyn Reflect)> {
+                self.iter()
+                    .nth(index)
+                    .map(|(key, value)| (key as &dyn Reflect, value as &dyn Reflect))
+            }
+
+            fn len(&self) -> usize {
+                Self::len(self)
+            }
+
+            fn iter(&self) -> MapIter {
+                MapIter {
+                    map: self,
+                    index: 0,
+                }
+            }
+
+            fn drain(self: Box<Self>) -> Vec<(Box<dyn Reflect>, Box<dyn Reflect>)> {
+                self.into_iter()
+                    .map(|(key, value)| {
+                        (
+                            Box::new(key) as Box<dyn Reflect>,
+                            Box::new(value) as Box<dyn Reflect>,
+                        )
+                    })
+                    .collect()
+            }
+
+            fn clone_dynamic(&self) -> DynamicMap {
+                let mut dynamic_map = DynamicMap::default();
+                dynamic_map.set_name(self.type_name().to_string());
+                for (k, v) in self {
+                    dynamic_map.insert_boxed(k.clone_value(), v.clone_value());
+                }
+                dynamic_map
+            }
+
+            fn insert_boxed(
+                &mut self,
+                key: Box<dyn Reflect>,
+                value: Box<dyn Reflect>,
+            ) -> Option<Box<dyn Reflect>> {
+                let key = key.take::<K>().unwrap_or_else(|key| {
+                    K::from_reflect(&*key).unwrap_or_else(|| {
+                        panic!(
+                            "Attempted to insert invalid key of type {}.",
+                            key.type_name()
+                        )
+                    })
+                });
+                let value = value.take::<V>().unwrap_or_else(|value| {
+                    V::from_reflect(&*value).unwrap_or_else(|| {
+                        panic!(
+                            "Attempted to insert invalid value of type {}.",
+                            value.type_name()
+                        )
+                    })
+                });
+                self.insert(key, value)
+                    .map(|old_value| Box::new(old_value) as Box<dyn Reflect>)
             }
-            Some(new_list)
-        } else {
-            None
         }
-    }
-}
 
-impl<K: FromReflect + Eq + Hash, V: FromReflect> Map for HashMap<K, V> {
-    fn get(&self, key: &dyn Reflect) -> Option<&dyn Reflect> {
-        key.downcast_ref::<K>()
-            .and_then(|key| HashMap::get(self, key))
-            .map(|value| value as &dyn Reflect)
-    }
+        impl<K: FromReflect $(+ $key_bound)+, V: FromReflect> Reflect for $ty<K, V> {
+            fn type_name(&self) -> &str {
+                std::any::type_name::<Self>()
+            }
 
-    fn get_mut(&mut self, key: &dyn Reflect) -> Option<&mut dyn Reflect> {
-        key.downcast_ref::<K>()
-            .and_then(move |key| HashMap::get_mut(self, key))
-            .map(|value| value as &mut dyn Reflect)
-    }
+            fn get_type_info(&self) -> &'static TypeInfo {
+                <Self as Typed>::type_info()
+            }
 
-    fn get_at(&self, index: usize) -> Option<(&dyn Reflect, &dyn Reflect)> {
-        self.iter()
-            .nth(index)
-            .map(|(key, value)| (key as &dyn Reflect, value as &dyn Reflect))
-    }
+            fn into_any(self: Box<Self>) -> Box<dyn Any> {
+                self
+            }
 
-    fn len(&self) -> usize {
-        Self::len(self)
-    }
+            fn as_any(&self) -> &dyn Any {
+                self
+            }
 
-    fn iter(&self) -> MapIter {
-        MapIter {
-            map: self,
-            index: 0,
+            fn as_any_mut(&mut self) -> &mut dyn Any {
+                self
+            }
+
+            fn as_reflect(&self) -> &dyn Reflect {
+                self
+            }
+
+            fn as_reflect_mut(&mut self) -> &mut dyn Reflect {
+                self
+            }
+
+            fn apply(&mut self, value: &dyn Reflect) {
+                map_apply(self, value);
+            }
+
+            fn set(&mut self, value: Box<dyn Reflect>) -> Result<(), Box<dyn Reflect>> {
+                *self = value.take()?;
+                Ok(())
+            }
+
+            fn reflect_ref(&self) -> ReflectRef {
+                ReflectRef::Map(self)
+            }
+
+            fn reflect_mut(&mut self) -> ReflectMut {
+                ReflectMut::Map(self)
+            }
+
+            fn clone_value(&self) -> Box<dyn Reflect> {
+                Box::new(self.clone_dynamic())
+            }
+
+            fn reflect_partial_eq(&self, value: &dyn Reflect) -> Option<bool> {
+                map_partial_eq(self, value)
+            }
         }
-    }
 
-    fn drain(self: Box<Self>) -> Vec<(Box<dyn Reflect>, Box<dyn Reflect>)> {
-        self.into_iter()
-            .map(|(key, value)| {
-                (
-                    Box::new(key) as Box<dyn Reflect>,
-                    Box::new(value) as Box<dyn Reflect>,
-                )
-            })
-            .collect()
-    }
+        impl<K: FromReflect $(+ $key_bound)+, V: FromReflect> Typed for $ty<K, V> {
+            fn type_info() -> &'static TypeInfo {
+                static CELL: GenericTypeInfoCell = GenericTypeInfoCell::new();
+                CELL.get_or_insert::<Self, _>(|| TypeInfo::Map(MapInfo::new::<Self, K, V>()))
+            }
+        }
 
-    fn clone_dynamic(&self) -> DynamicMap {
-        let mut dynamic_map = DynamicMap::default();
-        dynamic_map.set_name(self.type_name().to_string());
-        for (k, v) in self {
-            dynamic_map.insert_boxed(k.clone_value(), v.clone_value());
+        impl<K, V> GetTypeRegistration for $ty<K, V>
+        where
+            K: FromReflect $(+ $key_bound)+,
+            V: FromReflect,
+        {
+            fn get_type_registration() -> TypeRegistration {
+                let mut registration = TypeRegistration::of::<$ty<K, V>>();
+                registration.insert::<ReflectFromPtr>(FromType::<$ty<K, V>>::from_type());
+                registration
+            }
         }
-        dynamic_map
-    }
-
-    fn insert_boxed(
-        &mut self,
-        key: Box<dyn Reflect>,
-        value: Box<dyn Reflect>,
-    ) -> Option<Box<dyn Reflect>> {
-        let key = key.take::<K>().unwrap_or_else(|key| {
-            K::from_reflect(&*key).unwrap_or_else(|| {
-                panic!(
-                    "Attempted to insert invalid key of type {}.",
-                    key.type_name()
-                )
-            })
-        });
-        let value = value.take::<V>().unwrap_or_else(|value| {
-            V::from_reflect(&*value).unwrap_or_else(|| {
-                panic!(
-                    "Attempted to insert invalid value of type {}.",
-                    value.type_name()
-                )
-            })
-        });
-        self.insert(key, value)
-            .map(|old_value| Box::new(old_value) as Box<dyn Reflect>)
-    }
+
+        impl<K: FromReflect $(+ $key_bound)+, V: FromReflect> FromReflect for $ty<K, V> {
+            fn from_reflect(reflect: &dyn Reflect) -> Option<Self> {
+                if let ReflectRef::Map(ref_map) = reflect.reflect_ref() {
+                    let mut new_map = $new(ref_map.len());
+                    for (key, value) in ref_map.iter() {
+                        let new_key = K::from_reflect(key)?;
+                        let new_value = V::from_reflect(value)?;
+                        new_map.insert(new_key, new_value);
+                    }
+                    Some(new_map)
+                } else {
+                    None
+                }
+            }
+        }
+    };
 }
 
-impl<K: FromReflect + Eq + Hash, V: FromReflect> Reflect for HashMap<K, V> {
-    fn type_name(&self) -> &str {
-        std::any::type_name::<Self>()
-    }
+impl_reflect_for_map!(HashMap, Eq Hash, Self::with_capacity);
+impl_reflect_for_map!(BTreeMap, Ord, |_| Self::new());
+
+macro_rules! impl_reflect_for_set {
+    ($ty:ident, $($value_bound:ident)+, $new:expr) => {
+        impl<V: FromReflect $(+ $value_bound)+> Set for $ty<V> {
+            fn get(&self, value: &dyn Reflect) -> Option<&dyn Reflect> {
+                value
+                    .downcast_ref::<V>()
+                    .and_then(|value| $ty::get(self, value))
+                    .map(|value| value as &dyn Reflect)
+            }
 
-    fn get_type_info(&self) -> &'static TypeInfo {
-        <Self as Typed>::type_info()
-    }
+            fn get_at(&self, index: usize) -> Option<&dyn Reflect> {
+                self.iter().nth(index).map(|value| value as &dyn Reflect)
+            }
 
-    fn into_any(self: Box<Self>) -> Box<dyn Any> {
-        self
-    }
+            fn len(&self) -> usize {
+                Self::len(self)
+            }
 
-    fn as_any(&self) -> &dyn Any {
-        self
-    }
+            fn iter(&self) -> SetIter {
+                SetIter {
+                    set: self,
+                    index: 0,
+                }
+            }
 
-    fn as_any_mut(&mut self) -> &mut dyn Any {
-        self
-    }
+            fn drain(self: Box<Self>) -> Vec<Box<dyn Reflect>> {
+                self.into_iter()
+                    .map(|value| Box::new(value) as Box<dyn Reflect>)
+                    .collect()
+            }
 
-    fn as_reflect(&self) -> &dyn Reflect {
-        self
-    }
+            fn clone_dynamic(&self) -> DynamicSet {
+                let mut dynamic_set = DynamicSet::default();
+                dynamic_set.set_name(self.type_name().to_string());
+                for value in self {
+                    dynamic_set.insert_boxed(value.clone_value());
+                }
+                dynamic_set
+            }
 
-    fn as_reflect_mut(&mut self) -> &mut dyn Reflect {
-        self
-    }
+            fn insert_boxed(&mut self, value: Box<dyn Reflect>) -> bool {
+                let value = value.take::<V>().unwrap_or_else(|value| {
+                    V::from_reflect(&*value).unwrap_or_else(|| {
+                        panic!(
+                            "Attempted to insert invalid value of type {}.",
+                            value.type_name()
+                        )
+                    })
+                });
+                self.insert(value)
+            }
+        }
 
-    fn apply(&mut self, value: &dyn Reflect) {
-        map_apply(self, value);
-    }
+        impl<V: FromReflect $(+ $value_bound)+> Reflect for $ty<V> {
+            fn type_name(&self) -> &str {
+                std::any::type_name::<Self>()
+            }
 
-    fn set(&mut self, value: Box<dyn Reflect>) -> Result<(), Box<dyn Reflect>> {
-        *self = value.take()?;
-        Ok(())
-    }
+            fn get_type_info(&self) -> &'static TypeInfo {
+                <Self as Typed>::type_info()
+            }
 
-    fn reflect_ref(&self) -> ReflectRef {
-        ReflectRef::Map(self)
-    }
+            fn into_any(self: Box<Self>) -> Box<dyn Any> {
+                self
+            }
 
-    fn reflect_mut(&mut self) -> ReflectMut {
-        ReflectMut::Map(self)
-    }
+            fn as_any(&self) -> &dyn Any {
+                self
+            }
 
-    fn clone_value(&self) -> Box<dyn Reflect> {
-        Box::new(self.clone_dynamic())
-    }
+            fn as_any_mut(&mut self) -> &mut dyn Any {
+                self
+            }
 
-    fn reflect_partial_eq(&self, value: &dyn Reflect) -> Option<bool> {
-        map_partial_eq(self, value)
-    }
-}
+            fn as_reflect(&self) -> &dyn Reflect {
+                self
+            }
 
-impl<K: FromReflect + Eq + Hash, V: FromReflect> Typed for HashMap<K, V> {
-    fn type_info() -> &'static TypeInfo {
-        static CELL: GenericTypeInfoCell = GenericTypeInfoCell::new();
-        CELL.get_or_insert::<Self, _>(|| TypeInfo::Map(MapInfo::new::<Self, K, V>()))
-    }
-}
+            fn as_reflect_mut(&mut self) -> &mut dyn Reflect {
+                self
+            }
 
-impl<K, V> GetTypeRegistration for HashMap<K, V>
-where
-    K: FromReflect + Eq + Hash,
-    V: FromReflect,
-{
-    fn get_type_registration() -> TypeRegistration {
-        let mut registration = TypeRegistration::of::<HashMap<K, V>>();
-        registration.insert::<ReflectFromPtr>(FromType::<HashMap<K, V>>::from_type());
-        registration
-    }
-}
+            fn apply(&mut self, value: &dyn Reflect) {
+                set_apply(self, value);
+            }
 
-impl<K: FromReflect + Eq + Hash, V: FromReflect> FromReflect for HashMap<K, V> {
-    fn from_reflect(reflect: &dyn Reflect) -> Option<Self> {
-        if let ReflectRef::Map(ref_map) = reflect.reflect_ref() {
-            let mut new_map = Self::with_capacity(ref_map.len());
-            for (key, value) in ref_map.iter() {
-                let new_key = K::from_reflect(key)?;
-                let new_value = V::from_reflect(value)?;
-                new_map.insert(new_key, new_value);
-            }
-            Some(new_map)
-        } else {
-            None
+            fn set(&mut self, value: Box<dyn Reflect>) -> Result<(), Box<dyn Reflect>> {
+                *self = value.take()?;
+                Ok(())
+            }
+
+            fn reflect_ref(&self) -> ReflectRef {
+                ReflectRef::Set(self)
+            }
+
+            fn reflect_mut(&mut self) -> ReflectMut {
+                ReflectMut::Set(self)
+            }
+
+            fn clone_value(&self) -> Box<dyn Reflect> {
+                Box::new(self.clone_dynamic())
+            }
+
+            fn reflect_partial_eq(&self, value: &dyn Reflect) -> Option<bool> {
+                set_partial_eq(self, value)
+            }
         }
-    }
+
+        impl<V: FromReflect $(+ $value_bound)+> Typed for $ty<V> {
+            fn type_info() -> &'static TypeInfo {
+                static CELL: GenericTypeInfoCell = GenericTypeInfoCell::new();
+                CELL.get_or_insert::<Self, _>(|| TypeInfo::Set(SetInfo::new::<Self, V>()))
+            }
+        }
+
+        impl<V: FromReflect $(+ $value_bound)+> GetTypeRegistration for $ty<V> {
+            fn get_type_registration() -> TypeRegistration {
+                let mut registration = TypeRegistration::of::<$ty<V>>();
+                registration.insert::<ReflectFromPtr>(FromType::<$ty<V>>::from_type());
+                registration
+            }
+        }
+
+        impl<V: FromReflect $(+ $value_bound)+> FromReflect for $ty<V> {
+            fn from_reflect(reflect: &dyn Reflect) -> Option<Self> {
+                if let ReflectRef::Set(ref_set) = reflect.reflect_ref() {
+                    let mut new_set = $new(ref_set.len());
+                    for value in ref_set.iter() {
+                        new_set.insert(V::from_reflect(value)?);
+                    }
+                    Some(new_set)
+                } else {
+                    None
+                }
+            }
+        }
+    };
 }
 
+impl_reflect_for_set!(HashSet, Eq Hash, Self::with_capacity);
+impl_reflect_for_set!(BTreeSet, Ord, |_| Self::new());
+
 impl<T: Reflect, const N: usize> Array for [T; N] {
     #[inline]
     fn get(&self, index: usize) -> Option<&dyn Reflect> {
@@ -904,11 +1064,12 @@ impl FromReflect for Cow<'static, str> {
 mod tests {
     use crate as bevy_reflect;
     use crate::{
-        Enum, FromReflect, Reflect, ReflectSerialize, TypeInfo, TypeRegistry, Typed, VariantInfo,
-        VariantType,
+        Enum, FromReflect, List, Map, Reflect, ReflectRef, ReflectSerialize, Set, TypeInfo,
+        TypeRegistry, Typed, VariantInfo, VariantType,
     };
-    use bevy_utils::HashMap;
     use bevy_utils::{Duration, Instant};
+    use bevy_utils::{HashMap, HashSet};
+    use std::collections::{BTreeMap, BTreeSet, VecDeque};
     use std::f32::consts::{PI, TAU};
 
     #[test]
@@ -982,6 +1143,75 @@ mod tests {
         assert!(!a.reflect_partial_eq(c).unwrap_or_default());
     }
 
+    #[test]
+    fn btree_map_should_iterate_in_key_order() {
+        let mut map = BTreeMap::new();
+        map.insert(3usize, "c".to_string());
+        map.insert(1usize, "a".to_string());
+        map.insert(2usize, "b".to_string());
+
+        let keys: Vec<_> = Map::iter(&map)
+            .map(|(key, _)| *key.downcast_ref::<usize>().unwrap())
+            .collect();
+        assert_eq!(vec![1, 2, 3], keys);
+
+        let dynamic = Map::clone_dynamic(&map);
+        let keys: Vec<_> = dynamic
+            .iter()
+            .map(|(key, _)| *key.downcast_ref::<usize>().unwrap())
+            .collect();
+        assert_eq!(vec![1, 2, 3], keys);
+
+        let mut other = BTreeMap::new();
+        other.insert(2usize, "x".to_string());
+        Reflect::apply(&mut other, &map);
+        assert_eq!(map, other);
+        assert_eq!(Some(map), BTreeMap::from_reflect(&dynamic));
+    }
+
+    #[test]
+    fn should_reflect_sets() {
+        let mut a = HashSet::new();
+        a.insert(1usize);
+        a.insert(2usize);
+
+        assert!(Set::contains(&a, &1usize));
+        assert!(!Set::contains(&a, &3usize));
+        assert!(!Set::insert_boxed(&mut a, Box::new(1usize)));
+        assert!(Set::insert_boxed(&mut a, Box::new(3usize)));
+
+        let b: BTreeSet<usize> = [3, 2, 1].into_iter().collect();
+        let values: Vec<_> = Set::iter(&b)
+            .map(|value| *value.downcast_ref::<usize>().unwrap())
+            .collect();
+        assert_eq!(vec![1, 2, 3], values);
+
+        let a: &dyn Reflect = &a;
+        assert!(matches!(a.reflect_ref(), ReflectRef::Set(_)));
+        assert!(a.reflect_partial_eq(&b).unwrap_or_default());
+        assert!(a.reflect_partial_eq(&*b.clone_value()).unwrap_or_default());
+        assert_eq!(Some(b.clone()), BTreeSet::from_reflect(a));
+
+        let mut c = BTreeSet::new();
+        c.insert(4usize);
+        c.apply(a);
+        assert_eq!(vec![1, 2, 3, 4], c.into_iter().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn vec_deque_should_impl_list() {
+        let mut deque = VecDeque::from(vec![1usize, 2]);
+        List::push(&mut deque, Box::new(3usize));
+        assert_eq!(VecDeque::from(vec![1, 2, 3]), deque);
+
+        let value: &dyn Reflect = &deque;
+        assert!(value.reflect_partial_eq(&vec![1usize, 2, 3]).unwrap());
+        assert_eq!(
+            Some(deque.clone()),
+            VecDeque::from_reflect(&vec![1usize, 2, 3])
+        );
+    }
+
     #[test]
     fn should_partial_eq_option() {
         let a: &dyn Reflect = &Some(123);
@@ -1,11 +1,13 @@
 #![doc = include_str!("../README.md")]
 
 mod array;
+mod attributes;
 mod fields;
 mod list;
 mod map;
 mod path;
 mod reflect;
+mod set;
 mod struct_trait;
 mod tuple;
 mod tuple_struct;
@@ -45,6 +47,7 @@ pub mod prelude {
 }
 
 pub use array::*;
+pub use attributes::*;
 pub use enums::*;
 pub use fields::*;
 pub use impls::*;
@@ -52,6 +55,7 @@ pub use list::*;
 pub use map::*;
 pub use path::*;
 pub use reflect::*;
+pub use set::*;
 pub use struct_trait::*;
 pub use tuple::*;
 pub use tuple_struct::*;
@@ -103,6 +107,7 @@ mod tests {
         ser::{to_string_pretty, PrettyConfig},
         Deserializer,
     };
+    use std::collections::BTreeSet;
     use std::fmt::{Debug, Formatter};
 
     use super::prelude::*;
@@ -804,6 +809,23 @@ mod tests {
         let info = value.get_type_info();
         assert!(info.is::<MyMap>());
 
+        // Set
+        type MySet = BTreeSet<usize>;
+
+        let info = MySet::type_info();
+        if let TypeInfo::Set(info) = info {
+            assert!(info.is::<MySet>());
+            assert!(info.value_is::<usize>());
+            assert_eq!(std::any::type_name::<MySet>(), info.type_name());
+            assert_eq!(std::any::type_name::<usize>(), info.value_type_name());
+        } else {
+            panic!("Expected `TypeInfo::Set`");
+        }
+
+        let value: &dyn Reflect = &MySet::new();
+        let info = value.get_type_info();
+        assert!(info.is::<MySet>());
+
         // Value
         type MyValue = String;
 
@@ -1,6 +1,5 @@
 use std::any::{Any, TypeId};
 use std::fmt::{Debug, Formatter};
-use std::hash::Hash;
 
 use bevy_utils::{Entry, HashMap};
 
@@ -12,11 +11,15 @@ use crate::{DynamicInfo, Reflect, ReflectMut, ReflectRef, TypeInfo, Typed};
 /// Because the values are reflected, the underlying types of keys and values
 /// may differ between entries.
 ///
-///`ReflectValue` `Keys` are assumed to return a non-`None` hash. The ordering
-/// of `Map` entries is not guaranteed to be stable across runs or between
-/// instances.
+///`ReflectValue` `Keys` are assumed to return a non-`None` hash.
+/// The order of the entries is the one of the underlying collection: it is sorted by key
+/// for [`BTreeMap`], while the ordering of [`HashMap`] entries is not guaranteed to be
+/// stable across runs or between instances. [`DynamicMap`] preserves insertion order.
 ///
-/// This trait corresponds to types like [`std::collections::HashMap`].
+/// This trait corresponds to types like [`HashMap`] and [`BTreeMap`].
+///
+/// [`BTreeMap`]: std::collections::BTreeMap
+/// [`HashMap`]: bevy_utils::HashMap
 pub trait Map: Reflect {
     /// Returns a reference to the value associated with the given key.
     ///
@@ -74,7 +77,7 @@ pub struct MapInfo {
 
 impl MapInfo {
     /// Create a new [`MapInfo`].
-    pub fn new<TMap: Map, TKey: Hash + Reflect, TValue: Reflect>() -> Self {
+    pub fn new<TMap: Map, TKey: Reflect, TValue: Reflect>() -> Self {
         Self {
             type_name: std::any::type_name::<TMap>(),
             type_id: TypeId::of::<TMap>(),
@@ -1,20 +1,24 @@
 mod de;
+mod schema;
 mod ser;
 mod type_data;
 
 pub use de::*;
+pub use schema::*;
 pub use ser::*;
 pub use type_data::*;
 
 #[cfg(test)]
 mod tests {
-    use crate::{self as bevy_reflect, DynamicTupleStruct};
+    use crate::{self as bevy_reflect, DynamicTupleStruct, FromReflect};
     use crate::{
         serde::{ReflectSerializer, UntypedReflectDeserializer},
         type_registry::TypeRegistry,
         DynamicStruct, Reflect,
     };
+    use bevy_utils::HashSet;
     use serde::de::DeserializeSeed;
+    use std::collections::{BTreeMap, BTreeSet, VecDeque};
 
     #[test]
     fn test_serialization_struct() {
@@ -92,4 +96,43 @@ mod tests {
             "Expected {expected:?} found {deserialized:?}"
         );
     }
+
+    #[test]
+    fn test_serialization_collections() {
+        #[derive(Debug, Reflect, FromReflect, PartialEq)]
+        #[reflect(PartialEq)]
+        struct TestStruct {
+            btree_map: BTreeMap<String, u32>,
+            hash_set: HashSet<u32>,
+            btree_set: BTreeSet<String>,
+            vec_deque: VecDeque<u32>,
+        }
+
+        let mut registry = TypeRegistry::default();
+        registry.register::<TestStruct>();
+        registry.register::<String>();
+        registry.register::<BTreeMap<String, u32>>();
+        registry.register::<HashSet<u32>>();
+        registry.register::<BTreeSet<String>>();
+        registry.register::<VecDeque<u32>>();
+
+        let test_struct = TestStruct {
+            btree_map: [("b".to_string(), 2), ("a".to_string(), 1)].into(),
+            hash_set: [3, 4].into_iter().collect(),
+            btree_set: ["y".to_string(), "x".to_string()].into(),
+            vec_deque: [5, 6].into(),
+        };
+
+        let serializer = ReflectSerializer::new(&test_struct, &registry);
+        let serialized = ron::ser::to_string(&serializer).unwrap();
+        assert!(serialized.contains(r#"btree_map:{"a":1,"b":2}"#));
+        assert!(serialized.contains(r#"btree_set:["x","y"]"#));
+
+        let mut deserializer = ron::de::Deserializer::from_str(&serialized).unwrap();
+        let reflect_deserializer = UntypedReflectDeserializer::new(&registry);
+        let value = reflect_deserializer.deserialize(&mut deserializer).unwrap();
+        let deserialized = TestStruct::from_reflect(&*value).unwrap();
+
+        assert_eq!(test_struct, deserialized);
+    }
 }
@@ -0,0 +1,738 @@
+use crate::{
+    serde::SerializationData, NamedField, TypeInfo, TypeRegistry, UnnamedField, VariantInfo,
+};
+use serde::ser::{SerializeMap, SerializeSeq};
+use serde::{Serialize, Serializer};
+use std::any::TypeId;
+use std::borrow::Cow;
+use std::fmt::Write;
+
+/// The JSON Schema dialect of the generated schemas.
+const SCHEMA_DIALECT: &str = "https://json-schema.org/draft/2020-12/schema";
+
+/// A serializer producing a [JSON Schema] for every type registered in a [`TypeRegistry`].
+///
+/// Each registered type is described in the `$defs` section of the schema, keyed by its
+/// _full_ [type name]. The root schema validates the output of [`ReflectSerializer`]:
+/// a map with a single entry from the type name to the serialized value.
+///
+/// Types are described from their [`TypeInfo`], following the data model of the reflection
+/// serializer: structs are objects, tuples, tuple structs, lists, arrays and sets are arrays,
+/// maps are objects and enums are externally tagged.
+/// Fields marked with `#[reflect(skip_serializing)]` are omitted.
+/// Value types are described only when they are primitives or strings; other value types
+/// (and fields whose type is not registered) accept any value.
+///
+/// When the `documentation` feature is enabled, doc comments are emitted as descriptions.
+///
+/// ```
+/// # use bevy_reflect::{Reflect, TypeRegistry, serde::TypeRegistrySchema};
+/// #[derive(Reflect)]
+/// struct Player {
+///     name: String,
+///     health: u32,
+/// }
+///
+/// let mut registry = TypeRegistry::default();
+/// registry.register::<Player>();
+///
+/// let schema = serde_json::to_value(TypeRegistrySchema::new(&registry)).unwrap();
+/// let player = &schema["$defs"][std::any::type_name::<Player>()];
+/// assert_eq!("integer", player["properties"]["health"]["type"]);
+/// ```
+///
+/// [JSON Schema]: https://json-schema.org
+/// [type name]: std::any::type_name
+/// [`ReflectSerializer`]: crate::serde::ReflectSerializer
+pub struct TypeRegistrySchema<'a> {
+    pub registry: &'a TypeRegistry,
+}
+
+impl<'a> TypeRegistrySchema<'a> {
+    pub fn new(registry: &'a TypeRegistry) -> Self {
+        TypeRegistrySchema { registry }
+    }
+}
+
+impl<'a> Serialize for TypeRegistrySchema<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut state = serializer.serialize_map(None)?;
+        state.serialize_entry("$schema", SCHEMA_DIALECT)?;
+        state.serialize_entry("type", "object")?;
+        state.serialize_entry("minProperties", &1)?;
+        state.serialize_entry("maxProperties", &1)?;
+        state.serialize_entry(
+            "properties",
+            &DefinitionsSchema {
+                registry: self.registry,
+                as_refs: true,
+            },
+        )?;
+        state.serialize_entry("additionalProperties", &false)?;
+        state.serialize_entry(
+            "$defs",
+            &DefinitionsSchema {
+                registry: self.registry,
+                as_refs: false,
+            },
+        )?;
+        state.end()
+    }
+}
+
+/// A serializer producing the [JSON Schema] of a single reflected type.
+///
+/// Other types are referenced as `#/$defs/<type name>`, so this schema is meant to be embedded
+/// in the `$defs` of a [`TypeRegistrySchema`].
+///
+/// [JSON Schema]: https://json-schema.org
+pub struct TypeInfoSchema<'a> {
+    pub type_info: &'static TypeInfo,
+    pub registry: &'a TypeRegistry,
+}
+
+impl<'a> TypeInfoSchema<'a> {
+    pub fn new(type_info: &'static TypeInfo, registry: &'a TypeRegistry) -> Self {
+        TypeInfoSchema {
+            type_info,
+            registry,
+        }
+    }
+}
+
+impl<'a> Serialize for TypeInfoSchema<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let registry = self.registry;
+        let serialization_data = registry
+            .get(self.type_info.type_id())
+            .and_then(|registration| registration.data::<SerializationData>());
+
+        let mut state = serializer.serialize_map(None)?;
+        state.serialize_entry("title", self.type_info.type_name())?;
+        #[cfg(feature = "documentation")]
+        serialize_description(&mut state, self.type_info.docs())?;
+
+        match self.type_info {
+            TypeInfo::Struct(info) => {
+                let fields: Vec<_> = info
+                    .iter()
+                    .enumerate()
+                    .filter(|(index, _)| !is_ignored(serialization_data, *index))
+                    .map(|(_, field)| field)
+                    .collect();
+                serialize_object_entries(&mut state, &fields, registry)?;
+            }
+            TypeInfo::TupleStruct(info) => {
+                let fields: Vec<_> = info
+                    .iter()
+                    .filter(|field| !is_ignored(serialization_data, field.index()))
+                    .collect();
+                serialize_array_entries(&mut state, &fields, registry)?;
+            }
+            TypeInfo::Tuple(info) => {
+                let fields: Vec<_> = info.iter().collect();
+                serialize_array_entries(&mut state, &fields, registry)?;
+            }
+            TypeInfo::List(info) => {
+                state.serialize_entry("type", "array")?;
+                state.serialize_entry(
+                    "items",
+                    &TypeRefSchema::new(info.item_type_id(), info.item_type_name(), registry),
+                )?;
+            }
+            TypeInfo::Array(info) => {
+                state.serialize_entry("type", "array")?;
+                state.serialize_entry(
+                    "items",
+                    &TypeRefSchema::new(info.item_type_id(), info.item_type_name(), registry),
+                )?;
+                state.serialize_entry("minItems", &info.capacity())?;
+                state.serialize_entry("maxItems", &info.capacity())?;
+            }
+            TypeInfo::Set(info) => {
+                state.serialize_entry("type", "array")?;
+                state.serialize_entry(
+                    "items",
+                    &TypeRefSchema::new(info.value_type_id(), info.value_type_name(), registry),
+                )?;
+                state.serialize_entry("uniqueItems", &true)?;
+            }
+            TypeInfo::Map(info) => {
+                state.serialize_entry("type", "object")?;
+                state.serialize_entry(
+                    "additionalProperties",
+                    &TypeRefSchema::new(info.value_type_id(), info.value_type_name(), registry),
+                )?;
+            }
+            TypeInfo::Enum(info) => {
+                if info.type_name().starts_with("core::option::Option") {
+                    // Options are serialized as either `None` or their inner value
+                    let some = match info.variant("Some") {
+                        Some(VariantInfo::Tuple(variant)) => variant.field_at(0),
+                        _ => None,
+                    };
+                    if let Some(some) = some {
+                        let null = PrimitiveSchema {
+                            type_name: "null",
+                            minimum: None,
+                            length: None,
+                        };
+                        let some = TypeRefSchema::new(some.type_id(), some.type_name(), registry);
+                        state.serialize_entry("oneOf", &(null, some))?;
+                    }
+                } else {
+                    let variants: Vec<_> = info
+                        .iter()
+                        .map(|variant| VariantSchema { variant, registry })
+                        .collect();
+                    state.serialize_entry("oneOf", &variants)?;
+                }
+            }
+            TypeInfo::Value(info) => {
+                if let Some(primitive) = PrimitiveSchema::of(info.type_id()) {
+                    primitive.serialize_entries(&mut state)?;
+                }
+            }
+            TypeInfo::Dynamic(_) => {}
+        }
+
+        state.end()
+    }
+}
+
+/// The `$defs` (or root `properties`) of a [`TypeRegistrySchema`], sorted by type name.
+struct DefinitionsSchema<'a> {
+    registry: &'a TypeRegistry,
+    /// Whether to reference the definitions rather than describe them.
+    as_refs: bool,
+}
+
+impl<'a> Serialize for DefinitionsSchema<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut registrations: Vec<_> = self.registry.iter().collect();
+        registrations.sort_by_key(|registration| registration.type_name());
+
+        let mut state = serializer.serialize_map(Some(registrations.len()))?;
+        for registration in registrations {
+            if self.as_refs {
+                state.serialize_entry(
+                    registration.type_name(),
+                    &TypeRefSchema::new(
+                        registration.type_id(),
+                        registration.type_name(),
+                        self.registry,
+                    ),
+                )?;
+            } else {
+                state.serialize_entry(
+                    registration.type_name(),
+                    &TypeInfoSchema::new(registration.type_info(), self.registry),
+                )?;
+            }
+        }
+        state.end()
+    }
+}
+
+/// The schema of a value of the given type, as used by fields and items.
+///
+/// Primitives are inlined, registered types are referenced and other types accept any value.
+struct TypeRefSchema<'a> {
+    type_id: TypeId,
+    type_name: &'static str,
+    registry: &'a TypeRegistry,
+}
+
+impl<'a> TypeRefSchema<'a> {
+    fn new(type_id: TypeId, type_name: &'static str, registry: &'a TypeRegistry) -> Self {
+        Self {
+            type_id,
+            type_name,
+            registry,
+        }
+    }
+}
+
+impl<'a> Serialize for TypeRefSchema<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        if let Some(primitive) = PrimitiveSchema::of(self.type_id) {
+            return primitive.serialize(serializer);
+        }
+
+        let mut state = serializer.serialize_map(Some(1))?;
+        if self.registry.get(self.type_id).is_some() {
+            state.serialize_entry("$ref", &definition_ref(self.type_name))?;
+        } else {
+            state.serialize_entry("title", self.type_name)?;
+        }
+        state.end()
+    }
+}
+
+/// One of the alternatives of an enum schema.
+struct VariantSchema<'a> {
+    variant: &'a VariantInfo,
+    registry: &'a TypeRegistry,
+}
+
+impl<'a> Serialize for VariantSchema<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut state = serializer.serialize_map(None)?;
+        #[cfg(feature = "documentation")]
+        serialize_description(&mut state, self.variant.docs())?;
+
+        let name = self.variant.name();
+        if let VariantInfo::Unit(_) = self.variant {
+            state.serialize_entry("const", name)?;
+        } else {
+            state.serialize_entry("type", "object")?;
+            let value = VariantValueSchema {
+                variant: self.variant,
+                registry: self.registry,
+            };
+            state.serialize_entry("properties", &PropertiesSchema(vec![(name, value)]))?;
+            state.serialize_entry("required", &[name])?;
+            state.serialize_entry("additionalProperties", &false)?;
+        }
+        state.end()
+    }
+}
+
+/// The schema of the value of a non-unit variant.
+struct VariantValueSchema<'a> {
+    variant: &'a VariantInfo,
+    registry: &'a TypeRegistry,
+}
+
+impl<'a> Serialize for VariantValueSchema<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match self.variant {
+            VariantInfo::Tuple(variant) if variant.field_len() == 1 => {
+                let field = variant.field_at(0).unwrap();
+                TypeRefSchema::new(field.type_id(), field.type_name(), self.registry)
+                    .serialize(serializer)
+            }
+            VariantInfo::Tuple(variant) => {
+                let fields: Vec<_> = variant.iter().collect();
+                let mut state = serializer.serialize_map(None)?;
+                serialize_array_entries(&mut state, &fields, self.registry)?;
+                state.end()
+            }
+            VariantInfo::Struct(variant) => {
+                let fields: Vec<_> = variant.iter().collect();
+                let mut state = serializer.serialize_map(None)?;
+                serialize_object_entries(&mut state, &fields, self.registry)?;
+                state.end()
+            }
+            VariantInfo::Unit(_) => serializer.serialize_map(Some(0))?.end(),
+        }
+    }
+}
+
+/// A map from property names to their schema.
+struct PropertiesSchema<T>(Vec<(&'static str, T)>);
+
+impl<T> FromIterator<(&'static str, T)> for PropertiesSchema<T> {
+    fn from_iter<I: IntoIterator<Item = (&'static str, T)>>(iter: I) -> Self {
+        Self(iter.into_iter().collect())
+    }
+}
+
+impl<T: Serialize> Serialize for PropertiesSchema<T> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut state = serializer.serialize_map(Some(self.0.len()))?;
+        for (name, schema) in &self.0 {
+            state.serialize_entry(name, schema)?;
+        }
+        state.end()
+    }
+}
+
+/// The schema of a primitive or string value type.
+#[derive(Clone, Copy)]
+struct PrimitiveSchema {
+    type_name: &'static str,
+    minimum: Option<u8>,
+    length: Option<u8>,
+}
+
+impl PrimitiveSchema {
+    /// Returns the schema of the given type, if it is a primitive or string type.
+    fn of(type_id: TypeId) -> Option<Self> {
+        let schema = |type_name| PrimitiveSchema {
+            type_name,
+            minimum: None,
+            length: None,
+        };
+
+        let primitive = if type_id == TypeId::of::<bool>() {
+            schema("boolean")
+        } else if [
+            TypeId::of::<u8>(),
+            TypeId::of::<u16>(),
+            TypeId::of::<u32>(),
+            TypeId::of::<u64>(),
+            TypeId::of::<u128>(),
+            TypeId::of::<usize>(),
+        ]
+        .contains(&type_id)
+        {
+            PrimitiveSchema {
+                minimum: Some(0),
+                ..schema("integer")
+            }
+        } else if [
+            TypeId::of::<i8>(),
+            TypeId::of::<i16>(),
+            TypeId::of::<i32>(),
+            TypeId::of::<i64>(),
+            TypeId::of::<i128>(),
+            TypeId::of::<isize>(),
+        ]
+        .contains(&type_id)
+        {
+            schema("integer")
+        } else if [TypeId::of::<f32>(), TypeId::of::<f64>()].contains(&type_id) {
+            schema("number")
+        } else if type_id == TypeId::of::<char>() {
+            PrimitiveSchema {
+                length: Some(1),
+                ..schema("string")
+            }
+        } else if [
+            TypeId::of::<String>(),
+            TypeId::of::<&'static str>(),
+            TypeId::of::<Cow<'static, str>>(),
+        ]
+        .contains(&type_id)
+        {
+            schema("string")
+        } else {
+            return None;
+        };
+
+        Some(primitive)
+    }
+
+    fn serialize_entries<M: SerializeMap>(&self, state: &mut M) -> Result<(), M::Error> {
+        state.serialize_entry("type", self.type_name)?;
+        if let Some(minimum) = self.minimum {
+            state.serialize_entry("minimum", &minimum)?;
+        }
+        if let Some(length) = self.length {
+            state.serialize_entry("minLength", &length)?;
+            state.serialize_entry("maxLength", &length)?;
+        }
+        Ok(())
+    }
+}
+
+impl Serialize for PrimitiveSchema {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut state = serializer.serialize_map(None)?;
+        self.serialize_entries(&mut state)?;
+        state.end()
+    }
+}
+
+/// A list of the schemas of unnamed fields, used as `prefixItems`.
+struct PrefixItemsSchema<'a> {
+    fields: &'a [&'a UnnamedField],
+    registry: &'a TypeRegistry,
+}
+
+impl<'a> Serialize for PrefixItemsSchema<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut state = serializer.serialize_seq(Some(self.fields.len()))?;
+        for field in self.fields {
+            state.serialize_element(&TypeRefSchema::new(
+                field.type_id(),
+                field.type_name(),
+                self.registry,
+            ))?;
+        }
+        state.end()
+    }
+}
+
+/// Serializes the entries describing an object with the given named fields, all required.
+fn serialize_object_entries<M: SerializeMap>(
+    state: &mut M,
+    fields: &[&NamedField],
+    registry: &TypeRegistry,
+) -> Result<(), M::Error> {
+    let properties: PropertiesSchema<_> = fields
+        .iter()
+        .map(|field| (field.name(), FieldSchema { field, registry }))
+        .collect();
+    let required: Vec<_> = fields.iter().map(|field| field.name()).collect();
+
+    state.serialize_entry("type", "object")?;
+    state.serialize_entry("properties", &properties)?;
+    state.serialize_entry("required", &required)?;
+    state.serialize_entry("additionalProperties", &false)
+}
+
+/// Serializes the entries describing a fixed-length array with the given unnamed fields.
+fn serialize_array_entries<M: SerializeMap>(
+    state: &mut M,
+    fields: &[&UnnamedField],
+    registry: &TypeRegistry,
+) -> Result<(), M::Error> {
+    state.serialize_entry("type", "array")?;
+    state.serialize_entry("prefixItems", &PrefixItemsSchema { fields, registry })?;
+    state.serialize_entry("minItems", &fields.len())?;
+    state.serialize_entry("maxItems", &fields.len())
+}
+
+/// The schema of a named field, which includes its docs when available.
+struct FieldSchema<'a> {
+    field: &'a NamedField,
+    registry: &'a TypeRegistry,
+}
+
+impl<'a> Serialize for FieldSchema<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let schema =
+            TypeRefSchema::new(self.field.type_id(), self.field.type_name(), self.registry);
+
+        #[cfg(feature = "documentation")]
+        if let Some(docs) = self.field.docs() {
+            // Sibling keywords of `$ref` are allowed since draft 2019-09
+            let mut state = serializer.serialize_map(None)?;
+            state.serialize_entry("allOf", &[schema])?;
+            state.serialize_entry("description", docs.trim())?;
+            return state.end();
+        }
+
+        schema.serialize(serializer)
+    }
+}
+
+#[cfg(feature = "documentation")]
+fn serialize_description<M: SerializeMap>(
+    state: &mut M,
+    docs: Option<&str>,
+) -> Result<(), M::Error> {
+    if let Some(docs) = docs {
+        state.serialize_entry("description", docs.trim())?;
+    }
+    Ok(())
+}
+
+fn is_ignored(serialization_data: Option<&SerializationData>, index: usize) -> bool {
+    serialization_data
+        .map(|data| data.is_ignored_field(index))
+        .unwrap_or(false)
+}
+
+/// Returns the reference to the definition of the given type in `$defs`.
+///
+/// The type name is escaped as a JSON pointer and percent-encoded as an URI fragment.
+fn definition_ref(type_name: &str) -> String {
+    let mut reference = String::from("#/$defs/");
+    for c in type_name.chars() {
+        match c {
+            '~' => reference.push_str("~0"),
+            '/' => reference.push_str("~1"),
+            c if c.is_ascii_alphanumeric() || "-._:!$&'()*+,;=@".contains(c) => {
+                reference.push(c);
+            }
+            c => {
+                let mut buffer = [0; 4];
+                for byte in c.encode_utf8(&mut buffer).bytes() {
+                    write!(reference, "%{:02X}", byte).unwrap();
+                }
+            }
+        }
+    }
+    reference
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{definition_ref, TypeRegistrySchema};
+    use crate as bevy_reflect;
+    use crate::{Reflect, TypeRegistry};
+    use bevy_utils::HashMap;
+    use serde_json::{json, Value};
+    use std::any::type_name;
+
+    #[derive(Reflect)]
+    struct Position(f32, f32);
+
+    #[derive(Reflect)]
+    enum Team {
+        Neutral,
+        Player(u8),
+        Custom { name: String },
+    }
+
+    #[derive(Reflect)]
+    struct Unit {
+        position: Position,
+        team: Team,
+        tags: Vec<String>,
+        stats: HashMap<String, i32>,
+        target: Option<u32>,
+        #[reflect(skip_serializing)]
+        cache: usize,
+    }
+
+    fn schema() -> Value {
+        let mut registry = TypeRegistry::default();
+        registry.register::<Position>();
+        registry.register::<Team>();
+        registry.register::<Unit>();
+        registry.register::<Vec<String>>();
+        registry.register::<HashMap<String, i32>>();
+        registry.register::<Option<u32>>();
+
+        serde_json::to_value(TypeRegistrySchema::new(&registry)).unwrap()
+    }
+
+    fn reference<T>() -> Value {
+        json!({ "$ref": definition_ref(type_name::<T>()) })
+    }
+
+    #[test]
+    fn should_reference_every_registered_type() {
+        let schema = schema();
+
+        assert_eq!(
+            reference::<Unit>(),
+            schema["properties"][type_name::<Unit>()]
+        );
+        assert_eq!(json!(1), schema["maxProperties"]);
+        assert_eq!(
+            json!({ "title": "f32", "type": "number" }),
+            schema["$defs"]["f32"]
+        );
+    }
+
+    #[test]
+    fn should_describe_struct() {
+        let schema = schema();
+        let unit = &schema["$defs"][type_name::<Unit>()];
+
+        assert_eq!(
+            json!({
+                "title": type_name::<Unit>(),
+                "type": "object",
+                "properties": {
+                    "position": reference::<Position>(),
+                    "team": reference::<Team>(),
+                    "tags": reference::<Vec<String>>(),
+                    "stats": reference::<HashMap<String, i32>>(),
+                    "target": reference::<Option<u32>>(),
+                },
+                "required": ["position", "team", "tags", "stats", "target"],
+                "additionalProperties": false,
+            }),
+            *unit
+        );
+    }
+
+    #[test]
+    fn should_describe_tuple_struct() {
+        let schema = schema();
+
+        assert_eq!(
+            json!({
+                "title": type_name::<Position>(),
+                "type": "array",
+                "prefixItems": [{ "type": "number" }, { "type": "number" }],
+                "minItems": 2,
+                "maxItems": 2,
+            }),
+            schema["$defs"][type_name::<Position>()]
+        );
+    }
+
+    #[test]
+    fn should_describe_enum() {
+        let schema = schema();
+
+        assert_eq!(
+            json!([
+                { "const": "Neutral" },
+                {
+                    "type": "object",
+                    "properties": { "Player": { "type": "integer", "minimum": 0 } },
+                    "required": ["Player"],
+                    "additionalProperties": false,
+                },
+                {
+                    "type": "object",
+                    "properties": {
+                        "Custom": {
+                            "type": "object",
+                            "properties": { "name": { "type": "string" } },
+                            "required": ["name"],
+                            "additionalProperties": false,
+                        }
+                    },
+                    "required": ["Custom"],
+                    "additionalProperties": false,
+                },
+            ]),
+            schema["$defs"][type_name::<Team>()]["oneOf"]
+        );
+
+        assert_eq!(
+            json!([{ "type": "null" }, { "type": "integer", "minimum": 0 }]),
+            schema["$defs"][type_name::<Option<u32>>()]["oneOf"]
+        );
+    }
+
+    #[test]
+    fn should_describe_collections() {
+        let schema = schema();
+
+        let list = &schema["$defs"][type_name::<Vec<String>>()];
+        assert_eq!(json!("array"), list["type"]);
+        assert_eq!(json!({ "type": "string" }), list["items"]);
+
+        let map = &schema["$defs"][type_name::<HashMap<String, i32>>()];
+        assert_eq!(json!("object"), map["type"]);
+        assert_eq!(json!({ "type": "integer" }), map["additionalProperties"]);
+    }
+
+    #[test]
+    fn should_escape_definition_refs() {
+        assert_eq!(
+            "#/$defs/alloc::vec::Vec%3Cmy_crate::A~1B~0%20%C3%A9%3E",
+            definition_ref("alloc::vec::Vec<my_crate::A/B~ é>")
+        );
+    }
+}
@@ -1,5 +1,8 @@
+use crate::attributes::impl_custom_attribute_methods;
 use crate::utility::NonGenericTypeInfoCell;
-use crate::{DynamicInfo, NamedField, Reflect, ReflectMut, ReflectRef, TypeInfo, Typed};
+use crate::{
+    CustomAttributes, DynamicInfo, NamedField, Reflect, ReflectMut, ReflectRef, TypeInfo, Typed,
+};
 use bevy_utils::{Entry, HashMap};
 use std::fmt::{Debug, Formatter};
 use std::{
@@ -74,6 +77,7 @@ pub struct StructInfo {
     type_id: TypeId,
     fields: Box<[NamedField]>,
     field_indices: HashMap<&'static str, usize>,
+    custom_attributes: CustomAttributes,
     #[cfg(feature = "documentation")]
     docs: Option<&'static str>,
 }
@@ -99,6 +103,7 @@ impl StructInfo {
             type_id: TypeId::of::<T>(),
             fields: fields.to_vec().into_boxed_slice(),
             field_indices,
+            custom_attributes: CustomAttributes::default(),
             #[cfg(feature = "documentation")]
             docs: None,
         }
@@ -110,6 +115,8 @@ impl StructInfo {
         Self { docs, ..self }
     }
 
+    impl_custom_attribute_methods!("struct");
+
     /// Get the field with the given name.
     pub fn field(&self, name: &str) -> Option<&NamedField> {
         self.field_indices
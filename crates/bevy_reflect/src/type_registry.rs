@@ -239,6 +239,17 @@ impl TypeRegistry {
     pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut TypeRegistration> {
         self.registrations.values_mut()
     }
+
+    /// Returns an iterator over the registered types that contain the [`TypeData`] of type `T`,
+    /// along with that type data.
+    ///
+    /// This can be used to find every registered type implementing a reflected trait,
+    /// by querying the type data generated with [`#[reflect_trait]`](crate::reflect_trait).
+    pub fn iter_with_data<T: TypeData>(&self) -> impl Iterator<Item = (&TypeRegistration, &T)> {
+        self.registrations
+            .values()
+            .filter_map(|registration| registration.data::<T>().map(|data| (registration, data)))
+    }
 }
 
 impl TypeRegistryArc {
@@ -532,12 +543,12 @@ impl<T: Reflect> FromType<T> for ReflectFromPtr {
 mod test {
     use std::ptr::NonNull;
 
-    use crate::{GetTypeRegistration, ReflectFromPtr, TypeRegistration};
+    use crate::{GetTypeRegistration, ReflectFromPtr, TypeRegistration, TypeRegistry};
     use bevy_ptr::{Ptr, PtrMut};
     use bevy_utils::HashMap;
 
     use crate as bevy_reflect;
-    use crate::Reflect;
+    use crate::{reflect_trait, Reflect};
 
     #[test]
     fn test_reflect_from_ptr() {
@@ -615,4 +626,36 @@ mod test {
             "Option<HashMap<Option<String>, (String, Option<String>)>>"
         );
     }
+
+    #[test]
+    fn test_iter_with_data() {
+        #[reflect_trait]
+        trait Named {
+            fn name(&self) -> &str;
+        }
+
+        #[derive(Reflect)]
+        #[reflect(Named)]
+        struct Foo;
+
+        impl Named for Foo {
+            fn name(&self) -> &str {
+                "foo"
+            }
+        }
+
+        #[derive(Reflect)]
+        struct Bar;
+
+        let mut registry = TypeRegistry::empty();
+        registry.register::<Foo>();
+        registry.register::<Bar>();
+
+        let types: Vec<_> = registry.iter_with_data::<ReflectNamed>().collect();
+        assert_eq!(1, types.len());
+
+        let (registration, reflect_named) = types[0];
+        assert_eq!(std::any::TypeId::of::<Foo>(), registration.type_id());
+        assert_eq!("foo", reflect_named.get(&Foo).unwrap().name());
+    }
 }
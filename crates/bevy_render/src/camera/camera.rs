@@ -2,7 +2,10 @@ use crate::{
     camera::CameraProjection,
     prelude::Image,
     render_asset::RenderAssets,
-    render_resource::TextureView,
+    render_resource::{
+        BlendComponent, BlendFactor, BlendOperation, BlendState, OwnedBindingResource, TextureView,
+        TextureViewId,
+    },
     view::{ExtractedView, ExtractedWindows, VisibleEntities},
     Extract,
 };
@@ -24,7 +27,7 @@ use bevy_transform::components::GlobalTransform;
 use bevy_utils::HashSet;
 use bevy_window::{WindowCreated, WindowId, WindowResized, Windows};
 use std::{borrow::Cow, ops::Range};
-use wgpu::Extent3d;
+use wgpu::{Extent3d, TextureFormat};
 
 /// Render viewport configuration for the [`Camera`] component.
 ///
@@ -86,6 +89,9 @@ pub struct Camera {
     /// If set, this camera will render to the given [`Viewport`] rectangle within the configured [`RenderTarget`].
     pub viewport: Option<Viewport>,
     /// Cameras with a lower priority will be rendered before cameras with a higher priority.
+    ///
+    /// A camera sampling the [`RenderTarget::Image`] of other cameras is always rendered after
+    /// them, see [`CameraSampledTextures`].
     pub priority: isize,
     /// If this is set to `true`, this camera will be rendered to its specified [`RenderTarget`]. If `false`, this
     /// camera will not be rendered.
@@ -96,6 +102,16 @@ pub struct Camera {
     /// The "target" that this camera will render to.
     #[reflect(ignore)]
     pub target: RenderTarget,
+    /// If this is set to `true`, the camera will use an intermediate "high dynamic range" render
+    /// texture, whose colors are mapped to the displayable range by a tonemapping pass before
+    /// being written to the [`RenderTarget`]. This allows lighting values above 1.0, and
+    /// post-processing that relies on them.
+    ///
+    /// Cameras rendering to the same [`RenderTarget`] should use the same setting, as only cameras
+    /// with the same setting share their intermediate render texture.
+    pub hdr: bool,
+    /// How the output of this camera is written to its [`RenderTarget`].
+    pub output_mode: CameraOutputMode,
 }
 
 impl Default for Camera {
@@ -106,6 +122,8 @@ impl Default for Camera {
             viewport: None,
             computed: Default::default(),
             target: Default::default(),
+            hdr: false,
+            output_mode: Default::default(),
         }
     }
 }
@@ -275,6 +293,69 @@ impl Camera {
     }
 }
 
+/// How the output of a [`Camera`] is written to its [`RenderTarget`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Reflect, FromReflect)]
+pub enum CameraOutputMode {
+    /// The camera renders to the intermediate render texture it shares with the cameras of the
+    /// same target, which replaces the contents of the target.
+    #[default]
+    Write,
+    /// The camera renders to its own intermediate render texture, which is composited over the
+    /// contents of the target with the given blend mode.
+    ///
+    /// The texture is cleared to transparent unless the camera clears it to a custom color, so
+    /// only what the camera draws covers the target. Compositing cameras should have a higher
+    /// priority than the cameras they are composited over.
+    Composite(CameraBlendMode),
+}
+
+/// The blend mode compositing the output of a [`CameraOutputMode::Composite`] camera over its
+/// [`RenderTarget`].
+///
+/// The colors of the output are premultiplied by their alpha, as the camera draws over a
+/// transparent texture.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Reflect, FromReflect)]
+pub enum CameraBlendMode {
+    /// The output is drawn over the target according to its alpha.
+    #[default]
+    Alpha,
+    /// The colors of the output are added to the colors of the target.
+    Additive,
+    /// The colors of the target are multiplied by the colors of the output, according to its
+    /// alpha.
+    Multiply,
+}
+
+impl CameraBlendMode {
+    /// The [`BlendState`] of the pass compositing the output over the target.
+    pub fn blend_state(self) -> BlendState {
+        let keep_alpha = BlendComponent {
+            src_factor: BlendFactor::Zero,
+            dst_factor: BlendFactor::One,
+            operation: BlendOperation::Add,
+        };
+        match self {
+            CameraBlendMode::Alpha => BlendState::PREMULTIPLIED_ALPHA_BLENDING,
+            CameraBlendMode::Additive => BlendState {
+                color: BlendComponent {
+                    src_factor: BlendFactor::One,
+                    dst_factor: BlendFactor::One,
+                    operation: BlendOperation::Add,
+                },
+                alpha: keep_alpha,
+            },
+            CameraBlendMode::Multiply => BlendState {
+                color: BlendComponent {
+                    src_factor: BlendFactor::Dst,
+                    dst_factor: BlendFactor::OneMinusSrcAlpha,
+                    operation: BlendOperation::Add,
+                },
+                alpha: keep_alpha,
+            },
+        }
+    }
+}
+
 /// Configures the [`RenderGraph`](crate::render_graph::RenderGraph) name assigned to be run for a given [`Camera`] entity.
 #[derive(Component, Deref, DerefMut, Reflect, Default)]
 #[reflect(Component)]
@@ -319,6 +400,21 @@ impl RenderTarget {
         }
     }
 
+    /// Gets the format of the texture of this render target, given the format of the window
+    /// surfaces.
+    pub fn get_texture_format(
+        &self,
+        surface_format: TextureFormat,
+        images: &RenderAssets<Image>,
+    ) -> Option<TextureFormat> {
+        match self {
+            RenderTarget::Window(_) => Some(surface_format),
+            RenderTarget::Image(image_handle) => {
+                images.get(image_handle).map(|image| image.texture_format)
+            }
+        }
+    }
+
     pub fn get_render_target_info(
         &self,
         windows: &Windows,
@@ -440,6 +536,44 @@ pub struct ExtractedCamera {
     pub viewport: Option<Viewport>,
     pub render_graph: Cow<'static, str>,
     pub priority: isize,
+    pub output_mode: CameraOutputMode,
+}
+
+/// The textures sampled by the draws of a camera in the render world, which are recorded by the
+/// systems queuing them, like the ones of materials and sprites.
+///
+/// The [`CameraDriverNode`](crate::camera::CameraDriverNode) renders a camera sampling the
+/// [`RenderTarget::Image`] of other cameras after them, regardless of their
+/// [`Camera::priority`]. This component is only added to the cameras while some camera renders
+/// to an image.
+///
+/// Only the textures bound by materials, 2d materials and sprites are recorded. The images drawn by
+/// UI nodes aren't, so a camera whose UI shows the image of another camera must be given a higher
+/// priority than that camera.
+#[derive(Component, Default, Debug)]
+pub struct CameraSampledTextures {
+    texture_views: HashSet<TextureViewId>,
+}
+
+impl CameraSampledTextures {
+    /// Records that the camera samples `texture_view`.
+    pub fn insert(&mut self, texture_view: &TextureView) {
+        self.texture_views.insert(texture_view.id());
+    }
+
+    /// Records the texture views of `bindings` as sampled by the camera.
+    pub fn insert_bindings(&mut self, bindings: &[OwnedBindingResource]) {
+        for binding in bindings {
+            if let OwnedBindingResource::TextureView(texture_view) = binding {
+                self.insert(texture_view);
+            }
+        }
+    }
+
+    /// Returns `true` if the camera samples `texture_view`.
+    pub fn contains(&self, texture_view: &TextureView) -> bool {
+        self.texture_views.contains(&texture_view.id())
+    }
 }
 
 pub fn extract_cameras(
@@ -454,6 +588,9 @@ pub fn extract_cameras(
         )>,
     >,
 ) {
+    let render_to_image = query
+        .iter()
+        .any(|(_, camera, ..)| camera.is_active && matches!(camera.target, RenderTarget::Image(_)));
     for (entity, camera, camera_render_graph, transform, visible_entities) in query.iter() {
         if !camera.is_active {
             continue;
@@ -466,7 +603,8 @@ pub fn extract_cameras(
             if target_size.x == 0 || target_size.y == 0 {
                 continue;
             }
-            commands.get_or_spawn(entity).insert((
+            let mut entity_commands = commands.get_or_spawn(entity);
+            entity_commands.insert((
                 ExtractedCamera {
                     target: camera.target.clone(),
                     viewport: camera.viewport.clone(),
@@ -474,10 +612,12 @@ pub fn extract_cameras(
                     physical_target_size: Some(target_size),
                     render_graph: camera_render_graph.0.clone(),
                     priority: camera.priority,
+                    output_mode: camera.output_mode,
                 },
                 ExtractedView {
                     projection: camera.projection_matrix(),
                     transform: *transform,
+                    hdr: camera.hdr,
                     viewport: UVec4::new(
                         viewport_origin.x,
                         viewport_origin.y,
@@ -487,6 +627,36 @@ pub fn extract_cameras(
                 },
                 visible_entities.clone(),
             ));
+            if render_to_image {
+                entity_commands.insert(CameraSampledTextures::default());
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::CameraBlendMode;
+    use crate::render_resource::{BlendFactor, BlendState};
+
+    #[test]
+    fn blend_states_keep_the_target_alpha() {
+        assert_eq!(
+            CameraBlendMode::Alpha.blend_state(),
+            BlendState::PREMULTIPLIED_ALPHA_BLENDING
+        );
+        for blend_mode in [CameraBlendMode::Additive, CameraBlendMode::Multiply] {
+            let blend_state = blend_mode.blend_state();
+            assert_eq!(blend_state.alpha.src_factor, BlendFactor::Zero);
+            assert_eq!(blend_state.alpha.dst_factor, BlendFactor::One);
         }
+
+        let additive = CameraBlendMode::Additive.blend_state();
+        assert_eq!(additive.color.src_factor, BlendFactor::One);
+        assert_eq!(additive.color.dst_factor, BlendFactor::One);
+
+        let multiply = CameraBlendMode::Multiply.blend_state();
+        assert_eq!(multiply.color.src_factor, BlendFactor::Dst);
+        assert_eq!(multiply.color.dst_factor, BlendFactor::OneMinusSrcAlpha);
     }
 }
@@ -1,23 +1,128 @@
 use crate::{
-    camera::{ExtractedCamera, RenderTarget},
+    camera::{CameraSampledTextures, ExtractedCamera, RenderTarget},
+    prelude::Image,
+    render_asset::RenderAssets,
     render_graph::{Node, NodeRunError, RenderGraphContext, SlotValue},
     renderer::RenderContext,
     view::ExtractedWindows,
 };
 use bevy_ecs::{entity::Entity, prelude::QueryState, world::World};
 use bevy_utils::{tracing::warn, HashSet};
+use parking_lot::Mutex;
 use wgpu::{LoadOp, Operations, RenderPassColorAttachment, RenderPassDescriptor};
 
+/// Runs the render graph of every active camera.
+///
+/// The cameras are run in the order of their [`Camera::priority`](crate::camera::Camera::priority),
+/// except that a camera sampling the [`RenderTarget::Image`] of other cameras, as recorded in
+/// its [`CameraSampledTextures`], is run after them.
 pub struct CameraDriverNode {
-    cameras: QueryState<(Entity, &'static ExtractedCamera)>,
+    cameras: QueryState<(
+        Entity,
+        &'static ExtractedCamera,
+        Option<&'static CameraSampledTextures>,
+    )>,
+    /// The cameras of the dependency cycles last warned about, to only warn when they change.
+    cycle_cameras: Mutex<Vec<Entity>>,
 }
 
 impl CameraDriverNode {
     pub fn new(world: &mut World) -> Self {
         Self {
             cameras: world.query(),
+            cycle_cameras: Mutex::new(Vec::new()),
         }
     }
+
+    /// Returns the indices of the cameras, sorted by priority, that each camera has to be run
+    /// after, as it samples their [`RenderTarget::Image`].
+    fn dependencies(
+        &self,
+        world: &World,
+        sorted_cameras: &[(Entity, isize, RenderTarget)],
+    ) -> Vec<Vec<usize>> {
+        let images = world.get_resource::<RenderAssets<Image>>();
+        let image_targets = sorted_cameras
+            .iter()
+            .map(|(_, _, target)| match target {
+                RenderTarget::Image(handle) => images
+                    .and_then(|images| images.get(handle))
+                    .map(|image| &image.texture_view),
+                RenderTarget::Window(_) => None,
+            })
+            .collect::<Vec<_>>();
+        sorted_cameras
+            .iter()
+            .map(|(entity, _, target)| {
+                let sampled_textures = match self.cameras.get_manual(world, *entity) {
+                    Ok((_, _, Some(sampled_textures))) => sampled_textures,
+                    _ => return Vec::new(),
+                };
+                sorted_cameras
+                    .iter()
+                    .zip(&image_targets)
+                    .enumerate()
+                    .filter(|(_, ((_, _, other_target), image_target))| {
+                        other_target != target
+                            && image_target.map_or(false, |texture_view| {
+                                sampled_textures.contains(texture_view)
+                            })
+                    })
+                    .map(|(index, _)| index)
+                    .collect::<Vec<_>>()
+            })
+            .collect()
+    }
+}
+
+/// Orders the indices of the cameras sorted by priority, given the indices of the cameras each
+/// camera has to be run after.
+///
+/// Starting from the camera with the lowest priority that isn't ordered yet, the cameras it
+/// depends on are ordered before it, in the order of their priorities. So every camera is run
+/// after its dependencies, and the order of the cameras that don't depend on each other follows
+/// their priorities.
+///
+/// Returns the order, and the cameras depended on by a camera that they depend on in turn. The
+/// dependencies closing such a cycle are ignored.
+fn order_by_dependencies(dependencies: &[Vec<usize>]) -> (Vec<usize>, Vec<usize>) {
+    #[derive(Clone, Copy, PartialEq, Eq)]
+    enum Visit {
+        Pending,
+        Visiting,
+        Done,
+    }
+
+    fn visit(
+        index: usize,
+        dependencies: &[Vec<usize>],
+        visits: &mut [Visit],
+        order: &mut Vec<usize>,
+        cycles: &mut Vec<usize>,
+    ) {
+        visits[index] = Visit::Visiting;
+        let mut sorted_dependencies = dependencies[index].clone();
+        sorted_dependencies.sort_unstable();
+        for dependency in sorted_dependencies {
+            match visits[dependency] {
+                Visit::Pending => visit(dependency, dependencies, visits, order, cycles),
+                Visit::Visiting if !cycles.contains(&dependency) => cycles.push(dependency),
+                _ => {}
+            }
+        }
+        visits[index] = Visit::Done;
+        order.push(index);
+    }
+
+    let mut order = Vec::with_capacity(dependencies.len());
+    let mut cycles = Vec::new();
+    let mut visits = vec![Visit::Pending; dependencies.len()];
+    for index in 0..dependencies.len() {
+        if visits[index] == Visit::Pending {
+            visit(index, dependencies, &mut visits, &mut order, &mut cycles);
+        }
+    }
+    (order, cycles)
 }
 
 impl Node for CameraDriverNode {
@@ -33,25 +138,46 @@ impl Node for CameraDriverNode {
         let mut sorted_cameras = self
             .cameras
             .iter_manual(world)
-            .map(|(e, c)| (e, c.priority, c.target.clone()))
+            .map(|(e, c, _)| (e, c.priority, c.target.clone()))
             .collect::<Vec<_>>();
         // sort by priority and ensure within a priority, RenderTargets of the same type are packed together
         sorted_cameras.sort_by(|(_, p1, t1), (_, p2, t2)| match p1.cmp(p2) {
             std::cmp::Ordering::Equal => t1.cmp(t2),
             ord => ord,
         });
-        let mut camera_windows = HashSet::new();
         let mut previous_priority_target = None;
         let mut ambiguities = HashSet::new();
-        for (entity, priority, target) in sorted_cameras {
+        for (_, priority, target) in &sorted_cameras {
             let new_priority_target = (priority, target);
             if let Some(previous_priority_target) = previous_priority_target {
                 if previous_priority_target == new_priority_target {
-                    ambiguities.insert(new_priority_target.clone());
+                    ambiguities.insert((*priority, target.clone()));
                 }
             }
             previous_priority_target = Some(new_priority_target);
-            if let Ok((_, camera)) = self.cameras.get_manual(world, entity) {
+        }
+
+        let (order, cycles) = order_by_dependencies(&self.dependencies(world, &sorted_cameras));
+        let cycle_cameras = cycles
+            .iter()
+            .map(|&index| sorted_cameras[index].0)
+            .collect::<Vec<_>>();
+        let mut last_cycle_cameras = self.cycle_cameras.lock();
+        if *last_cycle_cameras != cycle_cameras {
+            if !cycle_cameras.is_empty() {
+                warn!(
+                    "The cameras {:?} sample the render target images of cameras sampling theirs, \
+                    so some cameras are rendered before the cameras they sample.",
+                    cycle_cameras
+                );
+            }
+            *last_cycle_cameras = cycle_cameras;
+        }
+        drop(last_cycle_cameras);
+
+        let mut camera_windows = HashSet::new();
+        for entity in order.into_iter().map(|index| sorted_cameras[index].0) {
+            if let Ok((_, camera, _)) = self.cameras.get_manual(world, entity) {
                 if let RenderTarget::Window(id) = camera.target {
                     camera_windows.insert(id);
                 }
@@ -107,3 +233,43 @@ impl Node for CameraDriverNode {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::order_by_dependencies;
+
+    #[test]
+    fn order_follows_priorities() {
+        assert_eq!(
+            order_by_dependencies(&[vec![], vec![], vec![]]),
+            (vec![0, 1, 2], vec![])
+        );
+    }
+
+    #[test]
+    fn order_runs_dependencies_first() {
+        // a window camera with priority 0 samples the image of a camera with priority 5, and a
+        // camera with priority 1 is composited over the window
+        assert_eq!(
+            order_by_dependencies(&[vec![2], vec![], vec![]]),
+            (vec![2, 0, 1], vec![])
+        );
+        // the dependencies of a dependency are run first, in the order of their priorities
+        assert_eq!(
+            order_by_dependencies(&[vec![3], vec![], vec![], vec![4, 2], vec![]]),
+            (vec![2, 4, 3, 0, 1], vec![])
+        );
+    }
+
+    #[test]
+    fn order_ignores_cycles() {
+        assert_eq!(
+            order_by_dependencies(&[vec![1], vec![0], vec![]]),
+            (vec![1, 0, 2], vec![0])
+        );
+        assert_eq!(
+            order_by_dependencies(&[vec![], vec![2], vec![1, 0]]),
+            (vec![0, 2, 1], vec![1])
+        );
+    }
+}
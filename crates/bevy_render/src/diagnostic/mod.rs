@@ -0,0 +1,7 @@
+//! Diagnostic providers for `bevy_diagnostic`.
+
+mod pipeline_cache_diagnostics_plugin;
+mod render_graph_diagnostics_plugin;
+
+pub use pipeline_cache_diagnostics_plugin::PipelineCacheDiagnosticsPlugin;
+pub use render_graph_diagnostics_plugin::RenderGraphDiagnosticsPlugin;
@@ -0,0 +1,72 @@
+use crate::render_resource::PipelineCacheStats;
+use bevy_app::prelude::*;
+use bevy_diagnostic::{Diagnostic, DiagnosticId, Diagnostics};
+use bevy_ecs::system::{Local, Res, ResMut};
+
+/// Adds diagnostics of the pipelines of the [`PipelineCache`](crate::render_resource::PipelineCache)
+/// to an [`App`].
+///
+/// The diagnostics are the number of pipelines waiting for their shaders, the number of pipelines
+/// created during the last frame and the number of pipelines that failed to be created since the
+/// start of the app.
+#[derive(Default)]
+pub struct PipelineCacheDiagnosticsPlugin;
+
+impl Plugin for PipelineCacheDiagnosticsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_startup_system(Self::setup_system)
+            .add_system(Self::diagnostic_system);
+    }
+}
+
+impl PipelineCacheDiagnosticsPlugin {
+    /// The number of pipelines waiting for their shaders or their imports to be loaded.
+    pub const QUEUED_PIPELINES: DiagnosticId =
+        DiagnosticId::from_u128(33410936702883519237811406137049834593);
+    /// The number of pipelines created during the last frame.
+    pub const COMPILED_PIPELINES: DiagnosticId =
+        DiagnosticId::from_u128(174236734150733862716384611342512879408);
+    /// The number of pipelines that failed to be created since the start of the app.
+    pub const FAILED_PIPELINES: DiagnosticId =
+        DiagnosticId::from_u128(291877612450930734129803442207195601157);
+
+    /// Registers the pipeline diagnostics for the current application.
+    pub fn setup_system(mut diagnostics: ResMut<Diagnostics>) {
+        diagnostics.add(Diagnostic::new(
+            Self::QUEUED_PIPELINES,
+            "pipelines_queued",
+            20,
+        ));
+        diagnostics.add(Diagnostic::new(
+            Self::COMPILED_PIPELINES,
+            "pipelines_compiled",
+            20,
+        ));
+        diagnostics.add(Diagnostic::new(
+            Self::FAILED_PIPELINES,
+            "pipelines_failed",
+            1,
+        ));
+    }
+
+    /// Updates the pipeline diagnostics, which are only measured when the
+    /// [`RenderPlugin`](crate::RenderPlugin) initialized a renderer.
+    pub fn diagnostic_system(
+        mut diagnostics: ResMut<Diagnostics>,
+        stats: Option<Res<PipelineCacheStats>>,
+        mut last_compiled: Local<usize>,
+    ) {
+        let stats = match stats {
+            Some(stats) => stats,
+            None => return,
+        };
+
+        diagnostics.add_measurement(Self::QUEUED_PIPELINES, || stats.queued() as f64);
+        let compiled = stats.compiled();
+        diagnostics.add_measurement(Self::COMPILED_PIPELINES, || {
+            (compiled - *last_compiled) as f64
+        });
+        *last_compiled = compiled;
+        diagnostics.add_measurement(Self::FAILED_PIPELINES, || stats.failed() as f64);
+    }
+}
@@ -0,0 +1,112 @@
+use crate::{
+    renderer::{RenderDevice, RenderGraphProfiler, RenderGraphTimings, RenderQueue},
+    RenderApp,
+};
+use bevy_app::prelude::*;
+use bevy_diagnostic::{fit_diagnostic_name, Diagnostic, DiagnosticId, Diagnostics};
+use bevy_ecs::system::{Local, Res, ResMut};
+use bevy_utils::HashMap;
+
+/// Adds the time spent running every node of the [`RenderGraph`](crate::render_graph::RenderGraph)
+/// as diagnostics to an [`App`].
+///
+/// Every node run during a frame gets a `<path> cpu` diagnostic, where the path is made of the
+/// name of the sub graph that ran the node and the name of the node, e.g. `core_3d/main_pass cpu`.
+/// If the adapter supports [`wgpu::Features::TIMESTAMP_QUERY`], the node also gets a `<path> gpu`
+/// diagnostic. Both are measured in milliseconds.
+///
+/// The start of the paths longer than
+/// [`MAX_DIAGNOSTIC_NAME_WIDTH`](bevy_diagnostic::MAX_DIAGNOSTIC_NAME_WIDTH) is replaced by `…`,
+/// see [`fit_diagnostic_name`]. The ids of the diagnostics are derived from the paths, see
+/// [`Self::cpu_diagnostic_id`] and [`Self::gpu_diagnostic_id`].
+///
+/// This plugin has to be added after the [`RenderPlugin`](crate::RenderPlugin).
+#[derive(Default)]
+pub struct RenderGraphDiagnosticsPlugin;
+
+impl Plugin for RenderGraphDiagnosticsPlugin {
+    fn build(&self, app: &mut App) {
+        let timings = RenderGraphTimings::default();
+        if let Ok(render_app) = app.get_sub_app_mut(RenderApp) {
+            let profiler = RenderGraphProfiler::new(
+                render_app.world.resource::<RenderDevice>(),
+                render_app.world.resource::<RenderQueue>(),
+                timings.clone(),
+            );
+            render_app
+                .insert_resource(profiler)
+                .insert_resource(timings.clone());
+        }
+        app.insert_resource(timings)
+            .add_system(Self::diagnostic_system);
+    }
+}
+
+impl RenderGraphDiagnosticsPlugin {
+    /// Returns the id of the diagnostic of the CPU time of the node at `path`, which is the same
+    /// in every run of the app.
+    pub fn cpu_diagnostic_id(path: &str) -> DiagnosticId {
+        diagnostic_id(path, "cpu")
+    }
+
+    /// Returns the id of the diagnostic of the GPU time of the node at `path`, which is the same
+    /// in every run of the app.
+    pub fn gpu_diagnostic_id(path: &str) -> DiagnosticId {
+        diagnostic_id(path, "gpu")
+    }
+
+    /// Updates the diagnostics of the render graph nodes, registering the diagnostics of the nodes
+    /// run for the first time.
+    pub fn diagnostic_system(
+        mut diagnostics: ResMut<Diagnostics>,
+        timings: Res<RenderGraphTimings>,
+        mut ids: Local<HashMap<(String, &'static str), DiagnosticId>>,
+    ) {
+        let mut add_measurement = |path: String, kind: &'static str, value: f64| {
+            let id = *ids.entry((path, kind)).or_insert_with_key(|(path, kind)| {
+                let id = diagnostic_id(path, kind);
+                let name = fit_diagnostic_name(path, kind);
+                diagnostics.add(Diagnostic::new(id, name, 20).with_suffix("ms"));
+                id
+            });
+            diagnostics.add_measurement(id, || value);
+        };
+
+        for node in timings.nodes() {
+            if let Some(gpu) = node.gpu {
+                add_measurement(node.path.clone(), "gpu", gpu.as_secs_f64() * 1000.0);
+            }
+            add_measurement(node.path, "cpu", node.cpu.as_secs_f64() * 1000.0);
+        }
+    }
+}
+
+/// Derives the id of a diagnostic of a node from its path.
+fn diagnostic_id(path: &str, kind: &str) -> DiagnosticId {
+    /// Distinguishes the ids of the node diagnostics from the other diagnostic ids.
+    const NAMESPACE: u128 = 0x5e7a_41d6_03c8_4f2b_9d17_0000_0000_0000;
+
+    DiagnosticId::from_name(NAMESPACE, &format!("{path} {kind}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::RenderGraphDiagnosticsPlugin;
+
+    #[test]
+    fn diagnostic_ids_are_derived_from_paths() {
+        let id = RenderGraphDiagnosticsPlugin::cpu_diagnostic_id("core_3d/main_pass");
+        assert_eq!(
+            id,
+            RenderGraphDiagnosticsPlugin::cpu_diagnostic_id("core_3d/main_pass")
+        );
+        assert_ne!(
+            id,
+            RenderGraphDiagnosticsPlugin::gpu_diagnostic_id("core_3d/main_pass")
+        );
+        assert_ne!(
+            id,
+            RenderGraphDiagnosticsPlugin::cpu_diagnostic_id("core_2d/main_pass")
+        );
+    }
+}
@@ -2,6 +2,7 @@ extern crate core;
 
 pub mod camera;
 pub mod color;
+pub mod diagnostic;
 pub mod extract_component;
 mod extract_param;
 pub mod extract_resource;
@@ -47,12 +48,12 @@ use crate::{
     mesh::MeshPlugin,
     primitives::{CubemapFrusta, Frustum},
     render_graph::RenderGraph,
-    render_resource::{PipelineCache, Shader, ShaderLoader},
+    render_resource::{PipelineCache, PipelineFailed, Shader, ShaderLoader},
     renderer::{render_system, RenderInstance, RenderTextureFormat},
     texture::{BevyDefault, ImagePlugin},
-    view::{ViewPlugin, WindowRenderPlugin},
+    view::{ScreenshotPlugin, ViewPlugin, WindowRenderPlugin},
 };
-use bevy_app::{App, AppLabel, Plugin};
+use bevy_app::{App, AppLabel, CoreStage, Plugin};
 use bevy_asset::{AddAsset, AssetServer};
 use bevy_ecs::prelude::*;
 use bevy_utils::tracing::debug;
@@ -140,15 +141,17 @@ impl Plugin for RenderPlugin {
         app.add_asset::<Shader>()
             .add_debug_asset::<Shader>()
             .init_asset_loader::<ShaderLoader>()
-            .init_debug_asset_loader::<ShaderLoader>()
             .register_type::<Color>();
 
         if let Some(backends) = options.backends {
-            let windows = app.world.resource_mut::<bevy_window::Windows>();
             let instance = wgpu::Instance::new(backends);
 
-            let surface = windows
-                .get_primary()
+            // headless apps don't have a primary window, and the adapter isn't required to
+            // support a surface
+            let surface = app
+                .world
+                .get_resource::<bevy_window::Windows>()
+                .and_then(|windows| windows.get_primary())
                 .and_then(|window| window.raw_handle())
                 .map(|wrapper| unsafe {
                     let handle = wrapper.get_handle();
@@ -158,7 +161,7 @@ impl Plugin for RenderPlugin {
             let request_adapter_options = wgpu::RequestAdapterOptions {
                 power_preference: options.power_preference,
                 compatible_surface: surface.as_ref(),
-                ..Default::default()
+                force_fallback_adapter: options.force_fallback_adapter,
             };
             let (device, queue, adapter_info, render_adapter, available_texture_formats) =
                 futures_lite::future::block_on(renderer::initialize_renderer(
@@ -184,7 +187,22 @@ impl Plugin for RenderPlugin {
                 .register_type::<Frustum>()
                 .register_type::<CubemapFrusta>();
 
-            let pipeline_cache = PipelineCache::new(device.clone());
+            let pipeline_cache_settings = app
+                .world
+                .get_resource::<settings::PipelineCacheSettings>()
+                .cloned()
+                .unwrap_or_default();
+            let mut pipeline_cache = PipelineCache::new(device.clone());
+            if let Some(path) = pipeline_cache_settings.persistence_path {
+                pipeline_cache = pipeline_cache.with_persistence_path(path);
+            }
+            // the failures of the pipelines are sent as events in the main world
+            app.insert_resource(pipeline_cache.stats().clone())
+                .add_event::<PipelineFailed>()
+                .add_system_to_stage(
+                    CoreStage::PreUpdate,
+                    render_resource::send_pipeline_failed_events,
+                );
             let asset_server = app.world.resource::<AssetServer>().clone();
 
             let mut render_app = App::empty();
@@ -340,6 +358,7 @@ impl Plugin for RenderPlugin {
 
         app.add_plugin(ValidParentCheckPlugin::<ComputedVisibility>::default())
             .add_plugin(WindowRenderPlugin)
+            .add_plugin(ScreenshotPlugin)
             .add_plugin(CameraPlugin)
             .add_plugin(ViewPlugin)
             .add_plugin(MeshPlugin)
@@ -0,0 +1,162 @@
+use anyhow::{anyhow, Result};
+use bevy_asset::{AssetLoader, LoadContext, LoadedAsset};
+use bevy_utils::BoxedFuture;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use wgpu::{PrimitiveTopology, VertexFormat};
+
+use crate::mesh::{Indices, Mesh, MeshVertexAttribute, VertexAttributeValues};
+
+/// Loader for meshes stored in the RON format written by the [`MeshSaver`](super::MeshSaver).
+///
+/// A mesh file lists the values of its vertex attributes by attribute name:
+/// ```ron
+/// (
+///     primitive_topology: TriangleList,
+///     attributes: {
+///         "Vertex_Position": Float32x3([(0.0, 0.0, 0.0), (1.0, 0.0, 0.0), (0.0, 1.0, 0.0)]),
+///     },
+///     indices: Some(U16([0, 1, 2])),
+/// )
+/// ```
+/// Only the attributes of [`Mesh`] itself, like [`Mesh::ATTRIBUTE_POSITION`], can be stored.
+#[derive(Default)]
+pub struct MeshLoader;
+
+pub(crate) const FILE_EXTENSIONS: &[&str] = &["mesh.ron"];
+
+/// The vertex attributes that can be stored in mesh files.
+const FILE_ATTRIBUTES: &[MeshVertexAttribute] = &[
+    Mesh::ATTRIBUTE_POSITION,
+    Mesh::ATTRIBUTE_NORMAL,
+    Mesh::ATTRIBUTE_UV_0,
+    Mesh::ATTRIBUTE_TANGENT,
+    Mesh::ATTRIBUTE_COLOR,
+    Mesh::ATTRIBUTE_JOINT_WEIGHT,
+    Mesh::ATTRIBUTE_JOINT_INDEX,
+];
+
+/// The content of a mesh file.
+#[derive(Serialize, Deserialize)]
+pub(crate) struct MeshFile {
+    pub primitive_topology: MeshFileTopology,
+    pub attributes: BTreeMap<String, VertexAttributeValues>,
+    #[serde(default)]
+    pub indices: Option<Indices>,
+}
+
+/// The [`PrimitiveTopology`] of a mesh file.
+#[derive(Serialize, Deserialize)]
+pub(crate) enum MeshFileTopology {
+    PointList,
+    LineList,
+    LineStrip,
+    TriangleList,
+    TriangleStrip,
+}
+
+impl From<PrimitiveTopology> for MeshFileTopology {
+    fn from(topology: PrimitiveTopology) -> Self {
+        match topology {
+            PrimitiveTopology::PointList => MeshFileTopology::PointList,
+            PrimitiveTopology::LineList => MeshFileTopology::LineList,
+            PrimitiveTopology::LineStrip => MeshFileTopology::LineStrip,
+            PrimitiveTopology::TriangleList => MeshFileTopology::TriangleList,
+            PrimitiveTopology::TriangleStrip => MeshFileTopology::TriangleStrip,
+        }
+    }
+}
+
+impl From<MeshFileTopology> for PrimitiveTopology {
+    fn from(topology: MeshFileTopology) -> Self {
+        match topology {
+            MeshFileTopology::PointList => PrimitiveTopology::PointList,
+            MeshFileTopology::LineList => PrimitiveTopology::LineList,
+            MeshFileTopology::LineStrip => PrimitiveTopology::LineStrip,
+            MeshFileTopology::TriangleList => PrimitiveTopology::TriangleList,
+            MeshFileTopology::TriangleStrip => PrimitiveTopology::TriangleStrip,
+        }
+    }
+}
+
+impl MeshFile {
+    /// Copies the topology, attributes and indices of `mesh`.
+    ///
+    /// Fails if `mesh` has attributes that can't be stored in mesh files.
+    pub fn from_mesh(mesh: &Mesh) -> Result<Self> {
+        let mut attributes = BTreeMap::new();
+        for (id, values) in mesh.attributes() {
+            let attribute = FILE_ATTRIBUTES
+                .iter()
+                .find(|attribute| attribute.id == id)
+                .ok_or_else(|| anyhow!("Mesh attribute {id:?} can't be saved in a mesh file"))?;
+            attributes.insert(attribute.name.to_string(), values.clone());
+        }
+
+        Ok(MeshFile {
+            primitive_topology: mesh.primitive_topology().into(),
+            attributes,
+            indices: mesh.indices().cloned(),
+        })
+    }
+
+    /// Builds the mesh described by the file, checking the attributes and indices first as
+    /// [`Mesh`] panics on invalid ones.
+    pub fn into_mesh(self) -> Result<Mesh> {
+        let mut mesh = Mesh::new(self.primitive_topology.into());
+        let mut vertex_count = None;
+        for (name, values) in self.attributes {
+            let attribute = FILE_ATTRIBUTES
+                .iter()
+                .find(|attribute| attribute.name == name)
+                .ok_or_else(|| anyhow!("Unknown mesh attribute {name}"))?;
+            let format = VertexFormat::from(&values);
+            if format != attribute.format {
+                return Err(anyhow!(
+                    "Mesh attribute {name} has the format {format:?} instead of {:?}",
+                    attribute.format
+                ));
+            }
+            match vertex_count {
+                Some(vertex_count) if vertex_count != values.len() => {
+                    return Err(anyhow!(
+                        "Mesh attribute {name} has {} vertices instead of {vertex_count}",
+                        values.len()
+                    ));
+                }
+                _ => vertex_count = Some(values.len()),
+            }
+            mesh.insert_attribute(attribute.clone(), values);
+        }
+
+        if let Some(indices) = &self.indices {
+            let vertex_count = vertex_count.unwrap_or(0);
+            if let Some(index) = indices.iter().find(|index| *index >= vertex_count) {
+                return Err(anyhow!(
+                    "Mesh index {index} is out of bounds for {vertex_count} vertices"
+                ));
+            }
+        }
+        mesh.set_indices(self.indices);
+
+        Ok(mesh)
+    }
+}
+
+impl AssetLoader for MeshLoader {
+    fn load<'a>(
+        &'a self,
+        bytes: &'a [u8],
+        load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<()>> {
+        Box::pin(async move {
+            let mesh_file: MeshFile = ron::de::from_bytes(bytes)?;
+            load_context.set_default_asset(LoadedAsset::new(mesh_file.into_mesh()?));
+            Ok(())
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        FILE_EXTENSIONS
+    }
+}
@@ -0,0 +1,135 @@
+use anyhow::Result;
+use bevy_asset::AssetSaver;
+use ron::ser::PrettyConfig;
+use std::path::Path;
+
+use crate::mesh::{
+    mesh_loader::{MeshFile, FILE_EXTENSIONS},
+    Mesh,
+};
+
+/// Saves [`Mesh`]es in the RON format read by the [`MeshLoader`](super::MeshLoader).
+///
+/// Meshes with attributes other than the ones of [`Mesh`] itself can't be saved.
+#[derive(Default)]
+pub struct MeshSaver;
+
+impl AssetSaver for MeshSaver {
+    type Asset = Mesh;
+
+    fn save(&self, mesh: &Mesh, _path: &Path) -> Result<Vec<u8>> {
+        let pretty_config = PrettyConfig::default()
+            .indentor("  ".to_string())
+            .new_line("\n".to_string())
+            .compact_arrays(true);
+        let ron = ron::ser::to_string_pretty(&MeshFile::from_mesh(mesh)?, pretty_config)?;
+        Ok(ron.into_bytes())
+    }
+
+    fn extensions(&self) -> &[&str] {
+        FILE_EXTENSIONS
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::MeshSaver;
+    use crate::mesh::{mesh_loader::MeshFile, Indices, Mesh, MeshVertexAttribute};
+    use bevy_asset::AssetSaver;
+    use std::path::Path;
+    use wgpu::{PrimitiveTopology, VertexFormat};
+
+    fn triangle() -> Mesh {
+        let mut mesh = Mesh::new(PrimitiveTopology::TriangleList);
+        mesh.insert_attribute(
+            Mesh::ATTRIBUTE_POSITION,
+            vec![[0.0, 0.0, 0.0], [1.0, 0.0, 0.0], [0.0, 1.0, 0.0]],
+        );
+        mesh.insert_attribute(Mesh::ATTRIBUTE_NORMAL, vec![[0.0, 0.0, 1.0]; 3]);
+        mesh.insert_attribute(
+            Mesh::ATTRIBUTE_UV_0,
+            vec![[0.0, 0.0], [1.0, 0.0], [0.0, 1.0]],
+        );
+        mesh.set_indices(Some(Indices::U16(vec![0, 1, 2])));
+        mesh
+    }
+
+    fn load(bytes: &[u8]) -> anyhow::Result<Mesh> {
+        ron::de::from_bytes::<MeshFile>(bytes)?.into_mesh()
+    }
+
+    #[test]
+    fn round_trip_mesh() {
+        let mesh = triangle();
+        let bytes = MeshSaver
+            .save(&mesh, Path::new("triangle.mesh.ron"))
+            .unwrap();
+        let loaded = load(&bytes).unwrap();
+
+        assert_eq!(loaded.primitive_topology(), PrimitiveTopology::TriangleList);
+        assert_eq!(
+            loaded.get_vertex_buffer_data(),
+            mesh.get_vertex_buffer_data()
+        );
+        assert_eq!(
+            loaded.get_index_buffer_bytes(),
+            mesh.get_index_buffer_bytes()
+        );
+    }
+
+    #[test]
+    fn custom_attributes_are_not_saved() {
+        let mut mesh = triangle();
+        mesh.insert_attribute(
+            MeshVertexAttribute::new("Vertex_Custom", 1_000, VertexFormat::Float32),
+            vec![0.0; 3],
+        );
+
+        assert!(MeshSaver
+            .save(&mesh, Path::new("triangle.mesh.ron"))
+            .is_err());
+    }
+
+    #[test]
+    fn invalid_mesh_files_are_rejected() {
+        let invalid_mesh_files = [
+            // the format of the attribute doesn't match
+            r#"(
+                primitive_topology: TriangleList,
+                attributes: {"Vertex_Position": Float32x2([(0.0, 0.0)])},
+            )"#,
+            // the attributes have different vertex counts
+            r#"(
+                primitive_topology: TriangleList,
+                attributes: {
+                    "Vertex_Position": Float32x3([(0.0, 0.0, 0.0)]),
+                    "Vertex_Uv": Float32x2([(0.0, 0.0), (1.0, 0.0)]),
+                },
+            )"#,
+            // an index is out of bounds
+            r#"(
+                primitive_topology: TriangleList,
+                attributes: {"Vertex_Position": Float32x3([(0.0, 0.0, 0.0)])},
+                indices: Some(U32([0, 0, 1])),
+            )"#,
+            // the attribute isn't one of the mesh attributes
+            r#"(
+                primitive_topology: TriangleList,
+                attributes: {"Vertex_Custom": Float32([0.0])},
+            )"#,
+        ];
+        for mesh_file in invalid_mesh_files {
+            assert!(load(mesh_file.as_bytes()).is_err(), "{mesh_file}");
+        }
+
+        let mesh = load(
+            br#"(
+                primitive_topology: LineList,
+                attributes: {"Vertex_Position": Float32x3([(0.0, 0.0, 0.0), (1.0, 0.0, 0.0)])},
+            )"#,
+        )
+        .unwrap();
+        assert_eq!(mesh.primitive_topology(), PrimitiveTopology::LineList);
+        assert_eq!(mesh.count_vertices(), 2);
+    }
+}
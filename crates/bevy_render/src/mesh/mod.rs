@@ -1,9 +1,13 @@
 #[allow(clippy::module_inception)]
 mod mesh;
+mod mesh_loader;
+mod mesh_saver;
 /// Generation for some primitive shape meshes.
 pub mod shape;
 
 pub use mesh::*;
+pub use mesh_loader::*;
+pub use mesh_saver::*;
 
 use crate::render_asset::RenderAssetPlugin;
 use bevy_app::{App, Plugin};
@@ -15,6 +19,8 @@ pub struct MeshPlugin;
 impl Plugin for MeshPlugin {
     fn build(&self, app: &mut App) {
         app.add_asset::<Mesh>()
+            .init_asset_loader::<MeshLoader>()
+            .init_asset_saver::<MeshSaver>()
             .add_asset::<skinning::SkinnedMeshInverseBindposes>()
             .register_type::<skinning::SkinnedMesh>()
             .add_plugin(RenderAssetPlugin::<Mesh>::default());
@@ -1,13 +1,16 @@
 use crate::{
     render_graph::{
         Edge, Node, NodeId, NodeLabel, NodeRunError, NodeState, RenderGraphContext,
-        RenderGraphError, SlotInfo, SlotLabel,
+        RenderGraphError, SlotInfo, SlotInfos, SlotLabel,
     },
     renderer::RenderContext,
 };
 use bevy_ecs::{prelude::World, system::Resource};
 use bevy_utils::HashMap;
-use std::{borrow::Cow, fmt::Debug};
+use std::{
+    borrow::Cow,
+    fmt::{Debug, Write},
+};
 
 use super::EdgeExistence;
 
@@ -514,6 +517,113 @@ impl RenderGraph {
     pub fn get_sub_graph_mut(&mut self, name: impl AsRef<str>) -> Option<&mut RenderGraph> {
         self.sub_graphs.get_mut(name.as_ref())
     }
+
+    /// Returns a description of this graph, its sub graphs and the slots of their nodes in the
+    /// [DOT language](https://graphviz.org/doc/info/lang.html), which can be rendered with Graphviz.
+    ///
+    /// Every node lists its input slots on its left and its output slots on its right.
+    /// Slot edges connect these slots, while node edges are dashed.
+    /// Sub graphs are drawn as clusters, since they are run by the nodes and not connected by edges.
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::new();
+        writeln!(dot, "digraph render_graph {{").unwrap();
+        writeln!(dot, "    rankdir=LR;").unwrap();
+        writeln!(dot, "    node [shape=record];").unwrap();
+        self.write_dot(&mut dot, 1);
+        writeln!(dot, "}}").unwrap();
+        dot
+    }
+
+    fn write_dot(&self, dot: &mut String, depth: usize) {
+        let indent = "    ".repeat(depth);
+        // the nodes are sorted to keep the output stable
+        let mut nodes: Vec<&NodeState> = self.iter_nodes().collect();
+        nodes.sort_by_key(|node| (node.name.clone(), node.id.uuid().to_string()));
+
+        for node in &nodes {
+            let slots = |prefix: &str, slots: &SlotInfos| {
+                slots
+                    .iter()
+                    .enumerate()
+                    .map(|(index, slot)| {
+                        format!(
+                            "<{}_{}> {}: {:?}",
+                            prefix,
+                            index,
+                            escape_dot_record(&slot.name),
+                            slot.slot_type
+                        )
+                    })
+                    .collect::<Vec<_>>()
+                    .join("|")
+            };
+            let name = node.name.as_deref().unwrap_or(node.type_name);
+            writeln!(
+                dot,
+                "{}\"{}\" [label=\"{{{}}}|{}\\n{}|{{{}}}\"];",
+                indent,
+                node.id.uuid(),
+                slots("in", &node.input_slots),
+                escape_dot_record(name),
+                escape_dot_record(node.type_name),
+                slots("out", &node.output_slots),
+            )
+            .unwrap();
+        }
+
+        for node in &nodes {
+            for edge in node.edges.output_edges() {
+                match edge {
+                    Edge::SlotEdge {
+                        input_node,
+                        input_index,
+                        output_node,
+                        output_index,
+                    } => writeln!(
+                        dot,
+                        "{}\"{}\":out_{} -> \"{}\":in_{};",
+                        indent,
+                        output_node.uuid(),
+                        output_index,
+                        input_node.uuid(),
+                        input_index
+                    ),
+                    Edge::NodeEdge {
+                        input_node,
+                        output_node,
+                    } => writeln!(
+                        dot,
+                        "{}\"{}\" -> \"{}\" [style=dashed];",
+                        indent,
+                        output_node.uuid(),
+                        input_node.uuid()
+                    ),
+                }
+                .unwrap();
+            }
+        }
+
+        let mut sub_graphs: Vec<(&str, &RenderGraph)> = self.iter_sub_graphs().collect();
+        sub_graphs.sort_by_key(|(name, _)| *name);
+        for (name, sub_graph) in sub_graphs {
+            writeln!(dot, "{}subgraph \"cluster_{}\" {{", indent, name).unwrap();
+            writeln!(dot, "{}    label=\"{}\";", indent, name).unwrap();
+            sub_graph.write_dot(dot, depth + 1);
+            writeln!(dot, "{}}}", indent).unwrap();
+        }
+    }
+}
+
+/// Escapes the characters with a special meaning in the labels of DOT records.
+fn escape_dot_record(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        if matches!(c, '{' | '}' | '|' | '<' | '>' | '"' | '\\') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
 }
 
 impl Debug for RenderGraph {
@@ -734,4 +844,36 @@ mod tests {
             "Adding to a duplicate edge should return an error"
         );
     }
+
+    #[test]
+    fn test_graph_dot() {
+        let mut graph = RenderGraph::default();
+        let a_id = graph.add_node("A", TestNode::new(0, 1));
+        let b_id = graph.add_node("B", TestNode::new(1, 0));
+        let c_id = graph.add_node("C", TestNode::new(0, 0));
+        graph.add_slot_edge("A", 0, "B", 0).unwrap();
+        graph.add_node_edge("C", "B").unwrap();
+
+        let mut sub_graph = RenderGraph::default();
+        let d_id = sub_graph.add_node("D", TestNode::new(0, 0));
+        graph.add_sub_graph("sub", sub_graph);
+
+        let dot = graph.to_dot();
+        assert!(dot.starts_with("digraph render_graph {"));
+        assert!(dot.contains(&format!("\"{}\" [label=\"{{}}|A\\n", a_id.uuid())));
+        assert!(dot.contains("{<out_0> out_0: TextureView}"));
+        assert!(dot.contains("{<in_0> in_0: TextureView}"));
+        assert!(dot.contains(&format!(
+            "\"{}\":out_0 -> \"{}\":in_0;",
+            a_id.uuid(),
+            b_id.uuid()
+        )));
+        assert!(dot.contains(&format!(
+            "\"{}\" -> \"{}\" [style=dashed];",
+            c_id.uuid(),
+            b_id.uuid()
+        )));
+        assert!(dot.contains("subgraph \"cluster_sub\" {"));
+        assert!(dot.contains(&format!("        \"{}\" [label=", d_id.uuid())));
+    }
 }
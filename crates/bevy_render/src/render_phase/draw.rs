@@ -64,7 +64,7 @@ pub trait PhaseItem: Sized + Send + Sync + 'static {
 
 // TODO: make this generic?
 /// An identifier for a [`Draw`] function stored in [`DrawFunctions`].
-#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash, PartialOrd, Ord)]
 pub struct DrawFunctionId(usize);
 
 /// Stores all draw functions for the [`PhaseItem`] type.
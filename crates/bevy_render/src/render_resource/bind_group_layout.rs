@@ -8,6 +8,7 @@ pub struct BindGroupLayoutId(Uuid);
 pub struct BindGroupLayout {
     id: BindGroupLayoutId,
     value: Arc<wgpu::BindGroupLayout>,
+    entries: Option<Arc<[wgpu::BindGroupLayoutEntry]>>,
 }
 
 impl PartialEq for BindGroupLayout {
@@ -26,6 +27,19 @@ impl BindGroupLayout {
     pub fn value(&self) -> &wgpu::BindGroupLayout {
         &self.value
     }
+
+    /// The entries the layout was created with, or `None` if it was converted from a
+    /// [`wgpu::BindGroupLayout`].
+    #[inline]
+    pub fn entries(&self) -> Option<&[wgpu::BindGroupLayoutEntry]> {
+        self.entries.as_deref()
+    }
+
+    #[must_use]
+    pub(crate) fn with_entries(mut self, entries: &[wgpu::BindGroupLayoutEntry]) -> Self {
+        self.entries = Some(entries.into());
+        self
+    }
 }
 
 impl From<wgpu::BindGroupLayout> for BindGroupLayout {
@@ -33,6 +47,7 @@ impl From<wgpu::BindGroupLayout> for BindGroupLayout {
         BindGroupLayout {
             id: BindGroupLayoutId(Uuid::new_v4()),
             value: Arc::new(value),
+            entries: None,
         }
     }
 }
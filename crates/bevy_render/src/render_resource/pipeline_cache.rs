@@ -1,26 +1,45 @@
 use crate::{
     render_resource::{
         AsModuleDescriptorError, BindGroupLayout, BindGroupLayoutId, ComputePipeline,
-        ComputePipelineDescriptor, ProcessShaderError, ProcessedShader,
+        ComputePipelineDescriptor, FragmentState, ProcessShaderError, ProcessedShader,
         RawComputePipelineDescriptor, RawFragmentState, RawRenderPipelineDescriptor,
-        RawVertexState, RenderPipeline, RenderPipelineDescriptor, Shader, ShaderImport,
-        ShaderProcessor, ShaderReflectError,
+        RawVertexState, RenderPipeline, RenderPipelineDescriptor, Shader, ShaderDefVal,
+        ShaderImport, ShaderProcessor, ShaderSourceMap, VertexBufferLayout, VertexState,
     },
     renderer::RenderDevice,
     Extract,
 };
 use bevy_asset::{AssetEvent, Assets, Handle};
 use bevy_ecs::system::{Res, ResMut};
-use bevy_ecs::{event::EventReader, system::Resource};
+use bevy_ecs::{
+    event::{EventReader, EventWriter},
+    system::Resource,
+};
+use bevy_tasks::IoTaskPool;
 use bevy_utils::{
     default,
-    tracing::{debug, error},
+    tracing::{debug, error, warn},
     Entry, HashMap, HashSet,
 };
-use std::{hash::Hash, iter::FusedIterator, mem, ops::Deref, sync::Arc};
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use std::{
+    borrow::Cow,
+    collections::BTreeMap,
+    fs,
+    hash::Hash,
+    io, iter, mem,
+    ops::Deref,
+    path::PathBuf,
+    sync::{
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+        Arc,
+    },
+};
 use thiserror::Error;
 use wgpu::{
-    BufferBindingType, PipelineLayoutDescriptor, ShaderModule,
+    BindGroupLayoutDescriptor, BindGroupLayoutEntry, BufferBindingType, ColorTargetState,
+    DepthStencilState, MultisampleState, PipelineLayoutDescriptor, PrimitiveState, ShaderModule,
     VertexBufferLayout as RawVertexBufferLayout,
 };
 
@@ -45,7 +64,7 @@ pub enum Pipeline {
 type CachedPipelineId = usize;
 
 /// Index of a cached render pipeline in a [`PipelineCache`].
-#[derive(Copy, Clone, Debug, Hash, Eq, PartialEq)]
+#[derive(Copy, Clone, Debug, Hash, Eq, PartialEq, PartialOrd, Ord)]
 pub struct CachedRenderPipelineId(CachedPipelineId);
 
 impl CachedRenderPipelineId {
@@ -103,7 +122,7 @@ impl CachedPipelineState {
 #[derive(Default)]
 struct ShaderData {
     pipelines: HashSet<CachedPipelineId>,
-    processed_shaders: HashMap<Vec<String>, Arc<ShaderModule>>,
+    processed_shaders: HashMap<Vec<ShaderDefVal>, Arc<ShaderModule>>,
     resolved_imports: HashMap<ShaderImport, Handle<Shader>>,
     dependents: HashSet<Handle<Shader>>,
 }
@@ -114,16 +133,24 @@ struct ShaderCache {
     shaders: HashMap<Handle<Shader>, Shader>,
     import_path_shaders: HashMap<ShaderImport, Handle<Shader>>,
     waiting_on_import: HashMap<ShaderImport, Vec<Handle<Shader>>>,
+    /// The loaded shaders waiting on their imports to be validated.
+    unvalidated: HashSet<Handle<Shader>>,
+    /// The keys identifying the loaded shaders in the saved pipeline cache.
+    keys: HashMap<Handle<Shader>, SavedShaderKey>,
+    handles: HashMap<SavedShaderKey, Handle<Shader>>,
     processor: ShaderProcessor,
 }
 
 impl ShaderCache {
+    /// Gets the shader module of the shader with the given shader defs, creating it if needed.
+    ///
+    /// The pipeline using the module, if any, is queued again when the shader changes.
     fn get(
         &mut self,
         render_device: &RenderDevice,
-        pipeline: CachedPipelineId,
+        pipeline: Option<CachedPipelineId>,
         handle: &Handle<Shader>,
-        shader_defs: &[String],
+        shader_defs: &[ShaderDefVal],
     ) -> Result<Arc<ShaderModule>, PipelineCacheError> {
         let shader = self
             .shaders
@@ -143,47 +170,36 @@ impl ShaderCache {
             return Err(PipelineCacheError::ShaderImportNotYetAvailable);
         }
 
-        data.pipelines.insert(pipeline);
+        if let Some(pipeline) = pipeline {
+            data.pipelines.insert(pipeline);
+        }
 
         // PERF: this shader_defs clone isn't great. use raw_entry_mut when it stabilizes
         let module = match data.processed_shaders.entry(shader_defs.to_vec()) {
             Entry::Occupied(entry) => entry.into_mut(),
             Entry::Vacant(entry) => {
                 let mut shader_defs = shader_defs.to_vec();
-                #[cfg(feature = "webgl")]
-                shader_defs.push(String::from("NO_ARRAY_TEXTURES_SUPPORT"));
-
-                // TODO: 3 is the value from CLUSTERED_FORWARD_STORAGE_BUFFER_COUNT declared in bevy_pbr
-                // consider exposing this in shaders in a more generally useful way, such as:
-                // # if AVAILABLE_STORAGE_BUFFER_BINDINGS == 3
-                // /* use storage buffers here */
-                // # elif
-                // /* use uniforms here */
-                if !matches!(
-                    render_device.get_supported_read_only_binding_type(3),
-                    BufferBindingType::Storage { .. }
-                ) {
-                    shader_defs.push(String::from("NO_STORAGE_BUFFERS_SUPPORT"));
-                }
+                push_platform_shader_defs(render_device, &mut shader_defs);
 
                 debug!(
                     "processing shader {:?}, with shader defs {:?}",
                     handle, shader_defs
                 );
-                let processed = self.processor.process(
+                let (processed, source_map) = self.processor.process_with_source_map(
                     shader,
                     &shader_defs,
                     &self.shaders,
                     &self.import_path_shaders,
                 )?;
-                let module_descriptor = match processed
-                    .get_module_descriptor(render_device.features())
-                {
-                    Ok(module_descriptor) => module_descriptor,
-                    Err(err) => {
-                        return Err(PipelineCacheError::AsModuleDescriptorError(err, processed));
-                    }
-                };
+                let module_descriptor =
+                    match processed.get_module_descriptor(render_device.features()) {
+                        Ok(module_descriptor) => module_descriptor,
+                        Err(err) => {
+                            return Err(PipelineCacheError::AsModuleDescriptorError(
+                                err, processed, source_map,
+                            ));
+                        }
+                    };
 
                 render_device
                     .wgpu_device()
@@ -198,7 +214,10 @@ impl ShaderCache {
                 if let Some(Some(wgpu::Error::Validation { description, .. })) =
                     bevy_utils::futures::now_or_never(error)
                 {
-                    return Err(PipelineCacheError::CreateShaderModule(description));
+                    return Err(PipelineCacheError::CreateShaderModule(
+                        description,
+                        processed,
+                    ));
                 }
 
                 entry.insert(Arc::new(shader_module))
@@ -208,6 +227,73 @@ impl ShaderCache {
         Ok(module.clone())
     }
 
+    /// Returns `true` if the imports of the shader, and the imports of its imports, are loaded.
+    fn imports_loaded(&self, handle: &Handle<Shader>) -> bool {
+        let mut visited = HashSet::new();
+        let mut pending = vec![handle.clone_weak()];
+        while let Some(handle) = pending.pop() {
+            if !visited.insert(handle.clone_weak()) {
+                continue;
+            }
+            let shader = match self.shaders.get(&handle) {
+                Some(shader) => shader,
+                None => return false,
+            };
+            let resolved_imports = self.data.get(&handle).map(|data| &data.resolved_imports);
+            for import in shader.imports() {
+                // an item import is resolved to its module when the module loads after the shader
+                let import_handle = iter::once(import.clone())
+                    .chain(import.parent_module())
+                    .find_map(|import| resolved_imports.and_then(|imports| imports.get(&import)));
+                match import_handle {
+                    Some(import_handle) => pending.push(import_handle.clone_weak()),
+                    None => return false,
+                }
+            }
+        }
+        true
+    }
+
+    /// Validates the loaded shaders whose imports are all loaded, with the shader defs every
+    /// pipeline has. Shaders using other shader defs are only validated by their pipelines.
+    fn validate_loaded_shaders(&mut self, render_device: &RenderDevice) {
+        if self.unvalidated.is_empty() {
+            return;
+        }
+        let ready = self
+            .unvalidated
+            .iter()
+            .filter(|handle| self.imports_loaded(handle))
+            .cloned()
+            .collect::<Vec<_>>();
+        let mut shader_defs = Vec::new();
+        push_platform_shader_defs(render_device, &mut shader_defs);
+        for handle in ready {
+            self.unvalidated.remove(&handle);
+            let shader = &self.shaders[&handle];
+            match self.processor.process_with_source_map(
+                shader,
+                &shader_defs,
+                &self.shaders,
+                &self.import_path_shaders,
+            ) {
+                Ok((processed, source_map)) => {
+                    if let Err(err) = processed.reflect(render_device.features()) {
+                        error!(
+                            "failed to validate shader {}:\n{}",
+                            shader.source_name(),
+                            err.emit_to_string(&processed, &source_map)
+                        );
+                    }
+                }
+                Err(ProcessShaderError::UnknownShaderDef { .. }) => {}
+                Err(err) => {
+                    error!("failed to process shader {}: {}", shader.source_name(), err);
+                }
+            }
+        }
+    }
+
     fn clear(&mut self, handle: &Handle<Shader>) -> Vec<CachedPipelineId> {
         let mut shaders_to_clear = vec![handle.clone_weak()];
         let mut pipelines_to_queue = Vec::new();
@@ -241,7 +327,11 @@ impl ShaderCache {
         }
 
         for import in shader.imports() {
-            if let Some(import_handle) = self.import_path_shaders.get(import) {
+            // `#import a::b::c` imports either the module `a::b::c` or the item `c` of `a::b`
+            let candidates = || iter::once(import.clone()).chain(import.parent_module());
+            if let Some(import_handle) =
+                candidates().find_map(|candidate| self.import_path_shaders.get(&candidate))
+            {
                 // resolve import because it is currently available
                 let data = self.data.entry(handle.clone_weak()).or_default();
                 data.resolved_imports
@@ -250,17 +340,33 @@ impl ShaderCache {
                 let data = self.data.entry(import_handle.clone_weak()).or_default();
                 data.dependents.insert(handle.clone_weak());
             } else {
-                let waiting = self.waiting_on_import.entry(import.clone()).or_default();
-                waiting.push(handle.clone_weak());
+                for candidate in candidates() {
+                    let waiting = self.waiting_on_import.entry(candidate).or_default();
+                    waiting.push(handle.clone_weak());
+                }
             }
         }
 
+        if shader.is_validated_with_imports() {
+            self.unvalidated.insert(handle.clone_weak());
+        } else {
+            self.unvalidated.remove(handle);
+        }
+        let key = SavedShaderKey::new(&shader);
+        if let Some(old_key) = self.keys.insert(handle.clone_weak(), key.clone()) {
+            self.handles.remove(&old_key);
+        }
+        self.handles.insert(key, handle.clone_weak());
         self.shaders.insert(handle.clone_weak(), shader);
         pipelines_to_queue
     }
 
     fn remove(&mut self, handle: &Handle<Shader>) -> Vec<CachedPipelineId> {
         let pipelines_to_queue = self.clear(handle);
+        self.unvalidated.remove(handle);
+        if let Some(key) = self.keys.remove(handle) {
+            self.handles.remove(&key);
+        }
         if let Some(shader) = self.shaders.remove(handle) {
             if let Some(import_path) = shader.import_path() {
                 self.import_path_shaders.remove(import_path);
@@ -271,6 +377,26 @@ impl ShaderCache {
     }
 }
 
+/// Adds the shader defs describing the limits of the platform, which every shader is processed
+/// with.
+fn push_platform_shader_defs(render_device: &RenderDevice, shader_defs: &mut Vec<ShaderDefVal>) {
+    #[cfg(feature = "webgl")]
+    shader_defs.push("NO_ARRAY_TEXTURES_SUPPORT".into());
+
+    // TODO: 3 is the value from CLUSTERED_FORWARD_STORAGE_BUFFER_COUNT declared in bevy_pbr
+    // consider exposing this in shaders in a more generally useful way, such as:
+    // # if AVAILABLE_STORAGE_BUFFER_BINDINGS == 3
+    // /* use storage buffers here */
+    // # elif
+    // /* use uniforms here */
+    if !matches!(
+        render_device.get_supported_read_only_binding_type(3),
+        BufferBindingType::Storage { .. }
+    ) {
+        shader_defs.push("NO_STORAGE_BUFFERS_SUPPORT".into());
+    }
+}
+
 #[derive(Default)]
 struct LayoutCache {
     layouts: HashMap<Vec<BindGroupLayoutId>, wgpu::PipelineLayout>,
@@ -315,6 +441,329 @@ pub struct PipelineCache {
     device: RenderDevice,
     pipelines: Vec<CachedPipeline>,
     waiting_pipelines: HashSet<CachedPipelineId>,
+    stats: PipelineCacheStats,
+    persistence: Option<PipelineCachePersistence>,
+    /// The saved shader specializations whose shader modules are not created yet.
+    prewarm_specializations: HashMap<SavedShaderKey, Vec<Vec<ShaderDefVal>>>,
+    /// The saved render pipelines that are not created yet.
+    prewarm_pipelines: Vec<SavedRenderPipeline>,
+    /// The saved render pipelines created before being queued, used by the first pipeline queued
+    /// with the same descriptor.
+    prewarmed_pipelines: Vec<(SavedRenderPipeline, RenderPipeline)>,
+}
+
+/// Statistics of the pipelines created by the [`PipelineCache`].
+///
+/// The `RenderPlugin` shares this resource between the render world and the main world, where a
+/// [`PipelineFailed`] event is sent for each pipeline that failed to be created.
+#[derive(Resource, Clone, Default)]
+pub struct PipelineCacheStats {
+    state: Arc<PipelineCacheStatsState>,
+}
+
+#[derive(Default)]
+struct PipelineCacheStatsState {
+    queued: AtomicUsize,
+    compiled: AtomicUsize,
+    failed: AtomicUsize,
+    /// The failures waiting to be sent to the main world.
+    failures: Mutex<Vec<PipelineFailed>>,
+}
+
+impl PipelineCacheStats {
+    /// The number of pipelines waiting for their shaders or their imports to be loaded.
+    pub fn queued(&self) -> usize {
+        self.state.queued.load(Ordering::Relaxed)
+    }
+
+    /// The number of pipelines created since the start of the app, including the pipelines
+    /// created again when their shaders changed.
+    pub fn compiled(&self) -> usize {
+        self.state.compiled.load(Ordering::Relaxed)
+    }
+
+    /// The number of pipelines that failed to be created since the start of the app.
+    pub fn failed(&self) -> usize {
+        self.state.failed.load(Ordering::Relaxed)
+    }
+}
+
+/// Sent when a pipeline queued in the [`PipelineCache`] fails to be created.
+#[derive(Debug, Clone)]
+pub struct PipelineFailed {
+    /// The label of the pipeline.
+    pub label: Option<Cow<'static, str>>,
+    /// The description of the error, pointing at the lines of the original shaders for shader
+    /// errors.
+    pub error: String,
+    /// The processed source of the shader that failed, for errors of WGSL and GLSL shaders.
+    pub processed_source: Option<String>,
+}
+
+pub(crate) fn send_pipeline_failed_events(
+    stats: Res<PipelineCacheStats>,
+    mut failed_events: EventWriter<PipelineFailed>,
+) {
+    failed_events.send_batch(mem::take(&mut *stats.state.failures.lock()));
+}
+
+/// Identifies a shader in the saved pipeline cache by its name, if it has one, and the hash of
+/// its source, as the ids of the handles of the shaders added at runtime change between runs.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+struct SavedShaderKey {
+    name: Option<String>,
+    source_hash: u64,
+}
+
+impl SavedShaderKey {
+    fn new(shader: &Shader) -> Self {
+        Self {
+            name: shader.name().map(str::to_string),
+            source_hash: shader.source_hash(),
+        }
+    }
+
+    /// Returns `true` if the key is of an older version of the shader identified by `key`.
+    fn is_replaced_by(&self, key: &SavedShaderKey) -> bool {
+        self.name.is_some() && self.name == key.name && self.source_hash != key.source_hash
+    }
+}
+
+/// A shader stage of a [`SavedRenderPipeline`].
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+struct SavedShaderStage {
+    shader: SavedShaderKey,
+    shader_defs: Vec<ShaderDefVal>,
+    entry_point: String,
+}
+
+/// A [`RenderPipelineDescriptor`] saved with the entries of its bind group layouts and the keys
+/// of its shaders.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+struct SavedRenderPipeline {
+    label: Option<String>,
+    layout: Option<Vec<Vec<BindGroupLayoutEntry>>>,
+    vertex: SavedShaderStage,
+    vertex_buffers: Vec<VertexBufferLayout>,
+    primitive: PrimitiveState,
+    depth_stencil: Option<DepthStencilState>,
+    multisample: MultisampleState,
+    fragment: Option<(SavedShaderStage, Vec<Option<ColorTargetState>>)>,
+}
+
+impl SavedRenderPipeline {
+    /// Saves the descriptor, unless one of its bind group layouts was converted from a wgpu
+    /// layout or one of its shaders isn't loaded.
+    fn new(
+        descriptor: &RenderPipelineDescriptor,
+        shader_key: impl Fn(&Handle<Shader>) -> Option<SavedShaderKey>,
+    ) -> Option<Self> {
+        let layout = match &descriptor.layout {
+            Some(layouts) => Some(
+                layouts
+                    .iter()
+                    .map(|layout| layout.entries().map(<[_]>::to_vec))
+                    .collect::<Option<Vec<_>>>()?,
+            ),
+            None => None,
+        };
+        let fragment = match &descriptor.fragment {
+            Some(fragment) => Some((
+                SavedShaderStage {
+                    shader: shader_key(&fragment.shader)?,
+                    shader_defs: fragment.shader_defs.clone(),
+                    entry_point: fragment.entry_point.to_string(),
+                },
+                fragment.targets.clone(),
+            )),
+            None => None,
+        };
+
+        Some(Self {
+            label: descriptor.label.as_deref().map(str::to_string),
+            layout,
+            vertex: SavedShaderStage {
+                shader: shader_key(&descriptor.vertex.shader)?,
+                shader_defs: descriptor.vertex.shader_defs.clone(),
+                entry_point: descriptor.vertex.entry_point.to_string(),
+            },
+            vertex_buffers: descriptor.vertex.buffers.clone(),
+            primitive: descriptor.primitive,
+            depth_stencil: descriptor.depth_stencil.clone(),
+            multisample: descriptor.multisample,
+            fragment,
+        })
+    }
+
+    /// Creates the descriptor of the saved pipeline, with new bind group layouts, if its shaders
+    /// are loaded.
+    fn to_descriptor(
+        &self,
+        render_device: &RenderDevice,
+        shader_handle: impl Fn(&SavedShaderKey) -> Option<Handle<Shader>>,
+    ) -> Option<RenderPipelineDescriptor> {
+        let vertex_shader = shader_handle(&self.vertex.shader)?;
+        let fragment = match &self.fragment {
+            Some((stage, targets)) => Some(FragmentState {
+                shader: shader_handle(&stage.shader)?,
+                shader_defs: stage.shader_defs.clone(),
+                entry_point: stage.entry_point.clone().into(),
+                targets: targets.clone(),
+            }),
+            None => None,
+        };
+        let layout = self.layout.as_ref().map(|layouts| {
+            layouts
+                .iter()
+                .map(|entries| {
+                    render_device.create_bind_group_layout(&BindGroupLayoutDescriptor {
+                        label: None,
+                        entries,
+                    })
+                })
+                .collect()
+        });
+
+        Some(RenderPipelineDescriptor {
+            label: self.label.clone().map(Into::into),
+            layout,
+            vertex: VertexState {
+                shader: vertex_shader,
+                shader_defs: self.vertex.shader_defs.clone(),
+                entry_point: self.vertex.entry_point.clone().into(),
+                buffers: self.vertex_buffers.clone(),
+            },
+            primitive: self.primitive,
+            depth_stencil: self.depth_stencil.clone(),
+            multisample: self.multisample,
+            fragment,
+        })
+    }
+
+    fn shaders(&self) -> impl Iterator<Item = &SavedShaderKey> {
+        iter::once(&self.vertex.shader)
+            .chain(self.fragment.as_ref().map(|(stage, _)| &stage.shader))
+    }
+}
+
+/// The shader specializations and render pipelines used by an app, saved to the
+/// [`PipelineCacheSettings::persistence_path`](crate::settings::PipelineCacheSettings::persistence_path).
+#[derive(Default, Serialize, Deserialize)]
+struct SavedPipelineCache {
+    /// The shader defs each shader was processed with.
+    shaders: BTreeMap<SavedShaderKey, Vec<Vec<ShaderDefVal>>>,
+    render_pipelines: Vec<SavedRenderPipeline>,
+}
+
+struct PipelineCachePersistence {
+    path: PathBuf,
+    saved: SavedPipelineCache,
+    /// Whether specializations or pipelines were added since the file was written.
+    changed: bool,
+    /// Whether a task of the [`IoTaskPool`] is writing the file.
+    writing: Arc<AtomicBool>,
+}
+
+impl PipelineCachePersistence {
+    fn new(path: PathBuf, saved: SavedPipelineCache) -> Self {
+        Self {
+            path,
+            saved,
+            changed: false,
+            writing: default(),
+        }
+    }
+
+    fn load(path: PathBuf) -> Self {
+        let saved = match fs::read_to_string(&path) {
+            Ok(saved) => ron::from_str(&saved).unwrap_or_else(|err| {
+                warn!(
+                    "ignoring the invalid pipeline cache {}: {}",
+                    path.display(),
+                    err
+                );
+                default()
+            }),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => default(),
+            Err(err) => {
+                warn!(
+                    "failed to read the pipeline cache {}: {}",
+                    path.display(),
+                    err
+                );
+                default()
+            }
+        };
+
+        Self::new(path, saved)
+    }
+
+    fn record_shader(&mut self, key: &SavedShaderKey, shader_defs: &[ShaderDefVal]) {
+        let specializations = self.saved.shaders.entry(key.clone()).or_default();
+        if !specializations
+            .iter()
+            .any(|saved_shader_defs| saved_shader_defs == shader_defs)
+        {
+            specializations.push(shader_defs.to_vec());
+            self.changed = true;
+        }
+    }
+
+    fn record_render_pipeline(&mut self, pipeline: SavedRenderPipeline) {
+        if !self.saved.render_pipelines.contains(&pipeline) {
+            self.saved.render_pipelines.push(pipeline);
+            self.changed = true;
+        }
+    }
+
+    /// Forgets the specializations and pipelines of the older versions of the shader of `key`.
+    fn forget_replaced_shaders(&mut self, key: &SavedShaderKey) {
+        let n_shaders = self.saved.shaders.len();
+        let n_render_pipelines = self.saved.render_pipelines.len();
+        self.saved
+            .shaders
+            .retain(|saved_key, _| !saved_key.is_replaced_by(key));
+        self.saved
+            .render_pipelines
+            .retain(|pipeline| !pipeline.shaders().any(|shader| shader.is_replaced_by(key)));
+        if self.saved.shaders.len() != n_shaders
+            || self.saved.render_pipelines.len() != n_render_pipelines
+        {
+            self.changed = true;
+        }
+    }
+
+    /// Writes the file in a task of the [`IoTaskPool`]. The changes made while a task is writing
+    /// the file are written once it's done.
+    fn save(&mut self) {
+        if !self.changed || self.writing.load(Ordering::Acquire) {
+            return;
+        }
+        self.changed = false;
+
+        let saved = match ron::to_string(&self.saved) {
+            Ok(saved) => saved,
+            Err(err) => {
+                error!("failed to serialize the pipeline cache: {}", err);
+                return;
+            }
+        };
+        self.writing.store(true, Ordering::Release);
+        let path = self.path.clone();
+        let writing = self.writing.clone();
+        IoTaskPool::get()
+            .spawn(async move {
+                if let Err(err) = fs::write(&path, saved) {
+                    error!(
+                        "failed to write the pipeline cache {}: {}",
+                        path.display(),
+                        err
+                    );
+                }
+                writing.store(false, Ordering::Release);
+            })
+            .detach();
+    }
 }
 
 impl PipelineCache {
@@ -330,9 +779,40 @@ impl PipelineCache {
             shader_cache: default(),
             waiting_pipelines: default(),
             pipelines: default(),
+            stats: default(),
+            persistence: None,
+            prewarm_specializations: default(),
+            prewarm_pipelines: default(),
+            prewarmed_pipelines: default(),
         }
     }
 
+    /// Saves the shader specializations and the render pipelines created by the cache to the
+    /// file at `path`, so that the next runs of the app create them as soon as their shaders are
+    /// loaded, instead of when a pipeline first uses them.
+    ///
+    /// The specializations and pipelines saved by the previous runs are read from the file and
+    /// prewarmed. A render pipeline queued with the same descriptor as a prewarmed one uses it
+    /// instead of being created again.
+    #[must_use]
+    pub fn with_persistence_path(mut self, path: impl Into<PathBuf>) -> Self {
+        let persistence = PipelineCachePersistence::load(path.into());
+        self.prewarm_specializations = persistence
+            .saved
+            .shaders
+            .iter()
+            .map(|(key, shader_defs)| (key.clone(), shader_defs.clone()))
+            .collect();
+        self.prewarm_pipelines = persistence.saved.render_pipelines.clone();
+        self.persistence = Some(persistence);
+        self
+    }
+
+    /// The statistics of the pipelines created by the cache.
+    pub fn stats(&self) -> &PipelineCacheStats {
+        &self.stats
+    }
+
     /// Get the state of a cached render pipeline.
     ///
     /// See [`PipelineCache::queue_render_pipeline()`].
@@ -471,6 +951,18 @@ impl PipelineCache {
             self.pipelines[cached_pipeline].state = CachedPipelineState::Queued;
             self.waiting_pipelines.insert(cached_pipeline);
         }
+
+        // the saved specializations and pipelines of the previous versions of the shader are
+        // never used again
+        if let (Some(persistence), Some(key)) =
+            (&mut self.persistence, self.shader_cache.keys.get(handle))
+        {
+            persistence.forget_replaced_shaders(key);
+            self.prewarm_specializations
+                .retain(|saved_key, _| !saved_key.is_replaced_by(key));
+            self.prewarm_pipelines
+                .retain(|pipeline| !pipeline.shaders().any(|shader| shader.is_replaced_by(key)));
+        }
     }
 
     fn remove_shader(&mut self, shader: &Handle<Shader>) {
@@ -481,43 +973,162 @@ impl PipelineCache {
         }
     }
 
+    fn get_shader_module(
+        &mut self,
+        id: Option<CachedPipelineId>,
+        handle: &Handle<Shader>,
+        shader_defs: &[ShaderDefVal],
+    ) -> Result<Arc<ShaderModule>, PipelineCacheError> {
+        let module = self
+            .shader_cache
+            .get(&self.device, id, handle, shader_defs)?;
+        if let (Some(persistence), Some(key)) =
+            (&mut self.persistence, self.shader_cache.keys.get(handle))
+        {
+            persistence.record_shader(key, shader_defs);
+        }
+        Ok(module)
+    }
+
+    /// Creates the shader modules of the saved specializations of the loaded shaders.
+    fn prewarm_shader_modules(&mut self) {
+        let shader_cache = &mut self.shader_cache;
+        let device = &self.device;
+        self.prewarm_specializations.retain(|key, specializations| {
+            let handle = match shader_cache.handles.get(key) {
+                Some(handle) => handle.clone_weak(),
+                None => return true,
+            };
+            specializations.retain(|shader_defs| {
+                // keep the specializations waiting on their shader or its imports
+                matches!(
+                    shader_cache.get(device, None, &handle, shader_defs),
+                    Err(PipelineCacheError::ShaderNotLoaded(_)
+                        | PipelineCacheError::ShaderImportNotYetAvailable)
+                )
+            });
+            !specializations.is_empty()
+        });
+    }
+
+    /// Creates the saved render pipelines whose shaders and imports are loaded.
+    fn prewarm_render_pipelines(&mut self) {
+        for saved in mem::take(&mut self.prewarm_pipelines) {
+            let shader_cache = &self.shader_cache;
+            let descriptor = saved.to_descriptor(&self.device, |key| {
+                shader_cache
+                    .handles
+                    .get(key)
+                    .filter(|handle| shader_cache.imports_loaded(handle))
+                    .map(Handle::clone_weak)
+            });
+            let descriptor = match descriptor {
+                Some(descriptor) => descriptor,
+                None => {
+                    self.prewarm_pipelines.push(saved);
+                    continue;
+                }
+            };
+
+            // the saved pipeline may be invalid on this device, in which case it's dropped
+            self.device
+                .wgpu_device()
+                .push_error_scope(wgpu::ErrorFilter::Validation);
+            let result = self.create_render_pipeline(None, &descriptor);
+            let error = self.device.wgpu_device().pop_error_scope();
+            match result {
+                Ok(pipeline) => {
+                    if !matches!(bevy_utils::futures::now_or_never(error), Some(Some(_))) {
+                        self.prewarmed_pipelines.push((saved, pipeline));
+                    }
+                }
+                Err(
+                    PipelineCacheError::ShaderNotLoaded(_)
+                    | PipelineCacheError::ShaderImportNotYetAvailable,
+                ) => self.prewarm_pipelines.push(saved),
+                // the error is reported if a pipeline is queued with the same descriptor
+                Err(_) => {}
+            }
+        }
+    }
+
     fn process_render_pipeline(
         &mut self,
         id: CachedPipelineId,
         descriptor: &RenderPipelineDescriptor,
     ) -> CachedPipelineState {
-        let vertex_module = match self.shader_cache.get(
-            &self.device,
+        let saved = match &self.persistence {
+            Some(_) => SavedRenderPipeline::new(descriptor, |handle| {
+                self.shader_cache.keys.get(handle).cloned()
+            }),
+            None => None,
+        };
+        let prewarmed = saved.as_ref().and_then(|saved| {
+            let index = self
+                .prewarmed_pipelines
+                .iter()
+                .position(|(prewarmed, _)| prewarmed == saved)?;
+            Some(self.prewarmed_pipelines.swap_remove(index).1)
+        });
+
+        let result = match prewarmed {
+            // the shader modules are still gotten, so that the pipeline is created again when its
+            // shaders change
+            Some(pipeline) => self
+                .get_shader_modules(Some(id), descriptor)
+                .map(|_| pipeline),
+            None => self.create_render_pipeline(Some(id), descriptor),
+        };
+        match result {
+            Ok(pipeline) => {
+                if let (Some(persistence), Some(saved)) = (&mut self.persistence, saved) {
+                    persistence.record_render_pipeline(saved);
+                }
+                CachedPipelineState::Ok(Pipeline::RenderPipeline(pipeline))
+            }
+            Err(err) => CachedPipelineState::Err(err),
+        }
+    }
+
+    /// Gets the vertex and fragment shader modules of a render pipeline.
+    fn get_shader_modules(
+        &mut self,
+        id: Option<CachedPipelineId>,
+        descriptor: &RenderPipelineDescriptor,
+    ) -> Result<(Arc<ShaderModule>, Option<Arc<ShaderModule>>), PipelineCacheError> {
+        let vertex_module = self.get_shader_module(
             id,
             &descriptor.vertex.shader,
             &descriptor.vertex.shader_defs,
-        ) {
-            Ok(module) => module,
-            Err(err) => {
-                return CachedPipelineState::Err(err);
+        )?;
+        let fragment_module = match &descriptor.fragment {
+            Some(fragment) => {
+                Some(self.get_shader_module(id, &fragment.shader, &fragment.shader_defs)?)
             }
+            None => None,
         };
+        Ok((vertex_module, fragment_module))
+    }
 
-        let fragment_data = if let Some(fragment) = &descriptor.fragment {
-            let fragment_module = match self.shader_cache.get(
-                &self.device,
-                id,
-                &fragment.shader,
-                &fragment.shader_defs,
-            ) {
-                Ok(module) => module,
-                Err(err) => {
-                    return CachedPipelineState::Err(err);
-                }
-            };
-            Some((
-                fragment_module,
-                fragment.entry_point.deref(),
-                fragment.targets.as_slice(),
-            ))
-        } else {
-            None
-        };
+    fn create_render_pipeline(
+        &mut self,
+        id: Option<CachedPipelineId>,
+        descriptor: &RenderPipelineDescriptor,
+    ) -> Result<RenderPipeline, PipelineCacheError> {
+        let (vertex_module, fragment_module) = self.get_shader_modules(id, descriptor)?;
+
+        let fragment_data =
+            descriptor
+                .fragment
+                .as_ref()
+                .zip(fragment_module)
+                .map(|(fragment, module)| {
+                    (
+                        module,
+                        fragment.entry_point.deref(),
+                        fragment.targets.as_slice(),
+                    )
+                });
 
         let vertex_buffer_layouts = descriptor
             .vertex
@@ -557,9 +1168,7 @@ impl PipelineCache {
                 }),
         };
 
-        let pipeline = self.device.create_render_pipeline(&descriptor);
-
-        CachedPipelineState::Ok(Pipeline::RenderPipeline(pipeline))
+        Ok(self.device.create_render_pipeline(&descriptor))
     }
 
     fn process_compute_pipeline(
@@ -567,17 +1176,13 @@ impl PipelineCache {
         id: CachedPipelineId,
         descriptor: &ComputePipelineDescriptor,
     ) -> CachedPipelineState {
-        let compute_module = match self.shader_cache.get(
-            &self.device,
-            id,
-            &descriptor.shader,
-            &descriptor.shader_defs,
-        ) {
-            Ok(module) => module,
-            Err(err) => {
-                return CachedPipelineState::Err(err);
-            }
-        };
+        let compute_module =
+            match self.get_shader_module(Some(id), &descriptor.shader, &descriptor.shader_defs) {
+                Ok(module) => module,
+                Err(err) => {
+                    return CachedPipelineState::Err(err);
+                }
+            };
 
         let layout = if let Some(layout) = &descriptor.layout {
             Some(self.layout_cache.get(&self.device, layout))
@@ -604,6 +1209,14 @@ impl PipelineCache {
     ///
     /// [`RenderStage::Render`]: crate::RenderStage::Render
     pub fn process_queue(&mut self) {
+        self.shader_cache.validate_loaded_shaders(&self.device);
+        if !self.prewarm_specializations.is_empty() {
+            self.prewarm_shader_modules();
+        }
+        if !self.prewarm_pipelines.is_empty() {
+            self.prewarm_render_pipelines();
+        }
+
         let waiting_pipelines = mem::take(&mut self.waiting_pipelines);
         let mut pipelines = mem::take(&mut self.pipelines);
 
@@ -622,31 +1235,79 @@ impl PipelineCache {
                 }
             };
 
-            if let CachedPipelineState::Err(err) = &pipeline.state {
-                match err {
+            match &pipeline.state {
+                CachedPipelineState::Ok(_) => {
+                    self.stats.state.compiled.fetch_add(1, Ordering::Relaxed);
+                }
+                CachedPipelineState::Err(
                     PipelineCacheError::ShaderNotLoaded(_)
-                    | PipelineCacheError::ShaderImportNotYetAvailable => {
-                        // retry
-                        self.waiting_pipelines.insert(id);
-                    }
-                    // shader could not be processed ... retrying won't help
-                    PipelineCacheError::ProcessShaderError(err) => {
-                        error!("failed to process shader: {}", err);
-                        continue;
-                    }
-                    PipelineCacheError::AsModuleDescriptorError(err, source) => {
-                        log_shader_error(source, err);
-                        continue;
-                    }
-                    PipelineCacheError::CreateShaderModule(description) => {
-                        error!("failed to create shader module: {}", description);
-                        continue;
+                    | PipelineCacheError::ShaderImportNotYetAvailable,
+                ) => {
+                    // retry
+                    self.waiting_pipelines.insert(id);
+                }
+                // shader could not be processed ... retrying won't help
+                CachedPipelineState::Err(err) => {
+                    match err {
+                        PipelineCacheError::ProcessShaderError(err) => {
+                            error!("failed to process shader: {}", err);
+                        }
+                        PipelineCacheError::AsModuleDescriptorError(err, source, source_map) => {
+                            log_shader_error(source, source_map, err);
+                        }
+                        PipelineCacheError::CreateShaderModule(description, _) => {
+                            error!("failed to create shader module: {}", description);
+                        }
+                        PipelineCacheError::ShaderNotLoaded(_)
+                        | PipelineCacheError::ShaderImportNotYetAvailable => unreachable!(),
                     }
+                    self.record_failure(&pipeline.descriptor, err);
                 }
+                CachedPipelineState::Queued => {}
             }
         }
 
         self.pipelines = pipelines;
+        self.stats
+            .state
+            .queued
+            .store(self.waiting_pipelines.len(), Ordering::Relaxed);
+        if let Some(persistence) = &mut self.persistence {
+            persistence.save();
+        }
+    }
+
+    fn record_failure(&self, descriptor: &PipelineDescriptor, err: &PipelineCacheError) {
+        let label = match descriptor {
+            PipelineDescriptor::RenderPipelineDescriptor(descriptor) => descriptor.label.clone(),
+            PipelineDescriptor::ComputePipelineDescriptor(descriptor) => descriptor.label.clone(),
+        };
+        let (error, processed_source) = match err {
+            PipelineCacheError::AsModuleDescriptorError(
+                AsModuleDescriptorError::ShaderReflectError(reflect_error),
+                source,
+                source_map,
+            ) => (
+                reflect_error.emit_to_string(source, source_map),
+                Some(source),
+            ),
+            PipelineCacheError::AsModuleDescriptorError(_, source, _)
+            | PipelineCacheError::CreateShaderModule(_, source) => (err.to_string(), Some(source)),
+            _ => (err.to_string(), None),
+        };
+
+        self.stats.state.failed.fetch_add(1, Ordering::Relaxed);
+        self.stats.state.failures.lock().push(PipelineFailed {
+            label,
+            error,
+            processed_source: processed_source
+                .and_then(|source| {
+                    source
+                        .get_wgsl_source()
+                        .or_else(|| source.get_glsl_source())
+                })
+                .map(str::to_string),
+        });
     }
 
     pub(crate) fn process_pipeline_queue_system(mut cache: ResMut<Self>) {
@@ -666,93 +1327,24 @@ impl PipelineCache {
                     }
                 }
                 AssetEvent::Removed { handle } => cache.remove_shader(handle),
+                AssetEvent::LoadedWithDependencies { .. } => {}
             }
         }
     }
 }
 
-fn log_shader_error(source: &ProcessedShader, error: &AsModuleDescriptorError) {
-    use codespan_reporting::{
-        diagnostic::{Diagnostic, Label},
-        files::SimpleFile,
-        term,
-    };
-
+fn log_shader_error(
+    source: &ProcessedShader,
+    source_map: &ShaderSourceMap,
+    error: &AsModuleDescriptorError,
+) {
     match error {
-        AsModuleDescriptorError::ShaderReflectError(error) => match error {
-            ShaderReflectError::WgslParse(error) => {
-                let source = source
-                    .get_wgsl_source()
-                    .expect("non-wgsl source for wgsl error");
-                let msg = error.emit_to_string(source);
-                error!("failed to process shader:\n{}", msg);
-            }
-            ShaderReflectError::GlslParse(errors) => {
-                let source = source
-                    .get_glsl_source()
-                    .expect("non-glsl source for glsl error");
-                let files = SimpleFile::new("glsl", source);
-                let config = codespan_reporting::term::Config::default();
-                let mut writer = term::termcolor::Ansi::new(Vec::new());
-
-                for err in errors {
-                    let mut diagnostic = Diagnostic::error().with_message(err.kind.to_string());
-
-                    if let Some(range) = err.meta.to_range() {
-                        diagnostic = diagnostic.with_labels(vec![Label::primary((), range)]);
-                    }
-
-                    term::emit(&mut writer, &config, &files, &diagnostic)
-                        .expect("cannot write error");
-                }
-
-                let msg = writer.into_inner();
-                let msg = String::from_utf8_lossy(&msg);
-
-                error!("failed to process shader: \n{}", msg);
-            }
-            ShaderReflectError::SpirVParse(error) => {
-                error!("failed to process shader:\n{}", error);
-            }
-            ShaderReflectError::Validation(error) => {
-                let (filename, source) = match source {
-                    ProcessedShader::Wgsl(source) => ("wgsl", source.as_ref()),
-                    ProcessedShader::Glsl(source, _) => ("glsl", source.as_ref()),
-                    ProcessedShader::SpirV(_) => {
-                        error!("failed to process shader:\n{}", error);
-                        return;
-                    }
-                };
-
-                let files = SimpleFile::new(filename, source);
-                let config = term::Config::default();
-                let mut writer = term::termcolor::Ansi::new(Vec::new());
-
-                let diagnostic = Diagnostic::error()
-                    .with_message(error.to_string())
-                    .with_labels(
-                        error
-                            .spans()
-                            .map(|(span, desc)| {
-                                Label::primary((), span.to_range().unwrap())
-                                    .with_message(desc.to_owned())
-                            })
-                            .collect(),
-                    )
-                    .with_notes(
-                        ErrorSources::of(error)
-                            .map(|source| source.to_string())
-                            .collect(),
-                    );
-
-                term::emit(&mut writer, &config, &files, &diagnostic).expect("cannot write error");
-
-                let msg = writer.into_inner();
-                let msg = String::from_utf8_lossy(&msg);
-
-                error!("failed to process shader: \n{}", msg);
-            }
-        },
+        AsModuleDescriptorError::ShaderReflectError(error) => {
+            error!(
+                "failed to process shader:\n{}",
+                error.emit_to_string(source, source_map)
+            );
+        }
         AsModuleDescriptorError::WgslConversion(error) => {
             error!("failed to convert shader to wgsl: \n{}", error);
         }
@@ -772,33 +1364,114 @@ pub enum PipelineCacheError {
     #[error(transparent)]
     ProcessShaderError(#[from] ProcessShaderError),
     #[error("{0}")]
-    AsModuleDescriptorError(AsModuleDescriptorError, ProcessedShader),
+    AsModuleDescriptorError(AsModuleDescriptorError, ProcessedShader, ShaderSourceMap),
     #[error("Shader import not yet available.")]
     ShaderImportNotYetAvailable,
     #[error("Could not create shader module: {0}")]
-    CreateShaderModule(String),
-}
-
-struct ErrorSources<'a> {
-    current: Option<&'a (dyn std::error::Error + 'static)>,
+    CreateShaderModule(String, ProcessedShader),
 }
 
-impl<'a> ErrorSources<'a> {
-    fn of(error: &'a dyn std::error::Error) -> Self {
-        Self {
-            current: error.source(),
-        }
+#[cfg(test)]
+mod tests {
+    use super::{
+        PipelineCachePersistence, SavedPipelineCache, SavedRenderPipeline, SavedShaderKey,
+    };
+    use crate::render_resource::{
+        FragmentState, RenderPipelineDescriptor, Shader, ShaderDefVal, VertexState,
+    };
+    use bevy_asset::{Handle, HandleUntyped};
+    use bevy_reflect::TypeUuid;
+    use bevy_utils::default;
+    use wgpu::{ColorTargetState, ColorWrites, MultisampleState, PrimitiveState, TextureFormat};
+
+    #[test]
+    fn persistence_records_specializations_once() {
+        let mut persistence = PipelineCachePersistence::new("pipeline_cache.ron".into(), default());
+        let key = SavedShaderKey::new(&Shader::from_wgsl("fn main() { }"));
+        let shader_defs = vec![
+            ShaderDefVal::from("VERTEX_UVS"),
+            ShaderDefVal::UInt("MAX_LIGHTS".to_string(), 8),
+        ];
+
+        persistence.record_shader(&key, &shader_defs);
+        assert!(persistence.changed);
+        persistence.changed = false;
+        persistence.record_shader(&key, &shader_defs);
+        assert!(!persistence.changed);
+        persistence.record_shader(&key, &[]);
+        assert!(persistence.changed);
+
+        let saved = ron::to_string(&persistence.saved).unwrap();
+        let loaded: SavedPipelineCache = ron::from_str(&saved).unwrap();
+        assert_eq!(
+            loaded.shaders.get(&key),
+            Some(&vec![shader_defs, Vec::new()])
+        );
     }
-}
 
-impl<'a> Iterator for ErrorSources<'a> {
-    type Item = &'a (dyn std::error::Error + 'static);
+    #[test]
+    fn saved_shader_keys_are_stable() {
+        const SOURCE: &str = "#define_import_path my_shader\nfn main() { }";
+        let key = SavedShaderKey::new(&Shader::from_wgsl(SOURCE));
+        assert_eq!(key.name.as_deref(), Some("my_shader"));
+        assert_eq!(key, SavedShaderKey::new(&Shader::from_wgsl(SOURCE)));
+
+        let modified = SavedShaderKey::new(&Shader::from_wgsl(format!("{SOURCE}\n")));
+        assert!(key.is_replaced_by(&modified));
+        assert!(!key.is_replaced_by(&key));
+        let unnamed = SavedShaderKey::new(&Shader::from_wgsl("fn main() { }"));
+        let other_unnamed = SavedShaderKey::new(&Shader::from_wgsl("fn other() { }"));
+        assert!(!unnamed.is_replaced_by(&other_unnamed));
+    }
 
-    fn next(&mut self) -> Option<Self::Item> {
-        let current = self.current;
-        self.current = self.current.and_then(std::error::Error::source);
-        current
+    #[test]
+    fn persistence_saves_render_pipelines() {
+        let shader = Shader::from_wgsl("#define_import_path my_shader\nfn main() { }");
+        let handle: Handle<Shader> = HandleUntyped::weak_from_u64(Shader::TYPE_UUID, 1).typed();
+        let descriptor = RenderPipelineDescriptor {
+            label: Some("my_pipeline".into()),
+            layout: None,
+            vertex: VertexState {
+                shader: handle.clone_weak(),
+                shader_defs: vec!["VERTEX_UVS".into()],
+                entry_point: "vertex".into(),
+                buffers: Vec::new(),
+            },
+            primitive: PrimitiveState::default(),
+            depth_stencil: None,
+            multisample: MultisampleState::default(),
+            fragment: Some(FragmentState {
+                shader: handle.clone_weak(),
+                shader_defs: Vec::new(),
+                entry_point: "fragment".into(),
+                targets: vec![Some(ColorTargetState {
+                    format: TextureFormat::Rgba8UnormSrgb,
+                    blend: None,
+                    write_mask: ColorWrites::ALL,
+                })],
+            }),
+        };
+        let key = SavedShaderKey::new(&shader);
+        assert!(SavedRenderPipeline::new(&descriptor, |_| None).is_none());
+        let saved = SavedRenderPipeline::new(&descriptor, |shader| {
+            (*shader == handle).then(|| key.clone())
+        })
+        .unwrap();
+
+        let mut persistence = PipelineCachePersistence::new("pipeline_cache.ron".into(), default());
+        persistence.record_render_pipeline(saved.clone());
+        persistence.record_render_pipeline(saved.clone());
+        assert_eq!(persistence.saved.render_pipelines.len(), 1);
+
+        let serialized = ron::to_string(&persistence.saved).unwrap();
+        let loaded: SavedPipelineCache = ron::from_str(&serialized).unwrap();
+        assert_eq!(loaded.render_pipelines, vec![saved]);
+
+        // the pipelines of the previous versions of a shader are forgotten
+        persistence.changed = false;
+        let modified = Shader::from_wgsl("#define_import_path my_shader\nfn vertex() { }");
+        persistence.forget_replaced_shaders(&SavedShaderKey::new(&modified));
+        assert!(persistence.saved.render_pipelines.is_empty());
+        assert!(persistence.changed);
     }
 }
-
-impl<'a> FusedIterator for ErrorSources<'a> {}
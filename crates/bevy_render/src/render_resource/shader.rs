@@ -6,8 +6,18 @@ use naga::valid::Capabilities;
 use naga::{valid::ModuleInfo, Module};
 use once_cell::sync::Lazy;
 use regex::Regex;
+use serde::{Deserialize, Serialize};
 use std::{
-    borrow::Cow, collections::HashSet, marker::Copy, ops::Deref, path::PathBuf, str::FromStr,
+    borrow::Cow,
+    collections::{hash_map::DefaultHasher, HashSet},
+    fmt,
+    hash::{Hash, Hasher},
+    iter::FusedIterator,
+    marker::Copy,
+    mem,
+    ops::{Deref, Range},
+    path::PathBuf,
+    str::FromStr,
 };
 use thiserror::Error;
 use wgpu::Features;
@@ -40,6 +50,8 @@ pub enum ShaderReflectError {
 #[uuid = "d95bc916-6c55-4de3-9622-37e7b6969fda"]
 pub struct Shader {
     source: Source,
+    /// The asset path of the shader, if it was loaded from a file.
+    path: Option<String>,
     import_path: Option<ShaderImport>,
     imports: Vec<ShaderImport>,
 }
@@ -49,6 +61,7 @@ impl Shader {
         let source = source.into();
         let shader_imports = SHADER_IMPORT_PROCESSOR.get_imports_from_str(&source);
         Shader {
+            path: None,
             imports: shader_imports.imports,
             import_path: shader_imports.import_path,
             source: Source::Wgsl(source),
@@ -59,6 +72,7 @@ impl Shader {
         let source = source.into();
         let shader_imports = SHADER_IMPORT_PROCESSOR.get_imports_from_str(&source);
         Shader {
+            path: None,
             imports: shader_imports.imports,
             import_path: shader_imports.import_path,
             source: Source::Glsl(source, stage),
@@ -67,6 +81,7 @@ impl Shader {
 
     pub fn from_spirv(source: impl Into<Cow<'static, [u8]>>) -> Shader {
         Shader {
+            path: None,
             imports: Vec::new(),
             import_path: None,
             source: Source::SpirV(source.into()),
@@ -91,6 +106,89 @@ impl Shader {
     pub fn imports(&self) -> impl ExactSizeIterator<Item = &ShaderImport> {
         self.imports.iter()
     }
+
+    /// The asset path of the shader if it was loaded from a file, its import path otherwise.
+    pub(crate) fn name(&self) -> Option<&str> {
+        match (&self.path, &self.import_path) {
+            (Some(path), _) => Some(path),
+            (None, Some(ShaderImport::AssetPath(path) | ShaderImport::Custom(path))) => Some(path),
+            (None, None) => None,
+        }
+    }
+
+    /// The name of the shader in error messages.
+    pub(crate) fn source_name(&self) -> &str {
+        self.name().unwrap_or("shader")
+    }
+
+    /// Hashes the source of the shader, with the same result in every run of the app.
+    pub(crate) fn source_hash(&self) -> u64 {
+        // unlike the hashers of `HashMap`s, `DefaultHasher::new` isn't randomly seeded
+        let mut hasher = DefaultHasher::new();
+        mem::discriminant(&self.source).hash(&mut hasher);
+        match &self.source {
+            Source::Wgsl(source) => source.hash(&mut hasher),
+            Source::Glsl(source, stage) => {
+                source.hash(&mut hasher);
+                stage.hash(&mut hasher);
+            }
+            Source::SpirV(source) => source.hash(&mut hasher),
+        }
+        hasher.finish()
+    }
+
+    /// Returns `true` if the shader was loaded from a file, isn't a module imported by other
+    /// shaders, and couldn't be validated by the [`ShaderLoader`] because it has imports or
+    /// shader defs. Such shaders are validated once their imports are loaded.
+    pub(crate) fn is_validated_with_imports(&self) -> bool {
+        self.path.is_some()
+            && !matches!(self.import_path, Some(ShaderImport::Custom(_)))
+            && !SHADER_IMPORT_PROCESSOR.is_self_contained(self)
+    }
+}
+
+/// A shader def, which enables the `#ifdef NAME` blocks of a shader. Shader defs with a value
+/// can also be compared in `#if NAME == value` blocks, or have their value substituted with
+/// `#{NAME}`.
+///
+/// A shader def without a value is created from its name: `"MY_SHADER_DEF".into()`.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ShaderDefVal {
+    Bool(String, bool),
+    Int(String, i32),
+    UInt(String, u32),
+}
+
+impl From<&str> for ShaderDefVal {
+    fn from(name: &str) -> Self {
+        ShaderDefVal::Bool(name.to_string(), true)
+    }
+}
+
+impl From<String> for ShaderDefVal {
+    fn from(name: String) -> Self {
+        ShaderDefVal::Bool(name, true)
+    }
+}
+
+impl ShaderDefVal {
+    /// The name of the shader def, as used in `#ifdef NAME`.
+    pub fn name(&self) -> &str {
+        match self {
+            ShaderDefVal::Bool(name, _)
+            | ShaderDefVal::Int(name, _)
+            | ShaderDefVal::UInt(name, _) => name,
+        }
+    }
+
+    /// The value of the shader def as written in a shader, which replaces `#{NAME}`.
+    pub fn value_as_string(&self) -> String {
+        match self {
+            ShaderDefVal::Bool(_, value) => value.to_string(),
+            ShaderDefVal::Int(_, value) => value.to_string(),
+            ShaderDefVal::UInt(_, value) => value.to_string(),
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -198,6 +296,164 @@ impl ProcessedShader {
     }
 }
 
+impl ShaderReflectError {
+    /// Formats the error for display, pointing at the lines of the original shaders the
+    /// erroneous lines of the processed shader come from.
+    pub fn emit_to_string(&self, source: &ProcessedShader, source_map: &ShaderSourceMap) -> String {
+        use codespan_reporting::{
+            diagnostic::{Diagnostic, Label},
+            files::SimpleFile,
+            term,
+        };
+
+        let (filename, source_str) = match source {
+            ProcessedShader::Wgsl(source) => ("wgsl", source.as_ref()),
+            ProcessedShader::Glsl(source, _) => ("glsl", source.as_ref()),
+            ProcessedShader::SpirV(_) => return self.to_string(),
+        };
+        let note = |offset: usize| {
+            let line = source_str
+                .as_bytes()
+                .iter()
+                .take(offset)
+                .filter(|byte| **byte == b'\n')
+                .count()
+                + 1;
+            source_map
+                .location(line)
+                .map(|location| format!("{}:{} is {}", filename, line, location))
+        };
+
+        let files = SimpleFile::new(filename, source_str);
+        let config = term::Config::default();
+        let mut writer = term::termcolor::Ansi::new(Vec::new());
+        match self {
+            ShaderReflectError::WgslParse(error) => {
+                let mut msg = error.emit_to_string(source_str);
+                if let Some(error_location) = error.location(source_str) {
+                    let line = error_location.line_number as usize;
+                    if let Some(location) = source_map.location(line) {
+                        msg.push_str(&format!("  = {}:{} is {}\n", filename, line, location));
+                    }
+                }
+                return msg;
+            }
+            ShaderReflectError::GlslParse(errors) => {
+                for err in errors {
+                    let mut diagnostic = Diagnostic::error().with_message(err.kind.to_string());
+
+                    if let Some(range) = err.meta.to_range() {
+                        diagnostic = diagnostic
+                            .with_notes(note(range.start).into_iter().collect())
+                            .with_labels(vec![Label::primary((), range)]);
+                    }
+
+                    term::emit(&mut writer, &config, &files, &diagnostic)
+                        .expect("cannot write error");
+                }
+            }
+            ShaderReflectError::SpirVParse(error) => return error.to_string(),
+            ShaderReflectError::Validation(error) => {
+                let diagnostic = Diagnostic::error()
+                    .with_message(error.to_string())
+                    .with_labels(
+                        error
+                            .spans()
+                            .map(|(span, desc)| {
+                                Label::primary((), span.to_range().unwrap())
+                                    .with_message(desc.to_owned())
+                            })
+                            .collect(),
+                    )
+                    .with_notes(
+                        ErrorSources::of(error)
+                            .map(|source| source.to_string())
+                            .chain(
+                                error
+                                    .spans()
+                                    .filter_map(|(span, _)| note(span.to_range()?.start)),
+                            )
+                            .collect(),
+                    );
+
+                term::emit(&mut writer, &config, &files, &diagnostic).expect("cannot write error");
+            }
+        }
+
+        String::from_utf8_lossy(&writer.into_inner()).into_owned()
+    }
+}
+
+struct ErrorSources<'a> {
+    current: Option<&'a (dyn std::error::Error + 'static)>,
+}
+
+impl<'a> ErrorSources<'a> {
+    fn of(error: &'a dyn std::error::Error) -> Self {
+        Self {
+            current: error.source(),
+        }
+    }
+}
+
+impl<'a> Iterator for ErrorSources<'a> {
+    type Item = &'a (dyn std::error::Error + 'static);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let current = self.current;
+        self.current = self.current.and_then(std::error::Error::source);
+        current
+    }
+}
+
+impl<'a> FusedIterator for ErrorSources<'a> {}
+
+/// A line of the source of a [`Shader`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ShaderSourceLocation<'a> {
+    /// The asset path of the shader, or its import path if it wasn't loaded from a file.
+    pub file: &'a str,
+    /// The line in the shader, starting at 1.
+    pub line: usize,
+}
+
+impl<'a> fmt::Display for ShaderSourceLocation<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.file, self.line)
+    }
+}
+
+/// Maps the lines of a [`ProcessedShader`] to the lines of the shaders they were copied from,
+/// so that errors point at the original sources rather than at the processed one.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ShaderSourceMap {
+    files: Vec<String>,
+    /// The index of the file and the line in that file of each processed line.
+    lines: Vec<(usize, usize)>,
+}
+
+impl ShaderSourceMap {
+    /// Returns where the given line of the processed shader, starting at 1, comes from.
+    pub fn location(&self, processed_line: usize) -> Option<ShaderSourceLocation<'_>> {
+        let (file, line) = *self.lines.get(processed_line.checked_sub(1)?)?;
+        Some(ShaderSourceLocation {
+            file: &self.files[file],
+            line,
+        })
+    }
+
+    fn push(&mut self, file: &str, line: usize) {
+        let file = match self.files.iter().position(|f| f == file) {
+            Some(index) => index,
+            None => {
+                self.files.push(file.to_string());
+                self.files.len() - 1
+            }
+        };
+        self.lines.push((file, line));
+    }
+}
+
 #[derive(Error, Debug)]
 pub enum AsModuleDescriptorError {
     #[error(transparent)]
@@ -260,6 +516,7 @@ impl AssetLoader for ShaderLoader {
                 ),
                 _ => panic!("unhandled extension: {}", ext),
             };
+            shader.path = Some(load_context.path().to_string_lossy().to_string());
 
             let shader_imports = SHADER_IMPORT_PROCESSOR.get_imports(&shader);
             if shader_imports.import_path.is_some() {
@@ -269,6 +526,25 @@ impl AssetLoader for ShaderLoader {
                     load_context.path().to_string_lossy().to_string(),
                 ));
             }
+
+            // Shaders without imports or shader defs are complete, so they are validated right
+            // away instead of when a pipeline first uses them. The other shaders are validated by
+            // the `PipelineCache` once their imports are loaded.
+            if SHADER_IMPORT_PROCESSOR.is_self_contained(&shader) {
+                let (processed, source_map) = ShaderProcessor::default().process_with_source_map(
+                    &shader,
+                    &[],
+                    &HashMap::default(),
+                    &HashMap::default(),
+                )?;
+                if let Err(err) = processed.reflect(Features::all()) {
+                    return Err(anyhow::anyhow!(
+                        "{}",
+                        err.emit_to_string(&processed, &source_map)
+                    ));
+                }
+            }
+
             let mut asset = LoadedAsset::new(shader);
             for import in shader_imports.imports {
                 if let ShaderImport::AssetPath(asset_path) = import {
@@ -299,10 +575,32 @@ pub enum ProcessShaderError {
     ShaderFormatDoesNotSupportShaderDefs,
     #[error("This Shader's formatdoes not support imports.")]
     ShaderFormatDoesNotSupportImports,
+    #[error("This Shader's format does not support importing single items of a module.")]
+    ShaderFormatDoesNotSupportItemImports,
     #[error("Unresolved import: {0:?}.")]
     UnresolvedImport(ShaderImport),
     #[error("The shader import {0:?} does not match the source file type. Support for this might be added in the future.")]
     MismatchedImportFormat(ShaderImport),
+    #[error("The shader import {import:?} has no item named '{item}'.")]
+    UnknownImportItem { import: ShaderImport, item: String },
+    #[error("The item '{item}' of the shader import {import:?} has the same name as an item of {other:?}.")]
+    ConflictingImportItem {
+        import: ShaderImport,
+        other: ShaderImport,
+        item: String,
+    },
+    #[error("Unknown shader def: '{shader_def_name}'.")]
+    UnknownShaderDef { shader_def_name: String },
+    #[error(
+        "Invalid shader def comparison for '{shader_def_name}': expected a {expected}, got '{value}'."
+    )]
+    InvalidShaderDefComparisonValue {
+        shader_def_name: String,
+        expected: String,
+        value: String,
+    },
+    #[error("Unknown shader def operator: '{operator}'.")]
+    UnknownShaderDefOperator { operator: String },
 }
 
 pub struct ShaderImportProcessor {
@@ -317,6 +615,19 @@ pub enum ShaderImport {
     Custom(String),
 }
 
+impl ShaderImport {
+    /// Returns the module an import would name an item of, `a::b` for `a::b::c`, when the
+    /// import itself isn't a module.
+    pub fn parent_module(&self) -> Option<ShaderImport> {
+        match self {
+            ShaderImport::Custom(path) => path
+                .rsplit_once("::")
+                .map(|(module, _item)| ShaderImport::Custom(module.to_string())),
+            ShaderImport::AssetPath(_) => None,
+        }
+    }
+}
+
 impl Default for ShaderImportProcessor {
     fn default() -> Self {
         Self {
@@ -351,10 +662,8 @@ impl ShaderImportProcessor {
                     .imports
                     .push(ShaderImport::AssetPath(import.as_str().to_string()));
             } else if let Some(cap) = self.import_custom_path_regex.captures(line) {
-                let import = cap.get(1).unwrap();
-                shader_imports
-                    .imports
-                    .push(ShaderImport::Custom(import.as_str().to_string()));
+                let (import, _items) = parse_custom_import(cap.get(1).unwrap().as_str());
+                shader_imports.imports.push(import);
             } else if let Some(cap) = self.define_import_path_regex.captures(line) {
                 let path = cap.get(1).unwrap();
                 shader_imports.import_path = Some(ShaderImport::Custom(path.as_str().to_string()));
@@ -363,16 +672,65 @@ impl ShaderImportProcessor {
 
         shader_imports
     }
+
+    /// Returns `true` if the shader can be processed without imports or shader defs, that is if
+    /// it has no preprocessor directive other than `#define_import_path`.
+    pub(crate) fn is_self_contained(&self, shader: &Shader) -> bool {
+        let source = match &shader.source {
+            Source::Wgsl(source) | Source::Glsl(source, _) => source,
+            Source::SpirV(_) => return false,
+        };
+        !source.contains("#{")
+            && source.lines().all(|line| {
+                !line.trim_start().starts_with('#') || self.define_import_path_regex.is_match(line)
+            })
+    }
 }
 
 pub static SHADER_IMPORT_PROCESSOR: Lazy<ShaderImportProcessor> =
     Lazy::new(ShaderImportProcessor::default);
 
+/// Parses the path of an `#import` directive, which is either a module (`a::b`) or some items
+/// of a module (`a::b::{c, d}`).
+fn parse_custom_import(path: &str) -> (ShaderImport, Option<Vec<String>>) {
+    let path = path.trim();
+    match path
+        .strip_suffix('}')
+        .and_then(|path| path.split_once("::{"))
+    {
+        Some((module, items)) => (
+            ShaderImport::Custom(module.trim().to_string()),
+            Some(
+                items
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|item| !item.is_empty())
+                    .map(str::to_string)
+                    .collect(),
+            ),
+        ),
+        None => (ShaderImport::Custom(path.to_string()), None),
+    }
+}
+
+type ShaderDefs<'a> = HashMap<&'a str, &'a ShaderDefVal>;
+
+/// Processes the preprocessor directives of a [`Shader`]:
+/// - `#ifdef NAME`, `#ifndef NAME`, `#if NAME == value` (or `!=`, `<`, `<=`, `>`, `>=`),
+///   `#else` and `#endif` keep or remove lines depending on the [`ShaderDefVal`]s.
+/// - `#{NAME}` is replaced by the value of the shader def `NAME`.
+/// - `#import a::b` copies the module `a::b`, `#import a::b::c` or `#import a::b::{c, d}`
+///   only copies the given items of the module, along with the items they use. The items that
+///   are only copied because they are used are renamed with the path of their module, as in
+///   `a__b__e`, so that modules can use items with the same name. Each module or item is only
+///   copied once, however many times it is imported.
 pub struct ShaderProcessor {
     ifdef_regex: Regex,
     ifndef_regex: Regex,
+    ifop_regex: Regex,
     else_regex: Regex,
     endif_regex: Regex,
+    def_regex: Regex,
 }
 
 impl Default for ShaderProcessor {
@@ -380,41 +738,140 @@ impl Default for ShaderProcessor {
         Self {
             ifdef_regex: Regex::new(r"^\s*#\s*ifdef\s*([\w|\d|_]+)").unwrap(),
             ifndef_regex: Regex::new(r"^\s*#\s*ifndef\s*([\w|\d|_]+)").unwrap(),
+            ifop_regex: Regex::new(r"^\s*#\s*if\s+([\w|\d|_]+)\s*([=!<>]*)\s*([-\w|\d]+)").unwrap(),
             else_regex: Regex::new(r"^\s*#\s*else").unwrap(),
             endif_regex: Regex::new(r"^\s*#\s*endif").unwrap(),
+            def_regex: Regex::new(r"#\{([\w|\d|_]+)\}").unwrap(),
         }
     }
 }
 
+/// The imports already copied into a shader being processed, so they are only copied once.
+#[derive(Default)]
+struct ImportedItems {
+    modules: HashSet<ShaderImport>,
+    items: HashMap<ShaderImport, HashSet<String>>,
+    /// The module each item requested by an item import comes from, as these items are copied
+    /// without their module path and two items with the same name can't be copied.
+    item_modules: HashMap<String, ShaderImport>,
+}
+
+/// The source of a shader being processed, along with where each of its lines comes from.
+#[derive(Default)]
+struct ProcessedSource {
+    source: String,
+    source_map: ShaderSourceMap,
+}
+
+impl ProcessedSource {
+    fn push_line(&mut self, line: &str, file: &str, line_number: usize) {
+        self.source.push_str(line);
+        self.source.push('\n');
+        self.source_map.push(file, line_number);
+    }
+}
+
 impl ShaderProcessor {
     pub fn process(
         &self,
         shader: &Shader,
-        shader_defs: &[String],
+        shader_defs: &[ShaderDefVal],
         shaders: &HashMap<Handle<Shader>, Shader>,
         import_handles: &HashMap<ShaderImport, Handle<Shader>>,
     ) -> Result<ProcessedShader, ProcessShaderError> {
+        self.process_with_source_map(shader, shader_defs, shaders, import_handles)
+            .map(|(processed, _source_map)| processed)
+    }
+
+    /// Processes the shader like [`ShaderProcessor::process`], also returning where each line of
+    /// the processed shader comes from.
+    pub fn process_with_source_map(
+        &self,
+        shader: &Shader,
+        shader_defs: &[ShaderDefVal],
+        shaders: &HashMap<Handle<Shader>, Shader>,
+        import_handles: &HashMap<ShaderImport, Handle<Shader>>,
+    ) -> Result<(ProcessedShader, ShaderSourceMap), ProcessShaderError> {
+        if let Source::SpirV(source) = &shader.source {
+            if shader_defs.is_empty() {
+                return Ok((
+                    ProcessedShader::SpirV(source.clone()),
+                    ShaderSourceMap::default(),
+                ));
+            }
+            return Err(ProcessShaderError::ShaderFormatDoesNotSupportShaderDefs);
+        }
+
+        // a shader def overrides the previous ones with the same name
+        let shader_defs = shader_defs
+            .iter()
+            .map(|shader_def| (shader_def.name(), shader_def))
+            .collect::<ShaderDefs>();
+        let mut imported = ImportedItems::default();
+        if let Some(import_path) = shader.import_path() {
+            imported.modules.insert(import_path.clone());
+        }
+        let mut processed = ProcessedSource::default();
+        self.process_source(
+            shader,
+            &shader_defs,
+            shaders,
+            import_handles,
+            &mut imported,
+            &mut processed,
+            None,
+        )?;
+
+        let processed_source = Cow::from(processed.source);
+        let processed_shader = match &shader.source {
+            Source::Wgsl(_source) => ProcessedShader::Wgsl(processed_source),
+            Source::Glsl(_source, stage) => ProcessedShader::Glsl(processed_source, *stage),
+            Source::SpirV(_source) => {
+                unreachable!("SpirV has early return");
+            }
+        };
+        Ok((processed_shader, processed.source_map))
+    }
+
+    /// Appends the processed lines of `shader` to `output`. The imports of the shader are copied
+    /// to `imports_output` if any, to `output` otherwise.
+    #[allow(clippy::too_many_arguments)]
+    fn process_source(
+        &self,
+        shader: &Shader,
+        shader_defs: &ShaderDefs,
+        shaders: &HashMap<Handle<Shader>, Shader>,
+        import_handles: &HashMap<ShaderImport, Handle<Shader>>,
+        imported: &mut ImportedItems,
+        output: &mut ProcessedSource,
+        mut imports_output: Option<&mut ProcessedSource>,
+    ) -> Result<(), ProcessShaderError> {
         let shader_str = match &shader.source {
             Source::Wgsl(source) => source.deref(),
             Source::Glsl(source, _stage) => source.deref(),
-            Source::SpirV(source) => {
-                if shader_defs.is_empty() {
-                    return Ok(ProcessedShader::SpirV(source.clone()));
-                }
-                return Err(ProcessShaderError::ShaderFormatDoesNotSupportShaderDefs);
+            Source::SpirV(_source) => {
+                return Err(ProcessShaderError::ShaderFormatDoesNotSupportImports);
             }
         };
+        let file = shader.source_name();
 
-        let shader_defs_unique = HashSet::<String>::from_iter(shader_defs.iter().cloned());
         let mut scopes = vec![true];
-        let mut final_string = String::new();
-        for line in shader_str.lines() {
+        for (index, line) in shader_str.lines().enumerate() {
             if let Some(cap) = self.ifdef_regex.captures(line) {
                 let def = cap.get(1).unwrap();
-                scopes.push(*scopes.last().unwrap() && shader_defs_unique.contains(def.as_str()));
+                scopes.push(*scopes.last().unwrap() && is_defined(shader_defs, def.as_str()));
             } else if let Some(cap) = self.ifndef_regex.captures(line) {
                 let def = cap.get(1).unwrap();
-                scopes.push(*scopes.last().unwrap() && !shader_defs_unique.contains(def.as_str()));
+                scopes.push(*scopes.last().unwrap() && !is_defined(shader_defs, def.as_str()));
+            } else if let Some(cap) = self.ifop_regex.captures(line) {
+                let is_true = *scopes.last().unwrap()
+                    && compare_shader_def(
+                        shader_defs,
+                        cap.get(1).unwrap().as_str(),
+                        cap.get(2).unwrap().as_str(),
+                        cap.get(3).unwrap().as_str(),
+                    )?;
+                scopes.push(is_true);
             } else if self.else_regex.is_match(line) {
                 let mut is_parent_scope_truthy = true;
                 if scopes.len() > 1 {
@@ -435,25 +892,30 @@ impl ShaderProcessor {
                 {
                     let import = ShaderImport::AssetPath(cap.get(1).unwrap().as_str().to_string());
                     self.apply_import(
-                        import_handles,
-                        shaders,
-                        &import,
                         shader,
+                        &import,
+                        None,
                         shader_defs,
-                        &mut final_string,
+                        shaders,
+                        import_handles,
+                        imported,
+                        imports_output.as_deref_mut().unwrap_or(&mut *output),
                     )?;
                 } else if let Some(cap) = SHADER_IMPORT_PROCESSOR
                     .import_custom_path_regex
                     .captures(line)
                 {
-                    let import = ShaderImport::Custom(cap.get(1).unwrap().as_str().to_string());
+                    let (import, items) =
+                        resolve_custom_import(cap.get(1).unwrap().as_str(), import_handles);
                     self.apply_import(
-                        import_handles,
-                        shaders,
-                        &import,
                         shader,
+                        &import,
+                        items,
                         shader_defs,
-                        &mut final_string,
+                        shaders,
+                        import_handles,
+                        imported,
+                        imports_output.as_deref_mut().unwrap_or(&mut *output),
                     )?;
                 } else if SHADER_IMPORT_PROCESSOR
                     .define_import_path_regex
@@ -461,8 +923,8 @@ impl ShaderProcessor {
                 {
                     // ignore import path lines
                 } else {
-                    final_string.push_str(line);
-                    final_string.push('\n');
+                    let line = self.substitute_shader_defs(line, shader_defs)?;
+                    output.push_line(&line, file, index + 1);
                 }
             }
         }
@@ -471,57 +933,495 @@ impl ShaderProcessor {
             return Err(ProcessShaderError::NotEnoughEndIfs);
         }
 
-        let processed_source = Cow::from(final_string);
+        Ok(())
+    }
 
-        match &shader.source {
-            Source::Wgsl(_source) => Ok(ProcessedShader::Wgsl(processed_source)),
-            Source::Glsl(_source, stage) => Ok(ProcessedShader::Glsl(processed_source, *stage)),
-            Source::SpirV(_source) => {
-                unreachable!("SpirV has early return");
-            }
+    /// Replaces the `#{NAME}` in the line by the value of the shader def `NAME`.
+    fn substitute_shader_defs<'a>(
+        &self,
+        line: &'a str,
+        shader_defs: &ShaderDefs,
+    ) -> Result<Cow<'a, str>, ProcessShaderError> {
+        if !line.contains("#{") {
+            return Ok(Cow::Borrowed(line));
         }
+
+        let mut substituted = String::with_capacity(line.len());
+        let mut last_end = 0;
+        for cap in self.def_regex.captures_iter(line) {
+            let substitution = cap.get(0).unwrap();
+            let name = cap.get(1).unwrap().as_str();
+            let shader_def =
+                shader_defs
+                    .get(name)
+                    .ok_or_else(|| ProcessShaderError::UnknownShaderDef {
+                        shader_def_name: name.to_string(),
+                    })?;
+            substituted.push_str(&line[last_end..substitution.start()]);
+            substituted.push_str(&shader_def.value_as_string());
+            last_end = substitution.end();
+        }
+        substituted.push_str(&line[last_end..]);
+
+        Ok(Cow::Owned(substituted))
     }
 
+    /// Copies the imported module to `output`, or only the given items of the module if any.
+    #[allow(clippy::too_many_arguments)]
     fn apply_import(
         &self,
-        import_handles: &HashMap<ShaderImport, Handle<Shader>>,
-        shaders: &HashMap<Handle<Shader>, Shader>,
-        import: &ShaderImport,
         shader: &Shader,
-        shader_defs: &[String],
-        final_string: &mut String,
+        import: &ShaderImport,
+        items: Option<Vec<String>>,
+        shader_defs: &ShaderDefs,
+        shaders: &HashMap<Handle<Shader>, Shader>,
+        import_handles: &HashMap<ShaderImport, Handle<Shader>>,
+        imported: &mut ImportedItems,
+        output: &mut ProcessedSource,
     ) -> Result<(), ProcessShaderError> {
         let imported_shader = import_handles
             .get(import)
             .and_then(|handle| shaders.get(handle))
             .ok_or_else(|| ProcessShaderError::UnresolvedImport(import.clone()))?;
-        let imported_processed =
-            self.process(imported_shader, shader_defs, shaders, import_handles)?;
 
-        match &shader.source {
-            Source::Wgsl(_) => {
-                if let ProcessedShader::Wgsl(import_source) = &imported_processed {
-                    final_string.push_str(import_source);
-                } else {
-                    return Err(ProcessShaderError::MismatchedImportFormat(import.clone()));
+        match (&shader.source, &imported_shader.source) {
+            (Source::Wgsl(_), Source::Wgsl(_)) | (Source::Glsl(_, _), Source::Glsl(_, _)) => {}
+            (Source::SpirV(_), _) => {
+                return Err(ProcessShaderError::ShaderFormatDoesNotSupportImports);
+            }
+            _ => return Err(ProcessShaderError::MismatchedImportFormat(import.clone())),
+        }
+
+        if imported.modules.contains(import) {
+            return Ok(());
+        }
+        if items.is_none() {
+            imported.modules.insert(import.clone());
+            // the module is copied whole, unless some of its items were already copied
+            if !imported.items.contains_key(import) {
+                return self.process_source(
+                    imported_shader,
+                    shader_defs,
+                    shaders,
+                    import_handles,
+                    imported,
+                    output,
+                    None,
+                );
+            }
+        }
+        if !matches!(imported_shader.source, Source::Wgsl(_)) {
+            return Err(ProcessShaderError::ShaderFormatDoesNotSupportItemImports);
+        }
+
+        // The imports of the module are copied directly to the output, so that only the items
+        // of the module itself are picked from the processed module.
+        let mut module = ProcessedSource::default();
+        self.process_source(
+            imported_shader,
+            shader_defs,
+            shaders,
+            import_handles,
+            imported,
+            &mut module,
+            Some(&mut *output),
+        )?;
+        let module_items = split_wgsl_items(&module.source);
+        let item_names = module_items
+            .iter()
+            .filter_map(|item| item.name.as_deref())
+            .collect::<HashSet<_>>();
+
+        let mut pending = match items {
+            Some(items) => {
+                if let Some(item) = items
+                    .iter()
+                    .find(|item| !item_names.contains(item.as_str()))
+                {
+                    return Err(ProcessShaderError::UnknownImportItem {
+                        import: import.clone(),
+                        item: item.clone(),
+                    });
                 }
+                items
             }
-            Source::Glsl(_, _) => {
-                if let ProcessedShader::Glsl(import_source, _) = &imported_processed {
-                    final_string.push_str(import_source);
-                } else {
-                    return Err(ProcessShaderError::MismatchedImportFormat(import.clone()));
+            None => item_names.iter().map(|name| name.to_string()).collect(),
+        };
+
+        // The requested items are copied along with the items they use, which keep the name
+        // they were copied with before if any, and are renamed with the module path otherwise.
+        let requested = pending.iter().cloned().collect::<HashSet<_>>();
+        let already_imported = imported.items.entry(import.clone()).or_default();
+        let mut visited = HashSet::new();
+        let mut renames = HashMap::default();
+        let mut selected = HashSet::new();
+        while let Some(name) = pending.pop() {
+            if !visited.insert(name.clone()) {
+                continue;
+            }
+            let output_name = if requested.contains(&name) || already_imported.contains(&name) {
+                name.clone()
+            } else {
+                mangle_item_name(import, &name)
+            };
+            if output_name != name {
+                renames.insert(name.clone(), output_name.clone());
+            }
+            if already_imported.contains(&output_name) {
+                continue;
+            }
+            if let Some(item) = module_items
+                .iter()
+                .find(|item| item.name.as_deref() == Some(name.as_str()))
+            {
+                pending.extend(
+                    item.identifiers
+                        .iter()
+                        .filter(|identifier| item_names.contains(identifier.as_str()))
+                        .cloned(),
+                );
+            }
+            selected.insert(name);
+        }
+
+        for name in selected.iter().filter(|name| !renames.contains_key(*name)) {
+            match imported.item_modules.get(name) {
+                Some(other) if other != import => {
+                    return Err(ProcessShaderError::ConflictingImportItem {
+                        import: import.clone(),
+                        other: other.clone(),
+                        item: name.clone(),
+                    });
+                }
+                _ => {
+                    imported.item_modules.insert(name.clone(), import.clone());
                 }
             }
-            Source::SpirV(_) => {
-                return Err(ProcessShaderError::ShaderFormatDoesNotSupportImports);
+        }
+
+        let lines = module.source.lines().collect::<Vec<_>>();
+        for item in &module_items {
+            if !matches!(&item.name, Some(name) if selected.contains(name)) {
+                continue;
+            }
+            let mut in_block_comment = false;
+            for (index, range) in &item.spans {
+                let mut text = &lines[*index][range.clone()];
+                if range.start > 0 {
+                    // the item starts after the end of another one on the same line
+                    text = text.trim_start();
+                    if text.is_empty() {
+                        continue;
+                    }
+                }
+                let text =
+                    rename_wgsl_identifiers(text, &renames, item.is_struct, &mut in_block_comment);
+                let location = module.source_map.location(index + 1).unwrap();
+                output.push_line(&text, location.file, location.line);
             }
         }
+        already_imported.extend(
+            selected
+                .into_iter()
+                .map(|name| renames.get(&name).cloned().unwrap_or(name)),
+        );
 
         Ok(())
     }
 }
 
+/// Resolves a custom `#import` directive to the imported module and the items to import from
+/// it, if any: `a::b::c` imports the module `a::b::c` if it exists, the item `c` of the module
+/// `a::b` otherwise.
+fn resolve_custom_import(
+    path: &str,
+    import_handles: &HashMap<ShaderImport, Handle<Shader>>,
+) -> (ShaderImport, Option<Vec<String>>) {
+    let (import, items) = parse_custom_import(path);
+    if items.is_some() || import_handles.contains_key(&import) {
+        return (import, items);
+    }
+    if let ShaderImport::Custom(path) = &import {
+        if let Some((module, item)) = path.rsplit_once("::") {
+            let module = ShaderImport::Custom(module.to_string());
+            if import_handles.contains_key(&module) {
+                return (module, Some(vec![item.to_string()]));
+            }
+        }
+    }
+    // reported as an unresolved import
+    (import, None)
+}
+
+fn is_defined(shader_defs: &ShaderDefs, name: &str) -> bool {
+    !matches!(
+        shader_defs.get(name),
+        None | Some(ShaderDefVal::Bool(_, false))
+    )
+}
+
+/// Evaluates the condition of an `#if` directive.
+fn compare_shader_def(
+    shader_defs: &ShaderDefs,
+    name: &str,
+    operator: &str,
+    value: &str,
+) -> Result<bool, ProcessShaderError> {
+    fn compare<T: PartialOrd>(
+        shader_def_value: T,
+        operator: &str,
+        value: T,
+    ) -> Result<bool, ProcessShaderError> {
+        match operator {
+            "==" => Ok(shader_def_value == value),
+            "!=" => Ok(shader_def_value != value),
+            "<" => Ok(shader_def_value < value),
+            "<=" => Ok(shader_def_value <= value),
+            ">" => Ok(shader_def_value > value),
+            ">=" => Ok(shader_def_value >= value),
+            _ => Err(ProcessShaderError::UnknownShaderDefOperator {
+                operator: operator.to_string(),
+            }),
+        }
+    }
+
+    let shader_def = shader_defs
+        .get(name)
+        .ok_or_else(|| ProcessShaderError::UnknownShaderDef {
+            shader_def_name: name.to_string(),
+        })?;
+    let invalid_value = |expected: &str| ProcessShaderError::InvalidShaderDefComparisonValue {
+        shader_def_name: name.to_string(),
+        expected: expected.to_string(),
+        value: value.to_string(),
+    };
+    match shader_def {
+        ShaderDefVal::Bool(_, shader_def_value) => compare(
+            *shader_def_value,
+            operator,
+            value.parse::<bool>().map_err(|_| invalid_value("bool"))?,
+        ),
+        ShaderDefVal::Int(_, shader_def_value) => compare(
+            *shader_def_value,
+            operator,
+            value.parse::<i32>().map_err(|_| invalid_value("i32"))?,
+        ),
+        ShaderDefVal::UInt(_, shader_def_value) => compare(
+            *shader_def_value,
+            operator,
+            value.parse::<u32>().map_err(|_| invalid_value("u32"))?,
+        ),
+    }
+}
+
+/// A top-level declaration of a WGSL module, with the comments and attributes preceding it.
+struct WgslItem {
+    /// The name of the declared function, struct, variable, constant or type alias, or `None`
+    /// for other declarations such as directives.
+    name: Option<String>,
+    /// Whether the item declares a struct.
+    is_struct: bool,
+    /// The parts of the source lines making up the declaration, as a declaration can start on
+    /// the line where the previous one ends.
+    spans: Vec<(usize, Range<usize>)>,
+    /// The identifiers used by the declaration.
+    identifiers: HashSet<String>,
+}
+
+/// Splits processed WGSL source into its top-level declarations.
+fn split_wgsl_items(source: &str) -> Vec<WgslItem> {
+    const DECLARATIONS: &[&str] = &[
+        "fn", "struct", "var", "let", "const", "override", "type", "alias",
+    ];
+
+    let mut items = Vec::new();
+    let mut spans = Vec::new();
+    let mut depth = 0usize;
+    let mut template_depth = 0usize;
+    let mut in_block_comment = false;
+    let mut declaration = None;
+    let mut name = None;
+    let mut identifiers = HashSet::new();
+    for (index, line) in source.lines().enumerate() {
+        let mut start = 0;
+        let mut chars = line.char_indices().peekable();
+        while let Some((i, c)) = chars.next() {
+            if in_block_comment {
+                if c == '*' && matches!(chars.peek(), Some((_, '/'))) {
+                    chars.next();
+                    in_block_comment = false;
+                }
+                continue;
+            }
+            let mut is_end = false;
+            match c {
+                '/' if matches!(chars.peek(), Some((_, '/'))) => break,
+                '/' if matches!(chars.peek(), Some((_, '*'))) => {
+                    chars.next();
+                    in_block_comment = true;
+                }
+                '{' => depth += 1,
+                '}' => {
+                    depth = depth.saturating_sub(1);
+                    if depth == 0 && matches!(declaration, Some("fn" | "struct")) {
+                        is_end = true;
+                    }
+                }
+                ';' if depth == 0 => is_end = true,
+                // skips the address space of `var<uniform> name`
+                '<' if declaration == Some("var") && name.is_none() => template_depth += 1,
+                '>' if template_depth > 0 => template_depth -= 1,
+                c if c.is_ascii_alphabetic() || c == '_' => {
+                    let mut end = i + 1;
+                    while let Some(&(j, c)) = chars.peek() {
+                        if !c.is_ascii_alphanumeric() && c != '_' {
+                            break;
+                        }
+                        end = j + 1;
+                        chars.next();
+                    }
+                    let identifier = &line[i..end];
+                    if depth == 0 && declaration.is_none() && DECLARATIONS.contains(&identifier) {
+                        declaration = DECLARATIONS
+                            .iter()
+                            .copied()
+                            .find(|declaration| *declaration == identifier);
+                    } else if declaration.is_some() && name.is_none() && template_depth == 0 {
+                        name = Some(identifier.to_string());
+                    } else {
+                        identifiers.insert(identifier.to_string());
+                    }
+                }
+                _ => {}
+            }
+
+            if is_end {
+                let mut end = i + 1;
+                // keeps the `;` following the `}` of a struct with the struct
+                if c == '}' {
+                    while let Some(&(j, c)) = chars.peek() {
+                        if c == ';' {
+                            end = j + 1;
+                            chars.next();
+                            break;
+                        }
+                        if !c.is_whitespace() {
+                            break;
+                        }
+                        chars.next();
+                    }
+                }
+                spans.push((index, start..end));
+                items.push(WgslItem {
+                    name: name.take(),
+                    is_struct: declaration == Some("struct"),
+                    spans: mem::take(&mut spans),
+                    identifiers: mem::take(&mut identifiers),
+                });
+                start = end;
+                declaration = None;
+                template_depth = 0;
+            }
+        }
+        if start == 0 || start < line.len() {
+            spans.push((index, start..line.len()));
+        }
+    }
+
+    items
+}
+
+/// Returns the name an item of `import` is copied with when it isn't imported by its own name.
+fn mangle_item_name(import: &ShaderImport, name: &str) -> String {
+    let module = match import {
+        ShaderImport::Custom(path) => path.replace("::", "__"),
+        ShaderImport::AssetPath(path) => path
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+            .collect(),
+    };
+    format!("{module}__{name}")
+}
+
+/// Renames the identifiers of a line of WGSL according to `renames`, leaving alone the comments,
+/// the attributes, the members accessed with `.` and, in structs, the field names.
+fn rename_wgsl_identifiers<'a>(
+    line: &'a str,
+    renames: &HashMap<String, String>,
+    is_struct: bool,
+    in_block_comment: &mut bool,
+) -> Cow<'a, str> {
+    if renames.is_empty() {
+        return Cow::Borrowed(line);
+    }
+
+    let mut renamed = String::new();
+    let mut last_end = 0;
+    let mut previous = None;
+    let mut attribute_depth = 0usize;
+    let mut chars = line.char_indices().peekable();
+    while let Some((i, c)) = chars.next() {
+        if *in_block_comment {
+            if c == '*' && matches!(chars.peek(), Some((_, '/'))) {
+                chars.next();
+                *in_block_comment = false;
+            }
+            continue;
+        }
+        match c {
+            '/' if matches!(chars.peek(), Some((_, '/'))) => break,
+            '/' if matches!(chars.peek(), Some((_, '*'))) => {
+                chars.next();
+                *in_block_comment = true;
+            }
+            // skips the name of the attribute, and its arguments if any
+            '@' => {
+                while matches!(chars.peek(), Some((_, c)) if c.is_ascii_alphanumeric() || *c == '_')
+                {
+                    chars.next();
+                }
+                if matches!(chars.peek(), Some((_, '('))) {
+                    attribute_depth = 1;
+                    chars.next();
+                }
+            }
+            '(' if attribute_depth > 0 => attribute_depth += 1,
+            ')' if attribute_depth > 0 => attribute_depth -= 1,
+            c if (c.is_ascii_alphabetic() || c == '_') && attribute_depth == 0 => {
+                let mut end = i + 1;
+                while let Some(&(j, c)) = chars.peek() {
+                    if !c.is_ascii_alphanumeric() && c != '_' {
+                        break;
+                    }
+                    end = j + 1;
+                    chars.next();
+                }
+                let rest = line[end..].trim_start();
+                let is_field = is_struct && rest.starts_with(':') && !rest.starts_with("::");
+                if previous != Some('.') && !is_field {
+                    if let Some(new_name) = renames.get(&line[i..end]) {
+                        renamed.push_str(&line[last_end..i]);
+                        renamed.push_str(new_name);
+                        last_end = end;
+                    }
+                }
+                previous = line[..end].chars().last();
+                continue;
+            }
+            _ => {}
+        }
+        if !c.is_whitespace() {
+            previous = Some(c);
+        }
+    }
+
+    if last_end == 0 {
+        return Cow::Borrowed(line);
+    }
+    renamed.push_str(&line[last_end..]);
+    Cow::Owned(renamed)
+}
+
 /// A reference to a shader asset.
 pub enum ShaderRef {
     /// Use the "default" shader for the current context.
@@ -557,7 +1457,10 @@ mod tests {
     use bevy_utils::HashMap;
     use naga::ShaderStage;
 
-    use crate::render_resource::{ProcessShaderError, Shader, ShaderImport, ShaderProcessor};
+    use crate::render_resource::{
+        ProcessShaderError, Shader, ShaderDefVal, ShaderImport, ShaderProcessor,
+        ShaderSourceLocation,
+    };
     #[rustfmt::skip]
 const WGSL: &str = r"
 struct View {
@@ -723,7 +1626,7 @@ fn vertex(
         let result = processor
             .process(
                 &Shader::from_wgsl(WGSL),
-                &["TEXTURE".to_string()],
+                &["TEXTURE".into()],
                 &HashMap::default(),
                 &HashMap::default(),
             )
@@ -968,7 +1871,7 @@ fn vertex(
         let result = processor
             .process(
                 &Shader::from_wgsl(WGSL_NESTED_IFDEF),
-                &["TEXTURE".to_string()],
+                &["TEXTURE".into()],
                 &HashMap::default(),
                 &HashMap::default(),
             )
@@ -1010,7 +1913,7 @@ fn vertex(
         let result = processor
             .process(
                 &Shader::from_wgsl(WGSL_NESTED_IFDEF_ELSE),
-                &["TEXTURE".to_string()],
+                &["TEXTURE".into()],
                 &HashMap::default(),
                 &HashMap::default(),
             )
@@ -1130,7 +2033,7 @@ fn vertex(
         let result = processor
             .process(
                 &Shader::from_wgsl(WGSL_NESTED_IFDEF),
-                &["ATTRIBUTE".to_string()],
+                &["ATTRIBUTE".into()],
                 &HashMap::default(),
                 &HashMap::default(),
             )
@@ -1172,7 +2075,7 @@ fn vertex(
         let result = processor
             .process(
                 &Shader::from_wgsl(WGSL_NESTED_IFDEF),
-                &["TEXTURE".to_string(), "ATTRIBUTE".to_string()],
+                &["TEXTURE".into(), "ATTRIBUTE".into()],
                 &HashMap::default(),
                 &HashMap::default(),
             )
@@ -1219,7 +2122,7 @@ fn in_main_present() { }
         let result = processor
             .process(
                 &Shader::from_wgsl(INPUT),
-                &["MAIN_PRESENT".to_string(), "IMPORT_PRESENT".to_string()],
+                &["MAIN_PRESENT".into(), "IMPORT_PRESENT".into()],
                 &shaders,
                 &import_handles,
             )
@@ -1274,7 +2177,7 @@ fn in_main() { }
         let result = processor
             .process(
                 &Shader::from_wgsl(INPUT),
-                &["DEEP".to_string()],
+                &["DEEP".into()],
                 &shaders,
                 &import_handles,
             )
@@ -1332,7 +2235,7 @@ fn baz() { }
         let result = processor
             .process(
                 &Shader::from_wgsl(INPUT),
-                &["FOO".to_string()],
+                &["FOO".into()],
                 &shaders,
                 &import_handles,
             )
@@ -1344,4 +2247,419 @@ fn baz() { }
             .unwrap();
         assert_eq!(result.get_wgsl_source().unwrap(), EXPECTED);
     }
+
+    #[test]
+    fn process_import_once() {
+        #[rustfmt::skip]
+        const BAR: &str = r"
+fn bar() { }
+";
+        #[rustfmt::skip]
+        const FOO: &str = r"
+#import BAR
+fn foo() { }
+";
+        #[rustfmt::skip]
+        const INPUT: &str = r"
+#import BAR
+#import FOO
+fn main() { }
+";
+        #[rustfmt::skip]
+        const EXPECTED: &str = r"
+
+fn bar() { }
+
+fn foo() { }
+fn main() { }
+";
+        let processor = ShaderProcessor::default();
+        let mut shaders = HashMap::default();
+        let mut import_handles = HashMap::default();
+        {
+            let bar_handle = Handle::<Shader>::default();
+            shaders.insert(bar_handle.clone_weak(), Shader::from_wgsl(BAR));
+            import_handles.insert(
+                ShaderImport::Custom("BAR".to_string()),
+                bar_handle.clone_weak(),
+            );
+        }
+        {
+            let foo_handle = HandleUntyped::weak_from_u64(Shader::TYPE_UUID, 1).typed();
+            shaders.insert(foo_handle.clone_weak(), Shader::from_wgsl(FOO));
+            import_handles.insert(
+                ShaderImport::Custom("FOO".to_string()),
+                foo_handle.clone_weak(),
+            );
+        }
+        let result = processor
+            .process(&Shader::from_wgsl(INPUT), &[], &shaders, &import_handles)
+            .unwrap();
+        assert_eq!(result.get_wgsl_source().unwrap(), EXPECTED);
+    }
+
+    #[test]
+    fn process_import_items() {
+        #[rustfmt::skip]
+        const LIGHTING: &str = r"
+#define_import_path bevy_pbr::lighting
+
+struct Light {
+    color: vec3<f32>,
+};
+
+// the attenuation of a light
+fn attenuation(distance: f32) -> f32 {
+    return 1.0 / (distance * distance);
+}
+
+fn point_light(light: Light, distance: f32) -> vec3<f32> {
+    return light.color * attenuation(distance);
+}
+
+fn spot_light(light: Light) -> vec3<f32> {
+    return light.color;
+}
+";
+        #[rustfmt::skip]
+        const INPUT: &str = r"
+#import bevy_pbr::lighting::{Light, attenuation}
+#import bevy_pbr::lighting::point_light
+fn main() { }
+";
+        #[rustfmt::skip]
+        const EXPECTED: &str = r"
+
+
+struct Light {
+    color: vec3<f32>,
+};
+
+// the attenuation of a light
+fn attenuation(distance: f32) -> f32 {
+    return 1.0 / (distance * distance);
+}
+
+fn point_light(light: Light, distance: f32) -> vec3<f32> {
+    return light.color * attenuation(distance);
+}
+fn main() { }
+";
+        let processor = ShaderProcessor::default();
+        let mut shaders = HashMap::default();
+        let mut import_handles = HashMap::default();
+        let lighting_handle = Handle::<Shader>::default();
+        shaders.insert(lighting_handle.clone_weak(), Shader::from_wgsl(LIGHTING));
+        import_handles.insert(
+            ShaderImport::Custom("bevy_pbr::lighting".to_string()),
+            lighting_handle.clone_weak(),
+        );
+        let result = processor
+            .process(&Shader::from_wgsl(INPUT), &[], &shaders, &import_handles)
+            .unwrap();
+        assert_eq!(result.get_wgsl_source().unwrap(), EXPECTED);
+
+        #[rustfmt::skip]
+        const UNKNOWN: &str = r"
+#import bevy_pbr::lighting::area_light
+";
+        let result = processor.process(&Shader::from_wgsl(UNKNOWN), &[], &shaders, &import_handles);
+        assert_eq!(
+            result,
+            Err(ProcessShaderError::UnknownImportItem {
+                import: ShaderImport::Custom("bevy_pbr::lighting".to_string()),
+                item: "area_light".to_string(),
+            })
+        );
+    }
+
+    #[test]
+    fn process_conflicting_import_items() {
+        #[rustfmt::skip]
+        const POINT: &str = r"
+#define_import_path lights::point
+
+fn falloff(distance: f32) -> f32 {
+    return 1.0 / (distance * distance);
+}
+
+fn point_light(distance: f32) -> f32 {
+    return falloff(distance);
+}
+";
+        #[rustfmt::skip]
+        const SPOT: &str = r"
+#define_import_path lights::spot
+
+fn falloff(distance: f32) -> f32 {
+    return 1.0 / distance;
+}
+
+fn spot_light(distance: f32) -> f32 {
+    return falloff(distance);
+}
+";
+        #[rustfmt::skip]
+        const INPUT: &str = r"
+#import lights::point::point_light
+#import lights::spot::spot_light
+fn main() { }
+";
+        #[rustfmt::skip]
+        const EXPECTED: &str = r"
+
+
+fn lights__point__falloff(distance: f32) -> f32 {
+    return 1.0 / (distance * distance);
+}
+
+fn point_light(distance: f32) -> f32 {
+    return lights__point__falloff(distance);
+}
+
+
+fn lights__spot__falloff(distance: f32) -> f32 {
+    return 1.0 / distance;
+}
+
+fn spot_light(distance: f32) -> f32 {
+    return lights__spot__falloff(distance);
+}
+fn main() { }
+";
+        let processor = ShaderProcessor::default();
+        let mut shaders = HashMap::default();
+        let mut import_handles = HashMap::default();
+        for (id, (path, source)) in [("lights::point", POINT), ("lights::spot", SPOT)]
+            .into_iter()
+            .enumerate()
+        {
+            let handle = HandleUntyped::weak_from_u64(Shader::TYPE_UUID, id as u64).typed();
+            shaders.insert(handle.clone_weak(), Shader::from_wgsl(source));
+            import_handles.insert(ShaderImport::Custom(path.to_string()), handle);
+        }
+        let result = processor
+            .process(&Shader::from_wgsl(INPUT), &[], &shaders, &import_handles)
+            .unwrap();
+        assert_eq!(result.get_wgsl_source().unwrap(), EXPECTED);
+
+        // items requested by their own name can't have the same name
+        #[rustfmt::skip]
+        const CONFLICTING: &str = r"
+#import lights::point::falloff
+#import lights::spot::falloff
+fn main() { }
+";
+        let result = processor.process(
+            &Shader::from_wgsl(CONFLICTING),
+            &[],
+            &shaders,
+            &import_handles,
+        );
+        assert_eq!(
+            result,
+            Err(ProcessShaderError::ConflictingImportItem {
+                import: ShaderImport::Custom("lights::spot".to_string()),
+                other: ShaderImport::Custom("lights::point".to_string()),
+                item: "falloff".to_string(),
+            })
+        );
+
+        // items imported from the same module twice are only copied once
+        #[rustfmt::skip]
+        const SAME_MODULE: &str = r"
+#import lights::point::point_light
+#import lights::point::falloff
+fn main() { }
+";
+        let result = processor.process(
+            &Shader::from_wgsl(SAME_MODULE),
+            &[],
+            &shaders,
+            &import_handles,
+        );
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn process_import_items_sharing_lines() {
+        #[rustfmt::skip]
+        const MODULE: &str = r"
+#define_import_path shared
+const SCALE: f32 = 2.0; const OFFSET: f32 = 1.0;
+struct Params { scale: f32, }; fn scaled(x: f32) -> f32 { return x * SCALE; }
+@group(0) @binding(0) var<storage, read_write> data: array<f32>;
+@group(0) @binding(1)
+var<uniform> params: Params;
+// multiplies the data by SCALE
+fn apply(i: u32) { data[i] = scaled(data[i]) * params.scale; }
+";
+        #[rustfmt::skip]
+        const INPUT: &str = r"
+#import shared::apply
+fn main() { }
+";
+        #[rustfmt::skip]
+        const EXPECTED: &str = r"
+
+const shared__SCALE: f32 = 2.0;
+struct shared__Params { scale: f32, };
+fn shared__scaled(x: f32) -> f32 { return x * shared__SCALE; }
+@group(0) @binding(0) var<storage, read_write> shared__data: array<f32>;
+@group(0) @binding(1)
+var<uniform> shared__params: shared__Params;
+// multiplies the data by SCALE
+fn apply(i: u32) { shared__data[i] = shared__scaled(shared__data[i]) * shared__params.scale; }
+fn main() { }
+";
+        let processor = ShaderProcessor::default();
+        let mut shaders = HashMap::default();
+        let mut import_handles = HashMap::default();
+        let handle = Handle::<Shader>::default();
+        shaders.insert(handle.clone_weak(), Shader::from_wgsl(MODULE));
+        import_handles.insert(ShaderImport::Custom("shared".to_string()), handle);
+        let result = processor
+            .process(&Shader::from_wgsl(INPUT), &[], &shaders, &import_handles)
+            .unwrap();
+        assert_eq!(result.get_wgsl_source().unwrap(), EXPECTED);
+    }
+
+    #[test]
+    fn process_shader_def_values() {
+        #[rustfmt::skip]
+        const INPUT: &str = r"
+#if MAX_LIGHTS > 4
+let lights: u32 = #{MAX_LIGHTS}u;
+#else
+let lights: u32 = 4u;
+#endif
+#if SHADOWS == false
+fn shadows() { }
+#endif
+#if OFFSET != -1
+fn offset() { }
+#endif
+";
+        #[rustfmt::skip]
+        const EXPECTED: &str = r"
+let lights: u32 = 8u;
+fn shadows() { }
+";
+        let processor = ShaderProcessor::default();
+        let result = processor
+            .process(
+                &Shader::from_wgsl(INPUT),
+                &[
+                    ShaderDefVal::UInt("MAX_LIGHTS".to_string(), 8),
+                    ShaderDefVal::Bool("SHADOWS".to_string(), false),
+                    ShaderDefVal::Int("OFFSET".to_string(), -1),
+                ],
+                &HashMap::default(),
+                &HashMap::default(),
+            )
+            .unwrap();
+        assert_eq!(result.get_wgsl_source().unwrap(), EXPECTED);
+
+        let result = processor.process(
+            &Shader::from_wgsl(INPUT),
+            &[ShaderDefVal::UInt("MAX_LIGHTS".to_string(), 8)],
+            &HashMap::default(),
+            &HashMap::default(),
+        );
+        assert_eq!(
+            result,
+            Err(ProcessShaderError::UnknownShaderDef {
+                shader_def_name: "SHADOWS".to_string()
+            })
+        );
+
+        let result = processor.process(
+            &Shader::from_wgsl(INPUT),
+            &[ShaderDefVal::Bool("MAX_LIGHTS".to_string(), true)],
+            &HashMap::default(),
+            &HashMap::default(),
+        );
+        assert_eq!(
+            result,
+            Err(ProcessShaderError::InvalidShaderDefComparisonValue {
+                shader_def_name: "MAX_LIGHTS".to_string(),
+                expected: "bool".to_string(),
+                value: "4".to_string(),
+            })
+        );
+    }
+
+    #[test]
+    fn process_shader_def_false_not_defined() {
+        #[rustfmt::skip]
+        const INPUT: &str = r"
+#ifdef FOO
+fn foo() { }
+#endif
+";
+        let processor = ShaderProcessor::default();
+        let result = processor
+            .process(
+                &Shader::from_wgsl(INPUT),
+                &[ShaderDefVal::Bool("FOO".to_string(), false)],
+                &HashMap::default(),
+                &HashMap::default(),
+            )
+            .unwrap();
+        assert_eq!(result.get_wgsl_source().unwrap(), "\n");
+    }
+
+    #[test]
+    fn process_source_map() {
+        #[rustfmt::skip]
+        const FOO: &str = r"
+#define_import_path foo
+
+fn foo() { }
+";
+        #[rustfmt::skip]
+        const INPUT: &str = r"
+#import foo
+#ifdef BAR
+fn bar() { }
+#endif
+fn main() { }
+";
+        let processor = ShaderProcessor::default();
+        let mut shaders = HashMap::default();
+        let mut import_handles = HashMap::default();
+        let foo_handle = Handle::<Shader>::default();
+        shaders.insert(foo_handle.clone_weak(), Shader::from_wgsl(FOO));
+        import_handles.insert(
+            ShaderImport::Custom("foo".to_string()),
+            foo_handle.clone_weak(),
+        );
+        let (result, source_map) = processor
+            .process_with_source_map(
+                &Shader::from_wgsl(INPUT).with_import_path("main"),
+                &[],
+                &shaders,
+                &import_handles,
+            )
+            .unwrap();
+        assert_eq!(
+            result.get_wgsl_source().unwrap(),
+            "\n\n\nfn foo() { }\nfn main() { }\n"
+        );
+        assert_eq!(
+            source_map.location(4),
+            Some(ShaderSourceLocation {
+                file: "foo",
+                line: 4
+            })
+        );
+        assert_eq!(
+            source_map.location(5),
+            Some(ShaderSourceLocation {
+                file: "main",
+                line: 6
+            })
+        );
+        assert_eq!(source_map.location(6), None);
+    }
 }
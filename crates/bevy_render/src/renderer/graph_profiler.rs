@@ -0,0 +1,334 @@
+use crate::{
+    render_resource::Buffer,
+    renderer::{RenderDevice, RenderQueue},
+};
+use bevy_ecs::system::Resource;
+#[cfg(feature = "trace")]
+use bevy_utils::tracing::trace;
+use bevy_utils::{tracing::error, Duration, Instant};
+use parking_lot::Mutex;
+use std::sync::Arc;
+use wgpu::{
+    BufferAsyncError, BufferDescriptor, BufferUsages, CommandEncoder, Features, Maintain, MapMode,
+    QuerySet, QuerySetDescriptor, QueryType,
+};
+
+/// The maximum number of timestamps written during a frame, two for every node run.
+const MAX_TIMESTAMPS: u32 = 512;
+/// The number of frames whose timestamps can be read back at the same time.
+const TIMESTAMP_READBACKS: usize = 3;
+
+/// The time spent running a node of the render graph during a frame.
+#[derive(Clone, Debug)]
+pub struct RenderGraphNodeTiming {
+    /// The path of the node, made of the name of the sub graph that ran it (if any) and the name
+    /// of the node, e.g. `core_3d/main_pass`.
+    pub path: String,
+    /// The CPU time spent recording the commands of the node, summed over all of its runs.
+    pub cpu: Duration,
+    /// The GPU time spent executing the commands of the node, summed over all of its runs.
+    ///
+    /// This is `None` if the adapter does not support [`Features::TIMESTAMP_QUERY`] or the
+    /// timestamps of the node have not been read back yet.
+    pub gpu: Option<Duration>,
+}
+
+/// The latest timings of the nodes of the render graph, measured by the [`RenderGraphProfiler`].
+///
+/// This resource is shared by the main world and the render world, so the timings can be read
+/// from either of them.
+#[derive(Resource, Clone, Default)]
+pub struct RenderGraphTimings {
+    nodes: Arc<Mutex<Vec<RenderGraphNodeTiming>>>,
+}
+
+impl RenderGraphTimings {
+    /// Returns the timings of the nodes run during the latest frame, in the order they were run.
+    pub fn nodes(&self) -> Vec<RenderGraphNodeTiming> {
+        self.nodes.lock().clone()
+    }
+
+    /// Returns the timing of the node with the given `path`, if it was run during the latest frame.
+    pub fn node(&self, path: &str) -> Option<RenderGraphNodeTiming> {
+        self.nodes
+            .lock()
+            .iter()
+            .find(|node| node.path == path)
+            .cloned()
+    }
+
+    fn set_cpu_timings(&self, cpu_timings: Vec<(String, Duration)>) {
+        let mut nodes = self.nodes.lock();
+        let previous = std::mem::take(&mut *nodes);
+        // keep the gpu timings until the timestamps of this frame are read back
+        *nodes = cpu_timings
+            .into_iter()
+            .map(|(path, cpu)| {
+                let gpu = previous
+                    .iter()
+                    .find(|node| node.path == path)
+                    .and_then(|node| node.gpu);
+                RenderGraphNodeTiming { path, cpu, gpu }
+            })
+            .collect();
+    }
+
+    fn set_gpu_timings(&self, gpu_timings: Vec<(String, Duration)>) {
+        let mut nodes = self.nodes.lock();
+        for (path, gpu) in gpu_timings {
+            if let Some(node) = nodes.iter_mut().find(|node| node.path == path) {
+                node.gpu = Some(gpu);
+            }
+        }
+    }
+}
+
+/// Measures the time spent running every node of the [`RenderGraph`](crate::render_graph::RenderGraph).
+///
+/// The CPU time is measured around [`Node::run`](crate::render_graph::Node::run). If the adapter
+/// supports [`Features::TIMESTAMP_QUERY`], timestamps are also written to the command encoder
+/// before and after every node, and read back a few frames later to measure the GPU time.
+///
+/// The graph is only profiled if this resource exists in the render world, which is done by the
+/// [`RenderGraphDiagnosticsPlugin`](crate::diagnostic::RenderGraphDiagnosticsPlugin).
+#[derive(Resource)]
+pub struct RenderGraphProfiler {
+    timings: RenderGraphTimings,
+    gpu: Option<GpuTimestamps>,
+    spans: Vec<NodeSpan>,
+}
+
+struct NodeSpan {
+    path: String,
+    start: Instant,
+    cpu: Duration,
+    timestamp_index: Option<u32>,
+}
+
+struct GpuTimestamps {
+    query_set: QuerySet,
+    resolve_buffer: Buffer,
+    readbacks: Vec<TimestampReadback>,
+    /// The index of the readback used by the current frame, if one of them is available.
+    current: Option<usize>,
+    timestamp_count: u32,
+    /// The number of nanoseconds between two ticks of the timestamps.
+    period: f32,
+}
+
+struct TimestampReadback {
+    buffer: Buffer,
+    /// The path of the node of every pair of timestamps.
+    paths: Vec<String>,
+    in_flight: bool,
+    mapped: Arc<Mutex<Option<Result<(), BufferAsyncError>>>>,
+}
+
+impl RenderGraphProfiler {
+    /// Creates a profiler publishing the measured timings to `timings`.
+    pub fn new(
+        render_device: &RenderDevice,
+        render_queue: &RenderQueue,
+        timings: RenderGraphTimings,
+    ) -> Self {
+        let gpu = if render_device.features().contains(Features::TIMESTAMP_QUERY) {
+            let size = MAX_TIMESTAMPS as u64 * std::mem::size_of::<u64>() as u64;
+            Some(GpuTimestamps {
+                query_set: render_device
+                    .wgpu_device()
+                    .create_query_set(&QuerySetDescriptor {
+                        label: Some("render_graph_timestamps"),
+                        ty: QueryType::Timestamp,
+                        count: MAX_TIMESTAMPS,
+                    }),
+                resolve_buffer: render_device.create_buffer(&BufferDescriptor {
+                    label: Some("render_graph_timestamps_resolve_buffer"),
+                    size,
+                    usage: BufferUsages::QUERY_RESOLVE | BufferUsages::COPY_SRC,
+                    mapped_at_creation: false,
+                }),
+                readbacks: (0..TIMESTAMP_READBACKS)
+                    .map(|_| TimestampReadback {
+                        buffer: render_device.create_buffer(&BufferDescriptor {
+                            label: Some("render_graph_timestamps_readback_buffer"),
+                            size,
+                            usage: BufferUsages::MAP_READ | BufferUsages::COPY_DST,
+                            mapped_at_creation: false,
+                        }),
+                        paths: Vec::new(),
+                        in_flight: false,
+                        mapped: Default::default(),
+                    })
+                    .collect(),
+                current: None,
+                timestamp_count: 0,
+                period: render_queue.get_timestamp_period(),
+            })
+        } else {
+            None
+        };
+
+        Self {
+            timings,
+            gpu,
+            spans: Vec::new(),
+        }
+    }
+
+    /// Returns the timings measured by this profiler.
+    pub fn timings(&self) -> &RenderGraphTimings {
+        &self.timings
+    }
+
+    /// Returns `true` if the GPU time of the nodes is measured.
+    pub fn measures_gpu(&self) -> bool {
+        self.gpu.is_some()
+    }
+
+    /// Publishes the timestamps read back since the last frame and prepares a new frame.
+    pub(crate) fn begin_frame(&mut self, render_device: &RenderDevice) {
+        self.spans.clear();
+        let gpu = match &mut self.gpu {
+            Some(gpu) => gpu,
+            None => return,
+        };
+
+        render_device.poll(Maintain::Poll);
+        for readback in gpu
+            .readbacks
+            .iter_mut()
+            .filter(|readback| readback.in_flight)
+        {
+            let mapped = match readback.mapped.lock().take() {
+                Some(mapped) => mapped,
+                None => continue,
+            };
+            readback.in_flight = false;
+            if let Err(err) = mapped {
+                error!("Failed to read the timestamps of the render graph: {}", err);
+                continue;
+            }
+
+            let timestamps: Vec<u64> = {
+                let data = readback.buffer.slice(..).get_mapped_range();
+                data.chunks_exact(std::mem::size_of::<u64>())
+                    .take(readback.paths.len() * 2)
+                    .map(|bytes| u64::from_le_bytes(bytes.try_into().unwrap()))
+                    .collect()
+            };
+            readback.buffer.unmap();
+
+            let mut gpu_timings: Vec<(String, Duration)> = Vec::new();
+            for (path, span) in readback.paths.drain(..).zip(timestamps.chunks_exact(2)) {
+                let nanos = span[1].saturating_sub(span[0]) as f64 * gpu.period as f64;
+                let duration = Duration::from_nanos(nanos as u64);
+                #[cfg(feature = "trace")]
+                trace!(
+                    node = path.as_str(),
+                    gpu_ms = duration.as_secs_f64() * 1000.0
+                );
+                match gpu_timings.iter_mut().find(|(p, _)| *p == path) {
+                    Some((_, total)) => *total += duration,
+                    None => gpu_timings.push((path, duration)),
+                }
+            }
+            self.timings.set_gpu_timings(gpu_timings);
+        }
+
+        gpu.current = gpu
+            .readbacks
+            .iter()
+            .position(|readback| !readback.in_flight);
+        gpu.timestamp_count = 0;
+    }
+
+    /// Starts measuring a run of the node at `path`, returning the index of its span.
+    pub(crate) fn begin_node(&mut self, path: String, encoder: &mut CommandEncoder) -> usize {
+        let timestamp_index = match &mut self.gpu {
+            Some(gpu) if gpu.current.is_some() && gpu.timestamp_count + 2 <= MAX_TIMESTAMPS => {
+                let index = gpu.timestamp_count;
+                encoder.write_timestamp(&gpu.query_set, index);
+                gpu.timestamp_count += 2;
+                Some(index)
+            }
+            _ => None,
+        };
+        self.spans.push(NodeSpan {
+            path,
+            start: Instant::now(),
+            cpu: Duration::ZERO,
+            timestamp_index,
+        });
+        self.spans.len() - 1
+    }
+
+    /// Stops measuring the run of the node started with [`Self::begin_node`].
+    pub(crate) fn end_node(&mut self, span: usize, encoder: &mut CommandEncoder) {
+        let span = &mut self.spans[span];
+        span.cpu = span.start.elapsed();
+        if let (Some(gpu), Some(index)) = (&self.gpu, span.timestamp_index) {
+            encoder.write_timestamp(&gpu.query_set, index + 1);
+        }
+    }
+
+    /// Publishes the CPU timings of the frame and copies its timestamps to a readback buffer.
+    pub(crate) fn end_frame(&mut self, encoder: &mut CommandEncoder) {
+        let mut cpu_timings: Vec<(String, Duration)> = Vec::new();
+        for span in &self.spans {
+            match cpu_timings.iter_mut().find(|(path, _)| *path == span.path) {
+                Some((_, total)) => *total += span.cpu,
+                None => cpu_timings.push((span.path.clone(), span.cpu)),
+            }
+        }
+        self.timings.set_cpu_timings(cpu_timings);
+
+        let gpu = match &mut self.gpu {
+            Some(gpu) => gpu,
+            None => return,
+        };
+        let readback = match gpu.current {
+            Some(current) if gpu.timestamp_count > 0 => &mut gpu.readbacks[current],
+            _ => return,
+        };
+        // the spans whose timestamps were written are in the order of their timestamps
+        readback.paths = self
+            .spans
+            .iter()
+            .filter(|span| span.timestamp_index.is_some())
+            .map(|span| span.path.clone())
+            .collect();
+        encoder.resolve_query_set(
+            &gpu.query_set,
+            0..gpu.timestamp_count,
+            &gpu.resolve_buffer,
+            0,
+        );
+        encoder.copy_buffer_to_buffer(
+            &gpu.resolve_buffer,
+            0,
+            &readback.buffer,
+            0,
+            gpu.timestamp_count as u64 * std::mem::size_of::<u64>() as u64,
+        );
+        readback.in_flight = true;
+    }
+
+    /// Starts mapping the readback buffer of the frame, once its commands are submitted.
+    pub(crate) fn after_submit(&mut self, render_device: &RenderDevice) {
+        let gpu = match &mut self.gpu {
+            Some(gpu) => gpu,
+            None => return,
+        };
+        let readback = match gpu.current.take() {
+            Some(current) => &gpu.readbacks[current],
+            None => return,
+        };
+        if !readback.in_flight {
+            return;
+        }
+        let mapped = readback.mapped.clone();
+        render_device.map_buffer(&readback.buffer.slice(..), MapMode::Read, move |result| {
+            *mapped.lock() = Some(result);
+        });
+    }
+}
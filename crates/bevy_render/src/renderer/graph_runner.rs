@@ -13,7 +13,7 @@ use crate::{
         Edge, NodeId, NodeRunError, NodeState, RenderGraph, RenderGraphContext, SlotLabel,
         SlotType, SlotValue,
     },
-    renderer::{RenderContext, RenderDevice},
+    renderer::{RenderContext, RenderDevice, RenderGraphProfiler},
 };
 
 pub(crate) struct RenderGraphRunner;
@@ -57,7 +57,11 @@ impl RenderGraphRunner {
         render_device: RenderDevice,
         queue: &wgpu::Queue,
         world: &World,
+        mut profiler: Option<&mut RenderGraphProfiler>,
     ) -> Result<(), RenderGraphRunnerError> {
+        if let Some(profiler) = profiler.as_deref_mut() {
+            profiler.begin_frame(&render_device);
+        }
         let command_encoder =
             render_device.create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
         let mut render_context = RenderContext {
@@ -65,12 +69,25 @@ impl RenderGraphRunner {
             command_encoder,
         };
 
-        Self::run_graph(graph, None, &mut render_context, world, &[])?;
+        Self::run_graph(
+            graph,
+            None,
+            &mut render_context,
+            world,
+            &[],
+            profiler.as_deref_mut(),
+        )?;
+        if let Some(profiler) = profiler.as_deref_mut() {
+            profiler.end_frame(&mut render_context.command_encoder);
+        }
         {
             #[cfg(feature = "trace")]
             let _span = info_span!("submit_graph_commands").entered();
             queue.submit(vec![render_context.command_encoder.finish()]);
         }
+        if let Some(profiler) = profiler {
+            profiler.after_submit(&render_context.render_device);
+        }
         Ok(())
     }
 
@@ -80,6 +97,7 @@ impl RenderGraphRunner {
         render_context: &mut RenderContext,
         world: &World,
         inputs: &[SlotValue],
+        mut profiler: Option<&mut RenderGraphProfiler>,
     ) -> Result<(), RenderGraphRunnerError> {
         let mut node_outputs: HashMap<NodeId, SmallVec<[SlotValue; 4]>> = HashMap::default();
         #[cfg(feature = "trace")]
@@ -182,10 +200,23 @@ impl RenderGraphRunner {
             {
                 let mut context = RenderGraphContext::new(graph, node_state, &inputs, &mut outputs);
                 {
+                    let node_name = node_state.name.as_deref().unwrap_or(node_state.type_name);
                     #[cfg(feature = "trace")]
-                    let _span = info_span!("node", name = node_state.type_name).entered();
+                    let _span =
+                        info_span!("node", name = node_name, type_name = node_state.type_name)
+                            .entered();
 
+                    let profiler_span = profiler.as_deref_mut().map(|profiler| {
+                        let path = match &graph_name {
+                            Some(graph_name) => format!("{}/{}", graph_name, node_name),
+                            None => node_name.to_string(),
+                        };
+                        profiler.begin_node(path, &mut render_context.command_encoder)
+                    });
                     node_state.node.run(&mut context, render_context, world)?;
+                    if let (Some(profiler), Some(span)) = (profiler.as_deref_mut(), profiler_span) {
+                        profiler.end_node(span, &mut render_context.command_encoder);
+                    }
                 }
 
                 for run_sub_graph in context.finish() {
@@ -198,6 +229,7 @@ impl RenderGraphRunner {
                         render_context,
                         world,
                         &run_sub_graph.inputs,
+                        profiler.as_deref_mut(),
                     )?;
                 }
             }
@@ -1,15 +1,17 @@
+mod graph_profiler;
 mod graph_runner;
 mod render_device;
 
 use bevy_derive::{Deref, DerefMut};
 use bevy_utils::tracing::{error, info, info_span};
+pub use graph_profiler::*;
 pub use graph_runner::*;
 pub use render_device::*;
 
 use crate::{
     render_graph::RenderGraph,
     settings::{WgpuSettings, WgpuSettingsPriority},
-    view::{ExtractedWindows, ViewTarget},
+    view::{capture_screenshots, ExtractedWindows, ViewTarget},
 };
 use bevy_ecs::prelude::*;
 use bevy_time::TimeSender;
@@ -22,16 +24,23 @@ pub fn render_system(world: &mut World) {
     world.resource_scope(|world, mut graph: Mut<RenderGraph>| {
         graph.update(world);
     });
+    // the profiler is taken out of the world, which is only borrowed immutably by the nodes
+    let mut profiler = world.remove_resource::<RenderGraphProfiler>();
     let graph = world.resource::<RenderGraph>();
     let render_device = world.resource::<RenderDevice>();
     let render_queue = world.resource::<RenderQueue>();
 
-    if let Err(e) = RenderGraphRunner::run(
+    let result = RenderGraphRunner::run(
         graph,
         render_device.clone(), // TODO: is this clone really necessary?
         &render_queue.0,
         world,
-    ) {
+        profiler.as_mut(),
+    );
+    if let Some(profiler) = profiler {
+        world.insert_resource(profiler);
+    }
+    if let Err(e) = result {
         error!("Error running render graph:");
         {
             let mut src: &dyn std::error::Error = &e;
@@ -47,6 +56,8 @@ pub fn render_system(world: &mut World) {
         panic!("Error running render graph: {}", e);
     }
 
+    capture_screenshots(world);
+
     {
         let _span = info_span!("present_frames").entered();
 
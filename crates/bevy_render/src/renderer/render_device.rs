@@ -82,7 +82,7 @@ impl RenderDevice {
         &self,
         desc: &wgpu::BindGroupLayoutDescriptor,
     ) -> BindGroupLayout {
-        BindGroupLayout::from(self.device.create_bind_group_layout(desc))
+        BindGroupLayout::from(self.device.create_bind_group_layout(desc)).with_entries(desc.entries)
     }
 
     /// Creates a [`PipelineLayout`](wgpu::PipelineLayout).
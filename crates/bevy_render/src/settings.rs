@@ -1,4 +1,4 @@
-use std::borrow::Cow;
+use std::{borrow::Cow, path::PathBuf};
 
 use bevy_ecs::system::Resource;
 pub use wgpu::{Backends, Features as WgpuFeatures, Limits as WgpuLimits, PowerPreference};
@@ -28,6 +28,13 @@ pub struct WgpuSettings {
     pub device_label: Option<Cow<'static, str>>,
     pub backends: Option<Backends>,
     pub power_preference: PowerPreference,
+    /// Only use a fallback adapter, which is usually a software rasterizer like lavapipe or WARP
+    /// (default: `true` if the `WGPU_FORCE_FALLBACK_ADAPTER` environment variable is `1` or
+    /// `true`).
+    ///
+    /// This allows rendering on machines without a GPU, for example to render images in CI
+    /// with the `HeadlessPlugins` of `bevy`.
+    pub force_fallback_adapter: bool,
     pub priority: WgpuSettingsPriority,
     /// The features to ensure are enabled regardless of what the adapter/backend supports.
     /// Setting these explicitly may cause renderer initialization to fail.
@@ -70,6 +77,7 @@ impl Default for WgpuSettings {
             device_label: Default::default(),
             backends,
             power_preference: PowerPreference::HighPerformance,
+            force_fallback_adapter: force_fallback_adapter_from_env(),
             priority,
             features: wgpu::Features::TEXTURE_ADAPTER_SPECIFIC_FORMAT_FEATURES,
             disabled_features: None,
@@ -79,6 +87,18 @@ impl Default for WgpuSettings {
     }
 }
 
+/// Get whether to force a fallback adapter from the environment variable
+/// `WGPU_FORCE_FALLBACK_ADAPTER`
+pub fn force_fallback_adapter_from_env() -> bool {
+    matches!(
+        std::env::var("WGPU_FORCE_FALLBACK_ADAPTER")
+            .as_deref()
+            .map(str::to_lowercase)
+            .as_deref(),
+        Ok("1") | Ok("true")
+    )
+}
+
 /// Get a features/limits priority from the environment variable `WGPU_SETTINGS_PRIO`
 pub fn settings_priority_from_env() -> Option<WgpuSettingsPriority> {
     Some(
@@ -94,3 +114,15 @@ pub fn settings_priority_from_env() -> Option<WgpuSettingsPriority> {
         },
     )
 }
+
+/// Configures the [`PipelineCache`](crate::render_resource::PipelineCache). Insert it before the
+/// `RenderPlugin` to use it.
+#[derive(Resource, Clone, Default)]
+pub struct PipelineCacheSettings {
+    /// The file to save the shader specializations and render pipelines used by the app to, so
+    /// that the next runs create them as soon as their shaders are loaded instead of when a
+    /// pipeline first uses them (default: `None`, nothing is saved).
+    ///
+    /// Not supported on wasm.
+    pub persistence_path: Option<PathBuf>,
+}
@@ -1,12 +1,13 @@
 use anyhow::Result;
 use bevy_asset::{AssetLoader, LoadContext, LoadedAsset};
 use bevy_ecs::prelude::{FromWorld, World};
+use bevy_reflect::{std_traits::ReflectDefault, FromReflect, Reflect};
 use bevy_utils::BoxedFuture;
 use thiserror::Error;
 
 use crate::{
     renderer::RenderDevice,
-    texture::{Image, ImageType, TextureError},
+    texture::{Image, ImageSampler, ImageType, TextureError},
 };
 
 use super::CompressedImageFormats;
@@ -17,6 +18,53 @@ pub struct ImageTextureLoader {
     supported_compressed_formats: CompressedImageFormats,
 }
 
+/// Settings for loading an [`Image`] with the [`ImageTextureLoader`].
+///
+/// These settings are read from the `.meta` file of the image, see
+/// [`AssetLoaderMeta`](bevy_asset::AssetLoaderMeta).
+#[derive(Reflect, FromReflect, Debug, Clone)]
+#[reflect(Default)]
+pub struct ImageLoaderSettings {
+    /// Whether the image is in the sRGB color space.
+    ///
+    /// Color textures usually are, while data textures such as normal maps aren't.
+    pub is_srgb: bool,
+    /// The sampler to use for the image.
+    pub sampler: ImageLoaderSampler,
+}
+
+impl Default for ImageLoaderSettings {
+    fn default() -> Self {
+        Self {
+            is_srgb: true,
+            sampler: ImageLoaderSampler::Default,
+        }
+    }
+}
+
+/// The sampler of an image loaded with [`ImageLoaderSettings`].
+#[derive(Reflect, FromReflect, Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[reflect(Default)]
+pub enum ImageLoaderSampler {
+    /// Use the default sampler of the [`ImageSettings`](super::ImageSettings) resource.
+    #[default]
+    Default,
+    /// Use linear min and mag filters.
+    Linear,
+    /// Use nearest min and mag filters.
+    Nearest,
+}
+
+impl From<ImageLoaderSampler> for ImageSampler {
+    fn from(sampler: ImageLoaderSampler) -> Self {
+        match sampler {
+            ImageLoaderSampler::Default => ImageSampler::Default,
+            ImageLoaderSampler::Linear => ImageSampler::linear(),
+            ImageLoaderSampler::Nearest => ImageSampler::nearest(),
+        }
+    }
+}
+
 const FILE_EXTENSIONS: &[&str] = &[
     #[cfg(feature = "basis-universal")]
     "basis",
@@ -45,17 +93,21 @@ impl AssetLoader for ImageTextureLoader {
         Box::pin(async move {
             // use the file extension for the image type
             let ext = load_context.path().extension().unwrap().to_str().unwrap();
+            let settings = load_context
+                .settings::<ImageLoaderSettings>()
+                .unwrap_or_default();
 
-            let dyn_img = Image::from_buffer(
+            let mut dyn_img = Image::from_buffer(
                 bytes,
                 ImageType::Extension(ext),
                 self.supported_compressed_formats,
-                true,
+                settings.is_srgb,
             )
             .map_err(|err| FileTextureError {
                 error: err,
                 path: format!("{}", load_context.path().display()),
             })?;
+            dyn_img.sampler_descriptor = settings.sampler.into();
 
             load_context.set_default_asset(LoadedAsset::new(dyn_img));
             Ok(())
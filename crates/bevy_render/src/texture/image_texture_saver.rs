@@ -0,0 +1,69 @@
+use anyhow::{anyhow, Result};
+use bevy_asset::AssetSaver;
+use image::{DynamicImage, ImageFormat, ImageOutputFormat};
+use std::{io::Cursor, path::Path};
+use wgpu::TextureFormat;
+
+use crate::texture::Image;
+
+/// Saver for images that can be written by the `image` crate, the counterpart of the
+/// [`ImageTextureLoader`](super::ImageTextureLoader).
+///
+/// Only images whose format is supported by [`Image::try_into_dynamic`] can be saved.
+#[derive(Default)]
+pub struct ImageTextureSaver;
+
+const FILE_EXTENSIONS: &[&str] = &[
+    #[cfg(feature = "bmp")]
+    "bmp",
+    #[cfg(feature = "png")]
+    "png",
+    #[cfg(feature = "tga")]
+    "tga",
+    #[cfg(feature = "jpeg")]
+    "jpg",
+    #[cfg(feature = "jpeg")]
+    "jpeg",
+];
+
+impl AssetSaver for ImageTextureSaver {
+    type Asset = Image;
+
+    fn save(&self, image: &Image, path: &Path) -> Result<Vec<u8>> {
+        let format = path
+            .extension()
+            .and_then(ImageFormat::from_extension)
+            .ok_or_else(|| anyhow!("Unsupported image extension for {}", path.display()))?;
+
+        let mut image = image.clone();
+        // the data of linear images is saved as is
+        if image.texture_descriptor.format == TextureFormat::Rgba8Unorm {
+            image.texture_descriptor.format = TextureFormat::Rgba8UnormSrgb;
+        }
+        let mut dyn_img = image.try_into_dynamic()?;
+        if format == ImageFormat::Jpeg {
+            // jpeg doesn't support transparency
+            dyn_img = DynamicImage::ImageRgb8(dyn_img.to_rgb8());
+        }
+
+        let mut bytes = Cursor::new(Vec::new());
+        dyn_img.write_to(&mut bytes, ImageOutputFormat::from(format))?;
+        Ok(bytes.into_inner())
+    }
+
+    fn extensions(&self) -> &[&str] {
+        FILE_EXTENSIONS
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_supported_file_extensions() {
+        for ext in FILE_EXTENSIONS {
+            assert!(ImageFormat::from_extension(ext).is_some());
+        }
+    }
+}
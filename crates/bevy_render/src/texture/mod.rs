@@ -8,6 +8,7 @@ mod hdr_texture_loader;
 #[allow(clippy::module_inception)]
 mod image;
 mod image_texture_loader;
+mod image_texture_saver;
 #[cfg(feature = "ktx2")]
 mod ktx2;
 mod texture_cache;
@@ -24,6 +25,7 @@ pub use hdr_texture_loader::*;
 
 pub use fallback_image::*;
 pub use image_texture_loader::*;
+pub use image_texture_saver::*;
 pub use texture_cache::*;
 
 use crate::{
@@ -53,6 +55,14 @@ impl Plugin for ImagePlugin {
             app.init_asset_loader::<ImageTextureLoader>();
         }
 
+        #[cfg(any(feature = "png", feature = "tga", feature = "jpeg", feature = "bmp"))]
+        {
+            app.init_asset_saver::<ImageTextureSaver>();
+        }
+
+        app.register_type::<ImageLoaderSettings>()
+            .register_type::<ImageLoaderSampler>();
+
         #[cfg(feature = "hdr")]
         {
             app.init_asset_loader::<HdrTextureLoader>();
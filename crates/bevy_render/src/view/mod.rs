@@ -1,15 +1,17 @@
+pub mod screenshot;
 pub mod visibility;
 pub mod window;
 
+pub use screenshot::*;
 pub use visibility::*;
 use wgpu::{
     Color, Extent3d, Operations, RenderPassColorAttachment, TextureDescriptor, TextureDimension,
-    TextureUsages,
+    TextureFormat, TextureUsages,
 };
 pub use window::*;
 
 use crate::{
-    camera::ExtractedCamera,
+    camera::{CameraOutputMode, ExtractedCamera},
     extract_resource::{ExtractResource, ExtractResourcePlugin},
     prelude::Image,
     rangefinder::ViewRangefinder3d,
@@ -25,6 +27,10 @@ use bevy_math::{Mat4, UVec4, Vec3, Vec4};
 use bevy_reflect::Reflect;
 use bevy_transform::components::GlobalTransform;
 use bevy_utils::HashMap;
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc,
+};
 
 pub struct ViewPlugin;
 
@@ -81,6 +87,8 @@ impl Default for Msaa {
 pub struct ExtractedView {
     pub projection: Mat4,
     pub transform: GlobalTransform,
+    /// Whether the view renders to a "high dynamic range" [`ViewTarget`].
+    pub hdr: bool,
     // uvec4(origin.x, origin.y, width, height)
     pub viewport: UVec4,
 }
@@ -115,24 +123,142 @@ pub struct ViewUniformOffset {
     pub offset: u32,
 }
 
+/// The textures a view renders to.
+///
+/// Views render to intermediate "main" textures, in [`ViewTarget::TEXTURE_FORMAT_HDR`] for
+/// [`Camera::hdr`](crate::camera::Camera::hdr) cameras, which are written to the texture of the
+/// [`RenderTarget`](crate::camera::RenderTarget) of the view, the "out" texture, at the end of its
+/// render graph. The main textures are shared by the cameras rendering to the same target.
 #[derive(Component)]
 pub struct ViewTarget {
-    pub view: TextureView,
-    pub sampled_target: Option<TextureView>,
+    main_textures: MainTargetTextures,
+    main_texture_format: TextureFormat,
+    out_texture: TextureView,
+    out_texture_format: TextureFormat,
+}
+
+#[derive(Clone)]
+struct MainTargetTextures {
+    a: TextureView,
+    b: TextureView,
+    /// The multisampled texture resolved into the current main texture, when MSAA is enabled.
+    sampled: Option<TextureView>,
+    /// 0 represents `a`, 1 represents `b`.
+    main_texture: Arc<AtomicUsize>,
+}
+
+/// The source and destination textures of a post-processing pass, returned by
+/// [`ViewTarget::post_process_write`].
+pub struct PostProcessWrite<'a> {
+    /// The current main texture, to read from.
+    pub source: &'a TextureView,
+    /// The texture to write to, which is the main texture after the call.
+    pub destination: &'a TextureView,
 }
 
 impl ViewTarget {
+    /// The format of the main textures of "high dynamic range" views.
+    pub const TEXTURE_FORMAT_HDR: TextureFormat = TextureFormat::Rgba16Float;
+
+    /// Retrieve this target's color attachment. This will use the multisampled texture and
+    /// resolve it to the [main texture](ViewTarget::main_texture) if MSAA is enabled, and use the
+    /// main texture directly otherwise.
     pub fn get_color_attachment(&self, ops: Operations<Color>) -> RenderPassColorAttachment {
-        RenderPassColorAttachment {
-            view: self.sampled_target.as_ref().unwrap_or(&self.view),
-            resolve_target: if self.sampled_target.is_some() {
-                Some(&self.view)
-            } else {
-                None
+        match &self.main_textures.sampled {
+            Some(sampled_texture) => RenderPassColorAttachment {
+                view: sampled_texture,
+                resolve_target: Some(self.main_texture()),
+                ops,
             },
+            None => self.get_unsampled_color_attachment(ops),
+        }
+    }
+
+    /// Retrieve an "unsampled" color attachment, which renders to the
+    /// [main texture](ViewTarget::main_texture) directly, even if MSAA is enabled.
+    pub fn get_unsampled_color_attachment(
+        &self,
+        ops: Operations<Color>,
+    ) -> RenderPassColorAttachment {
+        RenderPassColorAttachment {
+            view: self.main_texture(),
+            resolve_target: None,
             ops,
         }
     }
+
+    /// The current main texture of the view.
+    pub fn main_texture(&self) -> &TextureView {
+        if self.main_textures.main_texture.load(Ordering::SeqCst) == 0 {
+            &self.main_textures.a
+        } else {
+            &self.main_textures.b
+        }
+    }
+
+    /// The multisampled texture of the view, if MSAA is enabled.
+    pub fn sampled_main_texture(&self) -> Option<&TextureView> {
+        self.main_textures.sampled.as_ref()
+    }
+
+    /// The format of the main textures.
+    #[inline]
+    pub fn main_texture_format(&self) -> TextureFormat {
+        self.main_texture_format
+    }
+
+    /// Returns `true` if the main textures use [`ViewTarget::TEXTURE_FORMAT_HDR`].
+    #[inline]
+    pub fn is_hdr(&self) -> bool {
+        self.main_texture_format == ViewTarget::TEXTURE_FORMAT_HDR
+    }
+
+    /// The texture of the render target of the view.
+    #[inline]
+    pub fn out_texture(&self) -> &TextureView {
+        &self.out_texture
+    }
+
+    /// The format of the texture of the render target of the view.
+    #[inline]
+    pub fn out_texture_format(&self) -> TextureFormat {
+        self.out_texture_format
+    }
+
+    /// Swaps the main textures of the view for a post-processing pass, which reads the
+    /// [`source`](PostProcessWrite::source) texture and writes the whole
+    /// [`destination`](PostProcessWrite::destination) texture. The destination becomes the main
+    /// texture.
+    ///
+    /// This must only be called once per post-processing pass, as each call swaps the textures.
+    ///
+    /// A full-screen post-processing pass is a render graph [`Node`](crate::render_graph::Node)
+    /// taking the view entity as input, which queries the [`ViewTarget`] of the view in `run`,
+    /// binds the source texture and draws a fullscreen triangle into the destination texture.
+    /// The node is added to the graph of the camera after its main pass: in the `core_3d` and
+    /// `core_2d` graphs of `bevy_core_pipeline`, nodes ordered between the `bloom` and
+    /// `tonemapping` nodes read the "high dynamic range" colors of [`Camera::hdr`] views, and
+    /// nodes between the `tonemapping` and `upscaling` nodes read the tonemapped colors.
+    /// The `post_process_node` example adds a vignette this way.
+    ///
+    /// [`Camera::hdr`]: crate::camera::Camera::hdr
+    pub fn post_process_write(&self) -> PostProcessWrite {
+        let old_is_a_main_texture = self
+            .main_textures
+            .main_texture
+            .fetch_xor(1, Ordering::SeqCst);
+        if old_is_a_main_texture == 0 {
+            PostProcessWrite {
+                source: &self.main_textures.a,
+                destination: &self.main_textures.b,
+            }
+        } else {
+            PostProcessWrite {
+                source: &self.main_textures.b,
+                destination: &self.main_textures.a,
+            }
+        }
+    }
 }
 
 #[derive(Component)]
@@ -184,40 +310,85 @@ fn prepare_view_targets(
     render_device: Res<RenderDevice>,
     texture_format: Res<RenderTextureFormat>,
     mut texture_cache: ResMut<TextureCache>,
-    cameras: Query<(Entity, &ExtractedCamera)>,
+    cameras: Query<(Entity, &ExtractedCamera, &ExtractedView)>,
 ) {
-    let mut sampled_textures = HashMap::default();
-    for (entity, camera) in &cameras {
+    let mut textures = HashMap::default();
+    for (entity, camera, view) in &cameras {
         if let Some(target_size) = camera.physical_target_size {
-            if let Some(texture_view) = camera.target.get_texture_view(&windows, &images) {
-                let sampled_target = if msaa.samples > 1 {
-                    let sampled_texture = sampled_textures
-                        .entry(camera.target.clone())
-                        .or_insert_with(|| {
-                            texture_cache.get(
-                                &render_device,
-                                TextureDescriptor {
-                                    label: Some("sampled_color_attachment_texture"),
-                                    size: Extent3d {
-                                        width: target_size.x,
-                                        height: target_size.y,
-                                        depth_or_array_layers: 1,
-                                    },
-                                    mip_level_count: 1,
-                                    sample_count: msaa.samples,
-                                    dimension: TextureDimension::D2,
-                                    format: **texture_format,
-                                    usage: TextureUsages::RENDER_ATTACHMENT,
-                                },
-                            )
-                        });
-                    Some(sampled_texture.default_view.clone())
+            if let (Some(out_texture_view), Some(out_texture_format)) = (
+                camera.target.get_texture_view(&windows, &images),
+                camera.target.get_texture_format(**texture_format, &images),
+            ) {
+                let size = Extent3d {
+                    width: target_size.x,
+                    height: target_size.y,
+                    depth_or_array_layers: 1,
+                };
+                let main_texture_format = if view.hdr {
+                    ViewTarget::TEXTURE_FORMAT_HDR
                 } else {
-                    None
+                    **texture_format
                 };
+
+                // compositing cameras draw over their own textures instead of the shared ones
+                let composite_camera = match camera.output_mode {
+                    CameraOutputMode::Write => None,
+                    CameraOutputMode::Composite(_) => Some(entity),
+                };
+                let main_textures = textures
+                    .entry((camera.target.clone(), view.hdr, composite_camera))
+                    .or_insert_with(|| {
+                        let descriptor = TextureDescriptor {
+                            label: None,
+                            size,
+                            mip_level_count: 1,
+                            sample_count: 1,
+                            dimension: TextureDimension::D2,
+                            format: main_texture_format,
+                            usage: TextureUsages::RENDER_ATTACHMENT
+                                | TextureUsages::TEXTURE_BINDING,
+                        };
+                        MainTargetTextures {
+                            a: texture_cache
+                                .get(
+                                    &render_device,
+                                    TextureDescriptor {
+                                        label: Some("main_texture_a"),
+                                        ..descriptor
+                                    },
+                                )
+                                .default_view,
+                            b: texture_cache
+                                .get(
+                                    &render_device,
+                                    TextureDescriptor {
+                                        label: Some("main_texture_b"),
+                                        ..descriptor
+                                    },
+                                )
+                                .default_view,
+                            sampled: (msaa.samples > 1).then(|| {
+                                texture_cache
+                                    .get(
+                                        &render_device,
+                                        TextureDescriptor {
+                                            label: Some("main_texture_sampled"),
+                                            sample_count: msaa.samples,
+                                            usage: TextureUsages::RENDER_ATTACHMENT,
+                                            ..descriptor
+                                        },
+                                    )
+                                    .default_view
+                            }),
+                            main_texture: Arc::new(AtomicUsize::new(0)),
+                        }
+                    });
+
                 commands.entity(entity).insert(ViewTarget {
-                    view: texture_view.clone(),
-                    sampled_target,
+                    main_textures: main_textures.clone(),
+                    main_texture_format,
+                    out_texture: out_texture_view.clone(),
+                    out_texture_format,
                 });
             }
         }
@@ -0,0 +1,422 @@
+use crate::{
+    camera::RenderTarget,
+    render_asset::RenderAssets,
+    render_resource::Buffer,
+    renderer::{RenderDevice, RenderQueue},
+    texture::{Image, ImageTextureSaver},
+    view::ExtractedWindows,
+    RenderApp,
+};
+use bevy_app::{App, CoreStage, Plugin};
+use bevy_asset::AssetSaver;
+use bevy_ecs::prelude::*;
+use bevy_tasks::IoTaskPool;
+use bevy_utils::{
+    tracing::{error, info},
+    HashMap,
+};
+use parking_lot::Mutex;
+use std::{
+    num::NonZeroU32,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+use thiserror::Error;
+use wgpu::{
+    BufferDescriptor, BufferUsages, CommandEncoderDescriptor, Extent3d, ImageCopyBuffer,
+    ImageDataLayout, MapMode, TextureDimension, TextureFormat, TextureUsages,
+};
+
+/// Adds the [`ScreenshotManager`] and the [`ScreenshotCaptured`] and [`ScreenshotSaved`] events.
+pub struct ScreenshotPlugin;
+
+impl Plugin for ScreenshotPlugin {
+    fn build(&self, app: &mut App) {
+        let screenshot_manager = ScreenshotManager::default();
+        app.add_event::<ScreenshotCaptured>()
+            .add_event::<ScreenshotSaved>()
+            .insert_resource(screenshot_manager.clone())
+            .add_system_to_stage(CoreStage::PreUpdate, send_captured_screenshots);
+
+        // the render world copies the requested targets, in the `render_system`
+        if let Ok(render_app) = app.get_sub_app_mut(RenderApp) {
+            render_app.insert_resource(screenshot_manager);
+        }
+    }
+}
+
+/// Captures the contents of render targets into [`Image`]s.
+///
+/// A requested target is copied at the end of the next frame that renders it, and the captured
+/// image is sent in a [`ScreenshotCaptured`] event at the start of a frame once the copy has been
+/// read back, without stalling the frame of the capture.
+///
+/// The textures of [`RenderTarget::Image`] targets must have the
+/// [`TextureUsages::COPY_SRC`](wgpu::TextureUsages::COPY_SRC) usage. Only targets with an 8 bit
+/// RGBA or BGRA format can be captured, which excludes "high dynamic range" images. Windows can
+/// only be captured if their surface supports copies, which excludes the `webgl` feature.
+#[derive(Resource, Clone, Default)]
+pub struct ScreenshotManager {
+    state: Arc<Mutex<ScreenshotState>>,
+}
+
+#[derive(Default)]
+struct ScreenshotState {
+    /// The targets to capture, with the paths to save them to.
+    requests: HashMap<RenderTarget, Option<PathBuf>>,
+    /// The number of copied targets waiting to be read back.
+    pending_readbacks: usize,
+    /// The captured screenshots, waiting to be sent to the app world.
+    captured: Vec<CapturedScreenshot>,
+    /// The screenshots written to disk, waiting to be sent to the app world.
+    saved: Vec<ScreenshotSaved>,
+}
+
+struct CapturedScreenshot {
+    target: RenderTarget,
+    save_path: Option<PathBuf>,
+    image: Image,
+}
+
+/// An error returned when requesting a screenshot of a target that is already waiting for one.
+#[derive(Error, Debug)]
+#[error("a screenshot of {0:?} is already requested")]
+pub struct ScreenshotAlreadyRequestedError(pub RenderTarget);
+
+impl ScreenshotManager {
+    /// Requests a screenshot of the target, which is sent in a [`ScreenshotCaptured`] event once
+    /// captured.
+    pub fn take_screenshot(
+        &mut self,
+        target: RenderTarget,
+    ) -> Result<(), ScreenshotAlreadyRequestedError> {
+        self.request(target, None)
+    }
+
+    /// Requests a screenshot of the target, which is saved to the file at `path` once captured,
+    /// in the format of its extension, as the [`ImageTextureSaver`] writes it. The screenshot is also sent in a [`ScreenshotCaptured`]
+    /// event, and a [`ScreenshotSaved`] event is sent once the file is written.
+    pub fn save_screenshot_to_disk(
+        &mut self,
+        target: RenderTarget,
+        path: impl AsRef<Path>,
+    ) -> Result<(), ScreenshotAlreadyRequestedError> {
+        self.request(target, Some(path.as_ref().to_owned()))
+    }
+
+    /// Returns `true` if a screenshot of the target is requested and not captured yet.
+    pub fn is_requested(&self, target: &RenderTarget) -> bool {
+        self.state.lock().requests.contains_key(target)
+    }
+
+    fn request(
+        &mut self,
+        target: RenderTarget,
+        save_path: Option<PathBuf>,
+    ) -> Result<(), ScreenshotAlreadyRequestedError> {
+        let mut state = self.state.lock();
+        if state.requests.contains_key(&target) {
+            return Err(ScreenshotAlreadyRequestedError(target));
+        }
+        state.requests.insert(target, save_path);
+        Ok(())
+    }
+}
+
+/// Sent when the screenshot of a target requested with the [`ScreenshotManager`] is captured.
+pub struct ScreenshotCaptured {
+    pub target: RenderTarget,
+    pub image: Image,
+}
+
+/// Sent when the screenshot of a target requested with
+/// [`ScreenshotManager::save_screenshot_to_disk`] is written to disk, or failed to be.
+pub struct ScreenshotSaved {
+    pub target: RenderTarget,
+    pub path: PathBuf,
+    pub result: anyhow::Result<()>,
+}
+
+fn send_captured_screenshots(
+    screenshot_manager: Res<ScreenshotManager>,
+    mut captured_events: EventWriter<ScreenshotCaptured>,
+    mut saved_events: EventWriter<ScreenshotSaved>,
+) {
+    let (captured, saved) = {
+        let mut state = screenshot_manager.state.lock();
+        (
+            std::mem::take(&mut state.captured),
+            std::mem::take(&mut state.saved),
+        )
+    };
+    saved_events.send_batch(saved);
+    for screenshot in captured {
+        if let Some(path) = screenshot.save_path {
+            let state = screenshot_manager.state.clone();
+            let target = screenshot.target.clone();
+            let image = screenshot.image.clone();
+            IoTaskPool::get()
+                .spawn(async move {
+                    let result = save_image(image, &path);
+                    match &result {
+                        Ok(()) => info!("Screenshot saved to {}", path.display()),
+                        Err(err) => {
+                            error!("Failed to save screenshot to {}: {}", path.display(), err);
+                        }
+                    }
+                    state.lock().saved.push(ScreenshotSaved {
+                        target,
+                        path,
+                        result,
+                    });
+                })
+                .detach();
+        }
+        captured_events.send(ScreenshotCaptured {
+            target: screenshot.target,
+            image: screenshot.image,
+        });
+    }
+}
+
+fn save_image(image: Image, path: &Path) -> anyhow::Result<()> {
+    let bytes = ImageTextureSaver.save(&image, path)?;
+    std::fs::write(path, bytes)?;
+    Ok(())
+}
+
+/// A requested target copied to a buffer, waiting to be read.
+struct ScreenshotReadback {
+    target: RenderTarget,
+    save_path: Option<PathBuf>,
+    buffer: Buffer,
+    size: Extent3d,
+    padded_bytes_per_row: usize,
+    format: TextureFormat,
+}
+
+/// Copies the requested targets rendered this frame to buffers, and reads them back into
+/// [`Image`]s for the app world.
+///
+/// This runs after the render graph and before the swap chain textures of the windows are
+/// presented.
+pub(crate) fn capture_screenshots(world: &World) {
+    let screenshot_manager = match world.get_resource::<ScreenshotManager>() {
+        Some(screenshot_manager) => screenshot_manager,
+        None => return,
+    };
+    let render_device = world.resource::<RenderDevice>();
+    // run the callbacks of the finished readbacks, which lock the state
+    if screenshot_manager.state.lock().pending_readbacks > 0 {
+        render_device.wgpu_device().poll(wgpu::Maintain::Poll);
+    }
+    let mut state = screenshot_manager.state.lock();
+    if state.requests.is_empty() {
+        return;
+    }
+
+    let windows = world.resource::<ExtractedWindows>();
+    let images = world.resource::<RenderAssets<Image>>();
+
+    let mut encoder = render_device.create_command_encoder(&CommandEncoderDescriptor {
+        label: Some("screenshot_encoder"),
+    });
+    let mut readbacks = Vec::new();
+    state.requests.retain(|target, save_path| {
+        let (texture, format, width, height): (&wgpu::Texture, _, _, _) = match target {
+            RenderTarget::Window(window_id) => {
+                let window = match windows.get(window_id) {
+                    Some(window) => window,
+                    None => return true,
+                };
+                if let Some(usages) = window.swap_chain_texture_usages {
+                    if !usages.contains(TextureUsages::COPY_SRC) {
+                        error!(
+                            "Can't capture a screenshot of {:?}, its surface can't be copied",
+                            target
+                        );
+                        return false;
+                    }
+                }
+                match (
+                    window
+                        .swap_chain_texture
+                        .as_ref()
+                        .and_then(|texture_view| texture_view.surface_texture()),
+                    window.swap_chain_texture_format,
+                ) {
+                    (Some(texture), Some(format)) => (
+                        texture,
+                        format,
+                        window.physical_width,
+                        window.physical_height,
+                    ),
+                    // the window isn't rendered this frame
+                    _ => return true,
+                }
+            }
+            RenderTarget::Image(handle) => match images.get(handle) {
+                Some(gpu_image) => (
+                    &*gpu_image.texture,
+                    gpu_image.texture_format,
+                    gpu_image.size.x as u32,
+                    gpu_image.size.y as u32,
+                ),
+                // the image isn't loaded yet
+                None => return true,
+            },
+        };
+        if !matches!(
+            format,
+            TextureFormat::Rgba8Unorm
+                | TextureFormat::Rgba8UnormSrgb
+                | TextureFormat::Bgra8Unorm
+                | TextureFormat::Bgra8UnormSrgb
+        ) {
+            error!(
+                "Can't capture a screenshot of {:?}, its format {:?} is not supported",
+                target, format
+            );
+            return false;
+        }
+
+        let size = Extent3d {
+            width,
+            height,
+            depth_or_array_layers: 1,
+        };
+        let padded_bytes_per_row = RenderDevice::align_copy_bytes_per_row(width as usize * 4);
+        let buffer = render_device.create_buffer(&BufferDescriptor {
+            label: Some("screenshot_buffer"),
+            size: (padded_bytes_per_row * height as usize) as u64,
+            usage: BufferUsages::MAP_READ | BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+        encoder.copy_texture_to_buffer(
+            texture.as_image_copy(),
+            ImageCopyBuffer {
+                buffer: &buffer,
+                layout: ImageDataLayout {
+                    offset: 0,
+                    bytes_per_row: NonZeroU32::new(padded_bytes_per_row as u32),
+                    rows_per_image: None,
+                },
+            },
+            size,
+        );
+        readbacks.push(ScreenshotReadback {
+            target: target.clone(),
+            save_path: save_path.take(),
+            buffer,
+            size,
+            padded_bytes_per_row,
+            format,
+        });
+        false
+    });
+    state.pending_readbacks += readbacks.len();
+    drop(state);
+    if readbacks.is_empty() {
+        return;
+    }
+
+    world.resource::<RenderQueue>().submit([encoder.finish()]);
+    for readback in readbacks {
+        let state = screenshot_manager.state.clone();
+        let buffer = readback.buffer.clone();
+        render_device.map_buffer(&readback.buffer.slice(..), MapMode::Read, move |result| {
+            let mut state = state.lock();
+            state.pending_readbacks -= 1;
+            if let Err(err) = result {
+                error!(
+                    "Failed to read the screenshot of {:?}: {}",
+                    readback.target, err
+                );
+                return;
+            }
+            let image = {
+                let data = buffer.slice(..).get_mapped_range();
+                image_from_padded_rows(
+                    &data,
+                    readback.size,
+                    readback.padded_bytes_per_row,
+                    readback.format,
+                )
+            };
+            buffer.unmap();
+            state.captured.push(CapturedScreenshot {
+                target: readback.target,
+                save_path: readback.save_path,
+                image,
+            });
+        });
+    }
+    // the buffers are mapped once the copies are done, which is checked again in the next frames
+    render_device.wgpu_device().poll(wgpu::Maintain::Poll);
+}
+
+/// Builds an RGBA [`Image`] from the rows of an 8 bit RGBA or BGRA texture copied to a buffer,
+/// whose rows are padded to `padded_bytes_per_row` bytes.
+fn image_from_padded_rows(
+    data: &[u8],
+    size: Extent3d,
+    padded_bytes_per_row: usize,
+    format: TextureFormat,
+) -> Image {
+    let bytes_per_row = size.width as usize * 4;
+    let mut pixels = Vec::with_capacity(bytes_per_row * size.height as usize);
+    for row in data.chunks(padded_bytes_per_row).take(size.height as usize) {
+        pixels.extend_from_slice(&row[..bytes_per_row]);
+    }
+
+    let format = match format {
+        TextureFormat::Bgra8Unorm | TextureFormat::Bgra8UnormSrgb => {
+            for pixel in pixels.chunks_exact_mut(4) {
+                pixel.swap(0, 2);
+            }
+            if format == TextureFormat::Bgra8UnormSrgb {
+                TextureFormat::Rgba8UnormSrgb
+            } else {
+                TextureFormat::Rgba8Unorm
+            }
+        }
+        format => format,
+    };
+    Image::new(size, TextureDimension::D2, pixels, format)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn padded_rows_to_image() {
+        let size = Extent3d {
+            width: 2,
+            height: 2,
+            depth_or_array_layers: 1,
+        };
+        // two rows of two BGRA pixels, each padded to 12 bytes
+        let data = [
+            1, 2, 3, 4, 5, 6, 7, 8, 0, 0, 0, 0, //
+            9, 10, 11, 12, 13, 14, 15, 16, 0, 0, 0, 0,
+        ];
+
+        let image = image_from_padded_rows(&data, size, 12, TextureFormat::Bgra8UnormSrgb);
+        assert_eq!(
+            image.texture_descriptor.format,
+            TextureFormat::Rgba8UnormSrgb
+        );
+        assert_eq!(
+            image.data,
+            [3, 2, 1, 4, 7, 6, 5, 8, 11, 10, 9, 12, 15, 14, 13, 16]
+        );
+
+        let image = image_from_padded_rows(&data, size, 12, TextureFormat::Rgba8Unorm);
+        assert_eq!(image.texture_descriptor.format, TextureFormat::Rgba8Unorm);
+        assert_eq!(
+            image.data,
+            [1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16]
+        );
+    }
+}
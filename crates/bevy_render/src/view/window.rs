@@ -1,5 +1,5 @@
 use crate::{
-    render_resource::TextureView,
+    render_resource::{TextureFormat, TextureUsages, TextureView},
     renderer::{RenderAdapter, RenderDevice, RenderInstance},
     Extract, RenderApp, RenderStage,
 };
@@ -43,6 +43,10 @@ pub struct ExtractedWindow {
     pub physical_height: u32,
     pub present_mode: PresentMode,
     pub swap_chain_texture: Option<TextureView>,
+    /// The format of the swap chain texture, once the surface of the window is configured.
+    pub swap_chain_texture_format: Option<TextureFormat>,
+    /// The usages of the swap chain texture, once the surface of the window is configured.
+    pub swap_chain_texture_usages: Option<TextureUsages>,
     pub size_changed: bool,
     pub present_mode_changed: bool,
 }
@@ -88,6 +92,8 @@ fn extract_windows(
                     physical_height: new_height,
                     present_mode: window.present_mode(),
                     swap_chain_texture: None,
+                    swap_chain_texture_format: None,
+                    swap_chain_texture_usages: None,
                     size_changed: false,
                     present_mode_changed: false,
                 });
@@ -188,7 +194,7 @@ pub fn prepare_windows(
                 }),
             width: window.physical_width,
             height: window.physical_height,
-            usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+            usage: swap_chain_texture_usages(surface.get_supported_usages(&render_adapter)),
             present_mode: match window.present_mode {
                 PresentMode::Fifo => wgpu::PresentMode::Fifo,
                 PresentMode::Mailbox => wgpu::PresentMode::Mailbox,
@@ -220,5 +226,39 @@ pub fn prepare_windows(
         };
 
         window.swap_chain_texture = Some(TextureView::from(frame));
+        window.swap_chain_texture_format = Some(swap_chain_descriptor.format);
+        window.swap_chain_texture_usages = Some(swap_chain_descriptor.usage);
+    }
+}
+
+/// The swap chain textures are copied from to capture screenshots of the windows, if the surface
+/// supports it, except on WebGL.
+fn swap_chain_texture_usages(supported_usages: TextureUsages) -> TextureUsages {
+    if cfg!(feature = "webgl") {
+        TextureUsages::RENDER_ATTACHMENT
+    } else {
+        TextureUsages::RENDER_ATTACHMENT | (supported_usages & TextureUsages::COPY_SRC)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(not(feature = "webgl"))]
+    #[test]
+    fn swap_chain_copies_only_if_supported() {
+        assert_eq!(
+            swap_chain_texture_usages(TextureUsages::RENDER_ATTACHMENT),
+            TextureUsages::RENDER_ATTACHMENT
+        );
+        assert_eq!(
+            swap_chain_texture_usages(
+                TextureUsages::RENDER_ATTACHMENT
+                    | TextureUsages::COPY_SRC
+                    | TextureUsages::COPY_DST
+            ),
+            TextureUsages::RENDER_ATTACHMENT | TextureUsages::COPY_SRC
+        );
     }
 }
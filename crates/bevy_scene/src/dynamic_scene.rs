@@ -1,12 +1,14 @@
 use crate::{serde::SceneSerializer, DynamicSceneBuilder, Scene, SceneSpawnError};
 use anyhow::Result;
 use bevy_app::AppTypeRegistry;
+use bevy_asset::{AssetPath, HandleId};
 use bevy_ecs::{
     entity::EntityMap,
     reflect::{ReflectComponent, ReflectMapEntities},
     world::World,
 };
 use bevy_reflect::{Reflect, TypeRegistryArc, TypeUuid};
+use bevy_utils::HashMap;
 use serde::Serialize;
 
 /// A collection of serializable dynamic entities, each with its own run-time defined set of components.
@@ -19,6 +21,12 @@ use serde::Serialize;
 #[derive(Default, TypeUuid)]
 #[uuid = "749479b1-fb8c-4ff8-a775-623aa76014f5"]
 pub struct DynamicScene {
+    /// The asset paths of the [`Handle`](bevy_asset::Handle)s in this scene's components.
+    ///
+    /// Handles with a path are serialized as that path instead of as their [`HandleId`]. When the
+    /// scene is loaded, each of these paths is loaded through the
+    /// [`AssetServer`](bevy_asset::AssetServer) as a dependency of the scene.
+    pub handle_paths: HashMap<HandleId, AssetPath<'static>>,
     pub entities: Vec<DynamicEntity>,
 }
 
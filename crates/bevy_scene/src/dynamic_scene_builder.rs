@@ -1,6 +1,8 @@
 use crate::{DynamicEntity, DynamicScene};
 use bevy_app::AppTypeRegistry;
+use bevy_asset::{AssetPath, AssetServer, HandleId};
 use bevy_ecs::{prelude::Entity, reflect::ReflectComponent, world::World};
+use bevy_reflect::{Reflect, ReflectRef};
 use bevy_utils::{default, HashMap};
 
 /// A [`DynamicScene`] builder, used to build a scene from a [`World`] by extracting some entities.
@@ -22,8 +24,12 @@ use bevy_utils::{default, HashMap};
 /// builder.extract_entity(entity);
 /// let dynamic_scene = builder.build();
 /// ```
+///
+/// If the [`World`] contains an [`AssetServer`], the paths of the assets referenced by the
+/// extracted [`Handle`](bevy_asset::Handle)s are recorded in [`DynamicScene::handle_paths`].
 pub struct DynamicSceneBuilder<'w> {
     scene: HashMap<u32, DynamicEntity>,
+    handle_paths: HashMap<HandleId, AssetPath<'static>>,
     type_registry: AppTypeRegistry,
     world: &'w World,
 }
@@ -34,6 +40,7 @@ impl<'w> DynamicSceneBuilder<'w> {
     pub fn from_world(world: &'w World) -> Self {
         Self {
             scene: default(),
+            handle_paths: default(),
             type_registry: world.resource::<AppTypeRegistry>().clone(),
             world,
         }
@@ -44,6 +51,7 @@ impl<'w> DynamicSceneBuilder<'w> {
     pub fn from_world_with_type_registry(world: &'w World, type_registry: AppTypeRegistry) -> Self {
         Self {
             scene: default(),
+            handle_paths: default(),
             type_registry,
             world,
         }
@@ -52,6 +60,7 @@ impl<'w> DynamicSceneBuilder<'w> {
     /// Consume the builder, producing a [`DynamicScene`].
     pub fn build(self) -> DynamicScene {
         DynamicScene {
+            handle_paths: self.handle_paths,
             entities: self.scene.into_values().collect(),
         }
     }
@@ -90,6 +99,7 @@ impl<'w> DynamicSceneBuilder<'w> {
     /// ```
     pub fn extract_entities(&mut self, entities: impl Iterator<Item = Entity>) -> &mut Self {
         let type_registry = self.type_registry.read();
+        let asset_server = self.world.get_resource::<AssetServer>();
 
         for entity in entities {
             if self.scene.contains_key(&entity.id()) {
@@ -111,6 +121,13 @@ impl<'w> DynamicSceneBuilder<'w> {
 
                 if let Some(reflect_component) = reflect_component {
                     if let Some(component) = reflect_component.reflect(self.world, entity) {
+                        if let Some(asset_server) = asset_server {
+                            visit_handle_ids(component, &mut |handle_id| {
+                                if let Some(path) = asset_server.get_handle_path(handle_id) {
+                                    self.handle_paths.insert(handle_id, path.to_owned());
+                                }
+                            });
+                        }
                         entry.components.push(component.clone_value());
                     }
                 }
@@ -124,14 +141,68 @@ impl<'w> DynamicSceneBuilder<'w> {
     }
 }
 
+/// Calls `visit` with the id of every [`Handle`](bevy_asset::Handle) found in `value`,
+/// including the ones nested in fields, lists, maps and sets.
+fn visit_handle_ids(value: &dyn Reflect, visit: &mut impl FnMut(HandleId)) {
+    match value.reflect_ref() {
+        ReflectRef::Struct(value) => {
+            for field in value.iter_fields() {
+                visit_handle_ids(field, visit);
+            }
+        }
+        ReflectRef::TupleStruct(value) => {
+            for field in value.iter_fields() {
+                visit_handle_ids(field, visit);
+            }
+        }
+        ReflectRef::Tuple(value) => {
+            for field in value.iter_fields() {
+                visit_handle_ids(field, visit);
+            }
+        }
+        ReflectRef::List(value) => {
+            for item in value.iter() {
+                visit_handle_ids(item, visit);
+            }
+        }
+        ReflectRef::Array(value) => {
+            for item in value.iter() {
+                visit_handle_ids(item, visit);
+            }
+        }
+        ReflectRef::Map(value) => {
+            for (_, item) in value.iter() {
+                visit_handle_ids(item, visit);
+            }
+        }
+        ReflectRef::Set(value) => {
+            for item in value.iter() {
+                visit_handle_ids(item, visit);
+            }
+        }
+        ReflectRef::Enum(value) => {
+            for field in value.iter_fields() {
+                visit_handle_ids(field.value(), visit);
+            }
+        }
+        ReflectRef::Value(value) => {
+            if let Some(handle_id) = value.downcast_ref::<HandleId>() {
+                visit(*handle_id);
+            }
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use bevy_app::AppTypeRegistry;
+    use bevy_asset::{AssetPath, AssetServer, FileAssetIo, Handle};
     use bevy_ecs::{
         component::Component, prelude::Entity, query::With, reflect::ReflectComponent, world::World,
     };
 
-    use bevy_reflect::Reflect;
+    use bevy_reflect::{Reflect, TypeUuid};
+    use bevy_tasks::IoTaskPool;
 
     use super::DynamicSceneBuilder;
 
@@ -141,6 +212,14 @@ mod tests {
     #[derive(Component, Reflect, Default, Eq, PartialEq, Debug)]
     #[reflect(Component)]
     struct ComponentB;
+    #[derive(TypeUuid)]
+    #[uuid = "a1d7b93a-4b3e-4c4f-9d2a-56f0c8c3a1e2"]
+    struct AssetA;
+    #[derive(Component, Reflect, Default)]
+    #[reflect(Component)]
+    struct ComponentWithHandles {
+        handles: Vec<Handle<AssetA>>,
+    }
 
     #[test]
     fn extract_one_entity() {
@@ -234,4 +313,44 @@ mod tests {
         scene_entities.sort();
         assert_eq!(scene_entities, [entity_a_b.id(), entity_a.id()]);
     }
+
+    #[test]
+    fn extract_handle_paths() {
+        IoTaskPool::init(Default::default);
+        let mut world = World::default();
+
+        let atr = AppTypeRegistry::default();
+        {
+            let mut register = atr.write();
+            register.register::<Handle<AssetA>>();
+            register.register::<ComponentWithHandles>();
+        }
+        world.insert_resource(atr);
+
+        let asset_server = AssetServer::new(FileAssetIo::new("assets", false));
+        let handle_a = asset_server.load("models/a.test#Mesh0");
+        let handle_b = asset_server.load("models/b.test");
+        let (id_a, id_b) = (handle_a.id(), handle_b.id());
+        world.insert_resource(asset_server);
+
+        let entity = world
+            .spawn((
+                handle_b.clone(),
+                ComponentWithHandles {
+                    handles: vec![handle_a, handle_b],
+                },
+            ))
+            .id();
+
+        let mut builder = DynamicSceneBuilder::from_world(&world);
+        builder.extract_entity(entity);
+        let scene = builder.build();
+
+        assert_eq!(scene.handle_paths.len(), 2);
+        assert_eq!(
+            scene.handle_paths[&id_a],
+            AssetPath::from("models/a.test#Mesh0")
+        );
+        assert_eq!(scene.handle_paths[&id_b], AssetPath::from("models/b.test"));
+    }
 }
@@ -33,7 +33,8 @@ impl AssetLoader for SceneLoader {
                 type_registry: &*self.type_registry.read(),
             };
             let scene = scene_deserializer.deserialize(&mut deserializer)?;
-            load_context.set_default_asset(LoadedAsset::new(scene));
+            let asset_paths = scene.handle_paths.values().cloned().collect();
+            load_context.set_default_asset(LoadedAsset::new(scene).with_dependencies(asset_paths));
             Ok(())
         })
     }
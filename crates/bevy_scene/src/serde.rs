@@ -1,9 +1,11 @@
 use crate::{DynamicEntity, DynamicScene};
 use anyhow::Result;
+use bevy_asset::{with_handle_paths, AssetPath};
 use bevy_reflect::{
     serde::{ReflectSerializer, UntypedReflectDeserializer},
     Reflect, TypeRegistry, TypeRegistryArc,
 };
+use bevy_utils::HashMap;
 use serde::{
     de::{DeserializeSeed, Error, MapAccess, SeqAccess, Visitor},
     ser::{SerializeSeq, SerializeStruct},
@@ -26,8 +28,34 @@ impl<'a> Serialize for SceneSerializer<'a> {
     where
         S: serde::Serializer,
     {
-        let mut state = serializer.serialize_seq(Some(self.scene.entities.len()))?;
-        for entity in &self.scene.entities {
+        // Handles are written as their asset paths, so that they can be edited and loaded again
+        let mut handle_paths = self.scene.handle_paths.clone();
+        with_handle_paths(&mut handle_paths, || {
+            let mut state = serializer.serialize_struct(SCENE_STRUCT, 1)?;
+            state.serialize_field(
+                SCENE_FIELD_ENTITIES,
+                &EntitiesSerializer {
+                    entities: &self.scene.entities,
+                    registry: self.registry,
+                },
+            )?;
+            state.end()
+        })
+    }
+}
+
+pub struct EntitiesSerializer<'a> {
+    pub entities: &'a [DynamicEntity],
+    pub registry: &'a TypeRegistryArc,
+}
+
+impl<'a> Serialize for EntitiesSerializer<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let mut state = serializer.serialize_seq(Some(self.entities.len()))?;
+        for entity in self.entities {
             state.serialize_element(&EntitySerializer {
                 entity,
                 registry: self.registry,
@@ -92,10 +120,111 @@ impl<'a, 'de> DeserializeSeed<'de> for SceneDeserializer<'a> {
     where
         D: serde::Deserializer<'de>,
     {
-        Ok(DynamicScene {
-            entities: deserializer.deserialize_seq(SceneEntitySeqVisitor {
+        // Scenes saved before the asset paths were recorded are a list of entities instead of a
+        // struct, so the format has to describe which of the two it contains.
+        let mut handle_paths = HashMap::default();
+        let mut scene = with_handle_paths(&mut handle_paths, || {
+            deserializer.deserialize_any(SceneVisitor {
                 type_registry: self.type_registry,
-            })?,
+            })
+        })?;
+        scene.handle_paths.extend(handle_paths);
+        Ok(scene)
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(field_identifier, rename_all = "snake_case")]
+enum SceneField {
+    AssetPaths,
+    Entities,
+}
+
+pub const SCENE_STRUCT: &str = "Scene";
+pub const SCENE_FIELD_ASSET_PATHS: &str = "asset_paths";
+pub const SCENE_FIELD_ENTITIES: &str = "entities";
+
+struct SceneVisitor<'a> {
+    pub type_registry: &'a TypeRegistry,
+}
+
+impl<'a, 'de> Visitor<'de> for SceneVisitor<'a> {
+    type Value = DynamicScene;
+
+    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        formatter.write_str("scene struct or list of entities")
+    }
+
+    fn visit_seq<A>(self, seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        // The handles of these scenes are only known by their `HandleId`
+        let entities = SceneEntitySeqVisitor {
+            type_registry: self.type_registry,
+        }
+        .visit_seq(seq)?;
+
+        Ok(DynamicScene {
+            handle_paths: HashMap::default(),
+            entities,
+        })
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let mut asset_paths = None;
+        let mut entities = None;
+        while let Some(key) = map.next_key()? {
+            match key {
+                SceneField::AssetPaths => {
+                    if asset_paths.is_some() {
+                        return Err(Error::duplicate_field(SCENE_FIELD_ASSET_PATHS));
+                    }
+                    asset_paths = Some(map.next_value::<Vec<AssetPath<'static>>>()?);
+                }
+                SceneField::Entities => {
+                    if entities.is_some() {
+                        return Err(Error::duplicate_field(SCENE_FIELD_ENTITIES));
+                    }
+                    entities = Some(map.next_value_seed(SceneEntitiesDeserializer {
+                        type_registry: self.type_registry,
+                    })?);
+                }
+            }
+        }
+
+        // Scenes saved before handles were written as paths list the paths of their assets
+        // separately, and only refer to them by id
+        let handle_paths = asset_paths
+            .unwrap_or_default()
+            .into_iter()
+            .map(|path| (path.get_id().into(), path))
+            .collect();
+        let entities = entities.ok_or_else(|| Error::missing_field(SCENE_FIELD_ENTITIES))?;
+
+        Ok(DynamicScene {
+            handle_paths,
+            entities,
+        })
+    }
+}
+
+pub struct SceneEntitiesDeserializer<'a> {
+    pub type_registry: &'a TypeRegistry,
+}
+
+impl<'a, 'de> DeserializeSeed<'de> for SceneEntitiesDeserializer<'a> {
+    type Value = Vec<DynamicEntity>;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        deserializer.deserialize_seq(SceneEntitySeqVisitor {
+            type_registry: self.type_registry,
         })
     }
 }
@@ -251,3 +380,182 @@ impl<'a, 'de> Visitor<'de> for ComponentSeqVisitor<'a> {
         Ok(dynamic_properties)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{SceneDeserializer, SceneSerializer};
+    use crate::{DynamicEntity, DynamicScene};
+    use bevy_app::AppTypeRegistry;
+    use bevy_asset::{AssetPath, Handle, HandleId};
+    use bevy_ecs::{component::Component, reflect::ReflectComponent};
+    use bevy_reflect::{FromReflect, Reflect, TypeUuid};
+    use bevy_utils::HashMap;
+    use serde::de::DeserializeSeed;
+
+    #[derive(Component, Reflect, FromReflect, Default, PartialEq, Debug)]
+    #[reflect(Component)]
+    struct ComponentA {
+        value: u32,
+    }
+
+    #[derive(TypeUuid)]
+    #[uuid = "3f1c8a5e-6d2b-4e7a-9b0c-1a2d3e4f5a6b"]
+    struct AssetA;
+
+    #[derive(Component, Reflect, FromReflect, Default)]
+    #[reflect(Component)]
+    struct ComponentWithHandle {
+        handle: Handle<AssetA>,
+    }
+
+    fn registry() -> AppTypeRegistry {
+        let registry = AppTypeRegistry::default();
+        {
+            let mut registry = registry.write();
+            registry.register::<ComponentA>();
+            registry.register::<ComponentWithHandle>();
+            registry.register::<Handle<AssetA>>();
+            registry.register::<HandleId>();
+        }
+        registry
+    }
+
+    fn deserialize(registry: &AppTypeRegistry, input: &str) -> DynamicScene {
+        let mut deserializer = ron::de::Deserializer::from_str(input).unwrap();
+        SceneDeserializer {
+            type_registry: &registry.read(),
+        }
+        .deserialize(&mut deserializer)
+        .unwrap()
+    }
+
+    fn component_a(scene: &DynamicScene) -> ComponentA {
+        ComponentA::from_reflect(&*scene.entities[0].components[0]).unwrap()
+    }
+
+    fn handle_id(scene: &DynamicScene, entity: usize) -> HandleId {
+        ComponentWithHandle::from_reflect(&*scene.entities[entity].components[0])
+            .unwrap()
+            .handle
+            .id()
+    }
+
+    #[test]
+    fn round_trip_scene() {
+        let registry = registry();
+        let path = AssetPath::from("models/a.test#Mesh0");
+        let id = HandleId::from(path.clone());
+        let scene = DynamicScene {
+            handle_paths: HashMap::from_iter([(id, path.clone())]),
+            entities: vec![
+                DynamicEntity {
+                    entity: 3,
+                    components: vec![Box::new(ComponentA { value: 7 })],
+                },
+                DynamicEntity {
+                    entity: 4,
+                    components: vec![Box::new(ComponentWithHandle {
+                        handle: Handle::weak(id),
+                    })],
+                },
+            ],
+        };
+
+        let serialized = ron::to_string(&SceneSerializer::new(&scene, &registry)).unwrap();
+        assert!(serialized.contains(r#"id:Path("models/a.test#Mesh0")"#));
+
+        let deserialized = deserialize(&registry, &serialized);
+        assert_eq!(deserialized.handle_paths, scene.handle_paths);
+        assert_eq!(deserialized.entities.len(), 2);
+        assert_eq!(deserialized.entities[0].entity, 3);
+        assert_eq!(component_a(&deserialized), ComponentA { value: 7 });
+        assert_eq!(handle_id(&deserialized, 1), id);
+    }
+
+    #[test]
+    fn deserialize_edited_handle_path() {
+        let registry = registry();
+        let scene = deserialize(
+            &registry,
+            r#"(
+                entities: [
+                    (
+                        entity: 0,
+                        components: [
+                            {
+                                "bevy_scene::serde::tests::ComponentWithHandle": (
+                                    handle: (
+                                        id: Path("models/b.test#Mesh1"),
+                                    ),
+                                ),
+                            },
+                        ],
+                    ),
+                ],
+            )"#,
+        );
+
+        let path = AssetPath::from("models/b.test#Mesh1");
+        let id = HandleId::from(path.clone());
+        assert_eq!(scene.handle_paths, HashMap::from_iter([(id, path)]));
+        assert_eq!(handle_id(&scene, 0), id);
+    }
+
+    #[test]
+    fn deserialize_asset_paths_and_handle_ids() {
+        let registry = registry();
+        let path = AssetPath::from("models/a.test#Mesh0");
+        let id = HandleId::from(path.clone());
+        // Outside of a scene, handle ids are written as opaque ids
+        let serialized_id = ron::to_string(&id).unwrap();
+        assert!(serialized_id.starts_with("AssetPathId("));
+        let scene = deserialize(
+            &registry,
+            &format!(
+                r#"(
+                    asset_paths: [(path: "models/a.test", label: Some("Mesh0"))],
+                    entities: [
+                        (
+                            entity: 0,
+                            components: [
+                                {{
+                                    "bevy_scene::serde::tests::ComponentWithHandle": (
+                                        handle: (id: {serialized_id}),
+                                    ),
+                                }},
+                            ],
+                        ),
+                    ],
+                )"#
+            ),
+        );
+
+        assert_eq!(scene.handle_paths, HashMap::from_iter([(id, path)]));
+        assert_eq!(handle_id(&scene, 0), id);
+    }
+
+    #[test]
+    fn deserialize_list_of_entities() {
+        let registry = registry();
+        let scene = deserialize(
+            &registry,
+            r#"[
+                (
+                    entity: 0,
+                    components: [
+                        {
+                            "bevy_scene::serde::tests::ComponentA": (
+                                value: 5,
+                            ),
+                        },
+                    ],
+                ),
+            ]"#,
+        );
+
+        assert!(scene.handle_paths.is_empty());
+        assert_eq!(scene.entities.len(), 1);
+        assert_eq!(scene.entities[0].entity, 0);
+        assert_eq!(component_a(&scene), ComponentA { value: 5 });
+    }
+}
@@ -16,6 +16,7 @@ use bevy_ecs::{
 use bevy_log::error;
 use bevy_reflect::TypeUuid;
 use bevy_render::{
+    camera::CameraSampledTextures,
     extract_component::ExtractComponentPlugin,
     mesh::{Mesh, MeshVertexBufferLayout},
     prelude::Image,
@@ -31,7 +32,7 @@ use bevy_render::{
     },
     renderer::RenderDevice,
     texture::FallbackImage,
-    view::{ComputedVisibility, Msaa, Visibility, VisibleEntities},
+    view::{ComputedVisibility, ExtractedView, Msaa, Visibility, VisibleEntities},
     Extract, RenderApp, RenderStage,
 };
 use bevy_transform::components::{GlobalTransform, Transform};
@@ -306,20 +307,26 @@ pub fn queue_material2d_meshes<M: Material2d>(
     render_meshes: Res<RenderAssets<Mesh>>,
     render_materials: Res<RenderMaterials2d<M>>,
     material2d_meshes: Query<(&Handle<M>, &Mesh2dHandle, &Mesh2dUniform)>,
-    mut views: Query<(&VisibleEntities, &mut RenderPhase<Transparent2d>)>,
+    mut views: Query<(
+        &ExtractedView,
+        &VisibleEntities,
+        &mut RenderPhase<Transparent2d>,
+        Option<&mut CameraSampledTextures>,
+    )>,
 ) where
     M::Data: PartialEq + Eq + Hash + Clone,
 {
     if material2d_meshes.is_empty() {
         return;
     }
-    for (visible_entities, mut transparent_phase) in &mut views {
+    for (view, visible_entities, mut transparent_phase, mut sampled_textures) in &mut views {
         let draw_transparent_pbr = transparent_draw_functions
             .read()
             .get_id::<DrawMaterial2d<M>>()
             .unwrap();
 
-        let msaa_key = Mesh2dPipelineKey::from_msaa_samples(msaa.samples);
+        let view_key = Mesh2dPipelineKey::from_msaa_samples(msaa.samples)
+            | Mesh2dPipelineKey::from_hdr(view.hdr);
 
         for visible_entity in &visible_entities.entities {
             if let Ok((material2d_handle, mesh2d_handle, mesh2d_uniform)) =
@@ -327,7 +334,10 @@ pub fn queue_material2d_meshes<M: Material2d>(
             {
                 if let Some(material2d) = render_materials.get(material2d_handle) {
                     if let Some(mesh) = render_meshes.get(&mesh2d_handle.0) {
-                        let mesh_key = msaa_key
+                        if let Some(sampled_textures) = &mut sampled_textures {
+                            sampled_textures.insert_bindings(&material2d.bindings);
+                        }
+                        let mesh_key = view_key
                             | Mesh2dPipelineKey::from_primitive_topology(mesh.primitive_topology);
 
                         let pipeline_id = pipelines.specialize(
@@ -418,6 +428,7 @@ fn extract_materials_2d<M: Material2d>(
                 changed_assets.remove(handle);
                 removed.push(handle.clone_weak());
             }
+            AssetEvent::LoadedWithDependencies { .. } => {}
         }
     }
 
@@ -13,6 +13,7 @@ use bevy_ecs::{
 use bevy_math::{Rect, Vec2};
 use bevy_reflect::Uuid;
 use bevy_render::{
+    camera::CameraSampledTextures,
     color::Color,
     render_asset::RenderAssets,
     render_phase::{
@@ -23,7 +24,8 @@ use bevy_render::{
     renderer::{RenderDevice, RenderQueue, RenderTextureFormat},
     texture::{DefaultImageSampler, GpuImage, Image, ImageSampler, TextureFormatPixelInfo},
     view::{
-        ComputedVisibility, Msaa, ViewUniform, ViewUniformOffset, ViewUniforms, VisibleEntities,
+        ComputedVisibility, ExtractedView, Msaa, ViewTarget, ViewUniform, ViewUniformOffset,
+        ViewUniforms, VisibleEntities,
     },
     Extract,
 };
@@ -148,6 +150,7 @@ bitflags::bitflags! {
     pub struct SpritePipelineKey: u32 {
         const NONE                        = 0;
         const COLORED                     = (1 << 0);
+        const HDR                         = (1 << 1);
         const MSAA_RESERVED_BITS          = Self::MSAA_MASK_BITS << Self::MSAA_SHIFT_BITS;
     }
 }
@@ -165,6 +168,14 @@ impl SpritePipelineKey {
     pub fn msaa_samples(&self) -> u32 {
         1 << ((self.bits >> Self::MSAA_SHIFT_BITS) & Self::MSAA_MASK_BITS)
     }
+
+    pub fn from_hdr(hdr: bool) -> Self {
+        if hdr {
+            SpritePipelineKey::HDR
+        } else {
+            SpritePipelineKey::NONE
+        }
+    }
 }
 
 impl SpecializedRenderPipeline for SpritePipeline {
@@ -188,7 +199,7 @@ impl SpecializedRenderPipeline for SpritePipeline {
 
         let mut shader_defs = Vec::new();
         if key.contains(SpritePipelineKey::COLORED) {
-            shader_defs.push("COLORED".to_string());
+            shader_defs.push("COLORED".into());
         }
 
         RenderPipelineDescriptor {
@@ -203,7 +214,11 @@ impl SpecializedRenderPipeline for SpritePipeline {
                 shader_defs,
                 entry_point: "fragment".into(),
                 targets: vec![Some(ColorTargetState {
-                    format: self.dummy_white_gpu_image.texture_format,
+                    format: if key.contains(SpritePipelineKey::HDR) {
+                        ViewTarget::TEXTURE_FORMAT_HDR
+                    } else {
+                        self.dummy_white_gpu_image.texture_format
+                    },
                     blend: Some(BlendState::ALPHA_BLENDING),
                     write_mask: ColorWrites::ALL,
                 })],
@@ -275,6 +290,9 @@ pub fn extract_sprite_events(
             AssetEvent::Removed { handle } => AssetEvent::Removed {
                 handle: handle.clone_weak(),
             },
+            AssetEvent::LoadedWithDependencies { handle } => AssetEvent::LoadedWithDependencies {
+                handle: handle.clone_weak(),
+            },
         });
     }
 }
@@ -418,13 +436,18 @@ pub fn queue_sprites(
     gpu_images: Res<RenderAssets<Image>>,
     msaa: Res<Msaa>,
     mut extracted_sprites: ResMut<ExtractedSprites>,
-    mut views: Query<(&VisibleEntities, &mut RenderPhase<Transparent2d>)>,
+    mut views: Query<(
+        &ExtractedView,
+        &VisibleEntities,
+        &mut RenderPhase<Transparent2d>,
+        Option<&mut CameraSampledTextures>,
+    )>,
     events: Res<SpriteAssetEvents>,
 ) {
     // If an image has changed, the GpuImage has (probably) changed
     for event in &events.images {
         match event {
-            AssetEvent::Created { .. } => None,
+            AssetEvent::Created { .. } | AssetEvent::LoadedWithDependencies { .. } => None,
             AssetEvent::Modified { handle } | AssetEvent::Removed { handle } => {
                 image_bind_groups.values.remove(handle)
             }
@@ -448,13 +471,7 @@ pub fn queue_sprites(
         }));
 
         let draw_sprite_function = draw_functions.read().get_id::<DrawSprite>().unwrap();
-        let key = SpritePipelineKey::from_msaa_samples(msaa.samples);
-        let pipeline = pipelines.specialize(&mut pipeline_cache, &sprite_pipeline, key);
-        let colored_pipeline = pipelines.specialize(
-            &mut pipeline_cache,
-            &sprite_pipeline,
-            key | SpritePipelineKey::COLORED,
-        );
+        let msaa_key = SpritePipelineKey::from_msaa_samples(msaa.samples);
 
         // Vertex buffer indices
         let mut index = 0;
@@ -476,7 +493,15 @@ pub fn queue_sprites(
         });
         let image_bind_groups = &mut *image_bind_groups;
 
-        for (visible_entities, mut transparent_phase) in &mut views {
+        for (view, visible_entities, mut transparent_phase, mut sampled_textures) in &mut views {
+            let key = msaa_key | SpritePipelineKey::from_hdr(view.hdr);
+            let pipeline = pipelines.specialize(&mut pipeline_cache, &sprite_pipeline, key);
+            let colored_pipeline = pipelines.specialize(
+                &mut pipeline_cache,
+                &sprite_pipeline,
+                key | SpritePipelineKey::COLORED,
+            );
+
             view_entities.clear();
             view_entities.extend(visible_entities.entities.iter().map(|e| e.id() as usize));
             transparent_phase.items.reserve(extracted_sprites.len());
@@ -509,6 +534,9 @@ pub fn queue_sprites(
                         current_batch = new_batch;
                         current_image_size = Vec2::new(gpu_image.size.x, gpu_image.size.y);
                         current_batch_entity = commands.spawn((current_batch,)).id();
+                        if let Some(sampled_textures) = &mut sampled_textures {
+                            sampled_textures.insert(&gpu_image.texture_view);
+                        }
 
                         image_bind_groups
                             .values
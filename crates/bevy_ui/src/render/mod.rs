@@ -103,10 +103,16 @@ pub fn build_ui_render(app: &mut App) {
         );
         graph_2d
             .add_node_edge(
-                bevy_core_pipeline::core_2d::graph::node::MAIN_PASS,
+                bevy_core_pipeline::core_2d::graph::node::TONEMAPPING,
                 draw_ui_graph::node::UI_PASS,
             )
             .unwrap();
+        graph_2d
+            .add_node_edge(
+                draw_ui_graph::node::UI_PASS,
+                bevy_core_pipeline::core_2d::graph::node::UPSCALING,
+            )
+            .unwrap();
         graph_2d
             .add_slot_edge(
                 graph_2d.input_node().unwrap().id,
@@ -125,8 +131,14 @@ pub fn build_ui_render(app: &mut App) {
         );
         graph_3d
             .add_node_edge(
-                bevy_core_pipeline::core_3d::graph::node::MAIN_PASS,
+                bevy_core_pipeline::core_3d::graph::node::TONEMAPPING,
+                draw_ui_graph::node::UI_PASS,
+            )
+            .unwrap();
+        graph_3d
+            .add_node_edge(
                 draw_ui_graph::node::UI_PASS,
+                bevy_core_pipeline::core_3d::graph::node::UPSCALING,
             )
             .unwrap();
         graph_3d
@@ -254,6 +266,7 @@ pub fn extract_default_ui_camera_view<T: Component>(
                         0.0,
                         UI_CAMERA_FAR + UI_CAMERA_TRANSFORM_OFFSET,
                     ),
+                    hdr: camera.hdr,
                     viewport: UVec4::new(
                         physical_origin.x,
                         physical_origin.y,
@@ -523,13 +536,13 @@ pub fn queue_uinodes(
     mut image_bind_groups: ResMut<UiImageBindGroups>,
     gpu_images: Res<RenderAssets<Image>>,
     ui_batches: Query<(Entity, &UiBatch)>,
-    mut views: Query<&mut RenderPhase<TransparentUi>>,
+    mut views: Query<(&ExtractedView, &mut RenderPhase<TransparentUi>)>,
     events: Res<SpriteAssetEvents>,
 ) {
     // If an image has changed, the GpuImage has (probably) changed
     for event in &events.images {
         match event {
-            AssetEvent::Created { .. } => None,
+            AssetEvent::Created { .. } | AssetEvent::LoadedWithDependencies { .. } => None,
             AssetEvent::Modified { handle } | AssetEvent::Removed { handle } => {
                 image_bind_groups.values.remove(handle)
             }
@@ -546,8 +559,12 @@ pub fn queue_uinodes(
             layout: &ui_pipeline.view_layout,
         }));
         let draw_ui_function = draw_functions.read().get_id::<DrawUi>().unwrap();
-        let pipeline = pipelines.specialize(&mut pipeline_cache, &ui_pipeline, UiPipelineKey {});
-        for mut transparent_phase in &mut views {
+        for (view, mut transparent_phase) in &mut views {
+            let pipeline = pipelines.specialize(
+                &mut pipeline_cache,
+                &ui_pipeline,
+                UiPipelineKey { hdr: view.hdr },
+            );
             for (entity, batch) in &ui_batches {
                 image_bind_groups
                     .values
@@ -58,6 +58,21 @@ pub struct WindowSettings {
     pub close_when_requested: bool,
 }
 
+impl WindowSettings {
+    /// The settings of a "headless" app, which runs without windows until it sends the
+    /// [`bevy_app::AppExit`] event.
+    ///
+    /// Such an app renders to images, and is run by the
+    /// [`ScheduleRunnerPlugin`](bevy_app::ScheduleRunnerPlugin) instead of a windowing backend.
+    pub fn headless() -> Self {
+        WindowSettings {
+            add_primary_window: false,
+            exit_on_all_closed: false,
+            close_when_requested: true,
+        }
+    }
+}
+
 impl Default for WindowSettings {
     fn default() -> Self {
         WindowSettings {
@@ -124,3 +139,33 @@ impl Plugin for WindowPlugin {
 
 #[derive(Debug, Hash, PartialEq, Eq, Clone, SystemLabel)]
 pub struct ModifiesWindows;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_app::AppExit;
+
+    #[test]
+    fn headless_settings_run_without_windows() {
+        let mut app = App::new();
+        app.insert_resource(WindowSettings::headless())
+            .add_plugin(WindowPlugin);
+        assert!(app.world.resource::<Events<CreateWindow>>().is_empty());
+
+        app.update();
+        assert!(app.world.resource::<Windows>().get_primary().is_none());
+        // the app doesn't exit without windows
+        assert!(app.world.resource::<Events<AppExit>>().is_empty());
+    }
+
+    #[test]
+    fn default_settings_exit_without_windows() {
+        let mut app = App::new();
+        app.add_plugin(WindowPlugin);
+        assert_eq!(app.world.resource::<Events<CreateWindow>>().len(), 1);
+
+        // no windowing backend creates the window
+        app.update();
+        assert!(!app.world.resource::<Events<AppExit>>().is_empty());
+    }
+}
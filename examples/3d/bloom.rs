@@ -0,0 +1,101 @@
+//! Illustrates bloom and color grading, which are applied to "high dynamic range" cameras.
+
+use bevy::{
+    core_pipeline::{bloom::BloomSettings, tonemapping::ColorGrading},
+    prelude::*,
+};
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_startup_system(setup)
+        .add_system(update_settings)
+        .run();
+}
+
+fn setup(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    info!("Press up and down to change the bloom threshold");
+    info!("Press left and right to change the exposure");
+    info!("Press s to toggle the saturation");
+
+    // camera
+    commands.spawn((
+        Camera3dBundle {
+            camera: Camera {
+                // bloom and color grading are only applied to hdr cameras
+                hdr: true,
+                ..default()
+            },
+            transform: Transform::from_xyz(-2.0, 2.5, 5.0).looking_at(Vec3::ZERO, Vec3::Y),
+            ..default()
+        },
+        BloomSettings::default(),
+        ColorGrading::default(),
+    ));
+
+    // emissive spheres, whose colors are brighter than the bloom threshold
+    let mesh = meshes.add(Mesh::from(shape::UVSphere {
+        radius: 0.4,
+        ..default()
+    }));
+    for (x, color) in [
+        (-1.5, Color::rgb_linear(8.0, 1.0, 0.5)),
+        (0.0, Color::rgb_linear(1.0, 8.0, 0.5)),
+        (1.5, Color::rgb_linear(0.5, 1.0, 8.0)),
+    ] {
+        commands.spawn(PbrBundle {
+            mesh: mesh.clone(),
+            material: materials.add(StandardMaterial {
+                emissive: color,
+                ..default()
+            }),
+            transform: Transform::from_xyz(x, 0.5, 0.0),
+            ..default()
+        });
+    }
+
+    // plane
+    commands.spawn(PbrBundle {
+        mesh: meshes.add(Mesh::from(shape::Plane { size: 5.0 })),
+        material: materials.add(Color::rgb(0.3, 0.5, 0.3).into()),
+        ..default()
+    });
+    // light
+    commands.spawn(PointLightBundle {
+        transform: Transform::from_xyz(4.0, 8.0, 4.0),
+        ..default()
+    });
+}
+
+fn update_settings(
+    time: Res<Time>,
+    input: Res<Input<KeyCode>>,
+    mut cameras: Query<(&mut BloomSettings, &mut ColorGrading)>,
+) {
+    let delta = time.delta_seconds();
+    for (mut bloom, mut color_grading) in &mut cameras {
+        if input.pressed(KeyCode::Up) {
+            bloom.threshold += delta;
+        }
+        if input.pressed(KeyCode::Down) {
+            bloom.threshold = (bloom.threshold - delta).max(0.0);
+        }
+        if input.pressed(KeyCode::Right) {
+            color_grading.exposure += delta;
+        }
+        if input.pressed(KeyCode::Left) {
+            color_grading.exposure -= delta;
+        }
+        if input.just_pressed(KeyCode::S) {
+            color_grading.saturation = if color_grading.saturation == 1.0 {
+                0.0
+            } else {
+                1.0
+            };
+        }
+    }
+}
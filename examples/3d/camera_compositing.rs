@@ -0,0 +1,115 @@
+//! Composites the output of a camera over the output of the main camera with different blend modes.
+
+use bevy::{
+    prelude::*,
+    render::camera::{CameraBlendMode, CameraOutputMode},
+};
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_startup_system(setup)
+        .add_system(cycle_blend_mode)
+        .run();
+}
+
+/// Marks the camera composited over the main camera.
+#[derive(Component)]
+struct CompositeCamera;
+
+/// set up a simple 3D scene
+fn setup(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    // plane
+    commands.spawn(PbrBundle {
+        mesh: meshes.add(Mesh::from(shape::Plane { size: 5.0 })),
+        material: materials.add(Color::rgb(0.3, 0.5, 0.3).into()),
+        ..default()
+    });
+    // cube
+    commands.spawn(PbrBundle {
+        mesh: meshes.add(Mesh::from(shape::Cube { size: 1.0 })),
+        material: materials.add(Color::rgb(0.8, 0.7, 0.6).into()),
+        transform: Transform::from_xyz(0.0, 0.5, 0.0),
+        ..default()
+    });
+    // light
+    commands.spawn(PointLightBundle {
+        point_light: PointLight {
+            intensity: 1500.0,
+            shadows_enabled: true,
+            ..default()
+        },
+        transform: Transform::from_xyz(4.0, 8.0, 4.0),
+        ..default()
+    });
+    // camera
+    commands.spawn(Camera3dBundle {
+        transform: Transform::from_xyz(-2.0, 2.5, 5.0).looking_at(Vec3::ZERO, Vec3::Y),
+        ..default()
+    });
+
+    // camera
+    commands.spawn((
+        Camera3dBundle {
+            transform: Transform::from_xyz(10.0, 10., -5.0).looking_at(Vec3::ZERO, Vec3::Y),
+            camera: Camera {
+                // renders after / on top of the main camera
+                priority: 1,
+                // only what this camera draws is blended over the output of the main camera
+                output_mode: CameraOutputMode::Composite(CameraBlendMode::Alpha),
+                ..default()
+            },
+            ..default()
+        },
+        // the text is only drawn by the main camera
+        UiCameraConfig { show_ui: false },
+        CompositeCamera,
+    ));
+
+    commands.spawn(
+        TextBundle::from_section(
+            "Press space to cycle the blend mode\nBlend mode: Alpha",
+            TextStyle {
+                font: asset_server.load("fonts/FiraMono-Medium.ttf"),
+                font_size: 20.0,
+                color: Color::WHITE,
+            },
+        )
+        .with_style(Style {
+            position_type: PositionType::Absolute,
+            position: UiRect {
+                top: Val::Px(10.0),
+                left: Val::Px(10.0),
+                ..default()
+            },
+            ..default()
+        }),
+    );
+}
+
+fn cycle_blend_mode(
+    keyboard_input: Res<Input<KeyCode>>,
+    mut cameras: Query<&mut Camera, With<CompositeCamera>>,
+    mut texts: Query<&mut Text>,
+) {
+    if !keyboard_input.just_pressed(KeyCode::Space) {
+        return;
+    }
+    for mut camera in &mut cameras {
+        let blend_mode = match camera.output_mode {
+            CameraOutputMode::Composite(CameraBlendMode::Alpha) => CameraBlendMode::Additive,
+            CameraOutputMode::Composite(CameraBlendMode::Additive) => CameraBlendMode::Multiply,
+            _ => CameraBlendMode::Alpha,
+        };
+        camera.output_mode = CameraOutputMode::Composite(blend_mode);
+        for mut text in &mut texts {
+            text.sections[0].value =
+                format!("Press space to cycle the blend mode\nBlend mode: {blend_mode:?}");
+        }
+    }
+}
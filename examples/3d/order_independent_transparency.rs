@@ -0,0 +1,107 @@
+//! Compares the sorted transparency of a 3d camera with weighted blended order-independent
+//! transparency, on intersecting transparent meshes.
+//!
+//! Press space to switch between the two modes. The small spheres are drawn on top of the
+//! others in sorted mode, thanks to a `TransparentSortOverride`.
+
+use bevy::prelude::*;
+
+fn main() {
+    App::new()
+        .insert_resource(Msaa { samples: 4 })
+        .add_plugins(DefaultPlugins)
+        .add_startup_system(setup)
+        .add_system(toggle_transparency)
+        .run();
+}
+
+fn setup(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    // opaque plane
+    commands.spawn(PbrBundle {
+        mesh: meshes.add(Mesh::from(shape::Plane { size: 6.0 })),
+        material: materials.add(Color::rgb(0.3, 0.5, 0.3).into()),
+        ..default()
+    });
+
+    // intersecting transparent cubes, which flicker in sorted mode as their origins swap order
+    let cube = meshes.add(Mesh::from(shape::Cube { size: 1.0 }));
+    for (i, color) in [
+        Color::rgba(0.9, 0.2, 0.2, 0.5),
+        Color::rgba(0.2, 0.9, 0.2, 0.5),
+        Color::rgba(0.2, 0.2, 0.9, 0.5),
+    ]
+    .into_iter()
+    .enumerate()
+    {
+        commands.spawn(PbrBundle {
+            mesh: cube.clone(),
+            material: materials.add(StandardMaterial {
+                base_color: color,
+                alpha_mode: AlphaMode::Blend,
+                ..default()
+            }),
+            transform: Transform::from_xyz(i as f32 * 0.4 - 0.4, 0.5, i as f32 * 0.3 - 0.3)
+                .with_rotation(Quat::from_rotation_y(i as f32 * 0.5)),
+            ..default()
+        });
+    }
+
+    // a layer of small spheres, always drawn after the cubes when sorted
+    let sphere = meshes.add(Mesh::from(shape::UVSphere {
+        radius: 0.15,
+        ..default()
+    }));
+    let sphere_material = materials.add(StandardMaterial {
+        base_color: Color::rgba(1.0, 0.9, 0.3, 0.7),
+        alpha_mode: AlphaMode::Blend,
+        unlit: true,
+        ..default()
+    });
+    for i in 0..5 {
+        commands.spawn((
+            PbrBundle {
+                mesh: sphere.clone(),
+                material: sphere_material.clone(),
+                transform: Transform::from_xyz(i as f32 * 0.5 - 1.0, 0.6, 0.0),
+                ..default()
+            },
+            TransparentSortOverride {
+                depth_bias: 100.0,
+                ..default()
+            },
+        ));
+    }
+
+    // light
+    commands.spawn(PointLightBundle {
+        point_light: PointLight {
+            intensity: 1500.0,
+            shadows_enabled: true,
+            ..default()
+        },
+        transform: Transform::from_xyz(4.0, 8.0, 4.0),
+        ..default()
+    });
+    // camera
+    commands.spawn(Camera3dBundle {
+        transform: Transform::from_xyz(-2.0, 3.0, 5.0).looking_at(Vec3::ZERO, Vec3::Y),
+        ..default()
+    });
+}
+
+fn toggle_transparency(keyboard_input: Res<Input<KeyCode>>, mut cameras: Query<&mut Camera3d>) {
+    if !keyboard_input.just_pressed(KeyCode::Space) {
+        return;
+    }
+    for mut camera_3d in &mut cameras {
+        camera_3d.transparency = match camera_3d.transparency {
+            Camera3dTransparency::Sorted => Camera3dTransparency::WeightedBlendedOit,
+            Camera3dTransparency::WeightedBlendedOit => Camera3dTransparency::Sorted,
+        };
+        info!("Transparency: {:?}", camera_3d.transparency);
+    }
+}
@@ -101,8 +101,7 @@ fn setup(
                 ..default()
             },
             camera: Camera {
-                // render before the "main pass" camera
-                priority: -1,
+                // rendered before the "main pass" camera, as its material samples the image
                 target: RenderTarget::Image(image_handle.clone()),
                 ..default()
             },
@@ -0,0 +1,123 @@
+//! Renders a 3d scene to an image without a window, saves it to disk and exits.
+//!
+//! This runs on machines without a display. On machines without a GPU, a software adapter can
+//! be used by running the example with the `WGPU_FORCE_FALLBACK_ADAPTER=1` environment variable.
+
+use bevy::{
+    app::AppExit,
+    prelude::*,
+    render::{
+        camera::RenderTarget,
+        render_resource::{
+            Extent3d, TextureDescriptor, TextureDimension, TextureFormat, TextureUsages,
+        },
+        view::{ScreenshotManager, ScreenshotSaved},
+    },
+};
+
+/// The number of frames rendered before the screenshot is taken, leaving time for the assets
+/// and pipelines to be ready.
+const WARMUP_FRAMES: u32 = 10;
+
+fn main() {
+    App::new()
+        .add_plugins(HeadlessPlugins)
+        .add_startup_system(setup)
+        .add_system(take_screenshot)
+        .add_system(exit_when_saved)
+        .run();
+}
+
+#[derive(Resource)]
+struct RenderedImage(Handle<Image>);
+
+fn setup(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut images: ResMut<Assets<Image>>,
+) {
+    let size = Extent3d {
+        width: 640,
+        height: 480,
+        ..default()
+    };
+
+    // The image the camera renders to, which is copied from to take the screenshot.
+    let mut image = Image {
+        texture_descriptor: TextureDescriptor {
+            label: None,
+            size,
+            dimension: TextureDimension::D2,
+            format: TextureFormat::Rgba8UnormSrgb,
+            mip_level_count: 1,
+            sample_count: 1,
+            usage: TextureUsages::TEXTURE_BINDING
+                | TextureUsages::COPY_DST
+                | TextureUsages::COPY_SRC
+                | TextureUsages::RENDER_ATTACHMENT,
+        },
+        ..default()
+    };
+    image.resize(size);
+    let image_handle = images.add(image);
+
+    // plane
+    commands.spawn(PbrBundle {
+        mesh: meshes.add(Mesh::from(shape::Plane { size: 5.0 })),
+        material: materials.add(Color::rgb(0.3, 0.5, 0.3).into()),
+        ..default()
+    });
+    // cube
+    commands.spawn(PbrBundle {
+        mesh: meshes.add(Mesh::from(shape::Cube { size: 1.0 })),
+        material: materials.add(Color::rgb(0.8, 0.7, 0.6).into()),
+        transform: Transform::from_xyz(0.0, 0.5, 0.0),
+        ..default()
+    });
+    // light
+    commands.spawn(PointLightBundle {
+        transform: Transform::from_xyz(4.0, 8.0, 4.0),
+        ..default()
+    });
+    // camera
+    commands.spawn(Camera3dBundle {
+        camera: Camera {
+            target: RenderTarget::Image(image_handle.clone()),
+            ..default()
+        },
+        transform: Transform::from_xyz(-2.0, 2.5, 5.0).looking_at(Vec3::ZERO, Vec3::Y),
+        ..default()
+    });
+
+    commands.insert_resource(RenderedImage(image_handle));
+}
+
+fn take_screenshot(
+    mut frame: Local<u32>,
+    rendered_image: Res<RenderedImage>,
+    mut screenshot_manager: ResMut<ScreenshotManager>,
+) {
+    *frame += 1;
+    if *frame == WARMUP_FRAMES {
+        screenshot_manager
+            .save_screenshot_to_disk(
+                RenderTarget::Image(rendered_image.0.clone()),
+                "headless_render.png",
+            )
+            .unwrap();
+    }
+}
+
+/// Exits once the screenshot file is written, as the process would stop writing it on exit.
+fn exit_when_saved(
+    mut saved_events: EventReader<ScreenshotSaved>,
+    mut app_exit_events: EventWriter<AppExit>,
+) {
+    if let Some(saved) = saved_events.iter().next() {
+        if let Err(err) = &saved.result {
+            error!("Failed to save the rendered image: {}", err);
+        }
+        app_exit_events.send(AppExit);
+    }
+}
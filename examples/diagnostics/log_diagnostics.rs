@@ -17,5 +17,7 @@ fn main() {
         // .add_plugin(bevy::diagnostic::EntityCountDiagnosticsPlugin::default())
         // Uncomment this to add an asset count diagnostics:
         // .add_plugin(bevy::asset::diagnostic::AssetCountDiagnosticsPlugin::<Texture>::default())
+        // Uncomment this to add asset loading diagnostics:
+        // .add_plugin(bevy::asset::diagnostic::AssetLoadDiagnosticsPlugin::default())
         .run();
 }
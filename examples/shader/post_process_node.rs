@@ -0,0 +1,341 @@
+//! A custom post-processing effect, applied by a node added to the render graph of the 3d camera.
+//! Here a vignette darkens the borders of a 3d scene containing a rotating cube.
+//!
+//! The node reads the main texture of the view and writes the other one through
+//! `ViewTarget::post_process_write`, so it can be chained with the built-in and other custom
+//! post-processing nodes without an extra camera.
+
+use bevy::{
+    core_pipeline::{core_3d, fullscreen_vertex_shader::fullscreen_shader_vertex_state},
+    ecs::query::QueryItem,
+    prelude::*,
+    render::{
+        extract_component::{
+            ComponentUniforms, DynamicUniformIndex, ExtractComponent, ExtractComponentPlugin,
+            UniformComponentPlugin,
+        },
+        render_graph::{Node, NodeRunError, RenderGraph, RenderGraphContext, SlotInfo, SlotType},
+        render_resource::*,
+        renderer::{RenderContext, RenderDevice},
+        view::{ExtractedView, ViewTarget},
+        RenderApp,
+    },
+};
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugin(VignettePlugin)
+        .add_startup_system(setup)
+        .add_system(rotate)
+        .add_system(update_settings)
+        .run();
+}
+
+/// Adds the vignette node to the render graph of the 3d cameras.
+struct VignettePlugin;
+
+impl Plugin for VignettePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugin(ExtractComponentPlugin::<VignetteSettings>::default())
+            .add_plugin(UniformComponentPlugin::<VignetteSettings>::default());
+
+        let render_app = match app.get_sub_app_mut(RenderApp) {
+            Ok(render_app) => render_app,
+            Err(_) => return,
+        };
+        render_app.init_resource::<VignettePipeline>();
+
+        let node = VignetteNode::new(&mut render_app.world);
+        let mut graph = render_app.world.resource_mut::<RenderGraph>();
+        let core_3d_graph = graph.get_sub_graph_mut(core_3d::graph::NAME).unwrap();
+        core_3d_graph.add_node(VignetteNode::NAME, node);
+        core_3d_graph
+            .add_slot_edge(
+                core_3d_graph.input_node().unwrap().id,
+                core_3d::graph::input::VIEW_ENTITY,
+                VignetteNode::NAME,
+                VignetteNode::IN_VIEW,
+            )
+            .unwrap();
+        // The vignette reads the tonemapped colors, so it runs between tonemapping and the copy
+        // of the main texture to the render target. Effects reading "high dynamic range" colors
+        // would run between the BLOOM and TONEMAPPING nodes instead.
+        core_3d_graph
+            .add_node_edge(core_3d::graph::node::TONEMAPPING, VignetteNode::NAME)
+            .unwrap();
+        core_3d_graph
+            .add_node_edge(VignetteNode::NAME, core_3d::graph::node::UPSCALING)
+            .unwrap();
+    }
+}
+
+/// The settings of the vignette of a camera, which are also the uniform of the vignette shader.
+#[derive(Component, Clone, ShaderType)]
+struct VignetteSettings {
+    /// How much the borders are darkened, between 0.0 and 1.0.
+    intensity: f32,
+    /// The distance from the center of the screen where the vignette starts.
+    radius: f32,
+    /// The distance over which the vignette fades in.
+    smoothness: f32,
+}
+
+impl ExtractComponent for VignetteSettings {
+    type Query = &'static Self;
+    type Filter = With<Camera>;
+
+    fn extract_component(item: QueryItem<Self::Query>) -> Self {
+        item.clone()
+    }
+}
+
+#[derive(Resource)]
+struct VignettePipeline {
+    layout: BindGroupLayout,
+    pipeline_id: CachedRenderPipelineId,
+}
+
+impl FromWorld for VignettePipeline {
+    fn from_world(world: &mut World) -> Self {
+        let layout =
+            world
+                .resource::<RenderDevice>()
+                .create_bind_group_layout(&BindGroupLayoutDescriptor {
+                    label: Some("vignette_bind_group_layout"),
+                    entries: &[
+                        // the main texture of the view
+                        BindGroupLayoutEntry {
+                            binding: 0,
+                            visibility: ShaderStages::FRAGMENT,
+                            ty: BindingType::Texture {
+                                sample_type: TextureSampleType::Float { filterable: false },
+                                view_dimension: TextureViewDimension::D2,
+                                multisampled: false,
+                            },
+                            count: None,
+                        },
+                        BindGroupLayoutEntry {
+                            binding: 1,
+                            visibility: ShaderStages::FRAGMENT,
+                            ty: BindingType::Buffer {
+                                ty: BufferBindingType::Uniform,
+                                has_dynamic_offset: true,
+                                min_binding_size: Some(VignetteSettings::min_size()),
+                            },
+                            count: None,
+                        },
+                    ],
+                });
+
+        let shader = world
+            .resource::<AssetServer>()
+            .load("shaders/vignette.wgsl");
+
+        let pipeline_id =
+            world
+                .resource_mut::<PipelineCache>()
+                .queue_render_pipeline(RenderPipelineDescriptor {
+                    label: Some("vignette_pipeline".into()),
+                    layout: Some(vec![layout.clone()]),
+                    // the fullscreen triangle of the built-in post-processing passes
+                    vertex: fullscreen_shader_vertex_state(),
+                    fragment: Some(FragmentState {
+                        shader,
+                        shader_defs: vec![],
+                        entry_point: "fragment".into(),
+                        targets: vec![Some(ColorTargetState {
+                            // the camera of the example is "high dynamic range", a pipeline
+                            // supporting other cameras would be specialized on the format of
+                            // their main texture
+                            format: ViewTarget::TEXTURE_FORMAT_HDR,
+                            blend: None,
+                            write_mask: ColorWrites::ALL,
+                        })],
+                    }),
+                    primitive: PrimitiveState::default(),
+                    depth_stencil: None,
+                    multisample: MultisampleState::default(),
+                });
+
+        Self {
+            layout,
+            pipeline_id,
+        }
+    }
+}
+
+/// Applies the vignette of the views with [`VignetteSettings`].
+struct VignetteNode {
+    query: QueryState<
+        (
+            &'static ViewTarget,
+            &'static DynamicUniformIndex<VignetteSettings>,
+        ),
+        With<ExtractedView>,
+    >,
+}
+
+impl VignetteNode {
+    const NAME: &'static str = "vignette";
+    const IN_VIEW: &'static str = "view";
+
+    fn new(world: &mut World) -> Self {
+        Self {
+            query: world.query_filtered(),
+        }
+    }
+}
+
+impl Node for VignetteNode {
+    fn input(&self) -> Vec<SlotInfo> {
+        vec![SlotInfo::new(Self::IN_VIEW, SlotType::Entity)]
+    }
+
+    fn update(&mut self, world: &mut World) {
+        self.query.update_archetypes(world);
+    }
+
+    fn run(
+        &self,
+        graph: &mut RenderGraphContext,
+        render_context: &mut RenderContext,
+        world: &World,
+    ) -> Result<(), NodeRunError> {
+        let view_entity = graph.get_input_entity(Self::IN_VIEW)?;
+        // views without vignette settings are skipped
+        let (view_target, settings_index) = match self.query.get_manual(world, view_entity) {
+            Ok(result) => result,
+            Err(_) => return Ok(()),
+        };
+        if !view_target.is_hdr() {
+            return Ok(());
+        }
+
+        let vignette_pipeline = world.resource::<VignettePipeline>();
+        let pipeline = match world
+            .resource::<PipelineCache>()
+            .get_render_pipeline(vignette_pipeline.pipeline_id)
+        {
+            Some(pipeline) => pipeline,
+            None => return Ok(()),
+        };
+        let settings = match world
+            .resource::<ComponentUniforms<VignetteSettings>>()
+            .binding()
+        {
+            Some(settings) => settings,
+            None => return Ok(()),
+        };
+
+        // Reads the current main texture and makes the destination the new main texture, for
+        // the nodes running after this one.
+        let post_process = view_target.post_process_write();
+
+        let bind_group = render_context
+            .render_device
+            .create_bind_group(&BindGroupDescriptor {
+                label: Some("vignette_bind_group"),
+                layout: &vignette_pipeline.layout,
+                entries: &[
+                    BindGroupEntry {
+                        binding: 0,
+                        resource: BindingResource::TextureView(post_process.source),
+                    },
+                    BindGroupEntry {
+                        binding: 1,
+                        resource: settings,
+                    },
+                ],
+            });
+
+        let mut render_pass =
+            render_context
+                .command_encoder
+                .begin_render_pass(&RenderPassDescriptor {
+                    label: Some("vignette_pass"),
+                    color_attachments: &[Some(RenderPassColorAttachment {
+                        view: post_process.destination,
+                        resolve_target: None,
+                        ops: Operations::default(),
+                    })],
+                    depth_stencil_attachment: None,
+                });
+        render_pass.set_pipeline(pipeline);
+        render_pass.set_bind_group(0, &bind_group, &[settings_index.index()]);
+        render_pass.draw(0..3, 0..1);
+
+        Ok(())
+    }
+}
+
+#[derive(Component)]
+struct Rotates;
+
+fn setup(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    info!("Press up and down to change the intensity of the vignette");
+
+    // cube
+    commands.spawn((
+        PbrBundle {
+            mesh: meshes.add(Mesh::from(shape::Cube { size: 1.0 })),
+            material: materials.add(Color::rgb(0.8, 0.7, 0.6).into()),
+            transform: Transform::from_xyz(0.0, 0.5, 0.0),
+            ..default()
+        },
+        Rotates,
+    ));
+    // plane
+    commands.spawn(PbrBundle {
+        mesh: meshes.add(Mesh::from(shape::Plane { size: 5.0 })),
+        material: materials.add(Color::rgb(0.3, 0.5, 0.3).into()),
+        ..default()
+    });
+    // light
+    commands.spawn(PointLightBundle {
+        transform: Transform::from_xyz(4.0, 8.0, 4.0),
+        ..default()
+    });
+    // camera
+    commands.spawn((
+        Camera3dBundle {
+            camera: Camera {
+                hdr: true,
+                ..default()
+            },
+            transform: Transform::from_xyz(-2.0, 2.5, 5.0).looking_at(Vec3::ZERO, Vec3::Y),
+            ..default()
+        },
+        VignetteSettings {
+            intensity: 0.8,
+            radius: 0.4,
+            smoothness: 0.5,
+        },
+    ));
+}
+
+fn rotate(time: Res<Time>, mut query: Query<&mut Transform, With<Rotates>>) {
+    for mut transform in &mut query {
+        transform.rotate_y(time.delta_seconds());
+    }
+}
+
+fn update_settings(
+    time: Res<Time>,
+    input: Res<Input<KeyCode>>,
+    mut settings: Query<&mut VignetteSettings>,
+) {
+    for mut settings in &mut settings {
+        if input.pressed(KeyCode::Up) {
+            settings.intensity += time.delta_seconds();
+        }
+        if input.pressed(KeyCode::Down) {
+            settings.intensity -= time.delta_seconds();
+        }
+        settings.intensity = settings.intensity.clamp(0.0, 1.0);
+    }
+}
@@ -126,6 +126,7 @@ fn queue_custom(
                     pipeline,
                     draw_function: draw_custom,
                     distance: rangefinder.distance(&mesh_uniform.transform),
+                    batch_range: None,
                 });
             }
         }
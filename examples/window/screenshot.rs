@@ -0,0 +1,78 @@
+//! An example showing how to save screenshots of a window to disk.
+
+use bevy::{
+    prelude::*,
+    render::{
+        camera::RenderTarget,
+        view::{ScreenshotCaptured, ScreenshotManager},
+    },
+    window::WindowId,
+};
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_startup_system(setup)
+        .add_system(screenshot_on_spacebar)
+        .add_system(log_captured_screenshots)
+        .run();
+}
+
+fn screenshot_on_spacebar(
+    input: Res<Input<KeyCode>>,
+    mut screenshot_manager: ResMut<ScreenshotManager>,
+    mut counter: Local<u32>,
+) {
+    if input.just_pressed(KeyCode::Space) {
+        let path = format!("./screenshot-{}.png", *counter);
+        match screenshot_manager
+            .save_screenshot_to_disk(RenderTarget::Window(WindowId::primary()), path)
+        {
+            Ok(()) => *counter += 1,
+            Err(err) => warn!("{}", err),
+        }
+    }
+}
+
+fn log_captured_screenshots(mut captured_events: EventReader<ScreenshotCaptured>) {
+    for captured in captured_events.iter() {
+        let size = captured.image.size();
+        info!(
+            "Captured a {}x{} screenshot of {:?}",
+            size.x, size.y, captured.target
+        );
+    }
+}
+
+/// set up a simple 3D scene
+fn setup(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    info!("Press space to save a screenshot");
+
+    // plane
+    commands.spawn(PbrBundle {
+        mesh: meshes.add(Mesh::from(shape::Plane { size: 5.0 })),
+        material: materials.add(Color::rgb(0.3, 0.5, 0.3).into()),
+        ..default()
+    });
+    // cube
+    commands.spawn(PbrBundle {
+        mesh: meshes.add(Mesh::from(shape::Cube { size: 1.0 })),
+        material: materials.add(Color::rgb(0.8, 0.7, 0.6).into()),
+        transform: Transform::from_xyz(0.0, 0.5, 0.0),
+        ..default()
+    });
+    // light
+    commands.spawn(PointLightBundle {
+        transform: Transform::from_xyz(4.0, 8.0, 4.0),
+        ..default()
+    });
+    // camera
+    commands.spawn(Camera3dBundle {
+        transform: Transform::from_xyz(-2.0, 2.5, 5.0).looking_at(Vec3::ZERO, Vec3::Y),
+        ..default()
+    });
+}
@@ -0,0 +1,67 @@
+use std::{
+    fs::File,
+    io::{BufWriter, Write},
+    path::{Path, PathBuf},
+};
+
+use bevy_asset::{PackCompression, PackWriter};
+use clap::Parser;
+
+#[derive(Parser, Debug)]
+struct Args {
+    /// Folder of the assets to pack
+    assets: PathBuf,
+
+    /// Path of the asset pack to write
+    output: PathBuf,
+
+    #[arg(short, long)]
+    /// Compress the files with Zstandard at this level, when it makes them smaller
+    zstd: Option<i32>,
+}
+
+fn add_folder(
+    writer: &mut PackWriter,
+    root: &Path,
+    folder: &Path,
+    zstd_level: Option<i32>,
+) -> std::io::Result<usize> {
+    let mut count = 0;
+    for entry in std::fs::read_dir(folder)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            count += add_folder(writer, root, &path, zstd_level)?;
+            continue;
+        }
+
+        let bytes = std::fs::read(&path)?;
+        let pack_path = path.strip_prefix(root).unwrap();
+        let compressed = match zstd_level {
+            Some(level) => Some(zstd::stream::encode_all(&bytes[..], level)?),
+            None => None,
+        };
+        match compressed {
+            Some(compressed) if compressed.len() < bytes.len() => writer
+                .add_compressed_file(pack_path, PackCompression::Zstd, compressed)
+                .expect("Error adding file"),
+            _ => writer
+                .add_file(pack_path, bytes)
+                .expect("Error adding file"),
+        }
+        count += 1;
+    }
+    Ok(count)
+}
+
+fn main() {
+    let cli = Args::parse();
+
+    let mut writer = PackWriter::default();
+    let count = add_folder(&mut writer, &cli.assets, &cli.assets, cli.zstd)
+        .expect("Error reading asset folder");
+
+    let mut output = BufWriter::new(File::create(&cli.output).expect("Error creating asset pack"));
+    writer.write(&mut output).expect("Error writing asset pack");
+    output.flush().expect("Error writing asset pack");
+    println!("Packed {} files into {}", count, cli.output.display());
+}